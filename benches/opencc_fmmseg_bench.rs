@@ -1,5 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use once_cell::sync::Lazy;
+use opencc_fmmseg::dictionary_lib::DictionaryMaxlength;
 use opencc_fmmseg::OpenCC;
 use std::fs;
 use std::time::Duration;
@@ -74,6 +75,33 @@ fn bench_convert(c: &mut Criterion) {
     }
 }
 
+// Path to the artifact `cargo run -p dict-generate` produces by default;
+// construction benches are skipped (not failed) if it isn't present, since
+// this repo doesn't check dictionary builds into version control.
+const ZSTD_DICT_PATH: &str = "dictionary_maxlength.zstd";
+
+fn bench_construction(c: &mut Criterion) {
+    if !std::path::Path::new(ZSTD_DICT_PATH).exists() {
+        eprintln!(
+            "skipping bench_construction: {ZSTD_DICT_PATH} not found (run dict-generate first)"
+        );
+        return;
+    }
+
+    let mut group = c.benchmark_group("construction");
+    group.sample_size(20);
+
+    group.bench_function("load_cbor_compressed_eager", |b| {
+        b.iter(|| DictionaryMaxlength::load_cbor_compressed(ZSTD_DICT_PATH).unwrap());
+    });
+
+    group.bench_function("from_mmap_zstd", |b| {
+        b.iter(|| DictionaryMaxlength::from_mmap_zstd(ZSTD_DICT_PATH).unwrap());
+    });
+
+    group.finish();
+}
+
 fn configure_criterion() -> Criterion {
     Criterion::default()
         .sample_size(50)
@@ -84,6 +112,6 @@ fn configure_criterion() -> Criterion {
 criterion_group! {
     name = benches;
     config = configure_criterion();
-    targets = bench_convert
+    targets = bench_convert, bench_construction
 }
 criterion_main!(benches);
@@ -0,0 +1,139 @@
+//! Assembles per-conversion-family dictionary sub-blobs for selective embedding.
+//!
+//! This crate's `embed-st`/`embed-tw`/`embed-hk`/`embed-jp`/`embed-all` cargo
+//! features let a downstream binary embed only the `DictMaxLen` tables it
+//! actually needs (e.g. an S2T-only tool skips the `tw_*`/`hk_*`/`jp_*`
+//! tables entirely). For each enabled feature, this script reads the
+//! matching plaintext lexicons from `dicts/` (the same TSV format
+//! `DictionaryMaxlength::from_dicts` reads at runtime), Zstd-compresses a
+//! CBOR-encoded `BTreeMap<table name, (key, value) pairs>`, and writes the
+//! result to `$OUT_DIR/embed_<family>.zstd`. `src/dictionary_lib/embed.rs`
+//! then `include_bytes!`s whichever of these the enabled features select and
+//! rebuilds each table's `DictMaxLen` via `DictMaxLen::build_from_pairs`.
+//!
+//! If a family's source lexicons aren't present (e.g. a trimmed-down source
+//! checkout), this script still produces an empty sub-blob for that family
+//! rather than failing the build, and emits a `cargo:warning=` so the gap is
+//! visible instead of silently shipping a dictionary nobody noticed was empty.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// One conversion family: its feature name and the `(table name, source file)` pairs it embeds.
+struct Family {
+    feature: &'static str,
+    tables: &'static [(&'static str, &'static str)],
+}
+
+const FAMILIES: &[Family] = &[
+    Family {
+        feature: "st",
+        tables: &[
+            ("st_characters", "STCharacters.txt"),
+            ("st_phrases", "STPhrases.txt"),
+            ("ts_characters", "TSCharacters.txt"),
+            ("ts_phrases", "TSPhrases.txt"),
+            ("st_punctuations", "STPunctuations.txt"),
+            ("ts_punctuations", "TSPunctuations.txt"),
+        ],
+    },
+    Family {
+        feature: "tw",
+        tables: &[
+            ("tw_phrases", "TWPhrases.txt"),
+            ("tw_phrases_rev", "TWPhrasesRev.txt"),
+            ("tw_variants", "TWVariants.txt"),
+            ("tw_variants_rev", "TWVariantsRev.txt"),
+            ("tw_variants_rev_phrases", "TWVariantsRevPhrases.txt"),
+        ],
+    },
+    Family {
+        feature: "hk",
+        tables: &[
+            ("hk_variants", "HKVariants.txt"),
+            ("hk_variants_rev", "HKVariantsRev.txt"),
+            ("hk_variants_rev_phrases", "HKVariantsRevPhrases.txt"),
+        ],
+    },
+    Family {
+        feature: "jp",
+        tables: &[
+            ("jps_characters", "JPShinjitaiCharacters.txt"),
+            ("jps_phrases", "JPShinjitaiPhrases.txt"),
+            ("jp_variants", "JPVariants.txt"),
+            ("jp_variants_rev", "JPVariantsRev.txt"),
+        ],
+    },
+];
+
+/// Parses a `key\tvalue` TSV lexicon the same way `DictionaryMaxlength::from_dicts` does:
+/// blank lines and `#`-prefixed comments are skipped, and only the first
+/// whitespace-separated token of the value column is kept.
+fn parse_tsv(content: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut saw_data_line = false;
+
+    for raw_line in content.lines() {
+        let mut line = raw_line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if !saw_data_line {
+            if let Some(rest) = line.strip_prefix('\u{FEFF}') {
+                line = rest;
+            }
+            saw_data_line = true;
+        }
+        let Some((k, v)) = line.split_once('\t') else {
+            continue;
+        };
+        let first_value = v.split_whitespace().next().unwrap_or("");
+        pairs.push((k.to_owned(), first_value.to_owned()));
+    }
+
+    pairs
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=dicts");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    let base_dir = Path::new("dicts");
+
+    let embed_all = env::var("CARGO_FEATURE_EMBED_ALL").is_ok();
+
+    for family in FAMILIES {
+        let feature_var = format!("CARGO_FEATURE_EMBED_{}", family.feature.to_uppercase());
+        if !embed_all && env::var(&feature_var).is_err() {
+            continue;
+        }
+
+        let mut sub_blob: BTreeMap<&str, Vec<(String, String)>> = BTreeMap::new();
+        for &(table, filename) in family.tables {
+            let path = base_dir.join(filename);
+            match fs::read_to_string(&path) {
+                Ok(content) => {
+                    sub_blob.insert(table, parse_tsv(&content));
+                }
+                Err(_) => {
+                    println!(
+                        "cargo:warning=embed-{}: missing source lexicon {}, embedding an empty table for `{}`",
+                        family.feature,
+                        path.display(),
+                        table
+                    );
+                    sub_blob.insert(table, Vec::new());
+                }
+            }
+        }
+
+        let cbor = serde_cbor::to_vec(&sub_blob).expect("failed to CBOR-encode dictionary family");
+        let compressed = zstd::encode_all(cbor.as_slice(), 19)
+            .expect("failed to Zstd-compress dictionary family");
+
+        let out_path = Path::new(&out_dir).join(format!("embed_{}.zstd", family.feature));
+        fs::write(&out_path, compressed).expect("failed to write dictionary sub-blob");
+    }
+}
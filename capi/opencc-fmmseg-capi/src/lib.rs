@@ -27,6 +27,47 @@ pub extern "C" fn opencc_free(instance: *mut OpenCC) {
     }
 }
 
+/// Rebuilds `instance`'s active delimiter set from every character in
+/// `utf8_chars`, so segmentation stops breaking (or starts breaking) on
+/// exactly those characters instead of the built-in default. Pass an empty
+/// string to install a set with no delimiters at all; see
+/// [`opencc_reset_delimiters`] to go back to the default instead.
+///
+/// Returns `false` (and records a diagnostic via `OpenCC::set_last_error`)
+/// if `instance`/`utf8_chars` is NULL or `utf8_chars` isn't valid UTF-8.
+#[no_mangle]
+pub extern "C" fn opencc_set_delimiters(instance: *mut OpenCC, utf8_chars: *const c_char) -> bool {
+    if instance.is_null() || utf8_chars.is_null() {
+        OpenCC::set_last_error("Invalid argument: instance or utf8_chars is NULL");
+        return false;
+    }
+
+    let chars_c_str = unsafe { CStr::from_ptr(utf8_chars) };
+    let chars_str = match chars_c_str.to_str() {
+        Ok(s) => s,
+        Err(e) => {
+            OpenCC::set_last_error(&format!("Invalid UTF-8 in utf8_chars: {}", e));
+            return false;
+        }
+    };
+
+    let opencc = unsafe { &mut *instance };
+    opencc.set_delimiters(chars_str.chars());
+    OpenCC::clear_last_error();
+    true
+}
+
+/// Reverts `instance`'s delimiter set to the built-in default, undoing a
+/// prior [`opencc_set_delimiters`] call.
+#[no_mangle]
+pub extern "C" fn opencc_reset_delimiters(instance: *mut OpenCC) {
+    if instance.is_null() {
+        return;
+    }
+    let opencc = unsafe { &mut *instance };
+    opencc.reset_delimiters();
+}
+
 #[no_mangle]
 pub extern "C" fn opencc_get_parallel(instance: *mut OpenCC) -> bool {
     let opencc = unsafe { &*instance };
@@ -82,6 +123,100 @@ pub extern "C" fn opencc_convert(
         .into_raw()
 }
 
+/// UTF-8 input-handling mode for [`opencc_convert_ex`]/[`opencc_convert_cfg_ex`].
+///
+/// Validates with `std::str::from_utf8`; on the first invalid byte, records
+/// its offset via `OpenCC::set_last_error` and fails the call (returns
+/// `null`) without converting anything.
+pub const OPENCC_UTF8_STRICT: u32 = 0;
+
+/// UTF-8 input-handling mode for [`opencc_convert_ex`]/[`opencc_convert_cfg_ex`].
+///
+/// Runs `String::from_utf8_lossy` so malformed byte sequences become U+FFFD
+/// and conversion proceeds on the salvaged text. A non-fatal note recording
+/// how many replacements were made is still left in `last_error`, so a caller
+/// that doesn't check it gets the old silent-degradation behavior, and one
+/// that does can tell the input wasn't clean.
+pub const OPENCC_UTF8_LOSSY: u32 = 1;
+
+/// Decodes `bytes` as UTF-8 per `utf8_mode` (see [`OPENCC_UTF8_STRICT`] /
+/// [`OPENCC_UTF8_LOSSY`]).
+///
+/// Returns `Ok((text, had_replacement))`, where `had_replacement` is `true`
+/// only when lossy mode actually had to salvage something — callers use this
+/// to decide whether the lossy non-fatal note in `last_error` should survive
+/// a subsequent success, or be cleared like any other clean call. Returns
+/// `Err(())` for strict-mode validation failures; `last_error` is already set
+/// in every error/note case before returning.
+fn decode_utf8_input(bytes: &[u8], utf8_mode: u32) -> Result<(std::borrow::Cow<'_, str>, bool), ()> {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok((std::borrow::Cow::Borrowed(s), false)),
+        Err(e) if utf8_mode == OPENCC_UTF8_LOSSY => {
+            let lossy = String::from_utf8_lossy(bytes).into_owned();
+            let replaced = lossy.matches('\u{FFFD}').count();
+            OpenCC::set_last_error(&format!(
+                "UTF-8 input salvaged in lossy mode: {} invalid byte sequence(s) replaced with U+FFFD (first at byte offset {})",
+                replaced,
+                e.valid_up_to()
+            ));
+            Ok((std::borrow::Cow::Owned(lossy), true))
+        }
+        Err(e) => {
+            OpenCC::set_last_error(&format!(
+                "Invalid UTF-8 input at byte offset {} (strict mode)",
+                e.valid_up_to()
+            ));
+            Err(())
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn opencc_convert_ex(
+    instance: *const OpenCC,
+    input: *const c_char,
+    config: *const c_char,
+    punctuation: bool,
+    utf8_mode: u32,
+) -> *mut c_char {
+    if instance.is_null() || input.is_null() || config.is_null() {
+        OpenCC::set_last_error("Invalid argument: instance/input/config is NULL");
+        return ptr::null_mut();
+    }
+
+    let opencc = unsafe { &*instance };
+
+    let config_c_str = unsafe { CStr::from_ptr(config) };
+    let config_str_slice = config_c_str.to_str().unwrap_or("");
+
+    let cfg = match OpenccConfig::try_from(config_str_slice) {
+        Ok(c) => c,
+        Err(_) => {
+            let msg = format!("Invalid config: {}", config_str_slice);
+            OpenCC::set_last_error(&msg);
+            return std::ffi::CString::new(msg)
+                .unwrap_or_else(|_| std::ffi::CString::new("Invalid config").unwrap())
+                .into_raw();
+        }
+    };
+
+    let input_c_str = unsafe { CStr::from_ptr(input) };
+    let (input_str, had_replacement) = match decode_utf8_input(input_c_str.to_bytes(), utf8_mode) {
+        Ok(v) => v,
+        Err(()) => return ptr::null_mut(),
+    };
+
+    let result = opencc.convert_with_config(&input_str, cfg, punctuation);
+
+    if !had_replacement {
+        OpenCC::clear_last_error();
+    }
+
+    std::ffi::CString::new(result)
+        .unwrap_or_else(|_| std::ffi::CString::new("").unwrap())
+        .into_raw()
+}
+
 #[no_mangle]
 pub extern "C" fn opencc_convert_cfg(
     instance: *const OpenCC,
@@ -119,6 +254,51 @@ pub extern "C" fn opencc_convert_cfg(
         .into_raw()
 }
 
+/// Like [`opencc_convert_cfg`], but `utf8_mode` selects how malformed UTF-8
+/// in `input` is handled instead of silently collapsing it to an empty
+/// string. See [`OPENCC_UTF8_STRICT`]/[`OPENCC_UTF8_LOSSY`].
+#[no_mangle]
+pub extern "C" fn opencc_convert_cfg_ex(
+    instance: *const OpenCC,
+    input: *const c_char,
+    config: u32,
+    punctuation: bool,
+    utf8_mode: u32,
+) -> *mut c_char {
+    if instance.is_null() || input.is_null() {
+        return ptr::null_mut();
+    }
+
+    let opencc = unsafe { &*instance };
+
+    let cfg = match OpenccConfig::from_ffi(config) {
+        Some(c) => c,
+        None => {
+            let msg = format!("Invalid config: {}", config);
+            OpenCC::set_last_error(&msg);
+            return std::ffi::CString::new(msg)
+                .unwrap_or_else(|_| std::ffi::CString::new("Invalid config").unwrap())
+                .into_raw();
+        }
+    };
+
+    let input_c_str = unsafe { CStr::from_ptr(input) };
+    let (input_str, had_replacement) = match decode_utf8_input(input_c_str.to_bytes(), utf8_mode) {
+        Ok(v) => v,
+        Err(()) => return ptr::null_mut(),
+    };
+
+    let result = opencc.convert_with_config(&input_str, cfg, punctuation);
+
+    if !had_replacement {
+        OpenCC::clear_last_error();
+    }
+
+    std::ffi::CString::new(result)
+        .unwrap_or_else(|_| std::ffi::CString::new("").unwrap())
+        .into_raw()
+}
+
 #[no_mangle]
 pub extern "C" fn opencc_convert_cfg_mem(
     instance: *const OpenCC,
@@ -194,6 +374,93 @@ pub extern "C" fn opencc_convert_cfg_mem(
     ok
 }
 
+/// Binary-safe, NUL-tolerant counterpart to [`opencc_convert_cfg_mem`].
+///
+/// Reads exactly `in_len` bytes from `in_ptr` (no terminator scan, so
+/// embedded `\0` bytes survive) and writes exactly `out_written` converted
+/// bytes into `out_buf` with no trailing NUL appended — the caller already
+/// knows the length from `out_written`, unlike the `CStr`-based entry points
+/// where `CString::new(result).unwrap_or_else(|_| CString::new(""))` quietly
+/// collapses any result with an interior NUL to an empty string. Passing a
+/// null `out_buf` (or `out_cap == 0`) is a size-query: `out_written` is
+/// filled in and the call returns `true` without touching `out_buf`.
+///
+/// `in_ptr`'s bytes must be valid UTF-8 — Chinese text conversion is
+/// codepoint-preserving around embedded NULs, but it isn't a raw-byte
+/// transform, so malformed input fails the call via `OpenCC::set_last_error`
+/// rather than silently truncating or substituting.
+#[no_mangle]
+pub extern "C" fn opencc_convert_bytes(
+    instance: *const OpenCC,
+    in_ptr: *const u8,
+    in_len: usize,
+    config: u32,
+    punctuation: bool,
+    out_buf: *mut u8,
+    out_cap: usize,
+    out_written: *mut usize,
+) -> bool {
+    if out_written.is_null() {
+        return false;
+    }
+
+    if instance.is_null() || (in_ptr.is_null() && in_len != 0) {
+        OpenCC::set_last_error("Invalid argument: instance or in_ptr is NULL");
+        return false;
+    }
+
+    let opencc = unsafe { &*instance };
+
+    let input_bytes: &[u8] = if in_len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(in_ptr, in_len) }
+    };
+
+    let input_str = match std::str::from_utf8(input_bytes) {
+        Ok(s) => s,
+        Err(e) => {
+            OpenCC::set_last_error(&format!(
+                "Invalid UTF-8 input at byte offset {}",
+                e.valid_up_to()
+            ));
+            return false;
+        }
+    };
+
+    let cfg = match OpenccConfig::from_ffi(config) {
+        Some(c) => c,
+        None => {
+            OpenCC::set_last_error(&format!("Invalid config: {}", config));
+            return false;
+        }
+    };
+
+    let result = opencc.convert_with_config(input_str, cfg, punctuation);
+    let result_bytes = result.as_bytes();
+
+    unsafe {
+        *out_written = result_bytes.len();
+    }
+
+    if out_buf.is_null() || out_cap == 0 {
+        OpenCC::clear_last_error();
+        return true; // size-query ok
+    }
+
+    if out_cap < result_bytes.len() {
+        OpenCC::set_last_error("Output buffer too small");
+        return false;
+    }
+
+    unsafe {
+        ptr::copy_nonoverlapping(result_bytes.as_ptr(), out_buf, result_bytes.len());
+    }
+
+    OpenCC::clear_last_error();
+    true
+}
+
 #[deprecated(note = "Use `opencc_convert()` or `opencc_convert_cfg` instead")]
 #[no_mangle]
 pub extern "C" fn opencc_convert_len(
@@ -287,6 +554,8 @@ pub extern "C" fn opencc_error_free(ptr: *mut c_char) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::thread;
+    use std::time::Duration;
 
     #[test]
     fn test_opencc_zho_check() {
@@ -454,6 +723,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_opencc_last_error_is_thread_local() {
+        // One thread records an error, the other performs a clean conversion;
+        // each must read back only its own outcome, never the other's.
+        let erroring = thread::spawn(|| {
+            OpenCC::set_last_error("simulated error from thread A");
+            thread::sleep(Duration::from_millis(50));
+            OpenCC::get_last_error()
+        });
+
+        let clean = thread::spawn(|| {
+            OpenCC::clear_last_error();
+            let opencc = OpenCC::new();
+            let _ = opencc.convert("测试", "s2t", false);
+            thread::sleep(Duration::from_millis(50));
+            OpenCC::get_last_error()
+        });
+
+        let erroring_result = erroring.join().unwrap();
+        let clean_result = clean.join().unwrap();
+
+        assert_eq!(
+            erroring_result.as_deref(),
+            Some("simulated error from thread A")
+        );
+        assert!(clean_result.is_none());
+    }
+
     fn read_and_free(ptr: *mut c_char) -> String {
         unsafe {
             if ptr.is_null() {
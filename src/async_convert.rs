@@ -0,0 +1,191 @@
+//! Async Read/Write conversion surface, gated behind this crate's `async` feature.
+//!
+//! This mirrors the sync/async client split used by crates like `reqwest`: [`SyncConverter`]
+//! is the blocking counterpart already provided by [`OpenCC::convert`], and [`AsyncConverter`]
+//! is the async surface added here so a server or batch job can feed many large documents
+//! through one shared, already-loaded [`DictionaryMaxlength`](crate::dictionary_lib::DictionaryMaxlength)
+//! without blocking an executor thread.
+//!
+//! Because the dictionary is immutable after load, [`AsyncConverter::convert_async`] takes
+//! `self` as an `Arc<OpenCC>` so it can clone the converter cheaply onto every spawned task.
+//! It reuses the same delimiter-based segment splitting as the sync path
+//! ([`get_chars_range`](crate::OpenCC)): when [`set_parallel`](crate::OpenCC::set_parallel) is
+//! on, every segment's conversion is dispatched to the `tokio` blocking thread pool via
+//! `spawn_blocking` (so the CPU-bound FMM matching never ties up an async worker thread) and
+//! results are reassembled in original order by index; when it's off, segments convert one at
+//! a time on the calling task, yielding to the executor between each so one huge document
+//! can't starve other work.
+
+use std::io;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::task::JoinSet;
+
+use crate::OpenCC;
+
+/// Blocking conversion — the synchronous counterpart to [`AsyncConverter`], already
+/// implemented by [`OpenCC::convert`] itself. Exists so generic code can be written against
+/// either trait depending on whether the caller's executor is sync or async.
+pub trait SyncConverter {
+    /// Converts `input` to `config`, exactly like [`OpenCC::convert`].
+    fn convert_sync(&self, input: &str, config: &str, punctuation: bool) -> String;
+}
+
+impl SyncConverter for OpenCC {
+    fn convert_sync(&self, input: &str, config: &str, punctuation: bool) -> String {
+        self.convert(input, config, punctuation)
+    }
+}
+
+/// Async counterpart to [`SyncConverter`], for callers that can't afford to block an
+/// executor thread on a large document's worth of dictionary matching.
+#[async_trait]
+pub trait AsyncConverter {
+    /// Reads all of `reader`, converts it under `config`, and writes the result to `writer`,
+    /// without ever blocking the calling task on the conversion itself.
+    ///
+    /// Splits the decoded input into delimiter-bounded segments (see
+    /// [`get_chars_range`](crate::OpenCC)) and, if [`set_parallel`](OpenCC::set_parallel) is
+    /// enabled, dispatches each segment's conversion to the `tokio` blocking thread pool via
+    /// `spawn_blocking`, so the synchronous, CPU-bound FMM matching never occupies an async
+    /// worker thread — segments are gathered back in their original order before being
+    /// written, so output is identical to [`convert`](OpenCC::convert) regardless of task
+    /// completion order. With `set_parallel` off, segments convert sequentially on the
+    /// calling task, yielding to the executor between segments.
+    ///
+    /// # Errors
+    /// Fails with [`io::ErrorKind::InvalidData`] if `reader`'s bytes aren't valid UTF-8, or
+    /// propagates any [`io::Error`] reading from `reader`/writing to `writer`.
+    async fn convert_async<R, W>(
+        self: &Arc<Self>,
+        reader: R,
+        writer: W,
+        config: &str,
+        punctuation: bool,
+    ) -> io::Result<()>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send;
+}
+
+#[async_trait]
+impl AsyncConverter for OpenCC {
+    async fn convert_async<R, W>(
+        self: &Arc<Self>,
+        mut reader: R,
+        mut writer: W,
+        config: &str,
+        punctuation: bool,
+    ) -> io::Result<()>
+    where
+        R: AsyncRead + Unpin + Send,
+        W: AsyncWrite + Unpin + Send,
+    {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let chars: Vec<char> = text.chars().collect();
+        let segments: Vec<String> = self
+            .get_chars_range(&chars, true)
+            .into_iter()
+            .map(|r| chars[r].iter().collect())
+            .collect();
+
+        let converted = if self.is_parallel {
+            let total = segments.len();
+            let mut tasks = JoinSet::new();
+            for (index, segment) in segments.into_iter().enumerate() {
+                let converter = Arc::clone(self);
+                let config = config.to_string();
+                // convert() is synchronous, CPU-bound FMM matching — run it on the
+                // blocking thread pool so it can't tie up an async worker thread for
+                // the length of one segment's conversion.
+                tasks.spawn_blocking(move || {
+                    (index, converter.convert(&segment, &config, punctuation))
+                });
+            }
+
+            let mut ordered: Vec<Option<String>> = (0..total).map(|_| None).collect();
+            while let Some(result) = tasks.join_next().await {
+                let (index, piece) =
+                    result.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+                ordered[index] = Some(piece);
+            }
+            ordered
+                .into_iter()
+                .map(|piece| piece.expect("every segment index is populated exactly once"))
+                .collect::<Vec<_>>()
+        } else {
+            let mut out = Vec::with_capacity(segments.len());
+            for segment in segments {
+                out.push(self.convert(&segment, config, punctuation));
+                tokio::task::yield_now().await;
+            }
+            out
+        };
+
+        for piece in converted {
+            writer.write_all(piece.as_bytes()).await?;
+        }
+        writer.flush().await
+    }
+}
+
+#[tokio::test]
+async fn convert_async_converts_text_end_to_end_sequentially() {
+    let mut opencc = OpenCC::new();
+    opencc.set_parallel(false);
+    let opencc = Arc::new(opencc);
+
+    let input_file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(input_file.path(), "你好，世界！龙马精神！").unwrap();
+    let output_file = tempfile::NamedTempFile::new().unwrap();
+
+    let reader = tokio::fs::File::open(input_file.path()).await.unwrap();
+    let writer = tokio::fs::File::create(output_file.path()).await.unwrap();
+    opencc
+        .convert_async(reader, writer, "s2t", false)
+        .await
+        .unwrap();
+
+    let result = std::fs::read_to_string(output_file.path()).unwrap();
+    assert_eq!(result, "你好，世界！龍馬精神！");
+}
+
+#[tokio::test]
+async fn convert_async_converts_text_end_to_end_in_parallel() {
+    let mut opencc = OpenCC::new();
+    opencc.set_parallel(true);
+    let opencc = Arc::new(opencc);
+
+    let input_file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(input_file.path(), "你好，世界！龙马精神！").unwrap();
+    let output_file = tempfile::NamedTempFile::new().unwrap();
+
+    let reader = tokio::fs::File::open(input_file.path()).await.unwrap();
+    let writer = tokio::fs::File::create(output_file.path()).await.unwrap();
+    opencc
+        .convert_async(reader, writer, "s2t", false)
+        .await
+        .unwrap();
+
+    let result = std::fs::read_to_string(output_file.path()).unwrap();
+    assert_eq!(result, "你好，世界！龍馬精神！");
+}
+
+#[tokio::test]
+async fn convert_async_rejects_invalid_utf8() {
+    let opencc = Arc::new(OpenCC::new());
+    let reader = std::io::Cursor::new(vec![0xff, 0xfe, 0xfd]);
+    let writer: Vec<u8> = Vec::new();
+
+    let err = opencc
+        .convert_async(reader, writer, "s2t", false)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
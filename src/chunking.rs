@@ -0,0 +1,121 @@
+//! Optional ICU4X break-aware work chunking for the Rayon parallel path.
+//!
+//! [`segment_replace_with_union`](crate::OpenCC::segment_replace_with_union)'s
+//! `into_par_iter()` fan-out only gets real parallelism when
+//! [`get_chars_range`](crate::OpenCC::get_chars_range) found more than a
+//! handful of delimiter-separated ranges to hand to Rayon. Real CJK prose —
+//! and especially classical Chinese — can run hundreds of characters between
+//! qualifying delimiters, so the whole input becomes one giant range and the
+//! "parallel" path degrades to a single-threaded pass plus reduce overhead.
+//!
+//! Enabling this crate's `icu-chunking` feature and installing
+//! [`ChunkStrategy::IcuBreakAware`] via
+//! [`OpenCC::set_chunk_strategy`](crate::OpenCC::set_chunk_strategy) inserts
+//! an additional split inside every delimiter range at least
+//! [`OpenCC::set_min_chunk_len`](crate::OpenCC::set_min_chunk_len) chars wide,
+//! at a boundary reported by `icu_segmenter`'s sentence-break iterator — so a
+//! split never lands inside a grapheme cluster. Each resulting chunk is still
+//! matched independently through the same
+//! [`convert_by_union`](crate::OpenCC::convert_by_union) /
+//! [`Automaton`](crate::dictionary_lib::Automaton) path a delimiter-only
+//! range would use, so this only changes how work is *divided* across Rayon
+//! tasks — the concatenated output is byte-identical to
+//! [`ChunkStrategy::DelimiterOnly`].
+//!
+//! Without the `icu-chunking` feature compiled in,
+//! [`ChunkStrategy::IcuBreakAware`] silently behaves like
+//! [`ChunkStrategy::DelimiterOnly`] — selecting it is always safe, it just
+//! doesn't rebalance anything until the feature is enabled.
+
+use std::ops::Range;
+
+/// Runtime-selectable strategy for dividing a delimiter-bounded range of
+/// chars into the pieces
+/// [`segment_replace_with_union`](crate::OpenCC::segment_replace_with_union)
+/// hands to Rayon, installed via
+/// [`OpenCC::set_chunk_strategy`](crate::OpenCC::set_chunk_strategy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkStrategy {
+    /// Parallelize only across the ranges
+    /// [`get_chars_range`](crate::OpenCC::get_chars_range) already finds at
+    /// delimiter boundaries — this crate's behavior before this enum existed.
+    #[default]
+    DelimiterOnly,
+    /// Additionally split delimiter ranges wider than
+    /// [`OpenCC::set_min_chunk_len`](crate::OpenCC::set_min_chunk_len) at
+    /// ICU4X sentence-break boundaries (see the module docs).
+    IcuBreakAware,
+}
+
+/// Splits `range` into Rayon-sized pieces per `strategy`, never producing a
+/// piece shorter than `min_chunk_len` chars unless `range` itself is shorter.
+///
+/// `chars` is the full char buffer `range` indexes into — only the slice
+/// `chars[range.clone()]` is consulted. A no-op (`vec![range]`) for
+/// [`ChunkStrategy::DelimiterOnly`], and for [`ChunkStrategy::IcuBreakAware`]
+/// when the `icu-chunking` feature isn't compiled in.
+pub(crate) fn rebalance(
+    range: Range<usize>,
+    chars: &[char],
+    min_chunk_len: usize,
+    strategy: ChunkStrategy,
+) -> Vec<Range<usize>> {
+    match strategy {
+        ChunkStrategy::DelimiterOnly => vec![range],
+        ChunkStrategy::IcuBreakAware => icu_break_chunks(range, chars, min_chunk_len),
+    }
+}
+
+#[cfg(feature = "icu-chunking")]
+fn icu_break_chunks(
+    range: Range<usize>,
+    chars: &[char],
+    min_chunk_len: usize,
+) -> Vec<Range<usize>> {
+    use icu_segmenter::SentenceSegmenter;
+
+    let len = range.len();
+    if len <= min_chunk_len.max(1) {
+        return vec![range];
+    }
+
+    // `icu_segmenter` reports byte offsets into a `&str`, not `char` offsets
+    // into `&[char]`, so this segment is rebuilt as an owned `String` just
+    // for break detection, and every accepted break is translated back to a
+    // `char` offset via `char_indices` before being translated again into an
+    // absolute position in `chars`.
+    let segment: String = chars[range.clone()].iter().collect();
+    let byte_to_char: std::collections::HashMap<usize, usize> = segment
+        .char_indices()
+        .enumerate()
+        .map(|(char_idx, (byte_offset, _))| (byte_offset, char_idx))
+        .collect();
+
+    let segmenter = SentenceSegmenter::new();
+    let mut pieces = Vec::new();
+    let mut piece_start = 0usize;
+
+    for byte_break in segmenter.segment_str(&segment) {
+        if byte_break == 0 || byte_break >= segment.len() {
+            continue;
+        }
+        let Some(&char_break) = byte_to_char.get(&byte_break) else {
+            continue;
+        };
+        if char_break - piece_start >= min_chunk_len && len - char_break >= min_chunk_len {
+            pieces.push(range.start + piece_start..range.start + char_break);
+            piece_start = char_break;
+        }
+    }
+    pieces.push(range.start + piece_start..range.start + len);
+    pieces
+}
+
+#[cfg(not(feature = "icu-chunking"))]
+fn icu_break_chunks(
+    range: Range<usize>,
+    _chars: &[char],
+    _min_chunk_len: usize,
+) -> Vec<Range<usize>> {
+    vec![range]
+}
@@ -26,15 +26,18 @@ pub const FULL_DELIMITERS: &str =
 /// * **BMP fast path**: all code points `U+0000..=U+FFFF` are stored in a
 ///   65,536-bit table (`[u64; 1024]`, ~8 KB). Each character maps to one bit,
 ///   making lookup a constant-time O(1) operation with predictable branch-free code.
-/// * **Astral characters**: `U+10000..` are always reported as non-delimiters, since
-///   no delimiters exist in that range for this project.
-///
-/// This design avoids the hashing overhead of a `HashSet<char>` and is especially
-/// effective in hot loops that scan millions of characters.
-#[derive(Copy, Clone)]
+/// * **Astral tier**: `U+10000..` delimiters are rare, so rather than a third dense
+///   table they're kept in a small sorted [`Vec<u32>`] searched via binary search —
+///   only reached when a code point clears the BMP fast path, so it doesn't cost
+///   anything on the hot ASCII/BMP paths.
+///
+/// This design avoids the hashing overhead of a `HashSet<char>` on the hot paths
+/// and is especially effective in hot loops that scan millions of characters.
+#[derive(Clone, Default)]
 pub struct DelimiterSet {
     ascii_mask: u128,      // bits 0..=127
     bmp_bits: [u64; 1024], // 0x0000..=0xFFFF
+    astral: Vec<u32>,      // sorted, 0x10000..
 }
 
 impl DelimiterSet {
@@ -58,8 +61,112 @@ impl DelimiterSet {
             let b = u & 63;
             return ((self.bmp_bits[i] >> b) & 1) == 1;
         }
-        // Astral punctuation is virtually nonexistent in delimiters set; treat as non-delim
-        false
+        self.astral.binary_search(&u).is_ok()
+    }
+}
+
+/// Builder for a custom [`DelimiterSet`], for callers who need segmentation to
+/// stop breaking on a character [`FULL_DELIMITERS`] includes, start breaking on
+/// one it doesn't (e.g. keeping `/` or `-` intact inside product codes or URLs),
+/// or supply astral (`U+10000..`) punctuation — such as CJK Extension-B marks —
+/// that [`DelimiterSet::contains`] otherwise always reports as a non-delimiter.
+#[derive(Clone, Default)]
+pub struct DelimiterSetBuilder {
+    ascii_mask: u128,
+    bmp_bits: [u64; 1024],
+    astral: Vec<u32>,
+}
+
+impl DelimiterSetBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from the built-in [`FULL_DELIMITERS`] set, so callers can adjust
+    /// it with [`insert`](Self::insert)/[`remove`](Self::remove) instead of
+    /// re-listing every delimiter.
+    pub fn from_full_delimiters() -> Self {
+        let mut builder = Self::new();
+        builder.insert_chars(FULL_DELIMITERS.chars());
+        builder
+    }
+
+    /// Builds a set from exactly the characters in `chars`.
+    pub fn from_chars(chars: impl IntoIterator<Item = char>) -> Self {
+        let mut builder = Self::new();
+        builder.insert_chars(chars);
+        builder
+    }
+
+    /// Builds a set from every character across `ranges` (each an inclusive
+    /// `[lo, hi]` pair), letting callers declare whole Unicode blocks — e.g.
+    /// CJK Symbols and Punctuation (`U+3000..=U+303F`) — instead of
+    /// enumerating every codepoint with [`from_chars`](Self::from_chars).
+    pub fn from_ranges(ranges: impl IntoIterator<Item = (char, char)>) -> Self {
+        let mut builder = Self::new();
+        for (lo, hi) in ranges {
+            builder.insert_range(lo, hi);
+        }
+        builder
+    }
+
+    /// Marks `c` as a delimiter.
+    pub fn insert(&mut self, c: char) -> &mut Self {
+        let u = c as u32;
+        if u <= 0x7F {
+            self.ascii_mask |= 1u128 << u;
+        } else if u <= 0xFFFF {
+            let i = (u >> 6) as usize;
+            let b = u & 63;
+            self.bmp_bits[i] |= 1u64 << b;
+        } else if let Err(pos) = self.astral.binary_search(&u) {
+            self.astral.insert(pos, u);
+        }
+        self
+    }
+
+    /// Marks every character in `chars` as a delimiter.
+    pub fn insert_chars(&mut self, chars: impl IntoIterator<Item = char>) -> &mut Self {
+        for c in chars {
+            self.insert(c);
+        }
+        self
+    }
+
+    /// Marks every character in the inclusive range `lo..=hi` as a
+    /// delimiter, skipping the surrogate gap (`U+D800..=U+DFFF`) since it
+    /// contains no valid `char` values.
+    pub fn insert_range(&mut self, lo: char, hi: char) -> &mut Self {
+        for u in (lo as u32)..=(hi as u32) {
+            if let Some(c) = char::from_u32(u) {
+                self.insert(c);
+            }
+        }
+        self
+    }
+
+    /// Un-marks `c` as a delimiter.
+    pub fn remove(&mut self, c: char) -> &mut Self {
+        let u = c as u32;
+        if u <= 0x7F {
+            self.ascii_mask &= !(1u128 << u);
+        } else if u <= 0xFFFF {
+            let i = (u >> 6) as usize;
+            let b = u & 63;
+            self.bmp_bits[i] &= !(1u64 << b);
+        } else if let Ok(pos) = self.astral.binary_search(&u) {
+            self.astral.remove(pos);
+        }
+        self
+    }
+
+    /// Finishes the set.
+    pub fn build(&self) -> DelimiterSet {
+        DelimiterSet {
+            ascii_mask: self.ascii_mask,
+            bmp_bits: self.bmp_bits,
+            astral: self.astral.clone(),
+        }
     }
 }
 
@@ -99,6 +206,7 @@ pub static FULL_DELIMITER_SET: Lazy<DelimiterSet> = Lazy::new(|| {
     DelimiterSet {
         ascii_mask: ascii,
         bmp_bits: bmp,
+        astral: Vec::new(),
     }
 });
 
@@ -1,4 +1,5 @@
 use crate::dictionary_lib::{DictMaxLen, StarterUnion};
+use crate::normalize::normalize;
 use std::sync::Arc;
 
 /// One conversion round: a set of dictionaries + its computed `max_len` + the
@@ -34,12 +35,14 @@ fn compute_round<'a>(dicts: &'a [&'a DictMaxLen], union: Arc<StarterUnion>) -> D
     }
 }
 
-/// Holds up to three conversion rounds. Each round carries its own
+/// Holds an arbitrary number of conversion rounds. Each round carries its own
 /// dictionaries, `max_len`, and prebuilt [`StarterUnion`].
 ///
-/// This struct is a small orchestrator: you assemble rounds (R1 is required,
-/// R2/R3 are optional), then call [`apply_segment_replace`] with your engine’s
-/// segment/replace closure (e.g., a wrapper around `convert_by_union`).
+/// This struct is a small orchestrator: you assemble rounds (R1 is required
+/// via [`new`](Self::new), any further rounds pushed via
+/// [`with_round`](Self::with_round)), then call [`apply_segment_replace`]
+/// with your engine’s segment/replace closure (e.g., a wrapper around
+/// `convert_by_union`).
 ///
 /// # Example
 /// Minimal example that builds two tiny dictionaries, a shared union,
@@ -69,9 +72,7 @@ fn compute_round<'a>(dicts: &'a [&'a DictMaxLen], union: Arc<StarterUnion>) -> D
 /// For a full conversion, your closure would call your engine’s
 /// `segment_replace_with_union(input, dicts, max_len, union)`.
 pub struct DictRefs<'a> {
-    round_1: DictRound<'a>,
-    round_2: Option<DictRound<'a>>,
-    round_3: Option<DictRound<'a>>,
+    rounds: Vec<DictRound<'a>>,
 }
 
 impl<'a> DictRefs<'a> {
@@ -92,37 +93,43 @@ impl<'a> DictRefs<'a> {
     /// ```
     pub fn new(round_1_dicts: &'a [&'a DictMaxLen], round_1_union: Arc<StarterUnion>) -> Self {
         Self {
-            round_1: compute_round(round_1_dicts, round_1_union),
-            round_2: None,
-            round_3: None,
+            rounds: vec![compute_round(round_1_dicts, round_1_union)],
         }
     }
 
+    /// Appends another round, run after every round added so far.
+    ///
+    /// `union` should be built from `dicts`.
+    pub fn with_round(mut self, dicts: &'a [&'a DictMaxLen], union: Arc<StarterUnion>) -> Self {
+        self.rounds.push(compute_round(dicts, union));
+        self
+    }
+
     /// Adds **optional** round 2.
     ///
     /// `round_2_union` should be built from `round_2_dicts`.
+    #[deprecated(note = "use `with_round` instead")]
     pub fn with_round_2(
-        mut self,
+        self,
         round_2_dicts: &'a [&'a DictMaxLen],
         round_2_union: Arc<StarterUnion>,
     ) -> Self {
-        self.round_2 = Some(compute_round(round_2_dicts, round_2_union));
-        self
+        self.with_round(round_2_dicts, round_2_union)
     }
 
     /// Adds **optional** round 3.
     ///
     /// `round_3_union` should be built from `round_3_dicts`.
+    #[deprecated(note = "use `with_round` instead")]
     pub fn with_round_3(
-        mut self,
+        self,
         round_3_dicts: &'a [&'a DictMaxLen],
         round_3_union: Arc<StarterUnion>,
     ) -> Self {
-        self.round_3 = Some(compute_round(round_3_dicts, round_3_union));
-        self
+        self.with_round(round_3_dicts, round_3_union)
     }
 
-    /// Applies up to three rounds using a caller-provided segment/replace closure.
+    /// Applies every round in order using a caller-provided segment/replace closure.
     ///
     /// The closure receives:
     /// - `&str` — the input for that round (segment or whole string),
@@ -132,6 +139,11 @@ impl<'a> DictRefs<'a> {
     ///
     /// It must return the transformed `String` for that round.
     ///
+    /// `input` is first passed through [`normalize`](crate::normalize::normalize)
+    /// (a no-op unless this crate's `nfc`/`nfd`/`nfkc`/`nfkd` feature is
+    /// enabled), so every conversion entry point normalizes consistently
+    /// with how dictionary keys were built.
+    ///
     /// # Example
     /// ```
     /// # use std::sync::Arc;
@@ -153,19 +165,9 @@ impl<'a> DictRefs<'a> {
     where
         F: Fn(&str, &[&DictMaxLen], usize, &StarterUnion) -> String,
     {
-        let mut out = segment_replace(
-            input,
-            self.round_1.dicts,
-            self.round_1.max_len,
-            &self.round_1.union,
-        );
-
-        if let Some(r2) = &self.round_2 {
-            out = segment_replace(&out, r2.dicts, r2.max_len, &r2.union);
-        }
-        if let Some(r3) = &self.round_3 {
-            out = segment_replace(&out, r3.dicts, r3.max_len, &r3.union);
-        }
-        out
+        let normalized = normalize(input);
+        self.rounds.iter().fold(normalized, |acc, round| {
+            segment_replace(&acc, round.dicts, round.max_len, &round.union)
+        })
     }
 }
@@ -0,0 +1,263 @@
+//! A merged-trie matcher, offered as an alternative to the union-pruned
+//! forward-maximum-matching (FMM) engine in
+//! [`OpenCC::convert_by_union`](crate::OpenCC::convert_by_union).
+//!
+//! FMM re-probes every viable length at every `start_pos` across all
+//! dictionaries (`O(N·K·D)`, `D` = number of dictionaries in the round); this
+//! engine instead compiles the whole key set into a single trie once, so a
+//! multi-dictionary round only pays the `D` factor once (at build time, not
+//! per character) — selected at runtime via
+//! [`MatchEngine::Automaton`](crate::MatchEngine) and
+//! [`OpenCC::set_match_engine`](crate::OpenCC::set_match_engine); the
+//! union-FMM path above remains the default.
+//!
+//! # Construction
+//! Every key from every dictionary (in precedence order) is inserted into a
+//! trie; each node that terminates a key records an `(length, replacement)`
+//! output. Ties (two dictionaries contributing the exact same key) resolve
+//! toward the earlier dictionary, matching this crate's existing first-wins
+//! precedence (see
+//! [`DictMaxLen::build_from_pairs`](crate::dictionary_lib::DictMaxLen::build_from_pairs)).
+//!
+//! # Matching
+//! [`replace_leftmost_longest`](Automaton::replace_leftmost_longest)
+//! reproduces FMM's actual semantics: at each unmatched position, it
+//! descends the trie as far as the input literally allows, remembering the
+//! *last* (hence longest, since a trie's key length only ever grows with
+//! depth along a single path) complete key seen along that descent. Only
+//! once the descent can no longer be extended — the next character has no
+//! matching child, or the input ends — does it commit: the longest key
+//! found wins, matching FMM's "try the longest length first" rule exactly,
+//! not merely "the first key that happens to complete". If no key ever
+//! completed during the descent, the position's character is copied through
+//! unmatched and the next position is tried.
+//!
+//! An earlier version of this matcher used an Aho-Corasick-style
+//! failure-link automaton and committed to a replacement the instant *any*
+//! node carried an output, even when deeper trie children could still
+//! extend the match. That diverged from FMM whenever a short key was a
+//! prefix of a longer one in the same table (e.g. `"A"->"1"` alongside
+//! `"ABC"->"2"`, converting `"ABC"` to `"1C"` instead of `"2"`) — exactly
+//! the shape of the real `STCharacters`/`STPhrases` tables this crate
+//! ships. Committing only once the descent is provably exhausted fixes
+//! that: failure links fundamentally track the longest match *ending* at a
+//! position across every possible start, which is the wrong question here —
+//! FMM only ever asks "what's the longest key *starting* at this exact
+//! position".
+//!
+//! # Multi-round pipelines and astral input
+//! Keys are indexed by `char`, not by `StarterUnion`'s BMP-only bitmask, so
+//! there's no separate astral case to get right: a trie transition on a
+//! supplementary-plane starter is exactly as valid as one on a BMP starter.
+//! Per-round selection falls out of [`automaton_for_dicts`]'s cache being
+//! keyed by dictionary identity: [`OpenCC::segment_replace_with_union`](crate::OpenCC)
+//! requests an automaton for whatever `dictionaries` that round's
+//! [`StarterUnion`] was built from, so a multi-round config (`s2tw` and
+//! friends) transparently gets one compiled automaton per round.
+//!
+//! [`automaton_for_dicts`]: super::dictionary_maxlength::DictionaryMaxlength::automaton_for_dicts
+
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+
+use crate::dictionary_lib::dict_max_len::DictMaxLen;
+
+/// Selects which engine [`OpenCC`](crate::OpenCC) uses to match dictionary
+/// keys during segment replacement, installed via
+/// [`OpenCC::set_match_engine`](crate::OpenCC::set_match_engine).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchEngine {
+    /// Union-pruned forward maximum matching (the existing default):
+    /// re-probes viable lengths at every position via
+    /// [`convert_by_union`](crate::OpenCC::convert_by_union).
+    Fmm,
+    /// A single compiled [`Automaton`] scanned once per segment, generally
+    /// faster for dictionary sets with many tables, since the per-dictionary
+    /// factor is paid once at build time rather than at every position.
+    Automaton,
+}
+
+impl Default for MatchEngine {
+    fn default() -> Self {
+        Self::Fmm
+    }
+}
+
+/// Index of the automaton's root node.
+const ROOT: usize = 0;
+
+/// A dictionary match recorded at a trie node: the matched key's length (in
+/// `char`s) and its replacement text.
+#[derive(Debug, Clone)]
+struct Output {
+    len: usize,
+    replacement: Arc<str>,
+    /// Index into the `dicts` slice passed to [`Automaton::build`]; lower
+    /// wins precedence ties.
+    dict_rank: usize,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: FxHashMap<char, usize>,
+    /// Output terminating exactly at this node (this node's own path is a
+    /// dictionary key).
+    own_output: Option<Output>,
+}
+
+/// A compiled trie over a fixed set of dictionaries, supporting one
+/// left-to-right FMM-equivalent replacement pass per segment.
+///
+/// Build once per dictionary-set combination (see
+/// [`DictionaryMaxlength::automaton_for_dicts`](super::dictionary_maxlength::DictionaryMaxlength::automaton_for_dicts),
+/// which caches instances the same way
+/// [`union_for_dicts`](super::dictionary_maxlength::DictionaryMaxlength::union_for_dicts)
+/// caches [`StarterUnion`](super::StarterUnion)s), then reuse across many
+/// conversions via a cheap `Arc` clone.
+#[derive(Debug)]
+pub struct Automaton {
+    nodes: Vec<Node>,
+}
+
+impl Automaton {
+    /// Builds an automaton from `dicts`, probed in precedence order (earlier
+    /// dictionaries win exact-key ties, matching this crate's other
+    /// first-wins dictionary semantics).
+    pub fn build(dicts: &[&DictMaxLen]) -> Self {
+        let mut nodes = vec![Node::default()];
+
+        for (dict_rank, dict) in dicts.iter().enumerate() {
+            for (key, value) in dict.map.iter() {
+                if key.is_empty() {
+                    continue;
+                }
+
+                let mut cur = ROOT;
+                for &c in key.iter() {
+                    cur = match nodes[cur].children.get(&c) {
+                        Some(&next) => next,
+                        None => {
+                            nodes.push(Node::default());
+                            let next = nodes.len() - 1;
+                            nodes[cur].children.insert(c, next);
+                            next
+                        }
+                    };
+                }
+
+                let candidate = Output {
+                    len: key.len(),
+                    replacement: Arc::from(value.as_ref()),
+                    dict_rank,
+                };
+                match &nodes[cur].own_output {
+                    None => nodes[cur].own_output = Some(candidate),
+                    Some(existing) if candidate.dict_rank < existing.dict_rank => {
+                        nodes[cur].own_output = Some(candidate);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Self { nodes }
+    }
+
+    /// Scans `chars` left to right, replacing each matched key with its
+    /// dictionary value and leaving every other `char` untouched.
+    ///
+    /// At each unmatched position, descends the trie as far as `chars`
+    /// allows, remembering the longest complete key seen; only once the
+    /// descent cannot be extended further (no child for the next `char`, or
+    /// input exhausted) does it commit that key — reproducing FMM's
+    /// longest-match-at-this-position rule exactly, see the module docs.
+    pub fn replace_leftmost_longest(&self, chars: &[char]) -> String {
+        let mut out = String::with_capacity(chars.len());
+        let mut start = 0usize;
+
+        while start < chars.len() {
+            let mut node = ROOT;
+            let mut depth = 0usize;
+            let mut best: Option<&Output> = None;
+
+            while start + depth < chars.len() {
+                let c = chars[start + depth];
+                match self.nodes[node].children.get(&c) {
+                    Some(&next) => {
+                        node = next;
+                        depth += 1;
+                        if let Some(output) = &self.nodes[node].own_output {
+                            best = Some(output);
+                        }
+                    }
+                    None => break,
+                }
+            }
+
+            match best {
+                Some(output) => {
+                    out.push_str(&output.replacement);
+                    start += output.len;
+                }
+                None => {
+                    out.push(chars[start]);
+                    start += 1;
+                }
+            }
+        }
+
+        out
+    }
+}
+
+#[test]
+fn replace_leftmost_longest_prefers_longer_key_over_its_own_prefix() {
+    // "A" -> "1" and "ABC" -> "2" in the same table: FMM always tries the
+    // longest possible length first, so "ABC" must convert to "2", not "1C"
+    // (which is what committing the instant "A" completes would produce).
+    let dict = DictMaxLen::build_from_pairs(vec![
+        ("A".to_string(), "1".to_string()),
+        ("ABC".to_string(), "2".to_string()),
+    ]);
+    let automaton = Automaton::build(&[&dict]);
+
+    let chars: Vec<char> = "ABC".chars().collect();
+    assert_eq!(automaton.replace_leftmost_longest(&chars), "2");
+
+    // A trailing char after the longer match still falls through untouched,
+    // and a standalone "A" (no following "BC") still matches the short key.
+    let chars: Vec<char> = "ABCD".chars().collect();
+    assert_eq!(automaton.replace_leftmost_longest(&chars), "2D");
+    let chars: Vec<char> = "AX".chars().collect();
+    assert_eq!(automaton.replace_leftmost_longest(&chars), "1X");
+}
+
+#[test]
+fn replace_leftmost_longest_matches_fmm_on_real_dictionaries() {
+    use crate::{MatchEngine, OpenCC};
+
+    // The exact shape the review flagged: a phrase table and a character
+    // table sharing a dictionary-rank prefix relationship, mixed via
+    // `automaton_for_dicts(&[&d.st_phrases, &d.st_characters])` at
+    // `segment_replace_with_union`'s call site. Any text containing a
+    // multi-character ST phrase whose first character is also its own
+    // ST-character entry exercises the bug this test guards against.
+    let samples = [
+        "我们都是中国人，在北京天安门广场见面。",
+        "他在图书馆看了一本关于人工智能的书。",
+        "汉字从繁体转换为简体，经过了几千年的演变。",
+    ];
+
+    for text in samples {
+        let mut fmm = OpenCC::new();
+        fmm.set_match_engine(MatchEngine::Fmm);
+        let mut automaton = OpenCC::new();
+        automaton.set_match_engine(MatchEngine::Automaton);
+
+        assert_eq!(
+            fmm.convert(text, "s2t", false),
+            automaton.convert(text, "s2t", false),
+            "Automaton and FMM diverged on {text:?}"
+        );
+    }
+}
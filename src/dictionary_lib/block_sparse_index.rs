@@ -0,0 +1,158 @@
+//! A block-sparse, lazily-materialized alternative to
+//! [`DictMaxLen`](super::dict_max_len::DictMaxLen)'s dense BMP starter-index
+//! arrays, and a sibling of [`CompressedStarterIndex`](super::starter_index::CompressedStarterIndex).
+//!
+//! Borrows the lazy-block idea from rustc's `InitMask`: the BMP is
+//! partitioned into [`BLOCK_SIZE`]-starter blocks ([`NUM_BLOCKS`] of them,
+//! chosen so a presence bitset fits in one `u64`). A block is only
+//! allocated the first time a key lands a nonzero mask in it; blocks that
+//! stay entirely empty (e.g. whole unused Unicode planes for a
+//! punctuation-only table) never cost anything beyond one bit in
+//! `block_present`.
+//!
+//! Where [`CompressedStarterIndex`](super::starter_index::CompressedStarterIndex)
+//! trades a binary search over runs for a compact flat layout,
+//! [`BlockSparseStarterIndex`] keeps direct indexing (`O(1)`, no search) at
+//! the cost of per-block granularity rather than per-run — a better fit
+//! when live starters are clustered into a few contiguous Unicode blocks
+//! but not necessarily globally rare.
+
+/// Number of BMP code points per block.
+const BLOCK_SIZE: usize = 1024;
+/// Number of blocks covering the BMP (`0x10000 / BLOCK_SIZE`), chosen so the
+/// presence bitset fits in a single `u64`.
+const NUM_BLOCKS: usize = 0x10000 / BLOCK_SIZE;
+
+/// Block-sparse, lazily-materialized starter index; see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct BlockSparseStarterIndex {
+    block_present: u64,
+    mask_blocks: Vec<Option<Box<[u64; BLOCK_SIZE]>>>,
+    cap_blocks: Vec<Option<Box<[u8; BLOCK_SIZE]>>>,
+}
+
+impl BlockSparseStarterIndex {
+    /// Builds a block-sparse index from `(starter, mask, cap)` triples (BMP
+    /// only; order doesn't matter). A block is allocated on first write.
+    pub fn build(entries: &[(char, u64, u8)]) -> Self {
+        let mut block_present = 0u64;
+        let mut mask_blocks: Vec<Option<Box<[u64; BLOCK_SIZE]>>> = vec![None; NUM_BLOCKS];
+        let mut cap_blocks: Vec<Option<Box<[u8; BLOCK_SIZE]>>> = vec![None; NUM_BLOCKS];
+
+        for &(c, mask, cap) in entries {
+            if mask == 0 && cap == 0 {
+                continue;
+            }
+            let u = c as usize;
+            if u >= 0x10000 {
+                continue; // dense/block-sparse tables are BMP-only
+            }
+            let block = u / BLOCK_SIZE;
+            let offset = u % BLOCK_SIZE;
+
+            if block_present & (1u64 << block) == 0 {
+                mask_blocks[block] = Some(Box::new([0u64; BLOCK_SIZE]));
+                cap_blocks[block] = Some(Box::new([0u8; BLOCK_SIZE]));
+                block_present |= 1u64 << block;
+            }
+            mask_blocks[block].as_mut().unwrap()[offset] = mask;
+            cap_blocks[block].as_mut().unwrap()[offset] = cap;
+        }
+
+        BlockSparseStarterIndex {
+            block_present,
+            mask_blocks,
+            cap_blocks,
+        }
+    }
+
+    /// Number of blocks actually materialized (out of [`NUM_BLOCKS`]).
+    pub fn block_count(&self) -> usize {
+        self.block_present.count_ones() as usize
+    }
+
+    /// Returns the raw length mask for `starter`, or `0` if its block was
+    /// never materialized (no keys start with it).
+    #[inline]
+    pub fn get_mask(&self, starter: char) -> u64 {
+        let u = starter as usize;
+        if u >= 0x10000 {
+            return 0;
+        }
+        let block = u / BLOCK_SIZE;
+        if self.block_present & (1u64 << block) == 0 {
+            return 0;
+        }
+        self.mask_blocks[block].as_ref().unwrap()[u % BLOCK_SIZE]
+    }
+
+    /// Same contract as
+    /// [`DictMaxLen::starter_allows_dict`](super::dict_max_len::DictMaxLen::starter_allows_dict)'s
+    /// dense fast-path: `true` if a key of `length` exists starting with
+    /// `starter`, using `bit = length - 1` for `length <= 64`, or the
+    /// stored cap for longer keys.
+    #[inline]
+    pub fn allows(&self, starter: char, length: usize, bit: usize) -> bool {
+        let u = starter as usize;
+        if u >= 0x10000 {
+            return false;
+        }
+        let block = u / BLOCK_SIZE;
+        if self.block_present & (1u64 << block) == 0 {
+            return false;
+        }
+        let offset = u % BLOCK_SIZE;
+        if bit < 64 {
+            let mask_block = self.mask_blocks[block].as_ref().unwrap();
+            return (mask_block[offset] >> bit) & 1 != 0;
+        }
+        let cap_block = self.cap_blocks[block].as_ref().unwrap();
+        length <= cap_block[offset] as usize
+    }
+}
+
+#[test]
+fn build_materializes_only_blocks_with_live_starters() {
+    let entries = [('a', 0b1, 1u8), ('b', 0b10, 1u8)];
+    let index = BlockSparseStarterIndex::build(&entries);
+
+    assert_eq!(index.block_count(), 1); // 'a' and 'b' share one 1024-wide block
+    assert!(index.allows('a', 1, 0));
+    assert!(index.allows('b', 2, 1));
+    assert!(!index.allows('a', 2, 1));
+}
+
+#[test]
+fn lookups_for_unmaterialized_blocks_return_defaults() {
+    let entries = [('a', 0b1, 1u8)];
+    let index = BlockSparseStarterIndex::build(&entries);
+
+    // '你' (U+4F60) falls far outside 'a''s block.
+    assert!(!index.allows('你', 1, 0));
+    assert_eq!(index.get_mask('你'), 0);
+}
+
+#[test]
+fn all_zero_entries_allocate_no_blocks() {
+    let entries = [('a', 0u64, 0u8)];
+    let index = BlockSparseStarterIndex::build(&entries);
+    assert_eq!(index.block_count(), 0);
+    assert_eq!(index.get_mask('a'), 0);
+}
+
+#[test]
+fn empty_index_allows_nothing() {
+    let index = BlockSparseStarterIndex::build(&[]);
+    assert_eq!(index.block_count(), 0);
+    assert!(!index.allows('a', 1, 0));
+    assert_eq!(index.get_mask('a'), 0);
+}
+
+#[test]
+fn allows_falls_back_to_cap_for_bit_at_or_above_64() {
+    let entries = [('a', u64::MAX, 70u8)];
+    let index = BlockSparseStarterIndex::build(&entries);
+
+    assert!(index.allows('a', 70, 69));
+    assert!(!index.allows('a', 71, 70));
+}
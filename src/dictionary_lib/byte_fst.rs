@@ -0,0 +1,201 @@
+//! Byte-level finite-state transducer (FST) backend for phrase lookup.
+//!
+//! [`ByteFstDict`] is a sibling of [`FstDict`](super::fst_dict::FstDict): same
+//! shared-prefix transition-table idea, but keyed on the UTF-8 **bytes** of a
+//! phrase instead of its `char`s, with outputs deduplicated through a value
+//! pool instead of one `Box<str>` per key. Built once at dictionary-generation
+//! time (see [`build_byte_fst`]) and shipped as a serialized blob, so loading
+//! it at runtime is a single `bincode::deserialize` rather than a sort +
+//! insert pass over every entry — this is what lets
+//! [`DictionaryMaxlength::from_embedded_bincode`](super::DictionaryMaxlength::from_embedded_bincode)
+//! skip rebuilding the transducer on every cold start.
+//!
+//! # Structure
+//!
+//! The table is a flat `Vec<ByteFstState>`. State `0` is the root. Each state
+//! holds its outgoing byte transitions sorted by byte value (enabling binary
+//! search) plus an optional index into [`ByteFstDict::values`] when the state
+//! is accepting (i.e., some key ends there). Identical replacement strings
+//! share one [`values`](ByteFstDict::values) slot, found via
+//! [`build_byte_fst`]'s dedup pass — so `values` indices stay dense and
+//! monotonically assigned in first-seen order.
+//!
+//! # Longest match
+//!
+//! [`ByteFstDict::lookup_longest`] walks the table once, left to right over
+//! `bytes`, remembering the deepest accepting state seen so far — the same
+//! single-pass, last-accepting-state-wins strategy as
+//! [`FstDict::lookup_longest`](super::fst_dict::FstDict::lookup_longest), so
+//! it reproduces the same longest-match result, just operating on raw UTF-8
+//! bytes instead of decoded `char`s.
+use serde::{Deserialize, Serialize};
+
+/// One node of the byte transition table. See the [module docs](self).
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+struct ByteFstState {
+    /// Outgoing transitions, sorted by byte value for binary search.
+    transitions: Vec<(u8, u32)>,
+    /// Index into [`ByteFstDict::values`] if a key ends at this state.
+    value_idx: Option<u32>,
+}
+
+/// A compact, shared-prefix lookup table over `(phrase bytes, replacement)`
+/// entries, with replacement strings deduplicated through a value pool.
+///
+/// Built once via [`build_byte_fst`] from a dictionary's `(key, value)`
+/// pairs, then queried with [`ByteFstDict::lookup_longest`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ByteFstDict {
+    /// Transition table; `states[0]` is the root.
+    states: Vec<ByteFstState>,
+    /// Deduplicated arena of replacement strings, referenced by
+    /// [`ByteFstState::value_idx`]. Indices are assigned in first-seen order
+    /// while walking the sorted key list, so they stay stable across builds
+    /// that see the same keys in the same order.
+    values: Vec<Box<str>>,
+    /// Maximum key length in **characters** (not bytes) across this table,
+    /// mirroring [`DictMaxLen::max_len`](super::DictMaxLen::max_len) for the
+    /// same entries.
+    pub max_length: usize,
+}
+
+impl ByteFstDict {
+    /// Walks `bytes` left to right, returning the `(byte_length, value)` of
+    /// the **longest** key that is a prefix of `bytes`, or `None` if no key
+    /// matches (including when the table is empty).
+    ///
+    /// This is a single traversal: at each step the deepest accepting state
+    /// seen so far is remembered, and the walk stops as soon as the input is
+    /// exhausted or no outgoing transition matches the next byte. Callers
+    /// working in `char`s (as FMM segmentation does) should encode their
+    /// candidate slice to UTF-8 first and convert the returned byte length
+    /// back to a char count — see
+    /// [`DictMaxLen::lookup_longest`](super::DictMaxLen::lookup_longest).
+    pub fn lookup_longest(&self, bytes: &[u8]) -> Option<(usize, &str)> {
+        let mut state_idx = 0usize;
+        let mut best: Option<(usize, u32)> = None;
+
+        for (i, &b) in bytes.iter().enumerate() {
+            let state = &self.states[state_idx];
+            match state.transitions.binary_search_by_key(&b, |&(t, _)| t) {
+                Ok(pos) => state_idx = state.transitions[pos].1 as usize,
+                Err(_) => break,
+            }
+            if let Some(value_idx) = self.states[state_idx].value_idx {
+                best = Some((i + 1, value_idx));
+            }
+        }
+
+        best.map(|(len, value_idx)| (len, &*self.values[value_idx as usize]))
+    }
+
+    /// Returns `true` if this table has no keys at all.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Builds a [`ByteFstDict`] from `(key, value)` pairs.
+///
+/// ### Invariants this function establishes
+/// - **Sorted keys:** pairs are sorted by key bytes first, so shared prefixes
+///   are inserted contiguously and the transducer stays small regardless of
+///   the source map's iteration order.
+/// - **Stable, deduplicated value-pool indices:** identical replacement
+///   strings are assigned the same [`ByteFstDict::values`] slot, in the order
+///   they're first seen while walking the sorted keys; re-running this
+///   function over the same input always reproduces the same indices.
+/// - **First-wins duplicates:** if the same key appears twice, the first
+///   occurrence (in sorted order) wins; later duplicates are skipped — same
+///   rule as [`FstDict::build`](super::fst_dict::FstDict::build).
+///
+/// ### Empty input
+/// An empty iterator produces a table with only the root state, no values,
+/// and `max_length == 0`; [`lookup_longest`](ByteFstDict::lookup_longest)
+/// then always returns `None`.
+pub fn build_byte_fst<'a, I>(pairs: I) -> ByteFstDict
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let mut sorted: Vec<(&'a str, &'a str)> = pairs.into_iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut states = vec![ByteFstState::default()];
+    let mut values: Vec<Box<str>> = Vec::new();
+    let mut value_lookup: std::collections::HashMap<&str, u32> = std::collections::HashMap::new();
+    let mut max_length = 0usize;
+
+    let mut prev_key: Option<&str> = None;
+    for (key, value) in sorted {
+        if prev_key == Some(key) {
+            continue; // duplicate key: first-wins
+        }
+        prev_key = Some(key);
+
+        let mut state_idx = 0usize;
+        for &b in key.as_bytes() {
+            let transitions = &mut states[state_idx].transitions;
+            state_idx = match transitions.binary_search_by_key(&b, |&(t, _)| t) {
+                Ok(pos) => transitions[pos].1 as usize,
+                Err(pos) => {
+                    let new_idx = states.len() as u32;
+                    transitions.insert(pos, (b, new_idx));
+                    states.push(ByteFstState::default());
+                    new_idx as usize
+                }
+            };
+        }
+
+        let value_idx = *value_lookup.entry(value).or_insert_with(|| {
+            let idx = values.len() as u32;
+            values.push(Box::from(value));
+            idx
+        });
+        states[state_idx].value_idx = Some(value_idx);
+        max_length = max_length.max(key.chars().count());
+    }
+
+    ByteFstDict {
+        states,
+        values,
+        max_length,
+    }
+}
+
+#[test]
+fn lookup_longest_prefers_the_longest_matching_key() {
+    let dict = build_byte_fst([("你好", "您好"), ("你", "妳"), ("世界", "世間")]);
+
+    let (len, value) = dict.lookup_longest("你好吗".as_bytes()).unwrap();
+    assert_eq!(len, "你好".len());
+    assert_eq!(value, "您好");
+
+    let (len, value) = dict.lookup_longest("你".as_bytes()).unwrap();
+    assert_eq!(len, "你".len());
+    assert_eq!(value, "妳");
+}
+
+#[test]
+fn lookup_longest_returns_none_for_no_match() {
+    let dict = build_byte_fst([("你好", "您好")]);
+    assert!(dict.lookup_longest("世界".as_bytes()).is_none());
+}
+
+#[test]
+fn build_byte_fst_dedups_identical_values_and_first_wins_duplicates() {
+    let dict = build_byte_fst([("你好", "同"), ("世界", "同"), ("你好", "後")]);
+
+    let (_, value) = dict.lookup_longest("你好".as_bytes()).unwrap();
+    assert_eq!(value, "同");
+    let (_, value) = dict.lookup_longest("世界".as_bytes()).unwrap();
+    assert_eq!(value, "同");
+    assert_eq!(dict.max_length, 2);
+}
+
+#[test]
+fn empty_byte_fst_is_empty_and_never_matches() {
+    let dict = build_byte_fst(std::iter::empty());
+    assert!(dict.is_empty());
+    assert_eq!(dict.max_length, 0);
+    assert!(dict.lookup_longest("你好".as_bytes()).is_none());
+}
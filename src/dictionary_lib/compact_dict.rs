@@ -0,0 +1,327 @@
+//! A compact columnar binary codec for [`DictMaxLen`], offered as a smaller,
+//! faster-to-decode alternative to the generic serde layout (`map` as a
+//! serde map of `Box<[char]>` → `Box<str>`) the `#[derive(Serialize,
+//! Deserialize)]` on [`DictMaxLen`] itself produces.
+//!
+//! [`DictMaxLen::to_compact_bytes`]/[`DictMaxLen::from_compact_bytes`] round-trip
+//! this format; the serde derive is left untouched for interchange paths
+//! (CBOR, JSON) that expect to walk a self-describing map.
+//!
+//! # Layout
+//!
+//! Entries are sorted by `(starter char, key length)` before encoding, then
+//! split into three columns instead of one array of `(key, value)` pairs:
+//!
+//! ```text
+//! magic (4 bytes: b"OCCB")
+//! version (u16 LE)
+//! min_len, max_len (LEB128 varints)
+//! key_length_mask (u64 LE)
+//! starter_len_mask: count (varint), then count × (starter codepoint varint, mask u64 LE)
+//! key_count (varint)
+//! lengths column: key_count × zigzag-varint delta from the previous entry's length
+//!                 (0 for the first entry) — small because entries are sorted by
+//!                 starter then length, so adjacent keys rarely differ by much
+//! key chars column: total_chars (varint), then total_chars × u32 LE scalar values,
+//!                    packed back-to-back across every key in order
+//! values column: key_count × varint value byte-length, then the concatenated
+//!                UTF-8 value bytes for every key in order
+//! ```
+//!
+//! # Decoding
+//!
+//! [`from_compact_bytes`](DictMaxLen::from_compact_bytes) walks the three
+//! columns in lockstep with three cursors (one per column) rather than
+//! looking anything up by offset, filling `map` in a single forward pass,
+//! then calls [`populate_starter_indexes`](DictMaxLen::populate_starter_indexes)
+//! once to rebuild the dense BMP accelerators — the same finishing step
+//! [`build_from_pairs`](DictMaxLen::build_from_pairs) uses.
+
+use rustc_hash::FxHashMap;
+
+use super::dict_max_len::DictMaxLen;
+use super::dictionary_maxlength::DictionaryError;
+
+const COMPACT_MAGIC: [u8; 4] = *b"OCCB";
+const COMPACT_VERSION: u16 = 1;
+
+#[inline]
+fn invalid(message: impl Into<String>) -> DictionaryError {
+    DictionaryError::InvalidCompactDict(message.into())
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Result<u64, DictionaryError> {
+    let mut result: u64 = 0;
+    let mut shift: u32 = 0;
+    loop {
+        let byte = *buf
+            .get(*pos)
+            .ok_or_else(|| invalid("truncated varint in compact dictionary"))?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return Err(invalid("varint too long in compact dictionary"));
+        }
+    }
+}
+
+fn read_u64(buf: &[u8], pos: &mut usize) -> Result<u64, DictionaryError> {
+    let end = *pos + 8;
+    let bytes: [u8; 8] = buf
+        .get(*pos..end)
+        .ok_or_else(|| invalid("truncated fixed-width field in compact dictionary"))?
+        .try_into()
+        .unwrap();
+    *pos = end;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[inline]
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+#[inline]
+fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+impl DictMaxLen {
+    /// Encodes this table into the compact columnar format described in the
+    /// [module docs](super::compact_dict) — see [`from_compact_bytes`](Self::from_compact_bytes)
+    /// for the reverse direction.
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let mut entries: Vec<(&[char], &str)> = self
+            .map
+            .iter()
+            .map(|(k, v)| (k.as_ref(), v.as_ref()))
+            .collect();
+        entries.sort_unstable_by(|a, b| {
+            let starter_a = a.0.first();
+            let starter_b = b.0.first();
+            starter_a
+                .cmp(&starter_b)
+                .then_with(|| a.0.len().cmp(&b.0.len()))
+        });
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&COMPACT_MAGIC);
+        out.extend_from_slice(&COMPACT_VERSION.to_le_bytes());
+
+        write_varint(&mut out, self.min_len as u64);
+        write_varint(&mut out, self.max_len as u64);
+        out.extend_from_slice(&self.key_length_mask.to_le_bytes());
+
+        write_varint(&mut out, self.starter_len_mask.len() as u64);
+        for (&starter, &mask) in &self.starter_len_mask {
+            write_varint(&mut out, starter as u64);
+            out.extend_from_slice(&mask.to_le_bytes());
+        }
+
+        write_varint(&mut out, entries.len() as u64);
+
+        // Lengths column: zigzag-delta from the previous entry's length.
+        let mut prev_len: i64 = 0;
+        for (key, _) in &entries {
+            let len = key.len() as i64;
+            write_varint(&mut out, zigzag_encode(len - prev_len));
+            prev_len = len;
+        }
+
+        // Key chars column: one flat buffer of packed u32 scalars.
+        let total_chars: usize = entries.iter().map(|(k, _)| k.len()).sum();
+        write_varint(&mut out, total_chars as u64);
+        for (key, _) in &entries {
+            for &c in *key {
+                out.extend_from_slice(&(c as u32).to_le_bytes());
+            }
+        }
+
+        // Values column: per-key varint byte-length, then the concatenated bytes.
+        for (_, value) in &entries {
+            write_varint(&mut out, value.len() as u64);
+        }
+        for (_, value) in &entries {
+            out.extend_from_slice(value.as_bytes());
+        }
+
+        out
+    }
+
+    /// Decodes a table previously written by [`to_compact_bytes`](Self::to_compact_bytes),
+    /// filling `map` in one forward pass over the three columns and then
+    /// calling [`populate_starter_indexes`](Self::populate_starter_indexes)
+    /// once to rebuild the dense BMP accelerators.
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self, DictionaryError> {
+        let rest = bytes
+            .strip_prefix(&COMPACT_MAGIC)
+            .ok_or_else(|| invalid("missing or unrecognized compact dictionary magic header"))?;
+        if rest.len() < 2 {
+            return Err(invalid("truncated compact dictionary header"));
+        }
+        let version = u16::from_le_bytes([rest[0], rest[1]]);
+        if version != COMPACT_VERSION {
+            return Err(invalid(format!(
+                "compact dictionary format v{} required, found v{}",
+                COMPACT_VERSION, version
+            )));
+        }
+
+        let mut pos = COMPACT_MAGIC.len() + 2;
+        let min_len = read_varint(bytes, &mut pos)? as usize;
+        let max_len = read_varint(bytes, &mut pos)? as usize;
+        let key_length_mask = read_u64(bytes, &mut pos)?;
+
+        let starter_count = read_varint(bytes, &mut pos)? as usize;
+        let mut starter_len_mask = FxHashMap::default();
+        starter_len_mask.reserve(starter_count);
+        for _ in 0..starter_count {
+            let codepoint = read_varint(bytes, &mut pos)? as u32;
+            let starter = char::from_u32(codepoint)
+                .ok_or_else(|| invalid("invalid starter codepoint in compact dictionary"))?;
+            let mask = read_u64(bytes, &mut pos)?;
+            starter_len_mask.insert(starter, mask);
+        }
+
+        let key_count = read_varint(bytes, &mut pos)? as usize;
+
+        let mut lengths = Vec::with_capacity(key_count);
+        let mut prev_len: i64 = 0;
+        for _ in 0..key_count {
+            let delta = zigzag_decode(read_varint(bytes, &mut pos)?);
+            let len = prev_len + delta;
+            if len < 0 {
+                return Err(invalid("negative key length in compact dictionary"));
+            }
+            lengths.push(len as usize);
+            prev_len = len;
+        }
+
+        let total_chars = read_varint(bytes, &mut pos)? as usize;
+        let chars_end = pos + total_chars * 4;
+        let chars_bytes = bytes
+            .get(pos..chars_end)
+            .ok_or_else(|| invalid("truncated key chars column in compact dictionary"))?;
+        let mut chars = Vec::with_capacity(total_chars);
+        for chunk in chars_bytes.chunks_exact(4) {
+            let scalar = u32::from_le_bytes(chunk.try_into().unwrap());
+            chars.push(
+                char::from_u32(scalar)
+                    .ok_or_else(|| invalid("invalid char scalar in compact dictionary"))?,
+            );
+        }
+        pos = chars_end;
+
+        let mut value_lens = Vec::with_capacity(key_count);
+        for _ in 0..key_count {
+            value_lens.push(read_varint(bytes, &mut pos)? as usize);
+        }
+
+        let mut map: FxHashMap<Box<[char]>, Box<str>> = FxHashMap::default();
+        map.reserve(key_count);
+        let mut char_cursor = 0usize;
+        for i in 0..key_count {
+            let len = lengths[i];
+            let key: Box<[char]> = chars[char_cursor..char_cursor + len]
+                .to_vec()
+                .into_boxed_slice();
+            char_cursor += len;
+
+            let value_len = value_lens[i];
+            let value_end = pos + value_len;
+            let value_bytes = bytes
+                .get(pos..value_end)
+                .ok_or_else(|| invalid("truncated value bytes in compact dictionary"))?;
+            let value = std::str::from_utf8(value_bytes)
+                .map_err(|_| invalid("compact dictionary value is not valid UTF-8"))?
+                .to_string();
+            pos = value_end;
+
+            map.insert(key, value.into_boxed_str());
+        }
+
+        let mut dict = DictMaxLen {
+            map,
+            max_len,
+            min_len,
+            key_length_mask,
+            starter_len_mask,
+            fst: None,
+            byte_fst: None,
+            first_len_mask64: Vec::new(),
+            first_char_max_len: Vec::new(),
+            starter_base: 0,
+            compressed_starter_index: None,
+            block_sparse_starter_index: None,
+        };
+        dict.populate_starter_indexes();
+        Ok(dict)
+    }
+}
+
+#[test]
+fn to_compact_bytes_round_trips_lookups() {
+    let mut dict = DictMaxLen::build_from_pairs([
+        ("你好".to_string(), "您好".to_string()),
+        ("你".to_string(), "妳".to_string()),
+        ("世界".to_string(), "世間".to_string()),
+    ]);
+    dict.populate_starter_indexes();
+
+    let bytes = dict.to_compact_bytes();
+    let decoded = DictMaxLen::from_compact_bytes(&bytes).unwrap();
+
+    assert_eq!(decoded.min_len, dict.min_len);
+    assert_eq!(decoded.max_len, dict.max_len);
+    let ni: Vec<char> = "你好".chars().collect();
+    assert_eq!(decoded.get(&ni), Some("您好"));
+    let n: Vec<char> = "你".chars().collect();
+    assert_eq!(decoded.get(&n), Some("妳"));
+    let w: Vec<char> = "世界".chars().collect();
+    assert_eq!(decoded.get(&w), Some("世間"));
+}
+
+#[test]
+fn from_compact_bytes_rejects_bad_magic() {
+    let err = DictMaxLen::from_compact_bytes(b"not a compact dictionary").unwrap_err();
+    assert!(matches!(err, DictionaryError::InvalidCompactDict(_)));
+}
+
+#[test]
+fn from_compact_bytes_rejects_unsupported_version() {
+    let mut dict = DictMaxLen::build_from_pairs([("你好".to_string(), "您好".to_string())]);
+    dict.populate_starter_indexes();
+    let mut bytes = dict.to_compact_bytes();
+    // Version is the u16 LE immediately after the 4-byte magic.
+    bytes[4] = 0xff;
+    bytes[5] = 0xff;
+
+    let err = DictMaxLen::from_compact_bytes(&bytes).unwrap_err();
+    assert!(matches!(err, DictionaryError::InvalidCompactDict(_)));
+}
+
+#[test]
+fn from_compact_bytes_rejects_truncated_input() {
+    let mut dict = DictMaxLen::build_from_pairs([("你好".to_string(), "您好".to_string())]);
+    dict.populate_starter_indexes();
+    let bytes = dict.to_compact_bytes();
+
+    let err = DictMaxLen::from_compact_bytes(&bytes[..bytes.len() - 2]).unwrap_err();
+    assert!(matches!(err, DictionaryError::InvalidCompactDict(_)));
+}
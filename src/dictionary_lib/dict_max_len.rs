@@ -18,8 +18,32 @@
 //!   - `first_char_max_len: Vec<u8>` — per-starter max length (derived from mask)
 //!
 //! The dense tables are *indexed by the Unicode scalar value of the first
-//! character* (BMP only) and let the segmenter quickly decide if a given
-//! `(starter, length)` is even possible before attempting a hash lookup.
+//! character, offset by `starter_base`* (BMP only) and let the segmenter
+//! quickly decide if a given `(starter, length)` is even possible before
+//! attempting a hash lookup. Rather than always spanning the full BMP (576
+//! KiB), [`populate_starter_indexes`](DictMaxLen::populate_starter_indexes)
+//! watermarks them to just the range between the lowest and highest live
+//! starter codepoint (`starter_base` through `starter_base + len - 1`),
+//! which for real CJK dictionaries — whose starters cluster into a handful
+//! of contiguous Unicode blocks — typically cuts the tables to a few KiB.
+//! When a dictionary's starters are sparse enough that even the watermarked
+//! range is mostly empty,
+//! [`populate_starter_indexes`](DictMaxLen::populate_starter_indexes) instead
+//! builds a [`starter_index::CompressedStarterIndex`](super::starter_index::CompressedStarterIndex),
+//! a run-length-compressed equivalent with the same gating semantics at a
+//! fraction of the memory. Callers who know their workload can instead force
+//! [`block_sparse_index::BlockSparseStarterIndex`](super::block_sparse_index::BlockSparseStarterIndex),
+//! which keeps direct `O(1)` indexing (no binary search) by lazily
+//! materializing only the fixed-size blocks of the BMP that are actually
+//! touched — see
+//! [`StarterIndexMode`] and [`populate_starter_indexes_with_mode`](DictMaxLen::populate_starter_indexes_with_mode).
+//!
+//! The per-position probing this module accelerates is still `O(position
+//! count × candidate lengths)` overall; for an alternative built directly
+//! from a set of `DictMaxLen`s' keys that pays the per-dictionary factor
+//! only once, at build time, see [`automaton`](super::automaton)
+//! (`MatchEngine::Automaton`), which compiles a single merged trie and
+//! resolves ties toward the same maximum-matching semantics as FMM.
 //!
 //! ## Example
 //! ```ignore
@@ -52,9 +76,15 @@
 //! ## Related Functions
 //! - [`DictMaxLen::build_from_pairs`] — build from `(String, String)` pairs.
 //! - [`DictMaxLen::ensure_starter_indexes`] — ensure dense BMP arrays exist.
-//! - [`DictMaxLen::populate_starter_indexes`] — rebuild dense arrays from masks/map.
+//! - [`DictMaxLen::populate_starter_indexes`] — rebuild dense/compressed index from masks/map.
+//! - [`DictMaxLen::populate_starter_indexes_with_mode`] — same, with an explicit [`StarterIndexMode`].
 //! - [`DictMaxLen::is_populated`] — check if dense arrays are allocated.
 
+use crate::dictionary_lib::block_sparse_index::BlockSparseStarterIndex;
+use crate::dictionary_lib::byte_fst::ByteFstDict;
+use crate::dictionary_lib::fst_dict::FstDict;
+use crate::dictionary_lib::starter_index::CompressedStarterIndex;
+use crate::normalize::normalize;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
@@ -114,7 +144,9 @@ macro_rules! debug_note {
 ///   - `first_len_mask64: Vec<u64>` — per-starter length bitmasks for BMP
 ///   - `first_char_max_len: Vec<u8>` — per-starter max length (derived from mask)
 ///   These dense arrays are indexed by the Unicode scalar value of the first
-///   character (`0x0000..=0xFFFF`) and are rebuilt at load/build time.
+///   character, minus `starter_base` (`0x0000..=0xFFFF`), and are rebuilt at
+///   load/build time — usually watermark-bounded to the live starter range
+///   rather than spanning the full BMP; see `starter_base`.
 ///
 /// # Usage
 ///
@@ -131,8 +163,13 @@ macro_rules! debug_note {
 ///     // Dense BMP tables (rebuilt by `populate_starter_indexes`)
 ///     first_len_mask64: vec![0; 0x10000],
 ///     first_char_max_len: vec![0; 0x10000],
+///     starter_base: 0,
 ///     // Sparse per-starter masks (authoritative source)
 ///     starter_len_mask: FxHashMap::default(),
+///     fst: None,
+///     byte_fst: None,
+///     compressed_starter_index: None,
+///     block_sparse_starter_index: None,
 /// };
 ///
 /// // Add a single-char mapping: "你" -> "您"
@@ -224,6 +261,34 @@ pub struct DictMaxLen {
     #[serde(default)]
     pub starter_len_mask: FxHashMap<char, u64>,
 
+    /// Deterministic acyclic transducer (DAT) view over [`map`](Self::map).
+    ///
+    /// Built by [`ensure_fst`](Self::ensure_fst) from the same `(key, value)`
+    /// pairs as `map`, sharing common key prefixes to reduce memory use for
+    /// large, prefix-heavy lexicons. Serialized alongside the other fields so
+    /// that loading a prebuilt dictionary does not have to pay the sort +
+    /// insert cost of rebuilding it from `map` every time.
+    ///
+    /// `None` until built; use [`ensure_fst`](Self::ensure_fst) to build it
+    /// lazily, or query it directly via [`lookup_longest`](Self::lookup_longest).
+    #[serde(default)]
+    pub fst: Option<FstDict>,
+
+    /// Byte-level FST view over [`map`](Self::map), keyed on UTF-8 bytes
+    /// instead of `char`s, with replacement strings deduplicated through a
+    /// value pool (see [`crate::dictionary_lib::byte_fst`]).
+    ///
+    /// Unlike [`fst`](Self::fst), this isn't built lazily from `map` on a
+    /// normal load path — it's produced once by
+    /// [`build_byte_fst`](crate::dictionary_lib::build_byte_fst) when a
+    /// dictionary artifact is generated, then shipped pre-built inside the
+    /// serialized blob so loading it is a single deserialize with no sort +
+    /// insert pass. `None` for tables that were built directly from `map`
+    /// (e.g. via [`build_from_pairs`](Self::build_from_pairs)) rather than
+    /// loaded from such a blob.
+    #[serde(default)]
+    pub byte_fst: Option<ByteFstDict>,
+
     /// Runtime-only: length bitmask for the first character (Unicode BMP).
     ///
     /// Each `u64` stores a bitfield representing which phrase lengths exist
@@ -242,6 +307,78 @@ pub struct DictMaxLen {
     #[serde(skip)]
     #[serde(default)]
     pub first_char_max_len: Vec<u8>,
+
+    /// Runtime-only: Unicode scalar value of the first live entry in
+    /// `first_len_mask64`/`first_char_max_len`, when those arrays are built
+    /// watermark-bounded rather than spanning the full BMP.
+    ///
+    /// [`populate_starter_indexes`](Self::populate_starter_indexes) sizes the
+    /// dense arrays to just `hi - lo + 1`, where `lo`/`hi` are the lowest and
+    /// highest BMP starter codepoints actually present, instead of always
+    /// allocating all 65 536 entries — real dictionaries cluster their
+    /// starters into a handful of Unicode blocks, so this typically cuts the
+    /// dense tables from 576 KiB to a few KiB. A starter's index into the
+    /// dense arrays is `starter as u32 - starter_base`; callers must bounds-check
+    /// the result against the arrays' length before indexing.
+    ///
+    /// `0` when the dense arrays are empty (unpopulated) or were allocated by
+    /// [`ensure_starter_indexes`](Self::ensure_starter_indexes), which always
+    /// spans the full BMP.
+    #[serde(skip)]
+    #[serde(default)]
+    pub starter_base: u32,
+
+    /// Runtime-only: run-length-compressed alternative to
+    /// (`first_len_mask64`, `first_char_max_len`), used instead of the dense
+    /// BMP arrays when [`populate_starter_indexes`](Self::populate_starter_indexes)
+    /// (or [`populate_starter_indexes_with_mode`](Self::populate_starter_indexes_with_mode))
+    /// decides starter density is low enough that compressing pays off. See
+    /// [`starter_index`](crate::dictionary_lib::starter_index) for the layout.
+    ///
+    /// At most one of the dense arrays or this field is populated at a time;
+    /// [`starter_allows_dict`](Self::starter_allows_dict) checks whichever is
+    /// present.
+    #[serde(skip)]
+    #[serde(default)]
+    pub compressed_starter_index: Option<CompressedStarterIndex>,
+
+    /// Runtime-only: block-sparse, lazily-materialized alternative to the
+    /// dense BMP arrays, built only when
+    /// [`populate_starter_indexes_with_mode`](Self::populate_starter_indexes_with_mode)
+    /// is called with [`StarterIndexMode::BlockSparse`] — `Auto` never
+    /// selects this representation on its own. See
+    /// [`block_sparse_index`](crate::dictionary_lib::block_sparse_index) for
+    /// the layout.
+    ///
+    /// At most one of the dense arrays, [`compressed_starter_index`](Self::compressed_starter_index),
+    /// or this field is populated at a time;
+    /// [`starter_allows_dict`](Self::starter_allows_dict) checks whichever is
+    /// present.
+    #[serde(skip)]
+    #[serde(default)]
+    pub block_sparse_starter_index: Option<BlockSparseStarterIndex>,
+}
+
+/// Selects which starter-index representation
+/// [`populate_starter_indexes_with_mode`](DictMaxLen::populate_starter_indexes_with_mode)
+/// builds.
+///
+/// `Auto` (the default, and what plain [`populate_starter_indexes`](DictMaxLen::populate_starter_indexes)
+/// uses) picks dense or compressed based on observed starter density; `Dense`
+/// and `Compressed` force one representation regardless of density, for
+/// callers who know their workload (e.g. "always compress — I'm holding
+/// hundreds of dictionaries in memory at once"). `BlockSparse` forces the
+/// block-sparse lazily-materialized representation instead — `Auto` never
+/// picks it on its own, since it's a specialized trade (direct indexing,
+/// per-block rather than per-run granularity) rather than a strict
+/// improvement over `Compressed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StarterIndexMode {
+    #[default]
+    Auto,
+    Dense,
+    Compressed,
+    BlockSparse,
 }
 
 impl DictMaxLen {
@@ -342,7 +479,9 @@ impl DictMaxLen {
             // Keys must not be empty (debug-only guard); empty keys are allowed but not indexed.
             debug_assert!(!k.is_empty(), "Dictionary key must not be empty");
 
-            let chars: Box<[char]> = k.chars().collect::<Vec<_>>().into_boxed_slice();
+            // Normalize the key (no-op unless a `nfc`/`nfd`/`nfkc`/`nfkd` feature is
+            // enabled) so it matches the same form conversion input is normalized to.
+            let chars: Box<[char]> = normalize(&k).chars().collect::<Vec<_>>().into_boxed_slice();
             let len = chars.len();
 
             // Track per-starter cap
@@ -411,11 +550,16 @@ impl DictMaxLen {
             min_len,
             key_length_mask,
             starter_len_mask,
+            fst: None, // built below
+            byte_fst: None,
             first_len_mask64: Vec::new(),   // not built yet
             first_char_max_len: Vec::new(), // not built yet
+            starter_base: 0,
+            compressed_starter_index: None,
+            block_sparse_starter_index: None,
         };
 
-        // Build runtime accelerators for fast lookup.
+        // Build runtime accelerators for fast lookup (this also builds `fst`).
         dict.populate_starter_indexes();
 
         // Post-build sanity checks
@@ -468,6 +612,132 @@ impl DictMaxLen {
         dict
     }
 
+    /// Merges `(key, value)` pairs into this dictionary **in place**,
+    /// recomputing all derived metadata ([`min_len`](Self::min_len),
+    /// [`max_len`](Self::max_len), [`key_length_mask`](Self::key_length_mask),
+    /// [`starter_len_mask`](Self::starter_len_mask), the dense BMP
+    /// accelerators, and [`fst`](Self::fst)) so the result is consistent,
+    /// the same as after a fresh [`build_from_pairs`](Self::build_from_pairs).
+    ///
+    /// Unlike `build_from_pairs`'s first-wins duplicate handling, a key
+    /// already present in [`map`](Self::map) is **overwritten** — this is
+    /// meant for layering a user-supplied override dictionary on top of a
+    /// built-in one (see
+    /// [`DictionaryMaxlength::load_extra`](super::DictionaryMaxlength::load_extra)),
+    /// where the whole point is letting the new entries win.
+    ///
+    /// [`byte_fst`](Self::byte_fst), if present, is dropped rather than
+    /// incrementally updated — it's only ever pre-built by a
+    /// dictionary-generation tool (see [`build_byte_fst`](Self::build_byte_fst)),
+    /// so a runtime merge simply falls back to the (rebuilt) char-based
+    /// [`fst`](Self::fst) until the next generation pass.
+    pub fn merge_pairs<I>(&mut self, pairs: I)
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        for (k, v) in pairs {
+            let chars: Box<[char]> = normalize(&k).chars().collect::<Vec<_>>().into_boxed_slice();
+            if chars.is_empty() {
+                continue;
+            }
+            let len = chars.len();
+
+            if let Some(&c0) = chars.first() {
+                let entry = self.starter_len_mask.entry(c0).or_insert(0u64);
+                Self::set_key_len_bit(entry, len);
+            }
+
+            self.max_len = self.max_len.max(len);
+            self.min_len = if self.min_len == 0 {
+                len
+            } else {
+                self.min_len.min(len)
+            };
+            Self::set_key_len_bit(&mut self.key_length_mask, len);
+
+            self.map.insert(chars, v.into_boxed_str());
+        }
+
+        self.fst = None;
+        self.byte_fst = None;
+        self.populate_starter_indexes();
+    }
+
+    /// Inserts a single `(key, value)` pair, maintaining every derived index
+    /// in O(1) amortized instead of paying [`merge_pairs`](Self::merge_pairs)'s
+    /// full [`populate_starter_indexes`](Self::populate_starter_indexes) rebuild:
+    ///
+    /// - Sets the length bit in [`key_length_mask`](Self::key_length_mask)
+    ///   and widens [`min_len`](Self::min_len)/[`max_len`](Self::max_len).
+    /// - ORs the length bit into this starter's entry in
+    ///   [`starter_len_mask`](Self::starter_len_mask).
+    /// - If the starter falls within the (possibly watermark-bounded) dense
+    ///   arrays, ORs the same bit into `first_len_mask64` and bumps
+    ///   `first_char_max_len` in place — see [`dense_offset`](Self::dense_offset).
+    ///   A starter outside that range is left to the sparse path above; the
+    ///   dense arrays are never grown or re-watermarked here, so they stay a
+    ///   correct (if no longer maximal) subset of the dictionary's starters.
+    /// - Drops [`compressed_starter_index`](Self::compressed_starter_index)/
+    ///   [`block_sparse_starter_index`](Self::block_sparse_starter_index) if
+    ///   either is set, since this method has no incremental update for those
+    ///   representations — the gate methods fall back to `starter_len_mask`
+    ///   until the next [`populate_starter_indexes`](Self::populate_starter_indexes).
+    /// - Drops [`fst`](Self::fst)/[`byte_fst`](Self::byte_fst); neither is
+    ///   rebuilt here, and [`lookup_longest`](Self::lookup_longest) returns
+    ///   `None` until [`ensure_fst`](Self::ensure_fst) (or
+    ///   [`populate_starter_indexes`](Self::populate_starter_indexes)) rebuilds `fst`.
+    ///
+    /// An existing key's value is overwritten, matching `merge_pairs`.
+    pub fn insert(&mut self, key: Box<[char]>, value: impl Into<Box<str>>) {
+        if key.is_empty() {
+            return;
+        }
+        let len = key.len();
+        let c0 = key[0];
+
+        Self::set_key_len_bit(&mut self.key_length_mask, len);
+        self.max_len = self.max_len.max(len);
+        self.min_len = if self.min_len == 0 {
+            len
+        } else {
+            self.min_len.min(len)
+        };
+
+        let sparse_entry = self.starter_len_mask.entry(c0).or_insert(0u64);
+        Self::set_key_len_bit(sparse_entry, len);
+
+        if let Some(i) = self.dense_offset(c0 as u32) {
+            Self::set_key_len_bit(&mut self.first_len_mask64[i], len);
+            let len_u8 = len.min(u8::MAX as usize) as u8;
+            if len_u8 > self.first_char_max_len[i] {
+                self.first_char_max_len[i] = len_u8;
+            }
+        }
+
+        // These representations have no incremental update path; drop them
+        // so lookups fall back to `starter_len_mask` rather than read stale data.
+        self.compressed_starter_index = None;
+        self.block_sparse_starter_index = None;
+
+        self.map.insert(key, value.into());
+        self.fst = None;
+        self.byte_fst = None;
+    }
+
+    /// Batches [`insert`](Self::insert) over an iterator of `(key, value)`
+    /// pairs — the same O(1)-amortized-per-entry maintenance, just without
+    /// requiring the caller to loop.
+    pub fn extend<I, K, V>(&mut self, pairs: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Box<[char]>>,
+        V: Into<Box<str>>,
+    {
+        for (k, v) in pairs {
+            self.insert(k.into(), v.into());
+        }
+    }
+
     /// Ensures that the runtime starter index buffers exist and have the expected sizes.
     ///
     /// This method validates and (re)allocates the two **dense starter index arrays**:
@@ -479,7 +749,10 @@ impl DictMaxLen {
     /// (restricted to the **Basic Multilingual Plane**, 0x0000–0xFFFF).
     ///
     /// If either vector is not exactly `0x10000` entries long, it is cleared and
-    /// resized to that length, filled with zeros.
+    /// resized to that length, filled with zeros. [`starter_base`](Self::starter_base)
+    /// is reset to `0` to match, since this method always spans the full BMP
+    /// rather than the watermark-bounded range
+    /// [`populate_starter_indexes`](Self::populate_starter_indexes) may build.
     ///
     /// # Invariants
     /// - **Length**: exactly 65 536 entries.
@@ -500,7 +773,12 @@ impl DictMaxLen {
     ///     key_length_mask: 0,
     ///     first_len_mask64: Vec::new(),
     ///     first_char_max_len: Vec::new(),
+    ///     starter_base: 0,
     ///     starter_len_mask: Default::default(),
+    ///     fst: None,
+    ///     byte_fst: None,
+    ///     compressed_starter_index: None,
+    ///     block_sparse_starter_index: None,
     /// };
     ///
     /// dict.ensure_starter_indexes();
@@ -518,6 +796,7 @@ impl DictMaxLen {
             self.first_char_max_len.clear();
             self.first_char_max_len.resize(N, 0u8);
         }
+        self.starter_base = 0;
     }
 
     /// (Re)builds the **Basic Multilingual Plane (BMP)** starter index arrays from
@@ -552,6 +831,8 @@ impl DictMaxLen {
     ///    `len >= 64` to bit 63) and updating [`first_char_max_len`] with the true
     ///    maximum length seen for that starter.
     /// 4. Non-BMP starters (`u > 0xFFFF`) are ignored here (dense tables are BMP-only).
+    /// 5. Calls [`ensure_fst`](Self::ensure_fst), so [`fst`](Self::fst) is always
+    ///    available after this method returns (built once, then reused).
     ///
     /// Global fields [`min_len`](Self::min_len) and [`max_len`](Self::max_len) are
     /// **not** modified by this method; maintain them at build time or from
@@ -572,11 +853,13 @@ impl DictMaxLen {
     /// // Rebuild dense BMP accelerators (normally done during build)
     /// dict.populate_starter_indexes();
     ///
-    /// let idx = '你' as usize;
-    /// // Binary bit for length = 2 must be set
-    /// assert_ne!(dict.first_len_mask64[idx] & (1u64 << (2 - 1)), 0);
+    /// // Binary bit for length = 2 must be set. Go through `get_starter_mask`
+    /// // rather than indexing `first_len_mask64` directly: the dense arrays
+    /// // may be watermark-bounded (see `starter_base`), so a raw codepoint
+    /// // isn't necessarily a valid index into them.
+    /// assert_ne!(dict.get_starter_mask('你') & (1u64 << (2 - 1)), 0);
     /// // and the per-starter cap must be >= 2
-    /// assert!(dict.first_char_max_len[idx] as usize >= 2);
+    /// assert!(dict.starter_allows_dict('你', 2, 1));
     /// ```
     ///
     /// # Complexity
@@ -585,87 +868,224 @@ impl DictMaxLen {
     /// - From `map` (fallback): **O(N)**
     #[inline]
     pub fn populate_starter_indexes(&mut self) {
-        const BMP: usize = 0x10000;
-
-        // ensure vectors exist and sized
-        if self.first_len_mask64.len() != BMP {
-            self.first_len_mask64 = vec![0u64; BMP];
-        } else {
-            // clear in-place
-            for v in &mut self.first_len_mask64 {
-                *v = 0;
-            }
-        }
-        if self.first_char_max_len.len() != BMP {
-            self.first_char_max_len = vec![0u8; BMP];
-        } else {
-            for v in &mut self.first_char_max_len {
-                *v = 0;
-            }
-        }
-
-        if !self.starter_len_mask.is_empty() {
-            // --- Fast path: one pass over sparse per-starter masks ---
-            for (&c, &mask) in &self.starter_len_mask {
-                let u = c as u32;
-                if u > 0xFFFF {
-                    continue;
-                } // dense tables are BMP-only
-                let i = u as usize;
+        self.populate_starter_indexes_with_mode(StarterIndexMode::Auto);
+    }
 
-                // Exact per-starter length mask
-                self.first_len_mask64[i] = mask;
+    /// Same as [`populate_starter_indexes`](Self::populate_starter_indexes), but
+    /// lets the caller force the dense, run-length-compressed, or
+    /// block-sparse BMP representation instead of letting observed starter
+    /// density decide.
+    ///
+    /// `Auto` uses [`CompressedStarterIndex`] whenever live BMP starters are
+    /// sparse enough that compressing them uses meaningfully less memory than
+    /// the watermark-bounded dense arrays (fewer than 1/16th of the BMP
+    /// populated); otherwise it builds the dense arrays. The dense arrays
+    /// themselves are watermark-bounded: sized to just `hi - lo + 1` where
+    /// `lo`/`hi` are the lowest/highest live BMP starter codepoints, recorded
+    /// in [`starter_base`](Self::starter_base), rather than always spanning
+    /// the full BMP — empty if there are no live BMP starters. At most one of
+    /// the dense arrays, [`CompressedStarterIndex`], or
+    /// [`BlockSparseStarterIndex`] is kept at a time — the others are cleared
+    /// — since they serve the same purpose and
+    /// [`starter_allows_dict`](Self::starter_allows_dict) only ever consults
+    /// one.
+    pub fn populate_starter_indexes_with_mode(&mut self, mode: StarterIndexMode) {
+        const BMP: usize = 0x10000;
+        // Below this fraction of live BMP starters, a run-length-compressed
+        // index uses meaningfully less memory than the fixed 576 KiB dense
+        // arrays; above it, the dense arrays' O(1) indexing without a binary
+        // search wins out and the compression overhead isn't worth it.
+        const COMPRESSED_DENSITY_DIVISOR: usize = 16;
+        // Below this many live starters, the fixed per-run bookkeeping
+        // overhead of a compressed index isn't worth it even if density is
+        // technically low — small/toy dictionaries stay dense.
+        const MIN_ENTRIES_FOR_COMPRESSION: usize = 256;
 
-                // Derive cap from the mask's max length (1..=64) -> clamp to u8
-                if mask != 0 {
-                    // same as max_len_from_mask(mask), but inline to avoid fn call if you prefer:
-                    let max_len = 64 - mask.leading_zeros() as usize;
-                    self.first_char_max_len[i] = u8::try_from(max_len).unwrap_or(u8::MAX);
-                }
-            }
+        // --- Collect (starter, mask, cap) triples, BMP-only, sorted by starter ---
+        let mut entries: Vec<(char, u64, u8)> = if !self.starter_len_mask.is_empty() {
+            // Fast path: one pass over sparse per-starter masks.
+            self.starter_len_mask
+                .iter()
+                .filter(|&(&c, _)| (c as u32) <= 0xFFFF)
+                .map(|(&c, &mask)| {
+                    let cap = if mask != 0 {
+                        u8::try_from(64 - mask.leading_zeros() as usize).unwrap_or(u8::MAX)
+                    } else {
+                        0
+                    };
+                    (c, mask, cap)
+                })
+                .collect()
         } else {
-            // --- Fallback: derive both mask and cap by scanning keys once ---
+            // Fallback: derive both mask and cap by scanning keys once.
+            let mut scratch: FxHashMap<char, (u64, u8)> = FxHashMap::default();
             for k in self.map.keys() {
                 if k.is_empty() {
                     continue;
                 }
                 let c0 = k[0];
-                let u = c0 as u32;
-                if u > 0xFFFF {
-                    continue;
-                } // ignore astral in dense tables
-
-                let i = u as usize;
+                if (c0 as u32) > 0xFFFF {
+                    continue; // ignore astral in dense/compressed tables
+                }
                 let len = k.len();
-
-                // Set bit (1..=64→0..=63); collapse >=64 to bit63 if you want a "64+" bucket
                 let b = len.saturating_sub(1);
                 let bit = if b >= 64 { 63 } else { b };
-                self.first_len_mask64[i] |= 1u64 << bit;
-
-                // Update cap (true max, not capped at 64)
-                // If you want cap==mask max (≤64), keep the cast below; if you want true max, track separately.
                 let cap_u8 = u8::try_from(len).unwrap_or(u8::MAX);
-                if cap_u8 > self.first_char_max_len[i] {
-                    self.first_char_max_len[i] = cap_u8;
+
+                let entry = scratch.entry(c0).or_insert((0u64, 0u8));
+                entry.0 |= 1u64 << bit;
+                if cap_u8 > entry.1 {
+                    entry.1 = cap_u8;
+                }
+            }
+            scratch
+                .into_iter()
+                .map(|(c, (mask, cap))| (c, mask, cap))
+                .collect()
+        };
+        entries.sort_unstable_by_key(|&(c, _, _)| c);
+
+        let resolved_mode = match mode {
+            StarterIndexMode::Dense => StarterIndexMode::Dense,
+            StarterIndexMode::Compressed => StarterIndexMode::Compressed,
+            StarterIndexMode::BlockSparse => StarterIndexMode::BlockSparse,
+            StarterIndexMode::Auto => {
+                if entries.len() >= MIN_ENTRIES_FOR_COMPRESSION
+                    && entries.len() * COMPRESSED_DENSITY_DIVISOR <= BMP
+                {
+                    StarterIndexMode::Compressed
+                } else {
+                    StarterIndexMode::Dense
+                }
+            }
+        };
+
+        if resolved_mode == StarterIndexMode::BlockSparse {
+            self.block_sparse_starter_index = Some(BlockSparseStarterIndex::build(&entries));
+            self.compressed_starter_index = None;
+            // Drop the dense arrays — the whole point is cutting resident memory.
+            self.first_len_mask64 = Vec::new();
+            self.first_char_max_len = Vec::new();
+            self.starter_base = 0;
+        } else if resolved_mode == StarterIndexMode::Compressed {
+            self.compressed_starter_index = Some(CompressedStarterIndex::build(&entries));
+            self.block_sparse_starter_index = None;
+            // Drop the dense arrays — the whole point is cutting resident memory.
+            self.first_len_mask64 = Vec::new();
+            self.first_char_max_len = Vec::new();
+            self.starter_base = 0;
+        } else {
+            self.compressed_starter_index = None;
+            self.block_sparse_starter_index = None;
+
+            if entries.is_empty() {
+                self.first_len_mask64 = Vec::new();
+                self.first_char_max_len = Vec::new();
+                self.starter_base = 0;
+            } else {
+                // `entries` is sorted ascending by starter codepoint (see
+                // above), so the first/last entries are the watermark range
+                // directly — no separate min/max scan needed.
+                let lo = entries[0].0 as u32;
+                let hi = entries[entries.len() - 1].0 as u32;
+                let span = (hi - lo + 1) as usize;
+
+                self.first_len_mask64 = vec![0u64; span];
+                self.first_char_max_len = vec![0u8; span];
+                self.starter_base = lo;
+
+                for (c, mask, cap) in entries {
+                    let i = (c as u32 - lo) as usize;
+                    self.first_len_mask64[i] = mask;
+                    self.first_char_max_len[i] = cap;
                 }
             }
         }
 
         // NOTE: self.min_len / self.max_len are global and not touched here.
         // Keep them managed at build time (from pairs / recompute) or by key_length_mask.
+
+        self.ensure_fst();
     }
 
-    /// Checks whether the starter index arrays have been fully allocated.
+    /// Builds [`fst`](Self::fst) from the current contents of [`map`](Self::map)
+    /// if it hasn't been built yet.
     ///
-    /// This method returns `true` if and only if:
+    /// This is a no-op if `fst` is already `Some`, so it is safe to call on
+    /// every load path (e.g. after deserializing an older blob that predates
+    /// this field) without redundantly rebuilding an already-present table.
+    #[inline]
+    pub fn ensure_fst(&mut self) {
+        if self.fst.is_none() {
+            self.fst = Some(FstDict::build(
+                self.map.iter().map(|(k, v)| (&**k, &**v)),
+            ));
+        }
+    }
+
+    /// Builds [`byte_fst`](Self::byte_fst) from the current contents of
+    /// [`map`](Self::map), overwriting any existing value.
+    ///
+    /// Unlike [`ensure_fst`](Self::ensure_fst), this always rebuilds — it's
+    /// meant to be called once by a dictionary-generation tool (see
+    /// [`DictionaryMaxlength::build_all_byte_fsts`](super::DictionaryMaxlength::build_all_byte_fsts)),
+    /// not on every load, which is exactly the cost this field exists to let
+    /// regular loaders skip.
+    pub fn build_byte_fst(&mut self) {
+        let owned_pairs: Vec<(String, String)> = self
+            .map
+            .iter()
+            .map(|(k, v)| (k.iter().collect::<String>(), v.to_string()))
+            .collect();
+        self.byte_fst = Some(crate::dictionary_lib::byte_fst::build_byte_fst(
+            owned_pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+        ));
+    }
+
+    /// Looks up the longest key that is a prefix of `chars`, returning
+    /// `(length, value)` on a match.
     ///
-    /// - [`first_len_mask64`] has length `0x10000` (65 536 entries), **and**
-    /// - [`first_char_max_len`] has length `0x10000`.
+    /// Prefers [`byte_fst`](Self::byte_fst) when present (a pre-built
+    /// dictionary blob), encoding `chars` to UTF-8 on a small stack-allocated
+    /// buffer and converting the matched byte length back to a char count.
+    /// Otherwise falls back to [`fst`](Self::fst).
     ///
-    /// This is used as a quick sanity check to determine whether the
-    /// starter indexes have been built or at least allocated to cover
-    /// the entire **Basic Multilingual Plane (BMP)**.
+    /// Returns `None` if neither has been built (see
+    /// [`ensure_fst`](Self::ensure_fst)) or if no key matches.
+    #[inline]
+    pub fn lookup_longest(&self, chars: &[char]) -> Option<(usize, &str)> {
+        if let Some(byte_fst) = &self.byte_fst {
+            let mut buf: smallvec::SmallVec<[u8; 64]> = smallvec::SmallVec::new();
+            let mut tmp = [0u8; 4];
+            for &c in chars {
+                buf.extend_from_slice(c.encode_utf8(&mut tmp).as_bytes());
+            }
+            let (byte_len, value) = byte_fst.lookup_longest(&buf)?;
+            // `buf` is built entirely from `char::encode_utf8`, so this slice
+            // is always valid UTF-8.
+            let char_len = std::str::from_utf8(&buf[..byte_len])
+                .map(|s| s.chars().count())
+                .unwrap_or(0);
+            return Some((char_len, value));
+        }
+
+        self.fst.as_ref()?.lookup_longest(chars)
+    }
+
+    /// Checks whether the dense starter index arrays have been allocated.
+    ///
+    /// This method returns `true` if and only if both dense arrays are
+    /// non-empty and agree in length:
+    ///
+    /// - [`first_len_mask64`] is non-empty, **and**
+    /// - [`first_char_max_len`] has the same length as [`first_len_mask64`].
+    ///
+    /// This is a quick sanity check that the dense arrays are ready to use —
+    /// whether they were allocated by [`ensure_starter_indexes`](Self::ensure_starter_indexes)
+    /// (always the full BMP) or by [`populate_starter_indexes`](Self::populate_starter_indexes)
+    /// (usually watermark-bounded to the live starter range via
+    /// [`starter_base`](Self::starter_base)); it does **not** mean the arrays
+    /// span the entire BMP.
     ///
     /// # Example
     /// ```
@@ -678,7 +1098,12 @@ impl DictMaxLen {
     ///     key_length_mask: 0,
     ///     first_len_mask64: Vec::new(),
     ///     first_char_max_len: Vec::new(),
+    ///     starter_base: 0,
     ///     starter_len_mask: Default::default(),
+    ///     fst: None,
+    ///     byte_fst: None,
+    ///     compressed_starter_index: None,
+    ///     block_sparse_starter_index: None,
     /// };
     ///
     /// assert!(!dict.is_populated());
@@ -688,7 +1113,7 @@ impl DictMaxLen {
     /// ```
     #[inline]
     pub fn is_populated(&self) -> bool {
-        self.first_len_mask64.len() == 0x10000 && self.first_char_max_len.len() == 0x10000
+        !self.first_len_mask64.is_empty() && self.first_len_mask64.len() == self.first_char_max_len.len()
     }
 
     // ----- New: key_length_mask and starter_len_mask helpers -----
@@ -748,20 +1173,47 @@ impl DictMaxLen {
         }
     }
 
+    /// Resolves `u` (a starter's Unicode scalar value) to an index into the
+    /// watermark-bounded dense arrays ([`first_len_mask64`],
+    /// [`first_char_max_len`]), or `None` if `u` falls outside the populated
+    /// range — either because the dense arrays are empty (unpopulated) or
+    /// because `u` is below [`starter_base`](Self::starter_base) or beyond
+    /// its end.
+    #[inline(always)]
+    fn dense_offset(&self, u: u32) -> Option<usize> {
+        if u > 0xFFFF {
+            return None;
+        }
+        let offset = u.checked_sub(self.starter_base)? as usize;
+        if offset < self.first_len_mask64.len() {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
     /// Return the per-starter length mask for `starter`.
     ///
-    /// - **Dense BMP fast-path:** if the dense tables are populated
-    ///   (`first_len_mask64.len() == 0x10000`), returns the BMP entry directly
-    ///   (unchecked load guarded by the length check).
+    /// - **Dense BMP fast-path:** if `starter` falls within the (possibly
+    ///   watermark-bounded) dense arrays, returns the entry directly
+    ///   (unchecked load guarded by the bounds check).
+    /// - **Compressed/block-sparse fast-paths:** if
+    ///   [`compressed_starter_index`](Self::compressed_starter_index) or
+    ///   [`block_sparse_starter_index`](Self::block_sparse_starter_index) is
+    ///   populated instead (see `populate_starter_indexes_with_mode`), reads
+    ///   from whichever is present.
     /// - **Sparse path:** otherwise, looks up `starter` in
     ///   [`starter_len_mask`](Self::starter_len_mask) and returns `0` if absent.
     ///
     /// Only lengths `1..=64` are representable in the returned mask.
     #[inline(always)]
     pub fn get_starter_mask(&self, starter: char) -> u64 {
-        let u = starter as u32;
-        if u <= 0xFFFF && self.first_len_mask64.len() == 0x10000 {
-            unsafe { *self.first_len_mask64.get_unchecked(u as usize) }
+        if let Some(i) = self.dense_offset(starter as u32) {
+            unsafe { *self.first_len_mask64.get_unchecked(i) }
+        } else if let Some(compressed) = &self.compressed_starter_index {
+            compressed.get_mask(starter)
+        } else if let Some(block_sparse) = &self.block_sparse_starter_index {
+            block_sparse.get_mask(starter)
         } else {
             *self.starter_len_mask.get(&starter).unwrap_or(&0)
         }
@@ -791,15 +1243,20 @@ impl DictMaxLen {
     /// bitmasks** (1..=64 → bits 0..=63), optionally backed by a dense BMP table:
     ///
     /// - For **BMP characters** (`u <= 0xFFFF`):
-    ///   - If dense arrays are populated (`first_len_mask64` and `first_char_max_len`
-    ///     both have length `0x10000`):
+    ///   - If `starter` falls within the (possibly watermark-bounded, see
+    ///     [`starter_base`](Self::starter_base)) dense arrays:
     ///     1. For `length` in **1..=64**, test the corresponding bit in
-    ///        `first_len_mask64[u]`. This is the most selective and fastest path.
-    ///     2. For `length > 64`, compare against `first_char_max_len[u]` (a cap
-    ///        derived at build time from per-starter masks).
-    ///   - If dense arrays are **not** available, fall back to the sparse
-    ///     per-starter mask stored in [`starter_len_mask`]. Only lengths 1..=64
-    ///     are representable in this mask; lengths > 64 will return `false`.
+    ///        `first_len_mask64[u - starter_base]`. This is the most selective
+    ///        and fastest path.
+    ///     2. For `length > 64`, compare against `first_char_max_len[u - starter_base]`
+    ///        (a cap derived at build time from per-starter masks).
+    ///   - If dense arrays are **not** available (or `starter` falls outside
+    ///     their watermarked range), fall back in order to
+    ///     [`compressed_starter_index`](Self::compressed_starter_index),
+    ///     [`block_sparse_starter_index`](Self::block_sparse_starter_index),
+    ///     then the sparse per-starter mask stored in
+    ///     [`starter_len_mask`]. Only lengths 1..=64 are representable in the
+    ///     sparse mask; lengths > 64 will return `false`.
     ///
     /// - For **astral characters** (`u > 0xFFFF`), the dense BMP tables do not
     ///   apply; the method uses the sparse per-starter mask from
@@ -821,8 +1278,8 @@ impl DictMaxLen {
     ///
     /// # Safety
     /// Uses unchecked indexing (`get_unchecked`) in the dense BMP path, guarded
-    /// by prior length checks (`len == 0x10000`). This is safe because the vectors
-    /// are guaranteed to have the BMP size when the dense path is taken.
+    /// by [`dense_offset`](Self::dense_offset)'s bounds check against the
+    /// (possibly watermark-bounded) dense arrays' actual length.
     ///
     /// # Examples
     /// ```ignore
@@ -834,15 +1291,10 @@ impl DictMaxLen {
     /// ```
     #[inline(always)]
     pub fn starter_allows_dict(&self, starter: char, length: usize, bit: usize) -> bool {
-        let u = starter as u32;
-
         // Dense BMP fast-path
-        if u <= 0xFFFF
-            && self.first_char_max_len.len() == 0x10000
-            && self.first_len_mask64.len() == 0x10000
-        {
-            let i = u as usize;
-            // Safety: guarded by the length checks above.
+        if let Some(i) = self.dense_offset(starter as u32) {
+            // Safety: `dense_offset` bounds-checks `i` against both arrays,
+            // which are always the same length (built in lockstep).
             let m = unsafe { *self.first_len_mask64.get_unchecked(i) };
 
             // Exact lengths 1..=64 via bit test
@@ -855,6 +1307,16 @@ impl DictMaxLen {
             return length <= cap;
         }
 
+        // Compressed BMP fast-path (see `populate_starter_indexes_with_mode`)
+        if let Some(compressed) = &self.compressed_starter_index {
+            return compressed.allows(starter, length, bit);
+        }
+
+        // Block-sparse BMP fast-path (see `populate_starter_indexes_with_mode`)
+        if let Some(block_sparse) = &self.block_sparse_starter_index {
+            return block_sparse.allows(starter, length, bit);
+        }
+
         // Unified sparse path (BMP w/o dense OR astral)
         if bit >= 64 {
             return false; // sparse mask can’t represent >64
@@ -862,6 +1324,78 @@ impl DictMaxLen {
         let m = self.get_starter_mask(starter); // reads sparse; BMP-dense won’t reach here
         ((m >> bit) & 1) != 0
     }
+
+    /// Walks the BMP (`U+0000..=U+FFFF`, skipping the surrogate range, which
+    /// is never a valid `char`) and yields contiguous runs of starters that
+    /// share the same `(mask, cap)` value as `(start, end, mask, cap)` —
+    /// analogous to rustc's `range_as_init_chunks`, which collapses a
+    /// bitmask into alternating uniform runs.
+    ///
+    /// Reads straight from the dense arrays when [`is_populated`](Self::is_populated)
+    /// is `true` (honoring [`starter_base`](Self::starter_base) — a starter
+    /// outside the watermarked range reads as `(mask: 0, cap: 0)`, same as an
+    /// absent entry), and falls back to [`starter_len_mask`](Self::starter_len_mask)
+    /// otherwise, deriving `cap` from the mask's bit width the same way
+    /// [`populate_starter_indexes_with_mode`](Self::populate_starter_indexes_with_mode)'s
+    /// sparse fast path does (so `cap` saturates at 64 for sparse dictionaries,
+    /// same caveat as that fast path).
+    ///
+    /// The long zero-mask gaps between CJK blocks collapse into single
+    /// chunks, so a dictionary with a handful of populated Unicode ranges
+    /// yields a handful of chunks rather than 65 536 individual entries —
+    /// this is the run-length encoding a compact on-disk writer or a
+    /// `--dump-starters` CLI mode can use to show exactly which ranges a
+    /// dictionary gates.
+    pub fn starter_chunks(&self) -> impl Iterator<Item = (char, char, u64, u8)> + '_ {
+        let dense = self.is_populated();
+        let value_at = move |cp: u32| -> (u64, u8) {
+            if dense {
+                match self.dense_offset(cp) {
+                    Some(i) => (self.first_len_mask64[i], self.first_char_max_len[i]),
+                    None => (0, 0),
+                }
+            } else {
+                let mask = char::from_u32(cp)
+                    .and_then(|c| self.starter_len_mask.get(&c))
+                    .copied()
+                    .unwrap_or(0);
+                let cap = if mask != 0 {
+                    u8::try_from(64 - mask.leading_zeros() as usize).unwrap_or(u8::MAX)
+                } else {
+                    0
+                };
+                (mask, cap)
+            }
+        };
+
+        let mut chunks: Vec<(char, char, u64, u8)> = Vec::new();
+        let mut cp: u32 = 0;
+        while cp <= 0xFFFF {
+            if (0xD800..=0xDFFF).contains(&cp) {
+                cp = 0xE000;
+                continue;
+            }
+            let value = value_at(cp);
+            let start = cp;
+            let mut end = cp;
+            while end < 0xFFFF {
+                let next = end + 1;
+                if (0xD800..=0xDFFF).contains(&next) || value_at(next) != value {
+                    break;
+                }
+                end = next;
+            }
+            let (mask, cap) = value;
+            chunks.push((
+                char::from_u32(start).expect("non-surrogate BMP codepoint"),
+                char::from_u32(end).expect("non-surrogate BMP codepoint"),
+                mask,
+                cap,
+            ));
+            cp = end + 1;
+        }
+        chunks.into_iter()
+    }
 }
 
 impl Default for DictMaxLen {
@@ -876,6 +1410,12 @@ impl Default for DictMaxLen {
     ///   [`ensure_starter_indexes`](Self::ensure_starter_indexes) or
     ///   [`populate_starter_indexes`](Self::populate_starter_indexes) to allocate).
     /// - [`first_char_max_len`] — empty `Vec` (same allocation note as above).
+    /// - [`starter_base`] — `0` (meaningless until the dense arrays are populated).
+    /// - [`fst`] — `None` (call [`ensure_fst`](Self::ensure_fst) to build it).
+    /// - [`byte_fst`] — `None` (only ever set by a pre-built dictionary blob).
+    /// - [`compressed_starter_index`] — `None` (only ever set by
+    ///   [`populate_starter_indexes_with_mode`](Self::populate_starter_indexes_with_mode)
+    ///   choosing the compressed representation).
     ///
     /// This is equivalent to:
     /// ```
@@ -888,8 +1428,13 @@ impl Default for DictMaxLen {
     ///     max_len: 0,
     ///     key_length_mask: 0,
     ///     starter_len_mask: FxHashMap::default(),
+    ///     fst: None,
+    ///     byte_fst: None,
     ///     first_len_mask64: Vec::new(),
     ///     first_char_max_len: Vec::new(),
+    ///     starter_base: 0,
+    ///     compressed_starter_index: None,
+    ///     block_sparse_starter_index: None,
     /// };
     /// ```
     ///
@@ -910,8 +1455,13 @@ impl Default for DictMaxLen {
             max_len: 0,
             key_length_mask: 0,
             starter_len_mask: FxHashMap::default(),
+            fst: None,
+            byte_fst: None,
             first_len_mask64: Vec::new(),
             first_char_max_len: Vec::new(),
+            starter_base: 0,
+            compressed_starter_index: None,
+            block_sparse_starter_index: None,
         }
     }
 }
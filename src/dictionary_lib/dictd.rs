@@ -0,0 +1,166 @@
+//! Parsing for the dictd `.index`/`.dict` dictionary pair format.
+//!
+//! dictd (the "dict server" project) ships a dictionary as two files: a
+//! plain-text `.index` listing each headword with a byte offset and length
+//! into the matching `.dict` file, and the `.dict` file itself holding the
+//! concatenated definitions (optionally dictzip-compressed as `.dict.dz`).
+//! [`read_dictd_pairs`] turns such a pair into `(headword, definition)`
+//! pairs, so callers can feed them straight into
+//! [`DictMaxLen::merge_pairs`](crate::dictionary_lib::dict_max_len::DictMaxLen::merge_pairs)
+//! the same way as a plain tab-separated dictionary file (see
+//! [`DictionaryMaxlength::load_extra_dictd`](crate::dictionary_lib::DictionaryMaxlength::load_extra_dictd)).
+//!
+//! # Format notes
+//!
+//! Each `.index` line is tab-separated: `headword\t<offset>\t<length>`,
+//! where `<offset>`/`<length>` are packed in dictd's own base64 variant —
+//! digits first, then uppercase, then lowercase, then `+`/`/`, a different
+//! order from standard base64 — decoded by [`decode_dictd_base64`].
+//!
+//! dictzip (`.dict.dz`) is ordinary gzip with an extra field recording a
+//! chunk table for random-access seeks; this module doesn't use that table.
+//! It decompresses the whole `.dict` file once into memory and then slices
+//! it by offset/length like a plain `.dict`, which is simpler and sufficient
+//! for a one-time bulk import (unlike a long-running dictd server, which
+//! needs true random access against a live file without decompressing it
+//! end to end on every lookup).
+
+use crate::dictionary_lib::dictionary_maxlength::DictionaryError;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+/// dictd's base64 alphabet: digits, then uppercase, then lowercase, then `+`/`/`.
+const DICTD_BASE64_ALPHABET: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz+/";
+
+/// Decodes a dictd-style base64 string (see module docs for the alphabet)
+/// into an unsigned integer, packing 6 bits per character MSB-first, the
+/// same way dictd's own `.index` file packs offsets and lengths.
+fn decode_dictd_base64(encoded: &str) -> Option<u64> {
+    let mut value: u64 = 0;
+    for b in encoded.bytes() {
+        let digit = DICTD_BASE64_ALPHABET.iter().position(|&c| c == b)? as u64;
+        value = (value << 6) | digit;
+    }
+    Some(value)
+}
+
+/// Decompresses `bytes` if it looks like gzip (dictzip is plain gzip plus an
+/// extra field dictd's own tools use for random access), otherwise returns
+/// it unchanged.
+fn maybe_decompress_dictzip(bytes: Vec<u8>) -> Result<Vec<u8>, DictionaryError> {
+    if bytes.len() < 2 || bytes[0] != 0x1f || bytes[1] != 0x8b {
+        return Ok(bytes);
+    }
+
+    let mut decoder = flate2::read::MultiGzDecoder::new(&bytes[..]);
+    let mut out = Vec::new();
+    decoder
+        .read_to_end(&mut out)
+        .map_err(DictionaryError::IoError)?;
+    Ok(out)
+}
+
+/// Reads a dictd `.index`/`.dict` pair into `(headword, definition)` pairs.
+///
+/// # Arguments
+/// * `index_path` — the `.index` file (plain text, tab-separated).
+/// * `dict_path` — the matching `.dict` (or dictzip-compressed `.dict.dz`) file.
+///
+/// # Errors
+/// - [`DictionaryError::IoError`] if either file can't be read, or the
+///   `.dict` can't be decompressed.
+/// - [`DictionaryError::LoadFileError`] for a malformed `.index` line (wrong
+///   field count, undecodable base64 offset/length, or an offset/length
+///   range that falls outside the decompressed `.dict`).
+pub fn read_dictd_pairs<P: AsRef<Path>>(
+    index_path: P,
+    dict_path: P,
+) -> Result<Vec<(String, String)>, DictionaryError> {
+    let index_path = index_path.as_ref();
+    let index_text = fs::read_to_string(index_path).map_err(DictionaryError::IoError)?;
+
+    let dict_bytes = fs::read(dict_path).map_err(DictionaryError::IoError)?;
+    let dict_bytes = maybe_decompress_dictzip(dict_bytes)?;
+
+    let mut pairs = Vec::new();
+    for (lineno, line) in index_text.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split('\t');
+        let (Some(headword), Some(offset_enc), Some(length_enc)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(DictionaryError::LoadFileError {
+                path: index_path.display().to_string(),
+                lineno: lineno + 1,
+                message: "expected 3 tab-separated fields (headword, offset, length)".to_string(),
+            });
+        };
+
+        let offset = decode_dictd_base64(offset_enc).ok_or_else(|| {
+            DictionaryError::LoadFileError {
+                path: index_path.display().to_string(),
+                lineno: lineno + 1,
+                message: format!("invalid base64 offset: {:?}", offset_enc),
+            }
+        })? as usize;
+        let length = decode_dictd_base64(length_enc).ok_or_else(|| {
+            DictionaryError::LoadFileError {
+                path: index_path.display().to_string(),
+                lineno: lineno + 1,
+                message: format!("invalid base64 length: {:?}", length_enc),
+            }
+        })? as usize;
+
+        let end = offset
+            .checked_add(length)
+            .ok_or_else(|| DictionaryError::LoadFileError {
+                path: index_path.display().to_string(),
+                lineno: lineno + 1,
+                message: "offset + length overflows".to_string(),
+            })?;
+        let definition_bytes =
+            dict_bytes
+                .get(offset..end)
+                .ok_or_else(|| DictionaryError::LoadFileError {
+                    path: index_path.display().to_string(),
+                    lineno: lineno + 1,
+                    message: format!(
+                        "offset/length {}..{} out of range of .dict ({} bytes)",
+                        offset,
+                        end,
+                        dict_bytes.len()
+                    ),
+                })?;
+        let definition = String::from_utf8_lossy(definition_bytes).into_owned();
+
+        pairs.push((headword.to_string(), definition));
+    }
+
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_dictd_base64() {
+        // Digits come first in dictd's alphabet, so 'A' (the 11th symbol) is 10.
+        assert_eq!(decode_dictd_base64("0"), Some(0));
+        assert_eq!(decode_dictd_base64("9"), Some(9));
+        assert_eq!(decode_dictd_base64("A"), Some(10));
+        assert_eq!(decode_dictd_base64("/"), Some(63));
+        assert_eq!(decode_dictd_base64("!"), None);
+    }
+
+    #[test]
+    fn decodes_multi_char_values_msb_first() {
+        // Two symbols pack 12 bits total: high 6 bits from the first, low 6 from the second.
+        assert_eq!(decode_dictd_base64("10"), Some((1u64 << 6) | 0));
+    }
+}
@@ -9,25 +9,56 @@
 //! advanced users may access it for custom loading, serialization, or optimization.
 
 use crate::dictionary_lib::dict_max_len::DictMaxLen;
+use crate::dictionary_lib::parallel_zstd::{self, ParallelZstdEncoder};
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
-use serde_cbor::{from_reader, from_slice};
+use serde_cbor::from_slice;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Cursor, Write};
+use std::io::{BufWriter, Cursor, Read, Write};
 use std::path::Path;
 use std::sync::Mutex;
 use std::{fs, io};
-use zstd::{decode_all, Decoder, Encoder};
+use xxhash_rust::xxh64::xxh64;
+use zstd::{decode_all, Encoder};
 
+mod automaton_cache;
+mod lazy;
+mod packed;
 mod union_cache;
+
+pub use lazy::LazyDictionary;
 pub(crate) use union_cache::UnionKey;
 // so callers can say `UnionKey::S2T { punct: .. }`
 
 // Define a global mutable variable to store the error message
 static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
 
+/// 4-byte magic header identifying a schema-versioned, checksummed CBOR
+/// payload, written by [`DictionaryMaxlength::encode_with_schema_header`].
+const SCHEMA_MAGIC: [u8; 4] = *b"OCDM";
+
+/// Current [`DictionaryMaxlength`] schema version. Bump this whenever a
+/// field change would make an older serialized dictionary parse
+/// successfully but produce a wrong or incomplete result, so
+/// [`DictionaryMaxlength::decode_with_schema_header`] can reject the
+/// mismatch cleanly instead of silently misparsing it.
+const SCHEMA_VERSION: u16 = 1;
+
+/// Number of dictionary tables [`DictionaryMaxlength`] carries (`st_characters`
+/// through `ts_punctuations`). Stamped into the header by
+/// [`DictionaryMaxlength::encode_with_schema_header`] and checked on load so a
+/// payload serialized by a build with a different table layout is rejected
+/// up front rather than silently loading with some tables defaulted empty
+/// (every table field is `#[serde(default)]`, so a truncated CBOR map would
+/// otherwise deserialize "successfully" with missing tables).
+const TABLE_COUNT: u32 = 18;
+
+/// Byte length of the `[magic][version][table count][checksum]` header
+/// prepended by [`DictionaryMaxlength::encode_with_schema_header`].
+const SCHEMA_HEADER_LEN: usize = SCHEMA_MAGIC.len() + 2 + 4 + 8;
+
 /// Represents a collection of OpenCC dictionaries paired with their maximum word lengths.
 ///
 /// This structure is used internally by the `OpenCC` engine to support fast, segment-based
@@ -75,6 +106,144 @@ pub struct DictionaryMaxlength {
     #[serde(skip)]
     #[serde(default)]
     unions: union_cache::Unions,
+
+    #[serde(skip)]
+    #[serde(default)]
+    automatons: automaton_cache::Automatons,
+}
+
+/// Compression codec + level for [`DictionaryMaxlength::save_cbor`]/[`DictionaryMaxlength::load_cbor`].
+///
+/// Each variant has its own 4-byte magic header (see [`Codec::magic`]),
+/// written before the compressed payload so `load_cbor` can auto-detect
+/// which codec a file was saved with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// Zstandard at the given compression level (1-22; higher = smaller but slower).
+    ///
+    /// Level 19 matches the ratio the original hardcoded `save_cbor_compressed`
+    /// used; lower levels (1-3) trade size for much faster encoding, useful
+    /// for CI builds that don't need the smallest possible artifact.
+    Zstd { level: i32 },
+    /// LZ4 frame format (via `lz4_flex`).
+    ///
+    /// Decompresses several times faster than Zstd at a larger output size —
+    /// worth it for latency-sensitive FFI consumers (Python, JNI, C#) that
+    /// reload dictionaries often.
+    Lz4,
+    /// Bzip2 (via the `bzip2` crate), gated behind the `codec-bzip2` feature.
+    ///
+    /// Rarely smaller than Zstd at a comparable level and much slower to
+    /// both compress and decompress; mainly useful for interop with tooling
+    /// that already standardized on bzip2 elsewhere in a pipeline.
+    Bzip2,
+    /// LZMA/xz (via the `xz2` crate), gated behind the `codec-xz` feature.
+    ///
+    /// Trades decompression speed for the best compression ratio of the
+    /// four codecs — worth it for dictionary packs that are shipped once
+    /// and decompressed many times (e.g. an installer download).
+    Xz,
+    /// No compression: the raw CBOR payload follows the magic header as-is.
+    ///
+    /// Useful for debugging (the payload can be inspected after stripping
+    /// the 4-byte header) or when the caller is already compressing the
+    /// container at a higher level (e.g. an already-compressed archive).
+    None,
+}
+
+impl Codec {
+    const MAGIC_ZSTD: [u8; 4] = *b"OCZS";
+    const MAGIC_LZ4: [u8; 4] = *b"OCL4";
+    const MAGIC_BZIP2: [u8; 4] = *b"OCBZ";
+    const MAGIC_XZ: [u8; 4] = *b"OCXZ";
+    const MAGIC_NONE: [u8; 4] = *b"OCNO";
+
+    /// This codec's 4-byte magic header, written before the compressed payload.
+    fn magic(self) -> [u8; 4] {
+        match self {
+            Codec::Zstd { .. } => Self::MAGIC_ZSTD,
+            Codec::Lz4 => Self::MAGIC_LZ4,
+            Codec::Bzip2 => Self::MAGIC_BZIP2,
+            Codec::Xz => Self::MAGIC_XZ,
+            Codec::None => Self::MAGIC_NONE,
+        }
+    }
+}
+
+/// How [`DictionaryMaxlength::from_dicts_with_policy`]/[`from_dir_with_policy`](DictionaryMaxlength::from_dir_with_policy)
+/// treat a `.txt` data line missing its TAB separator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineErrorPolicy {
+    /// Abort the whole load on the first malformed line. This is what
+    /// [`DictionaryMaxlength::from_dicts`]/[`from_dir`](DictionaryMaxlength::from_dir)
+    /// use, surfacing it as [`DictionaryError::LoadFileError`].
+    Strict,
+    /// Skip malformed lines, recording each one as a [`BadLine`], and keep
+    /// loading the rest of that table and the rest of the pack.
+    Lenient,
+}
+
+/// One malformed `.txt` data line skipped under [`LineErrorPolicy::Lenient`].
+#[derive(Debug, Clone)]
+pub struct BadLine {
+    /// Table slot the line came from (e.g. `"st_phrases"`).
+    pub dict: &'static str,
+    /// 1-based line number within that table's file.
+    pub line_no: usize,
+    /// The raw (trimmed) line content, for diagnostics.
+    pub content: String,
+}
+
+/// Identifies one of [`DictionaryMaxlength`]'s eighteen dictionary tables.
+///
+/// Used by [`DictionaryMaxlength::load_extra`]/[`load_extra_dictd`](DictionaryMaxlength::load_extra_dictd)
+/// to pick which table a runtime-supplied file merges into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictField {
+    StCharacters,
+    StPhrases,
+    TsCharacters,
+    TsPhrases,
+    TwPhrases,
+    TwPhrasesRev,
+    TwVariants,
+    TwVariantsRev,
+    TwVariantsRevPhrases,
+    HkVariants,
+    HkVariantsRev,
+    HkVariantsRevPhrases,
+    JpsCharacters,
+    JpsPhrases,
+    JpVariants,
+    JpVariantsRev,
+    StPunctuations,
+    TsPunctuations,
+}
+
+impl DictField {
+    /// Borrows the [`DictMaxLen`] table this variant identifies from `dictionary`.
+    fn select(self, dictionary: &mut DictionaryMaxlength) -> &mut DictMaxLen {
+        match self {
+            DictField::StCharacters => &mut dictionary.st_characters,
+            DictField::StPhrases => &mut dictionary.st_phrases,
+            DictField::TsCharacters => &mut dictionary.ts_characters,
+            DictField::TsPhrases => &mut dictionary.ts_phrases,
+            DictField::TwPhrases => &mut dictionary.tw_phrases,
+            DictField::TwPhrasesRev => &mut dictionary.tw_phrases_rev,
+            DictField::TwVariants => &mut dictionary.tw_variants,
+            DictField::TwVariantsRev => &mut dictionary.tw_variants_rev,
+            DictField::TwVariantsRevPhrases => &mut dictionary.tw_variants_rev_phrases,
+            DictField::HkVariants => &mut dictionary.hk_variants,
+            DictField::HkVariantsRev => &mut dictionary.hk_variants_rev,
+            DictField::HkVariantsRevPhrases => &mut dictionary.hk_variants_rev_phrases,
+            DictField::JpsCharacters => &mut dictionary.jps_characters,
+            DictField::JpsPhrases => &mut dictionary.jps_phrases,
+            DictField::JpVariants => &mut dictionary.jp_variants,
+            DictField::JpVariantsRev => &mut dictionary.jp_variants_rev,
+            DictField::StPunctuations => &mut dictionary.st_punctuations,
+            DictField::TsPunctuations => &mut dictionary.ts_punctuations,
+        }
+    }
 }
 
 impl DictionaryMaxlength {
@@ -147,6 +316,13 @@ impl DictionaryMaxlength {
     /// - [`DictionaryError::IoError`] if Zstd decompression fails.
     /// - [`DictionaryError::CborParseError`] if CBOR deserialization fails.
     ///
+    /// # Normalization
+    /// This embedded blob is built assuming a **fixed** Unicode normalization
+    /// form (none, by default — see [`crate::normalize`]). Loading it with a
+    /// different `nfc`/`nfd`/`nfkc`/`nfkd` feature enabled than the one the
+    /// blob was generated with will silently degrade match rates, since
+    /// freshly normalized input will no longer agree with the blob's keys.
+    ///
     /// # See also
     /// - [`from_dicts`](#method.from_dicts) — loads from plaintext `.txt` files.
     /// - [`from_json`](#method.from_json) — loads from JSON.
@@ -165,6 +341,29 @@ impl DictionaryMaxlength {
         Ok(dictionary.finish())
     }
 
+    /// Loads only the conversion families selected by this build's `embed-*` cargo features.
+    ///
+    /// Unlike [`from_zstd`](Self::from_zstd), which embeds all eighteen tables
+    /// unconditionally, this stitches together just the `embed-st`/`embed-tw`/
+    /// `embed-hk`/`embed-jp` sub-blobs this build enabled (or all of them if
+    /// `embed-all` is enabled), leaving any other family as empty tables. See
+    /// the [`embed`](crate::dictionary_lib::embed) module and the crate-root
+    /// `build.rs` for how the sub-blobs are produced.
+    ///
+    /// # Returns
+    /// - `Ok(Self)` with the selected families populated and the rest empty.
+    /// - `Err(DictionaryError)` if a selected sub-blob fails to decompress or parse.
+    #[cfg(any(
+        feature = "embed-st",
+        feature = "embed-tw",
+        feature = "embed-hk",
+        feature = "embed-jp",
+        feature = "embed-all"
+    ))]
+    pub fn from_embedded_features() -> Result<Self, DictionaryError> {
+        crate::dictionary_lib::embed::from_embedded_features()
+    }
+
     /// Loads the dictionary from an embedded CBOR file.
     ///
     /// This constructor initializes a [`DictionaryMaxlength`] instance using
@@ -278,6 +477,22 @@ impl DictionaryMaxlength {
     /// - [`populate_all`](#method.populate_all) — rebuilds starter indexes after bulk edits.
     /// - [`finish`](#method.finish) — chaining version of `populate_all` after deserialization.
     pub fn from_dicts() -> Result<Self, DictionaryError> {
+        let (dictionary, _bad_lines) = Self::from_dicts_with_policy(LineErrorPolicy::Strict)?;
+        Ok(dictionary)
+    }
+
+    /// Like [`from_dicts`](Self::from_dicts), but lets the caller choose how
+    /// malformed `.txt` data lines are handled via `policy`, and returns
+    /// every line skipped under [`LineErrorPolicy::Lenient`] alongside the
+    /// dictionary instead of discarding that information.
+    ///
+    /// Under [`LineErrorPolicy::Strict`] this behaves exactly like
+    /// `from_dicts`, and the returned `Vec<BadLine>` is always empty (the
+    /// first malformed line aborts the load with
+    /// [`DictionaryError::LoadFileError`] instead).
+    pub fn from_dicts_with_policy(
+        policy: LineErrorPolicy,
+    ) -> Result<(Self, Vec<BadLine>), DictionaryError> {
         let base_dir = "dicts";
 
         // upfront check for base_dir existence
@@ -291,7 +506,19 @@ impl DictionaryMaxlength {
             )));
         }
 
-        let dict_files: HashMap<&str, &str> = [
+        let dict_files: HashMap<&str, String> = Self::default_dict_filenames()
+            .into_iter()
+            .map(|(slot, filename)| (slot, filename.to_string()))
+            .collect();
+
+        Self::load_dicts_from(base_dir, &dict_files, policy)
+    }
+
+    /// The 18 table-slot → standard-OpenCC-filename mappings [`from_dicts`](Self::from_dicts)
+    /// uses by default, and [`from_dir`](Self::from_dir) falls back to when no manifest
+    /// file is given (i.e. when `dir` already follows the upstream OpenCC naming convention).
+    fn default_dict_filenames() -> [(&'static str, &'static str); 18] {
+        [
             ("st_characters", "STCharacters.txt"),
             ("st_phrases", "STPhrases.txt"),
             ("ts_characters", "TSCharacters.txt"),
@@ -311,10 +538,29 @@ impl DictionaryMaxlength {
             ("st_punctuations", "STPunctuations.txt"),
             ("ts_punctuations", "TSPunctuations.txt"),
         ]
-        .into_iter()
-        .collect();
+    }
 
-        fn load_dict(base_dir: &str, filename: &str) -> Result<DictMaxLen, DictionaryError> {
+    /// Loads all 18 tables out of `base_dir` using `dict_files` to map each
+    /// slot name to its filename within that directory — the shared
+    /// implementation behind both [`from_dicts`](Self::from_dicts) (fixed
+    /// `"dicts"` directory, default filenames) and [`from_dir`](Self::from_dir)
+    /// (arbitrary directory, optionally manifest-supplied filenames).
+    ///
+    /// Under [`LineErrorPolicy::Lenient`], every table is loaded even if some
+    /// of its lines are malformed; all skipped lines across all 18 tables
+    /// come back in the returned `Vec<BadLine>`.
+    fn load_dicts_from(
+        base_dir: &str,
+        dict_files: &HashMap<&str, String>,
+        policy: LineErrorPolicy,
+    ) -> Result<(Self, Vec<BadLine>), DictionaryError> {
+        fn load_dict(
+            base_dir: &str,
+            filename: &str,
+            dict: &'static str,
+            policy: LineErrorPolicy,
+            bad_lines: &mut Vec<BadLine>,
+        ) -> Result<DictMaxLen, DictionaryError> {
             let path = Path::new(base_dir).join(filename);
             let path_str = path.display().to_string();
 
@@ -339,11 +585,23 @@ impl DictionaryMaxlength {
                 }
 
                 let Some((k, v)) = line.split_once('\t') else {
-                    return Err(DictionaryError::LoadFileError {
-                        path: path_str.clone(), // cloned only on error
-                        lineno: lineno + 1,     // human-friendly 1-based line
-                        message: "missing TAB separator".to_string(),
-                    });
+                    match policy {
+                        LineErrorPolicy::Strict => {
+                            return Err(DictionaryError::LoadFileError {
+                                path: path_str.clone(), // cloned only on error
+                                lineno: lineno + 1,     // human-friendly 1-based line
+                                message: "missing TAB separator".to_string(),
+                            });
+                        }
+                        LineErrorPolicy::Lenient => {
+                            bad_lines.push(BadLine {
+                                dict,
+                                line_no: lineno + 1,
+                                content: line.to_string(),
+                            });
+                            continue;
+                        }
+                    }
                 };
 
                 let first_value = v.split_whitespace().next().unwrap_or("");
@@ -353,28 +611,232 @@ impl DictionaryMaxlength {
             Ok(DictMaxLen::build_from_pairs(pairs))
         }
 
-        Ok(DictionaryMaxlength {
-            st_characters: load_dict(base_dir, dict_files["st_characters"])?,
-            st_phrases: load_dict(base_dir, dict_files["st_phrases"])?,
-            ts_characters: load_dict(base_dir, dict_files["ts_characters"])?,
-            ts_phrases: load_dict(base_dir, dict_files["ts_phrases"])?,
-            tw_phrases: load_dict(base_dir, dict_files["tw_phrases"])?,
-            tw_phrases_rev: load_dict(base_dir, dict_files["tw_phrases_rev"])?,
-            tw_variants: load_dict(base_dir, dict_files["tw_variants"])?,
-            tw_variants_rev: load_dict(base_dir, dict_files["tw_variants_rev"])?,
-            tw_variants_rev_phrases: load_dict(base_dir, dict_files["tw_variants_rev_phrases"])?,
-            hk_variants: load_dict(base_dir, dict_files["hk_variants"])?,
-            hk_variants_rev: load_dict(base_dir, dict_files["hk_variants_rev"])?,
-            hk_variants_rev_phrases: load_dict(base_dir, dict_files["hk_variants_rev_phrases"])?,
-            jps_characters: load_dict(base_dir, dict_files["jps_characters"])?,
-            jps_phrases: load_dict(base_dir, dict_files["jps_phrases"])?,
-            jp_variants: load_dict(base_dir, dict_files["jp_variants"])?,
-            jp_variants_rev: load_dict(base_dir, dict_files["jp_variants_rev"])?,
-            st_punctuations: load_dict(base_dir, dict_files["st_punctuations"])?,
-            ts_punctuations: load_dict(base_dir, dict_files["ts_punctuations"])?,
-            // runtime-only cache (serde-skipped)
+        let mut bad_lines = Vec::new();
+        macro_rules! load {
+            ($slot:literal) => {
+                load_dict(
+                    base_dir,
+                    dict_files[$slot].as_str(),
+                    $slot,
+                    policy,
+                    &mut bad_lines,
+                )?
+            };
+        }
+
+        let dictionary = DictionaryMaxlength {
+            st_characters: load!("st_characters"),
+            st_phrases: load!("st_phrases"),
+            ts_characters: load!("ts_characters"),
+            ts_phrases: load!("ts_phrases"),
+            tw_phrases: load!("tw_phrases"),
+            tw_phrases_rev: load!("tw_phrases_rev"),
+            tw_variants: load!("tw_variants"),
+            tw_variants_rev: load!("tw_variants_rev"),
+            tw_variants_rev_phrases: load!("tw_variants_rev_phrases"),
+            hk_variants: load!("hk_variants"),
+            hk_variants_rev: load!("hk_variants_rev"),
+            hk_variants_rev_phrases: load!("hk_variants_rev_phrases"),
+            jps_characters: load!("jps_characters"),
+            jps_phrases: load!("jps_phrases"),
+            jp_variants: load!("jp_variants"),
+            jp_variants_rev: load!("jp_variants_rev"),
+            st_punctuations: load!("st_punctuations"),
+            ts_punctuations: load!("ts_punctuations"),
+            // runtime-only caches (serde-skipped)
             unions: Default::default(),
-        })
+            automatons: Default::default(),
+        };
+
+        Ok((dictionary, bad_lines))
+    }
+
+    /// Loads all 18 tables from an arbitrary directory, optionally guided by
+    /// a JSON manifest instead of [`from_dicts`](Self::from_dicts)'s fixed
+    /// `"dicts"` directory and hardcoded upstream filenames.
+    ///
+    /// This is how a user regenerates a pack from updated upstream OpenCC
+    /// dictionaries, or ships their own patched tables, without recompiling
+    /// the crate — point `dir` at any directory and optionally pass a
+    /// manifest naming each file, then feed the result to
+    /// [`build_pack`](Self::build_pack) to produce a loadable container.
+    ///
+    /// # Arguments
+    /// * `dir` — Directory the `.txt` files are read from.
+    /// * `manifest_path` — Path to a JSON object mapping each of the 18 slot
+    ///   names (`st_characters`, `st_phrases`, ... — the same names
+    ///   [`from_dicts`](Self::from_dicts) uses) to a filename relative to
+    ///   `dir`. If `None`, falls back to the standard upstream OpenCC
+    ///   filenames (see [`from_dicts`](Self::from_dicts)'s docs), so a `dir`
+    ///   that already mirrors the upstream layout needs no manifest at all.
+    ///
+    /// # File format
+    /// Each `.txt` file uses the same tab-separated `key\tvalue` format as
+    /// [`from_dicts`](Self::from_dicts) — see its docs for the exact rules.
+    ///
+    /// # Errors
+    /// - [`DictionaryError::IoError`] if `dir`, the manifest, or a referenced
+    ///   `.txt` file can't be read.
+    /// - [`DictionaryError::InvalidBundle`] if the manifest isn't valid JSON,
+    ///   or is missing one of the 18 required slot names.
+    /// - [`DictionaryError::LoadFileError`] if a `.txt` data line is
+    ///   malformed (missing TAB).
+    pub fn from_dir(dir: &str, manifest_path: Option<&str>) -> Result<Self, DictionaryError> {
+        let (dictionary, _bad_lines) =
+            Self::from_dir_with_policy(dir, manifest_path, LineErrorPolicy::Strict)?;
+        Ok(dictionary)
+    }
+
+    /// Like [`from_dir`](Self::from_dir), but lets the caller choose how
+    /// malformed `.txt` data lines are handled via `policy` — see
+    /// [`from_dicts_with_policy`](Self::from_dicts_with_policy) for the
+    /// same distinction applied to the fixed `dicts/` layout.
+    pub fn from_dir_with_policy(
+        dir: &str,
+        manifest_path: Option<&str>,
+        policy: LineErrorPolicy,
+    ) -> Result<(Self, Vec<BadLine>), DictionaryError> {
+        if !Path::new(dir).exists() {
+            let msg = format!("Dictionary directory not found: {}", dir);
+            Self::set_last_error(&msg);
+            return Err(DictionaryError::IoError(io::Error::new(
+                io::ErrorKind::NotFound,
+                msg,
+            )));
+        }
+
+        let dict_files: HashMap<&str, String> = match manifest_path {
+            None => Self::default_dict_filenames()
+                .into_iter()
+                .map(|(slot, filename)| (slot, filename.to_string()))
+                .collect(),
+            Some(manifest_path) => {
+                let manifest_text = fs::read_to_string(manifest_path).map_err(|err| {
+                    let msg = format!("Failed to read manifest '{}': {}", manifest_path, err);
+                    Self::set_last_error(&msg);
+                    DictionaryError::IoError(err)
+                })?;
+                let manifest: HashMap<String, String> =
+                    serde_json::from_str(&manifest_text).map_err(|err| {
+                        let msg = format!("Failed to parse manifest '{}': {}", manifest_path, err);
+                        Self::set_last_error(&msg);
+                        DictionaryError::InvalidBundle(msg)
+                    })?;
+
+                let mut dict_files = HashMap::with_capacity(18);
+                for (slot, _) in Self::default_dict_filenames() {
+                    let Some(filename) = manifest.get(slot) else {
+                        let msg = format!("manifest '{}' is missing slot '{}'", manifest_path, slot);
+                        Self::set_last_error(&msg);
+                        return Err(DictionaryError::InvalidBundle(msg));
+                    };
+                    dict_files.insert(slot, filename.clone());
+                }
+                dict_files
+            }
+        };
+
+        Self::load_dicts_from(dir, &dict_files, policy)
+    }
+
+    /// Writes `self` to `out_path` as a compressed, header-stamped container
+    /// via [`save_compressed`](Self::save_compressed) — the natural next
+    /// step after building a fresh pack with [`from_dir`](Self::from_dir).
+    ///
+    /// Named to match the `DictionaryMaxlength::from_dir(...).build_pack(...)`
+    /// pipeline a pack-building CLI runs; identical to calling
+    /// [`save_compressed`](Self::save_compressed) directly.
+    pub fn build_pack(&self, out_path: &str, codec: Codec) -> Result<(), DictionaryError> {
+        Self::save_compressed(self, out_path, codec)
+    }
+
+    /// Parses a dictionary text file the same way [`from_dicts`](Self::from_dicts)'s
+    /// inner loader does — tab-separated `key\tvalue` lines, `#`-prefixed
+    /// comments and empty lines skipped, a leading BOM stripped from the
+    /// first data line, and only the first whitespace-separated token after
+    /// the TAB taken as the value — but returning owned pairs instead of
+    /// building a table directly, so the same parsing logic can feed either
+    /// a fresh [`DictMaxLen::build_from_pairs`] or an in-place
+    /// [`DictMaxLen::merge_pairs`].
+    fn parse_dict_file(path: &Path, content: &str) -> Result<Vec<(String, String)>, DictionaryError> {
+        let path_str = path.display().to_string();
+        let mut pairs: Vec<(String, String)> = Vec::new();
+        let mut saw_data_line = false;
+
+        for (lineno, raw_line) in content.lines().enumerate() {
+            let mut line = raw_line.trim_end();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if !saw_data_line {
+                if let Some(rest) = line.strip_prefix('\u{FEFF}') {
+                    line = rest;
+                }
+                saw_data_line = true;
+            }
+
+            let Some((k, v)) = line.split_once('\t') else {
+                return Err(DictionaryError::LoadFileError {
+                    path: path_str.clone(),
+                    lineno: lineno + 1,
+                    message: "missing TAB separator".to_string(),
+                });
+            };
+
+            let first_value = v.split_whitespace().next().unwrap_or("");
+            pairs.push((k.to_owned(), first_value.to_owned()));
+        }
+
+        Ok(pairs)
+    }
+
+    /// Merges a user-supplied dictionary text file into `target`, letting its
+    /// entries override any built-in mapping with the same key.
+    ///
+    /// Lets callers extend an already-loaded [`DictionaryMaxlength`] with
+    /// domain-specific phrase tables at runtime, without recompiling. The
+    /// file uses the same tab-separated format as the embedded `dicts/*.txt`
+    /// sources (see [`from_dicts`](Self::from_dicts)'s file-format docs).
+    ///
+    /// # Errors
+    /// - [`DictionaryError::IoError`] if `path` can't be read.
+    /// - [`DictionaryError::LoadFileError`] if a data line is malformed (missing TAB).
+    pub fn load_extra<P: AsRef<Path>>(
+        &mut self,
+        target: DictField,
+        path: P,
+    ) -> Result<(), DictionaryError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let pairs = Self::parse_dict_file(path, &content)?;
+        target.select(self).merge_pairs(pairs);
+        Ok(())
+    }
+
+    /// Merges headword→definition pairs from a dictd `.index`/`.dict` pair
+    /// into `target`, letting its entries override any built-in mapping with
+    /// the same key.
+    ///
+    /// This lets an existing offline dictd-format dictionary (as produced by
+    /// the `dict` server tooling) be reused directly as an OpenCC phrase
+    /// table, without first converting it to the tab-separated format
+    /// [`load_extra`](Self::load_extra) expects. See
+    /// [`crate::dictionary_lib::dictd`] for the format details and the
+    /// simplification this takes around dictzip's random-access chunk table.
+    ///
+    /// # Errors
+    /// Same as [`dictd::read_dictd_pairs`](crate::dictionary_lib::dictd::read_dictd_pairs).
+    pub fn load_extra_dictd<P: AsRef<Path>>(
+        &mut self,
+        target: DictField,
+        index_path: P,
+        dict_path: P,
+    ) -> Result<(), DictionaryError> {
+        let pairs = crate::dictionary_lib::dictd::read_dictd_pairs(index_path, dict_path)?;
+        target.select(self).merge_pairs(pairs);
+        Ok(())
     }
 
     /// Populates starter indexes for all inner [`DictMaxLen`] tables in this structure.
@@ -423,6 +885,37 @@ impl DictionaryMaxlength {
         self.ts_punctuations.populate_starter_indexes();
     }
 
+    /// Builds [`DictMaxLen::byte_fst`] for all eighteen tables from their
+    /// current [`map`](DictMaxLen::map) contents, overwriting any existing
+    /// value.
+    ///
+    /// This is a generation-time step, not part of [`finish`](Self::finish):
+    /// it's meant to be called once by a dictionary-building tool right
+    /// before writing out an artifact (e.g. via
+    /// [`to_bincode_compressed`](Self::to_bincode_compressed)), so that every
+    /// other loader in this crate keeps skipping this cost and simply
+    /// deserializes the pre-built tables.
+    pub fn build_all_byte_fsts(&mut self) {
+        self.st_characters.build_byte_fst();
+        self.st_phrases.build_byte_fst();
+        self.ts_characters.build_byte_fst();
+        self.ts_phrases.build_byte_fst();
+        self.tw_phrases.build_byte_fst();
+        self.tw_phrases_rev.build_byte_fst();
+        self.tw_variants.build_byte_fst();
+        self.tw_variants_rev.build_byte_fst();
+        self.tw_variants_rev_phrases.build_byte_fst();
+        self.hk_variants.build_byte_fst();
+        self.hk_variants_rev.build_byte_fst();
+        self.hk_variants_rev_phrases.build_byte_fst();
+        self.jps_characters.build_byte_fst();
+        self.jps_phrases.build_byte_fst();
+        self.jp_variants.build_byte_fst();
+        self.jp_variants_rev.build_byte_fst();
+        self.st_punctuations.build_byte_fst();
+        self.ts_punctuations.build_byte_fst();
+    }
+
     /// Finalizes internal metadata after deserialization or bulk loading.
     ///
     /// Dictionary structures loaded from CBOR, Zstd-compressed CBOR, or plaintext
@@ -604,11 +1097,7 @@ impl DictionaryMaxlength {
     /// [`finish`](Self::finish), meaning it includes all precomputed metadata
     /// already present in the internal structure.
     pub fn serialize_to_cbor<P: AsRef<Path>>(&self, path: P) -> Result<(), DictionaryError> {
-        let cbor_data = serde_cbor::to_vec(self).map_err(|err| {
-            let msg = format!("Failed to serialize to CBOR: {}", err);
-            Self::set_last_error(&msg);
-            DictionaryError::CborParseError(err)
-        })?;
+        let cbor_data = Self::encode_with_schema_header(self)?;
 
         fs::write(&path, cbor_data).map_err(|err| {
             let msg = format!("Failed to write CBOR file: {}", err);
@@ -653,13 +1142,109 @@ impl DictionaryMaxlength {
             DictionaryError::IoError(err)
         })?;
 
-        let dictionary: DictionaryMaxlength = from_slice(&cbor_data).map_err(|err| {
-            let msg = format!("Failed to deserialize CBOR: {}", err);
+        let dictionary = Self::decode_with_schema_header(&cbor_data)?;
+        Ok(dictionary.finish())
+    }
+
+    /// Encodes `dictionary` as `[4-byte magic][u16 schema version][u32 table
+    /// count][u64 xxh64 checksum of the CBOR payload][CBOR payload]`.
+    ///
+    /// Used by [`serialize_to_cbor`](Self::serialize_to_cbor) and, for the
+    /// inner payload underneath whichever [`Codec`] is in play, by
+    /// [`save_cbor`](Self::save_cbor). Pairing every stored dictionary with a
+    /// schema version and a checksum lets [`decode_with_schema_header`](Self::decode_with_schema_header)
+    /// tell a truncated file or a future incompatible schema apart from
+    /// ordinary CBOR corruption, instead of both surfacing as the same
+    /// opaque `serde_cbor` parse error.
+    fn encode_with_schema_header(dictionary: &DictionaryMaxlength) -> Result<Vec<u8>, DictionaryError> {
+        let payload = serde_cbor::to_vec(dictionary).map_err(|err| {
+            let msg = format!("Failed to serialize to CBOR: {}", err);
             Self::set_last_error(&msg);
             DictionaryError::CborParseError(err)
         })?;
+        let checksum = xxh64(&payload, 0);
+
+        let mut out = Vec::with_capacity(SCHEMA_HEADER_LEN + payload.len());
+        out.extend_from_slice(&SCHEMA_MAGIC);
+        out.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+        out.extend_from_slice(&TABLE_COUNT.to_le_bytes());
+        out.extend_from_slice(&checksum.to_le_bytes());
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
 
-        Ok(dictionary.finish())
+    /// Reverses [`encode_with_schema_header`](Self::encode_with_schema_header):
+    /// verifies the magic, schema version, and checksum before decoding the
+    /// CBOR payload, recording a precise [`set_last_error`](Self::set_last_error)
+    /// message on any mismatch.
+    ///
+    /// Bytes with no recognized magic header are treated as a legacy,
+    /// header-less CBOR payload (the format `serialize_to_cbor` produced
+    /// before this check existed) for backward compatibility.
+    fn decode_with_schema_header(bytes: &[u8]) -> Result<DictionaryMaxlength, DictionaryError> {
+        let Some(rest) = bytes.strip_prefix(&SCHEMA_MAGIC) else {
+            return from_slice(bytes).map_err(|err| {
+                let msg = format!("Failed to deserialize CBOR: {}", err);
+                Self::set_last_error(&msg);
+                DictionaryError::CborParseError(err)
+            });
+        };
+
+        if rest.len() < 2 + 4 + 8 {
+            let msg = "dictionary file is truncated: missing schema version/table count/checksum header after magic".to_string();
+            Self::set_last_error(&msg);
+            return Err(DictionaryError::InvalidBundle(msg));
+        }
+
+        let (version_bytes, rest) = rest.split_at(2);
+        let version = u16::from_le_bytes([version_bytes[0], version_bytes[1]]);
+        if version != SCHEMA_VERSION {
+            let msg = format!(
+                "dictionary schema v{} required, found v{}",
+                SCHEMA_VERSION, version
+            );
+            Self::set_last_error(&msg);
+            return Err(DictionaryError::SchemaMismatch {
+                expected: SCHEMA_VERSION,
+                found: version,
+            });
+        }
+
+        let (table_count_bytes, rest) = rest.split_at(4);
+        let table_count = u32::from_le_bytes(table_count_bytes.try_into().unwrap());
+        if table_count != TABLE_COUNT {
+            let msg = format!(
+                "dictionary table layout mismatch: {} table(s) expected, found {}",
+                TABLE_COUNT, table_count
+            );
+            Self::set_last_error(&msg);
+            return Err(DictionaryError::TableCountMismatch {
+                expected: TABLE_COUNT,
+                found: table_count,
+            });
+        }
+
+        let (checksum_bytes, payload) = rest.split_at(8);
+        let expected_checksum = u64::from_le_bytes(checksum_bytes.try_into().unwrap());
+        let actual_checksum = xxh64(payload, 0);
+        if actual_checksum != expected_checksum {
+            let msg = format!(
+                "dictionary payload corrupted: checksum mismatch over {} byte(s) starting at offset {}",
+                payload.len(),
+                SCHEMA_HEADER_LEN
+            );
+            Self::set_last_error(&msg);
+            return Err(DictionaryError::ChecksumMismatch {
+                offset: SCHEMA_HEADER_LEN,
+                len: payload.len(),
+            });
+        }
+
+        from_slice(payload).map_err(|err| {
+            let msg = format!("Failed to deserialize CBOR: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::CborParseError(err)
+        })
     }
 
     /// Stores a human-readable error message for later retrieval.
@@ -741,22 +1326,19 @@ impl DictionaryMaxlength {
     ///
     /// The dictionary is written **as-is** without calling [`finish`](Self::finish),
     /// assuming it is already in a finalized state.
+    ///
+    /// This is now a thin wrapper around [`save_cbor`](Self::save_cbor) with
+    /// [`Codec::Zstd`]` { level: 19 }`, kept for backward compatibility.
     pub fn save_cbor_compressed(
         dictionary: &DictionaryMaxlength,
         path: &str,
     ) -> Result<(), DictionaryError> {
-        let file = File::create(path).map_err(|e| DictionaryError::IoError(e))?;
-        let writer = BufWriter::new(file);
-        let mut encoder = Encoder::new(writer, 19).map_err(|e| DictionaryError::IoError(e))?;
-        serde_cbor::to_writer(&mut encoder, dictionary)
-            .map_err(|e| DictionaryError::CborParseError(e))?;
-        encoder.finish().map_err(|e| DictionaryError::IoError(e))?;
-        Ok(())
+        Self::save_cbor(dictionary, path, Codec::Zstd { level: 19 })
     }
 
     /// Loads the dictionary from a Zstd-compressed CBOR file.
     ///
-    /// This function reverses [`save_compressed`](Self::save_cbor_compressed) by:
+    /// This function reverses [`save_cbor_compressed`](Self::save_cbor_compressed) by:
     ///
     /// 1. Opening the specified file
     /// 2. Decompressing its Zstd stream
@@ -777,20 +1359,835 @@ impl DictionaryMaxlength {
     ///
     /// # Notes
     ///
-    /// Zstd compression makes large dictionary bundles highly compact while
-    /// maintaining fast load times.
+    /// This is now a thin wrapper around [`load_cbor`](Self::load_cbor), which
+    /// auto-detects the codec; kept for backward compatibility.
     pub fn load_cbor_compressed(path: &str) -> Result<DictionaryMaxlength, DictionaryError> {
+        Self::load_cbor(path)
+    }
+
+    /// Serializes the dictionary to CBOR and Zstd-compresses it across
+    /// multiple threads via [`ParallelZstdEncoder`] with its default settings
+    /// (available parallelism, 1 MiB blocks, level 19).
+    ///
+    /// This trades a small amount of compression ratio (each block compresses
+    /// independently rather than sharing the whole stream's context) for a
+    /// large reduction in wall-clock time on multicore machines — use
+    /// [`ParallelZstdEncoder`] directly to tune thread count or block size.
+    ///
+    /// # Returns
+    /// - `Ok(())` on success
+    /// - `Err(DictionaryError)` if serialization, compression, or I/O fails
+    pub fn save_cbor_compressed_parallel(
+        dictionary: &DictionaryMaxlength,
+        path: &str,
+    ) -> Result<(), DictionaryError> {
+        let cbor = Self::encode_with_schema_header(dictionary)?;
+        ParallelZstdEncoder::default().encode(&cbor, path)
+    }
+
+    /// Loads a dictionary written by
+    /// [`save_cbor_compressed_parallel`](Self::save_cbor_compressed_parallel).
+    ///
+    /// # Returns
+    /// - `Ok(DictionaryMaxlength)` if decoding succeeds
+    /// - `Err(DictionaryError)` if the file isn't a recognized block stream,
+    ///   or decompression/parsing fails
+    pub fn load_cbor_compressed_parallel(path: &str) -> Result<DictionaryMaxlength, DictionaryError> {
         let file = File::open(path).map_err(DictionaryError::IoError)?;
-        let reader = BufReader::new(file);
+        let cbor = parallel_zstd::decode_parallel(std::io::BufReader::new(file))?;
+        let dictionary = Self::decode_with_schema_header(&cbor)?;
+        Ok(dictionary.finish())
+    }
+
+    /// Serializes the dictionary to a CBOR file compressed with the given [`Codec`].
+    ///
+    /// The output is `[4-byte magic header][compressed CBOR payload]`; the
+    /// magic header lets [`load_cbor`](Self::load_cbor) auto-detect which
+    /// codec to use without the caller having to remember or pass it back in.
+    ///
+    /// # Arguments
+    /// * `dictionary` — The dictionary instance to serialize.
+    /// * `path` — Destination file path for the compressed CBOR output.
+    /// * `codec` — Which compressor to use, and at what level (for [`Codec::Zstd`]).
+    ///
+    /// # Returns
+    /// - `Ok(())` on success
+    /// - `Err(DictionaryError)` if serialization, compression, or I/O fails
+    ///
+    /// # Notes
+    /// The dictionary is written **as-is** without calling [`finish`](Self::finish),
+    /// assuming it is already in a finalized state.
+    pub fn save_cbor(
+        dictionary: &DictionaryMaxlength,
+        path: &str,
+        codec: Codec,
+    ) -> Result<(), DictionaryError> {
+        let file = File::create(path).map_err(DictionaryError::IoError)?;
+        let mut writer = BufWriter::new(file);
+        writer
+            .write_all(&codec.magic())
+            .map_err(DictionaryError::IoError)?;
+
+        let payload = Self::encode_with_schema_header(dictionary)?;
+
+        match codec {
+            Codec::Zstd { level } => {
+                let mut encoder =
+                    Encoder::new(writer, level).map_err(DictionaryError::IoError)?;
+                encoder
+                    .write_all(&payload)
+                    .map_err(DictionaryError::IoError)?;
+                encoder.finish().map_err(DictionaryError::IoError)?;
+            }
+            Codec::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(writer);
+                encoder
+                    .write_all(&payload)
+                    .map_err(DictionaryError::IoError)?;
+                encoder
+                    .finish()
+                    .map_err(|e| DictionaryError::IoError(io::Error::new(io::ErrorKind::Other, e)))?;
+            }
+            #[cfg(feature = "codec-bzip2")]
+            Codec::Bzip2 => {
+                let mut encoder =
+                    bzip2::write::BzEncoder::new(writer, bzip2::Compression::best());
+                encoder
+                    .write_all(&payload)
+                    .map_err(DictionaryError::IoError)?;
+                encoder.finish().map_err(DictionaryError::IoError)?;
+            }
+            #[cfg(not(feature = "codec-bzip2"))]
+            Codec::Bzip2 => return Err(DictionaryError::UnsupportedCodec(
+                "Bzip2 support was not compiled in; rebuild with the `codec-bzip2` feature".into(),
+            )),
+            #[cfg(feature = "codec-xz")]
+            Codec::Xz => {
+                let mut encoder = xz2::write::XzEncoder::new(writer, 6);
+                encoder
+                    .write_all(&payload)
+                    .map_err(DictionaryError::IoError)?;
+                encoder.finish().map_err(DictionaryError::IoError)?;
+            }
+            #[cfg(not(feature = "codec-xz"))]
+            Codec::Xz => return Err(DictionaryError::UnsupportedCodec(
+                "Xz support was not compiled in; rebuild with the `codec-xz` feature".into(),
+            )),
+            Codec::None => {
+                writer.write_all(&payload).map_err(DictionaryError::IoError)?;
+            }
+        }
 
-        // `zstd::Decoder::new` returns an `io::Error` internally, so `IoError` is fine here.
-        let mut decoder = Decoder::new(reader).map_err(DictionaryError::IoError)?;
+        Ok(())
+    }
 
-        let dictionary: DictionaryMaxlength =
-            from_reader(&mut decoder).map_err(DictionaryError::CborParseError)?;
+    /// Loads a dictionary from a CBOR file compressed with [`save_cbor`](Self::save_cbor),
+    /// auto-detecting the codec from its magic header.
+    ///
+    /// Files with no recognized magic header (i.e. produced by the original,
+    /// header-less [`save_cbor_compressed`](Self::save_cbor_compressed)) are
+    /// treated as raw Zstd-compressed CBOR for backward compatibility.
+    ///
+    /// # Arguments
+    /// * `path` — Path to a dictionary file produced by [`save_cbor`](Self::save_cbor)
+    ///   or the legacy [`save_cbor_compressed`](Self::save_cbor_compressed).
+    ///
+    /// # Returns
+    /// - `Ok(DictionaryMaxlength)` if decoding succeeds
+    /// - `Err(DictionaryError)` if the file cannot be opened, decompressed, or parsed
+    pub fn load_cbor(path: &str) -> Result<DictionaryMaxlength, DictionaryError> {
+        let bytes = fs::read(path).map_err(DictionaryError::IoError)?;
+        Self::load_cbor_compressed_from_slice(&bytes)
+    }
+
+    /// Decodes a dictionary from an in-memory buffer produced by
+    /// [`save_cbor`](Self::save_cbor) or the legacy
+    /// [`save_cbor_compressed`](Self::save_cbor_compressed), auto-detecting
+    /// the codec from its magic header exactly like [`load_cbor`](Self::load_cbor).
+    ///
+    /// This is the entry point for callers that already have the compressed
+    /// bytes in memory — e.g. an `include_bytes!`-embedded asset, a network
+    /// response body, or a WASM virtual filesystem read — and don't want to
+    /// round-trip through a real file.
+    ///
+    /// # Arguments
+    /// * `bytes` — A complete compressed dictionary buffer.
+    ///
+    /// # Returns
+    /// - `Ok(DictionaryMaxlength)` if decoding succeeds
+    /// - `Err(DictionaryError)` if decompression or CBOR parsing fails
+    pub fn load_cbor_compressed_from_slice(bytes: &[u8]) -> Result<DictionaryMaxlength, DictionaryError> {
+        let dictionary: DictionaryMaxlength = if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_ZSTD) {
+            let decompressed = decode_all(Cursor::new(rest)).map_err(DictionaryError::IoError)?;
+            Self::decode_with_schema_header(&decompressed)?
+        } else if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_LZ4) {
+            let mut decoder = lz4_flex::frame::FrameDecoder::new(rest);
+            let mut decompressed = Vec::new();
+            decoder
+                .read_to_end(&mut decompressed)
+                .map_err(DictionaryError::IoError)?;
+            Self::decode_with_schema_header(&decompressed)?
+        } else if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_BZIP2) {
+            #[cfg(feature = "codec-bzip2")]
+            {
+                let mut decoder = bzip2::read::BzDecoder::new(rest);
+                let mut decompressed = Vec::new();
+                decoder
+                    .read_to_end(&mut decompressed)
+                    .map_err(DictionaryError::IoError)?;
+                Self::decode_with_schema_header(&decompressed)?
+            }
+            #[cfg(not(feature = "codec-bzip2"))]
+            {
+                return Err(DictionaryError::UnsupportedCodec(
+                    "file is Bzip2-compressed, but this build lacks the `codec-bzip2` feature"
+                        .into(),
+                ));
+            }
+        } else if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_XZ) {
+            #[cfg(feature = "codec-xz")]
+            {
+                let mut decoder = xz2::read::XzDecoder::new(rest);
+                let mut decompressed = Vec::new();
+                decoder
+                    .read_to_end(&mut decompressed)
+                    .map_err(DictionaryError::IoError)?;
+                Self::decode_with_schema_header(&decompressed)?
+            }
+            #[cfg(not(feature = "codec-xz"))]
+            {
+                return Err(DictionaryError::UnsupportedCodec(
+                    "file is Xz-compressed, but this build lacks the `codec-xz` feature".into(),
+                ));
+            }
+        } else if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_NONE) {
+            Self::decode_with_schema_header(rest)?
+        } else {
+            // Legacy format: no magic header, raw Zstd-compressed CBOR.
+            let decompressed =
+                decode_all(Cursor::new(bytes)).map_err(DictionaryError::IoError)?;
+            Self::decode_with_schema_header(&decompressed)?
+        };
 
         Ok(dictionary.finish())
     }
+
+    /// Decodes a dictionary by reading it to completion from an arbitrary
+    /// [`Read`]er, then delegating to
+    /// [`load_cbor_compressed_from_slice`](Self::load_cbor_compressed_from_slice).
+    ///
+    /// Codec auto-detection needs the magic header up front, so this buffers
+    /// the whole reader into memory rather than streaming through a Zstd
+    /// decoder incrementally; for large dictionaries prefer
+    /// [`from_mmap`](Self::from_mmap) if the source is a real file.
+    ///
+    /// # Arguments
+    /// * `reader` — Any `Read` source positioned at the start of a compressed
+    ///   dictionary buffer (e.g. a `Cursor` over an embedded asset, or a
+    ///   network stream).
+    ///
+    /// # Returns
+    /// - `Ok(DictionaryMaxlength)` if reading and decoding succeed
+    /// - `Err(DictionaryError)` if the reader fails, or decompression/parsing fails
+    pub fn load_cbor_compressed_from_reader<R: Read>(
+        mut reader: R,
+    ) -> Result<DictionaryMaxlength, DictionaryError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(DictionaryError::IoError)?;
+        Self::load_cbor_compressed_from_slice(&bytes)
+    }
+
+    /// Alias for [`save_cbor`](Self::save_cbor) under the name callers
+    /// migrating off the legacy, Zstd-only `save_to_zstd_file` helper expect.
+    ///
+    /// Picking [`Codec::Bzip2`] or [`Codec::Xz`] here requires this crate to
+    /// be built with the matching `codec-bzip2`/`codec-xz` feature; without
+    /// it, this returns [`DictionaryError::UnsupportedCodec`].
+    pub fn save_compressed(
+        dictionary: &DictionaryMaxlength,
+        path: &str,
+        codec: Codec,
+    ) -> Result<(), DictionaryError> {
+        Self::save_cbor(dictionary, path, codec)
+    }
+
+    /// Alias for [`load_cbor`](Self::load_cbor) under the name callers
+    /// migrating off the legacy, Zstd-only `load_from_zstd_file` helper
+    /// expect. Sniffs the codec from the container's magic header exactly
+    /// like [`load_cbor`](Self::load_cbor), so files saved with any
+    /// [`Codec`] variant — including ones this build can't produce itself —
+    /// load as long as the matching feature is compiled in.
+    pub fn load_compressed(path: &str) -> Result<DictionaryMaxlength, DictionaryError> {
+        Self::load_cbor(path)
+    }
+
+    /// Serializes the dictionary to a `bincode`-encoded file.
+    ///
+    /// Like [`serialize_to_cbor`](Self::serialize_to_cbor), this writes the
+    /// dictionary as-is (no compression), but using `bincode`'s compact,
+    /// fixed-layout binary format instead of CBOR. This is the format
+    /// [`from_mmap`](Self::from_mmap) expects.
+    ///
+    /// On serialization or I/O failure, this method records a human-readable
+    /// error message in the global last-error buffer via
+    /// [`set_last_error`](Self::set_last_error).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` — Destination file path for the generated bincode file.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if serialization and writing succeed
+    /// - `Err(DictionaryError)` if an encoding or I/O error occurs
+    pub fn to_bincode<P: AsRef<Path>>(&self, path: P) -> Result<(), DictionaryError> {
+        let bytes = bincode::serialize(self).map_err(|err| {
+            let msg = format!("Failed to serialize to bincode: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::BincodeError(err)
+        })?;
+
+        fs::write(&path, bytes).map_err(|err| {
+            let msg = format!("Failed to write bincode file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        Ok(())
+    }
+
+    /// Deserializes a dictionary from a `bincode`-encoded file.
+    ///
+    /// This reads the whole file into memory up front (like
+    /// [`deserialize_from_cbor`](Self::deserialize_from_cbor)), then finalizes
+    /// metadata via [`finish`](Self::finish). For large custom dictionary
+    /// builds where an up-front full-file read is undesirable, prefer
+    /// [`from_mmap`](Self::from_mmap).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` — Source file path of the bincode dictionary to load.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Self)` if the file is successfully read and decoded
+    /// - `Err(DictionaryError)` if reading or deserialization fails
+    pub fn from_bincode<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        let bytes = fs::read(&path).map_err(|err| {
+            let msg = format!("Failed to read bincode file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        let dictionary: DictionaryMaxlength = bincode::deserialize(&bytes).map_err(|err| {
+            let msg = format!("Failed to deserialize bincode: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::BincodeError(err)
+        })?;
+
+        Ok(dictionary.finish())
+    }
+
+    /// Serializes the dictionary to a Zstd-compressed `bincode` file.
+    ///
+    /// Combines [`to_bincode`](Self::to_bincode)'s compact, fixed-layout
+    /// encoding with Zstd compression (level `19`), the same pairing
+    /// [`save_cbor_compressed`](Self::save_cbor_compressed) applies to CBOR.
+    /// Unlike the CBOR path, this does not write the [`Codec`] magic header or
+    /// schema header — there is only one encoding here, so auto-detection
+    /// isn't needed; [`from_bincode_compressed`](Self::from_bincode_compressed)
+    /// always assumes raw Zstd-compressed bincode.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` — Destination file path for the compressed bincode output.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success
+    /// - `Err(DictionaryError)` if serialization, compression, or I/O fails
+    pub fn to_bincode_compressed<P: AsRef<Path>>(&self, path: P) -> Result<(), DictionaryError> {
+        let bytes = bincode::serialize(self).map_err(|err| {
+            let msg = format!("Failed to serialize to bincode: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::BincodeError(err)
+        })?;
+
+        let file = File::create(&path).map_err(DictionaryError::IoError)?;
+        let mut encoder = Encoder::new(BufWriter::new(file), 19).map_err(|err| {
+            let msg = format!("Failed to start Zstd encoder: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+        encoder.write_all(&bytes).map_err(|err| {
+            let msg = format!("Failed to write compressed bincode file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+        encoder.finish().map_err(|err| {
+            let msg = format!("Failed to finalize Zstd stream: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        Ok(())
+    }
+
+    /// Loads a dictionary from a Zstd-compressed `bincode` file produced by
+    /// [`to_bincode_compressed`](Self::to_bincode_compressed).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` — Source file path of the compressed bincode dictionary.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Self)` if decompression and decoding succeed
+    /// - `Err(DictionaryError)` if the file cannot be opened, decompressed, or parsed
+    pub fn from_bincode_compressed<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        let compressed = fs::read(&path).map_err(|err| {
+            let msg = format!("Failed to read compressed bincode file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        Self::decode_bincode_compressed(&compressed)
+    }
+
+    /// Decompresses and `bincode`-decodes an in-memory buffer produced by
+    /// [`to_bincode_compressed`](Self::to_bincode_compressed), shared by
+    /// [`from_bincode_compressed`](Self::from_bincode_compressed) and
+    /// [`from_embedded_bincode`](Self::from_embedded_bincode).
+    fn decode_bincode_compressed(compressed: &[u8]) -> Result<Self, DictionaryError> {
+        let bytes = decode_all(Cursor::new(compressed)).map_err(|err| {
+            let msg = format!("Failed to decompress bincode file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        let dictionary: DictionaryMaxlength = bincode::deserialize(&bytes).map_err(|err| {
+            let msg = format!("Failed to deserialize bincode: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::BincodeError(err)
+        })?;
+
+        Ok(dictionary.finish())
+    }
+
+    /// Loads the default dictionary from an **embedded Zstd-compressed
+    /// `bincode` blob**, a faster-decoding alternative to
+    /// [`from_zstd`](Self::from_zstd)'s Zstd-compressed CBOR blob.
+    ///
+    /// `bincode`'s fixed, non-self-describing layout skips the per-field type
+    /// tags CBOR carries, which cuts deserialization time for the same
+    /// eighteen-table payload — the actual data and the [`finish`](Self::finish)
+    /// pass that rebuilds the dense BMP accelerators afterward are identical
+    /// either way. This is an opt-in alternative, not a replacement for
+    /// [`from_zstd`](Self::from_zstd): binaries built before this artifact
+    /// existed, or that only ship `dictionary_maxlength.zstd`, should keep
+    /// using `from_zstd`/`new`.
+    ///
+    /// # Behavior
+    /// 1. Reads the embedded `dicts/dictionary_maxlength.bincode.zst` file
+    ///    directly from the binary.
+    /// 2. Decompresses the Zstd data into raw `bincode` bytes.
+    /// 3. Deserializes the `bincode` payload into a [`DictionaryMaxlength`].
+    /// 4. Calls [`finish`](Self::finish) to populate all starter indexes.
+    ///
+    /// # Errors
+    /// - [`DictionaryError::IoError`] if Zstd decompression fails.
+    /// - [`DictionaryError::BincodeError`] if `bincode` deserialization fails.
+    ///
+    /// # Normalization
+    /// Same caveat as [`from_zstd`](Self::from_zstd): this embedded blob is
+    /// built assuming a fixed Unicode normalization form.
+    ///
+    /// # See also
+    /// - [`to_bincode_compressed`](Self::to_bincode_compressed) — produces the
+    ///   `dicts/dictionary_maxlength.bincode.zst` artifact this method embeds.
+    pub fn from_embedded_bincode() -> Result<Self, DictionaryError> {
+        let compressed = include_bytes!("dicts/dictionary_maxlength.bincode.zst");
+        Self::decode_bincode_compressed(compressed)
+    }
+
+    /// Loads a dictionary from a `bincode`-encoded file via a memory map.
+    ///
+    /// Rather than eagerly reading the whole file into a `Vec` (as
+    /// [`from_bincode`](Self::from_bincode) and [`deserialize_from_cbor`](Self::deserialize_from_cbor)
+    /// do), this maps the file into the process's address space and lets the
+    /// OS page bytes in on demand as `bincode` walks the buffer. This avoids
+    /// the up-front allocation-and-copy for large custom dictionary builds,
+    /// and lets multiple processes share the same backing pages for an
+    /// identical file.
+    ///
+    /// Once deserialized, the resulting [`DictionaryMaxlength`] owns regular
+    /// `String`/`Vec`-backed Rust data exactly like the other loaders — the
+    /// zero-copy benefit is in how the *file* is read, not in the shape of
+    /// the in-memory result. [`finish`](Self::finish) then builds only the
+    /// runtime accelerator fields (starter masks, `fst`), the same as for any
+    /// other loader.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` — Source file path of a bincode dictionary produced by
+    ///   [`to_bincode`](Self::to_bincode).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Self)` if the file is successfully mapped and decoded
+    /// - `Err(DictionaryError)` if mapping or deserialization fails
+    ///
+    /// # Safety
+    /// Memory-mapping a file is only sound if the file isn't concurrently
+    /// truncated or rewritten by another process while mapped; `memmap2`'s
+    /// `Mmap::map` is therefore `unsafe`. This method assumes dictionary
+    /// files are treated as immutable once published, which matches how the
+    /// embedded Zstd/CBOR blobs are already used in this crate.
+    pub fn from_mmap<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        let file = File::open(&path).map_err(|err| {
+            let msg = format!("Failed to open bincode file for mmap: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        // Safety: see the method-level `# Safety` note above.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(|err| {
+            let msg = format!("Failed to mmap bincode file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        let dictionary: DictionaryMaxlength = bincode::deserialize(&mmap[..]).map_err(|err| {
+            let msg = format!("Failed to deserialize mmapped bincode: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::BincodeError(err)
+        })?;
+
+        Ok(dictionary.finish())
+    }
+
+    /// Loads a dictionary from a Zstd-compressed, schema-headered CBOR file
+    /// (the `dictionary_maxlength.zstd` artifact produced by the dictionary
+    /// generator and [`save_cbor_compressed`](Self::save_cbor_compressed)) via
+    /// a memory map, rather than [`load_cbor`](Self::load_cbor)'s up-front
+    /// `fs::read` of the whole file.
+    ///
+    /// Like [`from_mmap`](Self::from_mmap), the zero-copy benefit is in how
+    /// the *file* is read, not in the shape of the decoded result: the mapped
+    /// bytes still have to be Zstd-decompressed and CBOR-parsed into owned
+    /// `String`/`Vec`-backed tables, since [`DictMaxLen`] isn't generic over a
+    /// borrowed lifetime. For the large, mostly-read-only phrase tables this
+    /// still cuts cold-start latency by letting the OS page the compressed
+    /// file in on demand (and share pages across processes) instead of
+    /// copying it into a heap `Vec` before decompression even starts; turning
+    /// the decoded tables themselves into `Arc<str>`-backed, truly zero-copy
+    /// structures would require a broader `DictMaxLen<'a>`-style rework and is
+    /// out of scope here.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` — Source file path of a Zstd-compressed dictionary produced by
+    ///   [`save_cbor_compressed`](Self::save_cbor_compressed) (or plain
+    ///   [`save_cbor`](Self::save_cbor) with [`Codec::Zstd`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Self)` if the file is successfully mapped, decompressed, and decoded
+    /// - `Err(DictionaryError)` if mapping, decompression, or parsing fails
+    ///
+    /// # Safety
+    /// See [`from_mmap`](Self::from_mmap)'s `# Safety` note: this assumes the
+    /// file isn't concurrently truncated or rewritten while mapped.
+    pub fn from_mmap_zstd<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        let file = File::open(&path).map_err(|err| {
+            let msg = format!("Failed to open Zstd file for mmap: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        // Safety: see the method-level `# Safety` note above.
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(|err| {
+            let msg = format!("Failed to mmap Zstd file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        Self::load_cbor_compressed_from_slice(&mmap[..])
+    }
+
+    /// Returns `(table name, table)` for all eighteen `DictMaxLen` tables, in
+    /// the same order and under the same names as [`from_embedded_features`]'s
+    /// family groupings. Shared by [`save_cbor_bundle`](Self::save_cbor_bundle)
+    /// and [`load_cbor_bundle`](Self::load_cbor_bundle) so both sides agree on
+    /// what a bundle entry's name means.
+    ///
+    /// [`from_embedded_features`]: crate::dictionary_lib::embed::from_embedded_features
+    fn bundle_tables(&self) -> [(&'static str, &DictMaxLen); 18] {
+        [
+            ("st_characters", &self.st_characters),
+            ("st_phrases", &self.st_phrases),
+            ("ts_characters", &self.ts_characters),
+            ("ts_phrases", &self.ts_phrases),
+            ("st_punctuations", &self.st_punctuations),
+            ("ts_punctuations", &self.ts_punctuations),
+            ("tw_phrases", &self.tw_phrases),
+            ("tw_phrases_rev", &self.tw_phrases_rev),
+            ("tw_variants", &self.tw_variants),
+            ("tw_variants_rev", &self.tw_variants_rev),
+            ("tw_variants_rev_phrases", &self.tw_variants_rev_phrases),
+            ("hk_variants", &self.hk_variants),
+            ("hk_variants_rev", &self.hk_variants_rev),
+            ("hk_variants_rev_phrases", &self.hk_variants_rev_phrases),
+            ("jps_characters", &self.jps_characters),
+            ("jps_phrases", &self.jps_phrases),
+            ("jp_variants", &self.jp_variants),
+            ("jp_variants_rev", &self.jp_variants_rev),
+        ]
+    }
+
+    /// Assigns a decoded `DictMaxLen` back to the field `bundle_tables` named it from.
+    fn set_bundle_table(&mut self, name: &str, table: DictMaxLen) {
+        match name {
+            "st_characters" => self.st_characters = table,
+            "st_phrases" => self.st_phrases = table,
+            "ts_characters" => self.ts_characters = table,
+            "ts_phrases" => self.ts_phrases = table,
+            "st_punctuations" => self.st_punctuations = table,
+            "ts_punctuations" => self.ts_punctuations = table,
+            "tw_phrases" => self.tw_phrases = table,
+            "tw_phrases_rev" => self.tw_phrases_rev = table,
+            "tw_variants" => self.tw_variants = table,
+            "tw_variants_rev" => self.tw_variants_rev = table,
+            "tw_variants_rev_phrases" => self.tw_variants_rev_phrases = table,
+            "hk_variants" => self.hk_variants = table,
+            "hk_variants_rev" => self.hk_variants_rev = table,
+            "hk_variants_rev_phrases" => self.hk_variants_rev_phrases = table,
+            "jps_characters" => self.jps_characters = table,
+            "jps_phrases" => self.jps_phrases = table,
+            "jp_variants" => self.jp_variants = table,
+            "jp_variants_rev" => self.jp_variants_rev = table,
+            // Unknown names can only come from a bundle written by a future,
+            // incompatible format version; silently dropping the entry is
+            // preferable to failing the whole load over one unrecognized table.
+            _ => {}
+        }
+    }
+
+    /// Serializes the dictionary as a bundle that trains one shared Zstd
+    /// dictionary across all eighteen `DictMaxLen` tables and compresses each
+    /// table against it, rather than compressing one monolithic CBOR blob
+    /// (as [`save_cbor`](Self::save_cbor) does) or each table independently.
+    ///
+    /// The individual OpenCC source dictionaries are many small, highly
+    /// similar TSV-derived tables (shared Han characters, the same key/value
+    /// layout), which compress poorly in isolation — a trained dictionary
+    /// lets Zstd learn that shared structure once and reuse it for every
+    /// table, shrinking the total size versus independent frames while
+    /// keeping per-table decode fast (no cross-table dependency at load
+    /// time beyond the one shared [`DDict`](zstd::dict::DecoderDictionary)).
+    ///
+    /// If there are too few non-empty tables to train a useful dictionary
+    /// from (fewer than [`BUNDLE_MIN_TRAINING_SAMPLES`]), or training itself
+    /// fails, this falls back to compressing each table independently with a
+    /// plain (dictionary-less) compressor — the bundle format stores `None`
+    /// for `trained_dict` in that case, and [`load_cbor_bundle`] detects it
+    /// and decompresses the same way.
+    ///
+    /// # Arguments
+    /// * `dictionary` — The dictionary instance to serialize.
+    /// * `path` — Destination file path for the bundle.
+    /// * `level` — Zstd compression level used for every table.
+    ///
+    /// # Returns
+    /// - `Ok(())` on success
+    /// - `Err(DictionaryError)` if training, compression, or I/O fails
+    ///
+    /// [`load_cbor_bundle`]: Self::load_cbor_bundle
+    pub fn save_cbor_bundle(
+        dictionary: &DictionaryMaxlength,
+        path: &str,
+        level: i32,
+    ) -> Result<(), DictionaryError> {
+        let tables = dictionary.bundle_tables();
+
+        let samples: Vec<Vec<u8>> = tables
+            .iter()
+            .map(|(_, table)| serde_cbor::to_vec(table))
+            .collect::<Result<_, _>>()
+            .map_err(DictionaryError::CborParseError)?;
+
+        let non_empty_samples = tables.iter().filter(|(_, t)| !t.map.is_empty()).count();
+        let trained_dict = if non_empty_samples >= BUNDLE_MIN_TRAINING_SAMPLES {
+            zstd::dict::from_samples(&samples, BUNDLE_TARGET_DICT_SIZE).ok()
+        } else {
+            None
+        };
+
+        let mut compressor = match &trained_dict {
+            Some(dict) => zstd::bulk::Compressor::with_dictionary(level, dict),
+            None => zstd::bulk::Compressor::new(level),
+        }
+        .map_err(DictionaryError::IoError)?;
+
+        let entries = tables
+            .iter()
+            .zip(samples.iter())
+            .map(|((name, _), sample)| {
+                let compressed = compressor
+                    .compress(sample)
+                    .map_err(DictionaryError::IoError)?;
+                Ok(BundleEntry {
+                    name: (*name).to_owned(),
+                    raw_len: sample.len(),
+                    compressed,
+                })
+            })
+            .collect::<Result<Vec<_>, DictionaryError>>()?;
+
+        let bundle = Bundle {
+            version: BUNDLE_FORMAT_VERSION,
+            trained_dict,
+            entries,
+        };
+
+        let file = File::create(path).map_err(DictionaryError::IoError)?;
+        let mut writer = BufWriter::new(file);
+        writer
+            .write_all(&BUNDLE_MAGIC)
+            .map_err(DictionaryError::IoError)?;
+        serde_cbor::to_writer(&mut writer, &bundle).map_err(DictionaryError::CborParseError)?;
+
+        Ok(())
+    }
+
+    /// Loads a dictionary bundle written by [`save_cbor_bundle`](Self::save_cbor_bundle).
+    ///
+    /// Rebuilds the shared `DDict` from the bundle's trained-dictionary blob
+    /// (or falls back to plain decompression if the bundle was written
+    /// without one — see [`save_cbor_bundle`](Self::save_cbor_bundle)) once,
+    /// and reuses it to decompress every table.
+    ///
+    /// # Arguments
+    /// * `path` — Path to a bundle produced by [`save_cbor_bundle`](Self::save_cbor_bundle).
+    ///
+    /// # Returns
+    /// - `Ok(DictionaryMaxlength)` if decoding succeeds
+    /// - `Err(DictionaryError)` if the file is missing, isn't a recognized
+    ///   bundle, or decompression/parsing fails
+    pub fn load_cbor_bundle(path: &str) -> Result<DictionaryMaxlength, DictionaryError> {
+        let bytes = fs::read(path).map_err(DictionaryError::IoError)?;
+        let rest = bytes.strip_prefix(&BUNDLE_MAGIC).ok_or_else(|| {
+            DictionaryError::InvalidBundle("missing or unrecognized bundle magic header".into())
+        })?;
+
+        let bundle: Bundle =
+            serde_cbor::from_slice(rest).map_err(DictionaryError::CborParseError)?;
+        if bundle.version != BUNDLE_FORMAT_VERSION {
+            return Err(DictionaryError::InvalidBundle(format!(
+                "unsupported bundle format version {}",
+                bundle.version
+            )));
+        }
+
+        let mut decompressor = match &bundle.trained_dict {
+            Some(dict) => zstd::bulk::Decompressor::with_dictionary(dict),
+            None => zstd::bulk::Decompressor::new(),
+        }
+        .map_err(DictionaryError::IoError)?;
+
+        let mut dictionary = DictionaryMaxlength::default();
+        for entry in &bundle.entries {
+            let decompressed = decompressor
+                .decompress(&entry.compressed, entry.raw_len)
+                .map_err(DictionaryError::IoError)?;
+            let table: DictMaxLen =
+                serde_cbor::from_slice(&decompressed).map_err(DictionaryError::CborParseError)?;
+            dictionary.set_bundle_table(&entry.name, table);
+        }
+
+        Ok(dictionary.finish())
+    }
+
+    /// Resolves one of this crate's built-in [`DictMaxLen`] field names (`"st_phrases"`,
+    /// `"tw_variants"`, …) to the loaded table it names, or `None` if `key` doesn't match any
+    /// of them. Used by [`scripting`](crate::scripting) to resolve a Lua-registered pipeline's
+    /// `dict_keys` without the script needing direct access to `DictionaryMaxlength`'s fields.
+    #[cfg(feature = "scripting")]
+    pub(crate) fn dict_by_key(&self, key: &str) -> Option<&DictMaxLen> {
+        Some(match key {
+            "st_characters" => &self.st_characters,
+            "st_phrases" => &self.st_phrases,
+            "ts_characters" => &self.ts_characters,
+            "ts_phrases" => &self.ts_phrases,
+            "tw_phrases" => &self.tw_phrases,
+            "tw_phrases_rev" => &self.tw_phrases_rev,
+            "tw_variants" => &self.tw_variants,
+            "tw_variants_rev" => &self.tw_variants_rev,
+            "tw_variants_rev_phrases" => &self.tw_variants_rev_phrases,
+            "hk_variants" => &self.hk_variants,
+            "hk_variants_rev" => &self.hk_variants_rev,
+            "hk_variants_rev_phrases" => &self.hk_variants_rev_phrases,
+            "jps_characters" => &self.jps_characters,
+            "jps_phrases" => &self.jps_phrases,
+            "jp_variants" => &self.jp_variants,
+            "jp_variants_rev" => &self.jp_variants_rev,
+            "st_punctuations" => &self.st_punctuations,
+            "ts_punctuations" => &self.ts_punctuations,
+            _ => return None,
+        })
+    }
+}
+
+/// Format version written to every [`Bundle`] header by
+/// [`DictionaryMaxlength::save_cbor_bundle`]; bumped whenever the bundle
+/// layout changes in a way [`DictionaryMaxlength::load_cbor_bundle`] can't
+/// read transparently.
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// 4-byte magic header identifying a [`Bundle`] file, checked by
+/// [`DictionaryMaxlength::load_cbor_bundle`] before anything else.
+const BUNDLE_MAGIC: [u8; 4] = *b"OCBD";
+
+/// Target size, in bytes, for the Zstd dictionary trained by
+/// [`DictionaryMaxlength::save_cbor_bundle`]. ~100 KB is enough to capture
+/// the shared Han characters and TAB-separated layout common to every
+/// table without the dictionary itself dominating the bundle size.
+const BUNDLE_TARGET_DICT_SIZE: usize = 100 * 1024;
+
+/// Below this many non-empty table samples, `ZDICT_trainFromBuffer` has too
+/// little material to learn a useful shared dictionary from, so
+/// [`DictionaryMaxlength::save_cbor_bundle`] skips training and falls back
+/// to per-table plain compression.
+const BUNDLE_MIN_TRAINING_SAMPLES: usize = 4;
+
+/// One compressed `DictMaxLen` table inside a [`Bundle`].
+#[derive(Serialize, Deserialize)]
+struct BundleEntry {
+    /// Field name from [`DictionaryMaxlength::bundle_tables`], used by
+    /// [`DictionaryMaxlength::set_bundle_table`] to route the decoded table
+    /// back to the right field.
+    name: String,
+    /// Decompressed (CBOR-encoded) length, required up front by the
+    /// `zstd::bulk::Decompressor` API.
+    raw_len: usize,
+    /// Zstd-compressed CBOR bytes, compressed against `Bundle::trained_dict`
+    /// when present.
+    compressed: Vec<u8>,
+}
+
+/// On-disk shape of a dictionary bundle, written after [`BUNDLE_MAGIC`] by
+/// [`DictionaryMaxlength::save_cbor_bundle`] and read back by
+/// [`DictionaryMaxlength::load_cbor_bundle`].
+#[derive(Serialize, Deserialize)]
+struct Bundle {
+    version: u32,
+    /// Trained Zstd dictionary blob shared by every entry, or `None` if
+    /// there wasn't enough training material and entries were compressed
+    /// independently instead.
+    trained_dict: Option<Vec<u8>>,
+    entries: Vec<BundleEntry>,
 }
 
 impl Default for DictionaryMaxlength {
@@ -822,8 +2219,9 @@ impl Default for DictionaryMaxlength {
             jp_variants_rev: DictMaxLen::default(),
             st_punctuations: DictMaxLen::default(),
             ts_punctuations: DictMaxLen::default(),
-            // runtime-only cache (serde-skipped)
+            // runtime-only caches (serde-skipped)
             unions: Default::default(),
+            automatons: Default::default(),
         };
 
         dicts.finish()
@@ -847,6 +2245,10 @@ impl Default for DictionaryMaxlength {
 ///   - Wraps a [`serde_cbor::Error`] that occurred while serializing or
 ///     deserializing CBOR dictionary data.
 ///
+/// - [`DictionaryError::BincodeError`]
+///   - Wraps a [`bincode::Error`] that occurred while serializing or
+///     deserializing bincode dictionary data (including mmap-backed loads).
+///
 /// - [`DictionaryError::LoadFileError`]
 ///   - Reports a logical or format error while parsing a plaintext dictionary
 ///     file line-by-line (for example, a missing TAB separator). Carries the
@@ -870,6 +2272,9 @@ pub enum DictionaryError {
     /// CBOR serialization or deserialization failure.
     CborParseError(serde_cbor::Error),
 
+    /// `bincode` serialization or deserialization failure.
+    BincodeError(bincode::Error),
+
     /// Text dictionary (.txt) format error while loading or parsing a file line-by-line.
     LoadFileError {
         /// Path of the dictionary file where the error occurred.
@@ -879,6 +2284,75 @@ pub enum DictionaryError {
         /// Short human-readable description of the issue.
         message: String,
     },
+
+    /// A dictionary bundle (see [`DictionaryMaxlength::save_cbor_bundle`]) had
+    /// a bad magic header or an unsupported format version.
+    InvalidBundle(String),
+
+    /// A schema-versioned CBOR payload (see
+    /// [`DictionaryMaxlength::serialize_to_cbor`]) was written by an
+    /// incompatible schema version.
+    SchemaMismatch {
+        /// The schema version this build expects ([`SCHEMA_VERSION`]).
+        expected: u16,
+        /// The schema version actually found in the file's header.
+        found: u16,
+    },
+
+    /// A schema-versioned CBOR payload (see
+    /// [`DictionaryMaxlength::serialize_to_cbor`]) named a table count that
+    /// doesn't match this build's [`TABLE_COUNT`], indicating the payload
+    /// was produced by a build with a different table layout.
+    TableCountMismatch {
+        /// The table count this build expects ([`TABLE_COUNT`]).
+        expected: u32,
+        /// The table count actually found in the file's header.
+        found: u32,
+    },
+
+    /// A schema-versioned CBOR payload's `xxh64` checksum didn't match its
+    /// contents, indicating truncation or corruption.
+    ChecksumMismatch {
+        /// Byte offset of the payload (i.e. header length) within the file.
+        offset: usize,
+        /// Length in bytes of the payload the checksum was computed over.
+        len: usize,
+    },
+
+    /// A packed dictionary (see
+    /// [`DictionaryMaxlength::serialize_to_packed`]) had a bad magic header,
+    /// an unsupported format version, or truncated/malformed contents.
+    InvalidPacked(String),
+
+    /// A memory-mapped dictionary (see
+    /// [`DictMaxLen::write_mmap`](super::dict_max_len::DictMaxLen::write_mmap))
+    /// had a bad magic header, an unsupported format version, or
+    /// truncated/malformed contents.
+    InvalidMmapDict(String),
+
+    /// A compact columnar dictionary (see
+    /// [`DictMaxLen::to_compact_bytes`](super::dict_max_len::DictMaxLen::to_compact_bytes))
+    /// had a bad magic header, an unsupported format version, or
+    /// truncated/malformed contents.
+    InvalidCompactDict(String),
+
+    /// A flagged mmap index (see
+    /// [`DictMaxLen::save_index`](super::dict_max_len::DictMaxLen::save_index))
+    /// had a bad magic header, an unsupported format version, truncated or
+    /// malformed contents, or was written on a host with different
+    /// endianness than the one loading it.
+    InvalidMmapIndex(String),
+
+    /// A container (see [`DictionaryMaxlength::save_cbor`]) named a [`Codec`]
+    /// whose backend wasn't compiled into this build (e.g. a Bzip2 or Xz
+    /// file opened without the matching `codec-bzip2`/`codec-xz` feature).
+    UnsupportedCodec(String),
+
+    /// A lazily-decoded bundle (see
+    /// [`DictionaryMaxlength::build_lazy`]/[`open_lazy`](DictionaryMaxlength::open_lazy))
+    /// had a bad magic header, an unsupported format version, a table-count
+    /// mismatch, a missing table, or truncated/malformed section contents.
+    InvalidLazyBundle(String),
 }
 
 impl std::fmt::Display for DictionaryError {
@@ -886,6 +2360,7 @@ impl std::fmt::Display for DictionaryError {
         match self {
             DictionaryError::IoError(e) => write!(f, "I/O error: {}", e),
             DictionaryError::CborParseError(e) => write!(f, "Failed to parse CBOR: {}", e),
+            DictionaryError::BincodeError(e) => write!(f, "Failed to parse bincode: {}", e),
             DictionaryError::LoadFileError {
                 path,
                 lineno,
@@ -893,6 +2368,48 @@ impl std::fmt::Display for DictionaryError {
             } => {
                 write!(f, "Error in {} at line {}: {}", path, lineno, message)
             }
+            DictionaryError::InvalidBundle(message) => {
+                write!(f, "Invalid dictionary bundle: {}", message)
+            }
+            DictionaryError::SchemaMismatch { expected, found } => {
+                write!(
+                    f,
+                    "dictionary schema v{} required, found v{}",
+                    expected, found
+                )
+            }
+            DictionaryError::TableCountMismatch { expected, found } => {
+                write!(
+                    f,
+                    "dictionary table layout mismatch: {} table(s) expected, found {}",
+                    expected, found
+                )
+            }
+            DictionaryError::ChecksumMismatch { offset, len } => {
+                write!(
+                    f,
+                    "dictionary payload corrupted: checksum mismatch over {} byte(s) starting at offset {}",
+                    len, offset
+                )
+            }
+            DictionaryError::InvalidMmapDict(message) => {
+                write!(f, "Invalid mmap dictionary: {}", message)
+            }
+            DictionaryError::InvalidPacked(message) => {
+                write!(f, "Invalid packed dictionary: {}", message)
+            }
+            DictionaryError::InvalidCompactDict(message) => {
+                write!(f, "Invalid compact dictionary: {}", message)
+            }
+            DictionaryError::InvalidMmapIndex(message) => {
+                write!(f, "Invalid mmap index: {}", message)
+            }
+            DictionaryError::UnsupportedCodec(message) => {
+                write!(f, "Unsupported codec: {}", message)
+            }
+            DictionaryError::InvalidLazyBundle(message) => {
+                write!(f, "Invalid lazy dictionary bundle: {}", message)
+            }
         }
     }
 }
@@ -902,7 +2419,18 @@ impl Error for DictionaryError {
         match self {
             DictionaryError::IoError(e) => Some(e),
             DictionaryError::CborParseError(e) => Some(e),
+            DictionaryError::BincodeError(e) => Some(e),
             DictionaryError::LoadFileError { .. } => None,
+            DictionaryError::InvalidBundle(_) => None,
+            DictionaryError::SchemaMismatch { .. } => None,
+            DictionaryError::TableCountMismatch { .. } => None,
+            DictionaryError::ChecksumMismatch { .. } => None,
+            DictionaryError::InvalidPacked(_) => None,
+            DictionaryError::InvalidMmapDict(_) => None,
+            DictionaryError::InvalidCompactDict(_) => None,
+            DictionaryError::InvalidMmapIndex(_) => None,
+            DictionaryError::UnsupportedCodec(_) => None,
+            DictionaryError::InvalidLazyBundle(_) => None,
         }
     }
 }
@@ -920,6 +2448,12 @@ impl From<serde_cbor::Error> for DictionaryError {
     }
 }
 
+impl From<bincode::Error> for DictionaryError {
+    fn from(err: bincode::Error) -> Self {
+        DictionaryError::BincodeError(err)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1096,8 +2630,9 @@ mod tests {
             jp_variants_rev: DictMaxLen::default(),
             st_punctuations: DictMaxLen::default(),
             ts_punctuations: DictMaxLen::default(),
-            // runtime-only cache (serde-skipped)
+            // runtime-only caches (serde-skipped)
             unions: Default::default(),
+            automatons: Default::default(),
         };
 
         dicts.to_dicts(output_dir)?;
@@ -0,0 +1,73 @@
+//! Internal: cached [`Automaton`] instances for arbitrary dictionary
+//! combinations, used by the [`MatchEngine::Automaton`](crate::MatchEngine::Automaton)
+//! matching path.
+//!
+//! Unlike [`union_cache`](super::union_cache), there is no fixed enum of
+//! well-known combinations here — an automaton is only ever requested for
+//! whatever dictionary set a given conversion round already assembled for
+//! its [`StarterUnion`], so this cache is keyed purely by dictionary
+//! identity, the same way [`union_cache::Unions::dynamic`](super::union_cache::Unions)
+//! is.
+
+use std::sync::{Arc, RwLock};
+
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
+
+use super::DictionaryMaxlength;
+use crate::dictionary_lib::dict_max_len::DictMaxLen;
+use crate::dictionary_lib::Automaton;
+
+type DictId = usize;
+
+#[inline]
+fn dict_id(dict: &DictMaxLen) -> DictId {
+    dict as *const DictMaxLen as usize
+}
+
+/// Cache of [`Automaton`] instances keyed by the identity of the
+/// dictionaries they were built from.
+#[derive(Default, Debug)]
+pub(super) struct Automatons {
+    dynamic: RwLock<FxHashMap<SmallVec<[DictId; 4]>, Arc<Automaton>>>,
+}
+
+impl DictionaryMaxlength {
+    /// Returns a cached [`Automaton`] built from `dicts`, building and
+    /// caching it on first use. Later calls with the same dictionaries (in
+    /// the same order) return a cloned `Arc`, mirroring
+    /// [`union_for_dicts`](Self::union_for_dicts).
+    #[inline]
+    pub(crate) fn automaton_for_dicts(&self, dicts: &[&DictMaxLen]) -> Arc<Automaton> {
+        let key: SmallVec<[DictId; 4]> = dicts.iter().map(|d| dict_id(d)).collect();
+
+        if let Some(automaton) = self.automatons.dynamic.read().unwrap().get(&key) {
+            return automaton.clone();
+        }
+
+        let automaton = Arc::new(Automaton::build(dicts));
+        self.automatons
+            .dynamic
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| automaton.clone())
+            .clone()
+    }
+}
+
+#[test]
+fn automaton_for_dicts_cached() {
+    let d = DictionaryMaxlength::default();
+    let a = d.automaton_for_dicts(&[&d.st_phrases, &d.st_characters]);
+    let b = d.automaton_for_dicts(&[&d.st_phrases, &d.st_characters]);
+    assert!(std::ptr::eq(Arc::as_ptr(&a), Arc::as_ptr(&b)));
+}
+
+#[test]
+fn automaton_for_dicts_distinct_by_set() {
+    let d = DictionaryMaxlength::default();
+    let a = d.automaton_for_dicts(&[&d.st_phrases, &d.st_characters]);
+    let b = d.automaton_for_dicts(&[&d.ts_phrases, &d.ts_characters]);
+    assert!(!std::ptr::eq(Arc::as_ptr(&a), Arc::as_ptr(&b)));
+}
@@ -0,0 +1,527 @@
+//! A lazily-decoding container for [`DictionaryMaxlength`], built for
+//! short-lived processes that only ever touch a handful of the eighteen
+//! [`DictMaxLen`] tables — e.g. a CLI invocation doing a single `s2t`
+//! conversion never needs the Hong Kong or Japanese tables at all.
+//!
+//! [`save_cbor`](DictionaryMaxlength::save_cbor) and
+//! [`serialize_to_packed`](super::packed)/`deserialize_from_packed` both
+//! decode every table up front, because the container as a whole is one
+//! compressed blob (or, for the packed format, one flat byte stream) with
+//! no way to skip past a table without decoding the ones before it. This
+//! format instead gives every table its own independently-compressed CBOR
+//! section with a fixed-size index recording each section's offset and
+//! length, so [`open_lazy`](DictionaryMaxlength::open_lazy) only has to
+//! `mmap` the file and parse the index — no section is decompressed or
+//! deserialized until [`LazyDictionary`] is asked for that table, and the
+//! result is cached so a second request for the same table is a plain
+//! reference return.
+//!
+//! # On-disk layout
+//!
+//! ```text
+//! magic (4 bytes: b"OCLZ")
+//! version (u16 LE)
+//! table_count (u32 LE)
+//! index: table_count × (name_len u16 LE, name bytes, offset u64 LE, len u64 LE)
+//! sections: each table's codec-compressed CBOR bytes, back-to-back
+//! ```
+//!
+//! `offset`/`len` in the index are absolute byte positions within the file
+//! (not relative to the sections area), so [`LazyDictionary`] can slice the
+//! mapped bytes directly without re-deriving a base position. Each section
+//! carries its own codec magic prefix (the same four-byte magics
+//! [`Codec::magic`] defines), auto-detected on first access exactly like
+//! [`load_cbor_compressed_from_slice`](DictionaryMaxlength::load_cbor_compressed_from_slice) —
+//! there is no single bundle-wide codec, so [`build_lazy`](DictionaryMaxlength::build_lazy)'s
+//! `codec` argument simply picks the one used for every section it writes.
+//!
+//! # Caveats
+//!
+//! A table's dense BMP accelerators
+//! ([`DictMaxLen::first_len_mask64`]/[`DictMaxLen::first_char_max_len`]) are
+//! rebuilt from `map`/`starter_len_mask` on first access via
+//! [`populate_starter_indexes`](DictMaxLen::populate_starter_indexes),
+//! exactly like [`deserialize_from_cbor`](DictionaryMaxlength::deserialize_from_cbor)
+//! — a table accessed once per process lifetime gains nothing from a
+//! precomputed accelerator over a lazily-built one.
+
+use std::fs::{self, File};
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use memmap2::Mmap;
+use rustc_hash::FxHashMap;
+use zstd::{decode_all, Encoder};
+
+use super::{Codec, DictionaryError, DictionaryMaxlength};
+use crate::dictionary_lib::dict_max_len::DictMaxLen;
+
+const LAZY_MAGIC: [u8; 4] = *b"OCLZ";
+const LAZY_VERSION: u16 = 1;
+
+/// Number of `DictMaxLen` tables a lazy bundle must carry — kept in lock
+/// step with [`DictionaryMaxlength::bundle_tables`]'s length.
+const TABLE_COUNT: u32 = 18;
+
+#[inline]
+fn invalid(message: impl Into<String>) -> DictionaryError {
+    DictionaryError::InvalidLazyBundle(message.into())
+}
+
+/// Compresses `payload` with `codec`, prefixed with that codec's magic so
+/// [`decompress_section`] can auto-detect it later — mirrors
+/// [`DictionaryMaxlength::save_cbor`]'s per-codec match, but over an
+/// in-memory buffer rather than a file writer.
+fn compress_section(codec: Codec, payload: &[u8]) -> Result<Vec<u8>, DictionaryError> {
+    let mut out = codec.magic().to_vec();
+    match codec {
+        Codec::Zstd { level } => {
+            let mut encoder = Encoder::new(&mut out, level).map_err(DictionaryError::IoError)?;
+            encoder.write_all(payload).map_err(DictionaryError::IoError)?;
+            encoder.finish().map_err(DictionaryError::IoError)?;
+        }
+        Codec::Lz4 => {
+            let mut encoder = lz4_flex::frame::FrameEncoder::new(&mut out);
+            encoder.write_all(payload).map_err(DictionaryError::IoError)?;
+            encoder
+                .finish()
+                .map_err(|e| DictionaryError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+        }
+        #[cfg(feature = "codec-bzip2")]
+        Codec::Bzip2 => {
+            let mut encoder = bzip2::write::BzEncoder::new(&mut out, bzip2::Compression::best());
+            encoder.write_all(payload).map_err(DictionaryError::IoError)?;
+            encoder.finish().map_err(DictionaryError::IoError)?;
+        }
+        #[cfg(not(feature = "codec-bzip2"))]
+        Codec::Bzip2 => {
+            return Err(DictionaryError::UnsupportedCodec(
+                "Bzip2 support was not compiled in; rebuild with the `codec-bzip2` feature".into(),
+            ));
+        }
+        #[cfg(feature = "codec-xz")]
+        Codec::Xz => {
+            let mut encoder = xz2::write::XzEncoder::new(&mut out, 6);
+            encoder.write_all(payload).map_err(DictionaryError::IoError)?;
+            encoder.finish().map_err(DictionaryError::IoError)?;
+        }
+        #[cfg(not(feature = "codec-xz"))]
+        Codec::Xz => {
+            return Err(DictionaryError::UnsupportedCodec(
+                "Xz support was not compiled in; rebuild with the `codec-xz` feature".into(),
+            ));
+        }
+        Codec::None => out.extend_from_slice(payload),
+    }
+    Ok(out)
+}
+
+/// Inverse of [`compress_section`]: detects the codec from `bytes`' magic
+/// prefix and returns the decompressed payload.
+fn decompress_section(bytes: &[u8]) -> Result<Vec<u8>, DictionaryError> {
+    if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_ZSTD) {
+        decode_all(Cursor::new(rest)).map_err(DictionaryError::IoError)
+    } else if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_LZ4) {
+        let mut decoder = lz4_flex::frame::FrameDecoder::new(rest);
+        let mut out = Vec::new();
+        decoder.read_to_end(&mut out).map_err(DictionaryError::IoError)?;
+        Ok(out)
+    } else if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_BZIP2) {
+        #[cfg(feature = "codec-bzip2")]
+        {
+            let mut decoder = bzip2::read::BzDecoder::new(rest);
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out).map_err(DictionaryError::IoError)?;
+            Ok(out)
+        }
+        #[cfg(not(feature = "codec-bzip2"))]
+        {
+            Err(DictionaryError::UnsupportedCodec(
+                "section is Bzip2-compressed, but this build lacks the `codec-bzip2` feature"
+                    .into(),
+            ))
+        }
+    } else if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_XZ) {
+        #[cfg(feature = "codec-xz")]
+        {
+            let mut decoder = xz2::read::XzDecoder::new(rest);
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out).map_err(DictionaryError::IoError)?;
+            Ok(out)
+        }
+        #[cfg(not(feature = "codec-xz"))]
+        {
+            Err(DictionaryError::UnsupportedCodec(
+                "section is Xz-compressed, but this build lacks the `codec-xz` feature".into(),
+            ))
+        }
+    } else if let Some(rest) = bytes.strip_prefix(&Codec::MAGIC_NONE) {
+        Ok(rest.to_vec())
+    } else {
+        Err(invalid("unrecognized section codec magic"))
+    }
+}
+
+/// One [`OnceLock`] slot per table, matching [`DictionaryMaxlength`]'s own
+/// field names so [`LazyDictionary`]'s accessors read the same way the
+/// eager struct's fields do.
+#[derive(Default)]
+struct LazyCells {
+    st_characters: OnceLock<DictMaxLen>,
+    st_phrases: OnceLock<DictMaxLen>,
+    ts_characters: OnceLock<DictMaxLen>,
+    ts_phrases: OnceLock<DictMaxLen>,
+    st_punctuations: OnceLock<DictMaxLen>,
+    ts_punctuations: OnceLock<DictMaxLen>,
+    tw_phrases: OnceLock<DictMaxLen>,
+    tw_phrases_rev: OnceLock<DictMaxLen>,
+    tw_variants: OnceLock<DictMaxLen>,
+    tw_variants_rev: OnceLock<DictMaxLen>,
+    tw_variants_rev_phrases: OnceLock<DictMaxLen>,
+    hk_variants: OnceLock<DictMaxLen>,
+    hk_variants_rev: OnceLock<DictMaxLen>,
+    hk_variants_rev_phrases: OnceLock<DictMaxLen>,
+    jps_characters: OnceLock<DictMaxLen>,
+    jps_phrases: OnceLock<DictMaxLen>,
+    jp_variants: OnceLock<DictMaxLen>,
+    jp_variants_rev: OnceLock<DictMaxLen>,
+}
+
+/// A memory-mapped [`DictionaryMaxlength`] bundle whose eighteen tables are
+/// decoded one at a time, on first access, instead of all up front.
+///
+/// Built by [`DictionaryMaxlength::build_lazy`] and opened with
+/// [`DictionaryMaxlength::open_lazy`] — see the module docs for the
+/// on-disk layout.
+pub struct LazyDictionary {
+    mmap: Mmap,
+    sections: FxHashMap<&'static str, (usize, usize)>,
+    cells: LazyCells,
+}
+
+macro_rules! lazy_accessor {
+    ($name:ident) => {
+        /// Decodes and caches this table on first call; later calls return
+        /// the cached table directly.
+        pub fn $name(&self) -> Result<&DictMaxLen, DictionaryError> {
+            self.table(stringify!($name), &self.cells.$name)
+        }
+    };
+}
+
+impl LazyDictionary {
+    lazy_accessor!(st_characters);
+    lazy_accessor!(st_phrases);
+    lazy_accessor!(ts_characters);
+    lazy_accessor!(ts_phrases);
+    lazy_accessor!(st_punctuations);
+    lazy_accessor!(ts_punctuations);
+    lazy_accessor!(tw_phrases);
+    lazy_accessor!(tw_phrases_rev);
+    lazy_accessor!(tw_variants);
+    lazy_accessor!(tw_variants_rev);
+    lazy_accessor!(tw_variants_rev_phrases);
+    lazy_accessor!(hk_variants);
+    lazy_accessor!(hk_variants_rev);
+    lazy_accessor!(hk_variants_rev_phrases);
+    lazy_accessor!(jps_characters);
+    lazy_accessor!(jps_phrases);
+    lazy_accessor!(jp_variants);
+    lazy_accessor!(jp_variants_rev);
+
+    /// Shared implementation behind every named accessor: returns the
+    /// already-cached table if present, otherwise decompresses and decodes
+    /// its section, populates its starter indexes, caches it, and returns
+    /// the cached reference.
+    fn table(&self, name: &'static str, cell: &OnceLock<DictMaxLen>) -> Result<&DictMaxLen, DictionaryError> {
+        if let Some(table) = cell.get() {
+            return Ok(table);
+        }
+
+        let &(start, len) = self
+            .sections
+            .get(name)
+            .ok_or_else(|| invalid(format!("lazy bundle is missing table '{}'", name)))?;
+        let section = self
+            .mmap
+            .get(start..start + len)
+            .ok_or_else(|| invalid(format!("truncated section for table '{}'", name)))?;
+
+        let cbor_bytes = decompress_section(section)?;
+        let mut table: DictMaxLen = serde_cbor::from_slice(&cbor_bytes)?;
+        table.populate_starter_indexes();
+
+        // Ignore a lost race: another thread may have decoded and cached
+        // the same table first, in which case its value wins and ours is
+        // simply dropped.
+        let _ = cell.set(table);
+        Ok(cell.get().expect("cell was just set or already populated"))
+    }
+
+    /// Forces every table and assembles an owned [`DictionaryMaxlength`],
+    /// for callers that ultimately want the eager struct (e.g. to build a
+    /// [`StarterUnion`](crate::dictionary_lib::StarterUnion) spanning
+    /// several tables) but still want `open_lazy`'s near-instant startup
+    /// when only some of those tables turn out to be needed at runtime.
+    pub fn materialize(&self) -> Result<DictionaryMaxlength, DictionaryError> {
+        let mut dictionary = DictionaryMaxlength::default();
+        for name in TABLE_NAMES {
+            let table = self.table(name, self.cell_for(name))?;
+            dictionary.set_bundle_table(name, clone_dict(table));
+        }
+        Ok(dictionary.finish())
+    }
+
+    fn cell_for(&self, name: &str) -> &OnceLock<DictMaxLen> {
+        match name {
+            "st_characters" => &self.cells.st_characters,
+            "st_phrases" => &self.cells.st_phrases,
+            "ts_characters" => &self.cells.ts_characters,
+            "ts_phrases" => &self.cells.ts_phrases,
+            "st_punctuations" => &self.cells.st_punctuations,
+            "ts_punctuations" => &self.cells.ts_punctuations,
+            "tw_phrases" => &self.cells.tw_phrases,
+            "tw_phrases_rev" => &self.cells.tw_phrases_rev,
+            "tw_variants" => &self.cells.tw_variants,
+            "tw_variants_rev" => &self.cells.tw_variants_rev,
+            "tw_variants_rev_phrases" => &self.cells.tw_variants_rev_phrases,
+            "hk_variants" => &self.cells.hk_variants,
+            "hk_variants_rev" => &self.cells.hk_variants_rev,
+            "hk_variants_rev_phrases" => &self.cells.hk_variants_rev_phrases,
+            "jps_characters" => &self.cells.jps_characters,
+            "jps_phrases" => &self.cells.jps_phrases,
+            "jp_variants" => &self.cells.jp_variants,
+            "jp_variants_rev" => &self.cells.jp_variants_rev,
+            other => unreachable!("unknown lazy bundle table name '{}'", other),
+        }
+    }
+}
+
+/// `DictMaxLen` doesn't implement `Clone`; [`materialize`](LazyDictionary::materialize)
+/// round-trips through CBOR instead, which is cheap relative to the
+/// decompression already paid for by [`LazyDictionary::table`].
+fn clone_dict(table: &DictMaxLen) -> DictMaxLen {
+    let bytes = serde_cbor::to_vec(table).expect("DictMaxLen always serializes");
+    let mut cloned: DictMaxLen = serde_cbor::from_slice(&bytes).expect("just-serialized bytes always parse");
+    cloned.populate_starter_indexes();
+    cloned
+}
+
+/// The eighteen table names, in the same order [`DictionaryMaxlength::bundle_tables`] uses.
+const TABLE_NAMES: [&str; 18] = [
+    "st_characters",
+    "st_phrases",
+    "ts_characters",
+    "ts_phrases",
+    "st_punctuations",
+    "ts_punctuations",
+    "tw_phrases",
+    "tw_phrases_rev",
+    "tw_variants",
+    "tw_variants_rev",
+    "tw_variants_rev_phrases",
+    "hk_variants",
+    "hk_variants_rev",
+    "hk_variants_rev_phrases",
+    "jps_characters",
+    "jps_phrases",
+    "jp_variants",
+    "jp_variants_rev",
+];
+
+impl DictionaryMaxlength {
+    /// Writes every table to `path` as an independently-compressed,
+    /// offset-indexed lazy bundle — see the module docs for the on-disk
+    /// layout. Read it back with [`open_lazy`](Self::open_lazy).
+    pub fn build_lazy<P: AsRef<Path>>(&self, path: P, codec: Codec) -> Result<(), DictionaryError> {
+        let tables = self.bundle_tables();
+
+        let mut sections: Vec<(&'static str, Vec<u8>)> = Vec::with_capacity(tables.len());
+        for (name, table) in tables {
+            let cbor = serde_cbor::to_vec(table)?;
+            sections.push((name, compress_section(codec, &cbor)?));
+        }
+
+        let header_len = LAZY_MAGIC.len() + 2 + 4;
+        let index_len: usize = sections
+            .iter()
+            .map(|(name, _)| 2 + name.len() + 8 + 8)
+            .sum();
+        let mut offset = header_len + index_len;
+
+        let mut out = Vec::with_capacity(offset + sections.iter().map(|(_, d)| d.len()).sum::<usize>());
+        out.extend_from_slice(&LAZY_MAGIC);
+        out.extend_from_slice(&LAZY_VERSION.to_le_bytes());
+        out.extend_from_slice(&(sections.len() as u32).to_le_bytes());
+
+        for (name, data) in &sections {
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&(offset as u64).to_le_bytes());
+            out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            offset += data.len();
+        }
+
+        for (_, data) in &sections {
+            out.extend_from_slice(data);
+        }
+
+        fs::write(&path, out).map_err(|err| {
+            let msg = format!("Failed to write lazy dictionary bundle: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })
+    }
+
+    /// Opens a lazy bundle written by [`build_lazy`](Self::build_lazy),
+    /// memory-mapping `path` and parsing just its index — no table is
+    /// decompressed or deserialized until [`LazyDictionary`] is asked for
+    /// it.
+    ///
+    /// # Safety
+    ///
+    /// This relies on [`memmap2::Mmap::map`], which is unsafe because the
+    /// file could be truncated or modified by another process while
+    /// mapped; as with the rest of this crate's mmap-backed formats
+    /// ([`MmapDict`](super::super::mmap_dict::MmapDict),
+    /// [`MmapIndex`](super::super::mmap_index::MmapIndex)), that's treated
+    /// as an acceptable risk for a dictionary file the caller controls.
+    pub fn open_lazy<P: AsRef<Path>>(path: P) -> Result<LazyDictionary, DictionaryError> {
+        let file = File::open(&path).map_err(|err| {
+            let msg = format!("Failed to open lazy dictionary bundle: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        // Safety: see the method-level `# Safety` note above.
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|err| {
+            let msg = format!("Failed to mmap lazy dictionary bundle: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        let bytes = &mmap[..];
+        let rest = bytes
+            .strip_prefix(&LAZY_MAGIC)
+            .ok_or_else(|| invalid("missing or unrecognized lazy bundle magic header"))?;
+        if rest.len() < 2 + 4 {
+            return Err(invalid("truncated lazy bundle header"));
+        }
+        let (version_bytes, rest) = rest.split_at(2);
+        let version = u16::from_le_bytes([version_bytes[0], version_bytes[1]]);
+        if version != LAZY_VERSION {
+            return Err(invalid(format!(
+                "lazy bundle format v{} required, found v{}",
+                LAZY_VERSION, version
+            )));
+        }
+
+        let (count_bytes, mut rest) = rest.split_at(4);
+        let table_count = u32::from_le_bytes(count_bytes.try_into().unwrap());
+        if table_count != TABLE_COUNT {
+            return Err(DictionaryError::TableCountMismatch {
+                expected: TABLE_COUNT,
+                found: table_count,
+            });
+        }
+
+        let mut sections = FxHashMap::default();
+        sections.reserve(table_count as usize);
+        for _ in 0..table_count {
+            if rest.len() < 2 {
+                return Err(invalid("truncated lazy bundle index entry (missing name length)"));
+            }
+            let (name_len_bytes, after_len) = rest.split_at(2);
+            let name_len = u16::from_le_bytes([name_len_bytes[0], name_len_bytes[1]]) as usize;
+
+            if after_len.len() < name_len + 16 {
+                return Err(invalid("truncated lazy bundle index entry"));
+            }
+            let (name_bytes, after_name) = after_len.split_at(name_len);
+            let name = std::str::from_utf8(name_bytes)
+                .map_err(|_| invalid("lazy bundle index entry name is not valid UTF-8"))?;
+            let static_name = TABLE_NAMES
+                .iter()
+                .copied()
+                .find(|known| *known == name)
+                .ok_or_else(|| invalid(format!("unrecognized lazy bundle table name '{}'", name)))?;
+
+            let (offset_bytes, after_offset) = after_name.split_at(8);
+            let (len_bytes, next_entry) = after_offset.split_at(8);
+            let offset = u64::from_le_bytes(offset_bytes.try_into().unwrap()) as usize;
+            let len = u64::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+
+            sections.insert(static_name, (offset, len));
+            rest = next_entry;
+        }
+
+        for name in TABLE_NAMES {
+            if !sections.contains_key(name) {
+                return Err(invalid(format!("lazy bundle is missing table '{}'", name)));
+            }
+        }
+
+        Ok(LazyDictionary {
+            mmap,
+            sections,
+            cells: LazyCells::default(),
+        })
+    }
+}
+
+#[test]
+fn build_then_open_lazy_decodes_tables_on_demand() {
+    let dictionary = DictionaryMaxlength::default();
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dictionary.build_lazy(file.path(), Codec::Zstd { level: 3 }).unwrap();
+
+    let lazy = DictionaryMaxlength::open_lazy(file.path()).unwrap();
+    let table = lazy.st_characters().unwrap();
+    assert_eq!(table.map.len(), dictionary.st_characters.map.len());
+
+    // Second access returns the cached table.
+    let again = lazy.st_characters().unwrap();
+    assert_eq!(table.map.len(), again.map.len());
+}
+
+#[test]
+fn materialize_produces_every_table() {
+    let dictionary = DictionaryMaxlength::default();
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dictionary.build_lazy(file.path(), Codec::None).unwrap();
+
+    let lazy = DictionaryMaxlength::open_lazy(file.path()).unwrap();
+    let materialized = lazy.materialize().unwrap();
+
+    for (name, table) in dictionary.bundle_tables() {
+        let (_, materialized_table) = materialized
+            .bundle_tables()
+            .into_iter()
+            .find(|(found_name, _)| *found_name == name)
+            .unwrap();
+        assert_eq!(materialized_table.map.len(), table.map.len());
+    }
+}
+
+#[test]
+fn open_lazy_rejects_bad_magic() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(file.path(), b"not a lazy dictionary bundle").unwrap();
+    assert!(DictionaryMaxlength::open_lazy(file.path()).is_err());
+}
+
+#[test]
+fn open_lazy_rejects_unsupported_version() {
+    let dictionary = DictionaryMaxlength::default();
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dictionary.build_lazy(file.path(), Codec::None).unwrap();
+
+    let mut bytes = fs::read(file.path()).unwrap();
+    let version_pos = LAZY_MAGIC.len();
+    bytes[version_pos] = 0xff;
+    bytes[version_pos + 1] = 0xff;
+    fs::write(file.path(), &bytes).unwrap();
+
+    assert!(DictionaryMaxlength::open_lazy(file.path()).is_err());
+}
@@ -0,0 +1,362 @@
+//! A purpose-built compact binary format for [`DictionaryMaxlength`],
+//! offered as a ship-in-app alternative to CBOR
+//! ([`serialize_to_cbor`](DictionaryMaxlength::serialize_to_cbor)).
+//!
+//! CBOR keeps every key as a self-describing array of `char`s plus a
+//! length-prefixed string value, which is bulky for lexicons this large.
+//! This format instead:
+//!
+//! - Sorts each table's keys and **front-codes** them: every entry stores
+//!   only the length of the prefix shared with the previous key plus the
+//!   differing suffix, since adjacent sorted CJK phrases commonly share a
+//!   leading character or more.
+//! - Encodes every length, offset, and starter codepoint as an **LEB128
+//!   varint** rather than a fixed-width integer, so the common case (short
+//!   phrases, BMP codepoints) costs one or two bytes instead of four or eight.
+//! - Stores the precomputed starter metadata ([`DictMaxLen::first_len_mask64`],
+//!   [`DictMaxLen::first_char_max_len`], [`DictMaxLen::starter_len_mask`])
+//!   alongside the pairs. The dense BMP arrays are stored only when they
+//!   span the full BMP (as [`ensure_starter_indexes`](DictMaxLen::ensure_starter_indexes)
+//!   builds them); watermark-bounded dense arrays and compressed or
+//!   block-sparse starter indexes are not stored, and a table saved with one
+//!   of those loads with only `starter_len_mask` populated.
+//!   [`deserialize_from_packed`](DictionaryMaxlength::deserialize_from_packed)
+//!   installs whatever dense arrays were stored directly instead of rebuilding them via
+//!   [`populate_starter_indexes`](DictMaxLen::populate_starter_indexes) —
+//!   unlike [`deserialize_from_cbor`](DictionaryMaxlength::deserialize_from_cbor),
+//!   this loader does **not** call [`finish`](DictionaryMaxlength::finish).
+//!
+//! CBOR remains the interchange format (human-inspectable with any CBOR
+//! tool, stable across this crate's schema-versioned header); this format
+//! is for shipping inside an application where load latency and binary size
+//! matter more than interchange friendliness.
+//!
+//! [`fst`](DictMaxLen::fst)/[`byte_fst`](DictMaxLen::byte_fst) are not part
+//! of this format and are left `None` on load;
+//! [`DictMaxLen::lookup_longest`] returns `None` until
+//! [`DictMaxLen::ensure_fst`] rebuilds `fst`.
+
+use std::fs;
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+
+use super::{DictionaryError, DictionaryMaxlength};
+use crate::dictionary_lib::dict_max_len::DictMaxLen;
+
+/// 4-byte magic header identifying a packed dictionary blob.
+const PACKED_MAGIC: [u8; 4] = *b"OCPK";
+
+/// Current packed format version. Bump whenever the on-disk layout changes
+/// in a way that would otherwise misparse silently.
+const PACKED_VERSION: u16 = 1;
+
+#[inline]
+fn invalid(message: impl Into<String>) -> DictionaryError {
+    DictionaryError::InvalidPacked(message.into())
+}
+
+/// Appends `value` to `out` as an LEB128 unsigned varint.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Reads an LEB128 unsigned varint starting at `*pos`, advancing `*pos` past it.
+fn read_varint(buf: &[u8], pos: &mut usize) -> Result<u64, DictionaryError> {
+    let mut result: u64 = 0;
+    let mut shift: u32 = 0;
+    loop {
+        let byte = *buf
+            .get(*pos)
+            .ok_or_else(|| invalid("truncated varint in packed dictionary"))?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return Err(invalid("varint too long in packed dictionary"));
+        }
+    }
+}
+
+/// Reads a little-endian `u64` starting at `*pos`, advancing `*pos` past it.
+fn read_u64(buf: &[u8], pos: &mut usize) -> Result<u64, DictionaryError> {
+    let end = *pos + 8;
+    let bytes: [u8; 8] = buf
+        .get(*pos..end)
+        .ok_or_else(|| invalid("truncated fixed-width field in packed dictionary"))?
+        .try_into()
+        .unwrap();
+    *pos = end;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Length (in `char`s) of the common leading prefix of `a` and `b`.
+fn shared_prefix_len(a: &[char], b: &[char]) -> usize {
+    a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count()
+}
+
+/// Encodes one [`DictMaxLen`] table: sorted, front-coded `(key, value)`
+/// pairs, followed by its global metadata and starter accelerators.
+fn encode_dict(dict: &DictMaxLen, out: &mut Vec<u8>) {
+    let mut entries: Vec<(&[char], &str)> = dict
+        .map
+        .iter()
+        .map(|(k, v)| (k.as_ref(), v.as_ref()))
+        .collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+    write_varint(out, entries.len() as u64);
+
+    let mut prev: &[char] = &[];
+    for (key, value) in &entries {
+        let shared = shared_prefix_len(prev, key);
+        let suffix = &key[shared..];
+
+        write_varint(out, shared as u64);
+        write_varint(out, suffix.len() as u64);
+        for &c in suffix {
+            write_varint(out, c as u64);
+        }
+
+        let value_bytes = value.as_bytes();
+        write_varint(out, value_bytes.len() as u64);
+        out.extend_from_slice(value_bytes);
+
+        prev = key;
+    }
+
+    write_varint(out, dict.min_len as u64);
+    write_varint(out, dict.max_len as u64);
+    out.extend_from_slice(&dict.key_length_mask.to_le_bytes());
+
+    write_varint(out, dict.starter_len_mask.len() as u64);
+    for (&starter, &mask) in &dict.starter_len_mask {
+        write_varint(out, starter as u64);
+        out.extend_from_slice(&mask.to_le_bytes());
+    }
+
+    // This format always stores the full BMP span (not the watermark-bounded
+    // range `populate_starter_indexes` may use), so check the exact length
+    // rather than `is_populated()` — a watermarked dict would otherwise be
+    // flagged dense and have its (shorter) arrays written and misread as if
+    // they spanned the whole BMP.
+    let dense = dict.first_len_mask64.len() == 0x10000 && dict.first_char_max_len.len() == 0x10000;
+    out.push(dense as u8);
+    if dense {
+        for &mask in &dict.first_len_mask64 {
+            out.extend_from_slice(&mask.to_le_bytes());
+        }
+        out.extend_from_slice(&dict.first_char_max_len);
+    }
+}
+
+/// Decodes one [`DictMaxLen`] table written by [`encode_dict`], reading
+/// starting at `*pos` and advancing it past the table.
+fn decode_dict(buf: &[u8], pos: &mut usize) -> Result<DictMaxLen, DictionaryError> {
+    let count = read_varint(buf, pos)? as usize;
+
+    let mut map: FxHashMap<Box<[char]>, Box<str>> = FxHashMap::default();
+    map.reserve(count);
+    let mut prev: Vec<char> = Vec::new();
+
+    for _ in 0..count {
+        let shared = read_varint(buf, pos)? as usize;
+        let suffix_len = read_varint(buf, pos)? as usize;
+        if shared > prev.len() {
+            return Err(invalid(
+                "front-coded shared-prefix length exceeds previous key",
+            ));
+        }
+
+        let mut key = Vec::with_capacity(shared + suffix_len);
+        key.extend_from_slice(&prev[..shared]);
+        for _ in 0..suffix_len {
+            let codepoint = read_varint(buf, pos)? as u32;
+            let c = char::from_u32(codepoint)
+                .ok_or_else(|| invalid("invalid char codepoint in packed dictionary key"))?;
+            key.push(c);
+        }
+
+        let value_len = read_varint(buf, pos)? as usize;
+        let value_end = *pos + value_len;
+        let value_bytes = buf
+            .get(*pos..value_end)
+            .ok_or_else(|| invalid("truncated value in packed dictionary"))?;
+        let value = std::str::from_utf8(value_bytes)
+            .map_err(|_| invalid("packed dictionary value is not valid UTF-8"))?
+            .to_string();
+        *pos = value_end;
+
+        map.insert(key.clone().into_boxed_slice(), value.into_boxed_str());
+        prev = key;
+    }
+
+    let min_len = read_varint(buf, pos)? as usize;
+    let max_len = read_varint(buf, pos)? as usize;
+    let key_length_mask = read_u64(buf, pos)?;
+
+    let starter_count = read_varint(buf, pos)? as usize;
+    let mut starter_len_mask = FxHashMap::default();
+    starter_len_mask.reserve(starter_count);
+    for _ in 0..starter_count {
+        let codepoint = read_varint(buf, pos)? as u32;
+        let starter = char::from_u32(codepoint)
+            .ok_or_else(|| invalid("invalid starter codepoint in packed dictionary"))?;
+        let mask = read_u64(buf, pos)?;
+        starter_len_mask.insert(starter, mask);
+    }
+
+    let dense = *buf
+        .get(*pos)
+        .ok_or_else(|| invalid("truncated packed dictionary (missing dense-table flag)"))?
+        != 0;
+    *pos += 1;
+
+    let (first_len_mask64, first_char_max_len) = if dense {
+        const BMP: usize = 0x10000;
+        let mut masks = Vec::with_capacity(BMP);
+        for _ in 0..BMP {
+            masks.push(read_u64(buf, pos)?);
+        }
+        let caps_end = *pos + BMP;
+        let caps = buf
+            .get(*pos..caps_end)
+            .ok_or_else(|| invalid("truncated packed dense cap table"))?
+            .to_vec();
+        *pos = caps_end;
+        (masks, caps)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    Ok(DictMaxLen {
+        map,
+        max_len,
+        min_len,
+        key_length_mask,
+        starter_len_mask,
+        fst: None,
+        byte_fst: None,
+        first_len_mask64,
+        first_char_max_len,
+        // This format's dense arrays (when present) always span the full
+        // BMP, never a watermarked sub-range, so the base is always 0.
+        starter_base: 0,
+        compressed_starter_index: None,
+        block_sparse_starter_index: None,
+    })
+}
+
+impl DictionaryMaxlength {
+    /// Encodes every dictionary table into this crate's compact packed
+    /// format (see the module docs for the on-disk layout) and writes it to `path`.
+    pub fn serialize_to_packed<P: AsRef<Path>>(&self, path: P) -> Result<(), DictionaryError> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&PACKED_MAGIC);
+        out.extend_from_slice(&PACKED_VERSION.to_le_bytes());
+        for (_, table) in self.bundle_tables() {
+            encode_dict(table, &mut out);
+        }
+
+        fs::write(&path, out).map_err(|err| {
+            let msg = format!("Failed to write packed dictionary file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })
+    }
+
+    /// Reads a dictionary from a file previously written by
+    /// [`serialize_to_packed`](Self::serialize_to_packed).
+    ///
+    /// Unlike [`deserialize_from_cbor`](Self::deserialize_from_cbor), this
+    /// does **not** call [`finish`](Self::finish): the whole point of this
+    /// format is that the dense starter accelerators (when the file holds
+    /// them — see the module docs) are installed directly from the packed
+    /// bytes, so rebuilding them would waste the
+    /// time this format exists to save.
+    pub fn deserialize_from_packed<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        let bytes = fs::read(&path).map_err(|err| {
+            let msg = format!("Failed to read packed dictionary file: {}", err);
+            Self::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        let rest = bytes
+            .strip_prefix(&PACKED_MAGIC)
+            .ok_or_else(|| invalid("missing or unrecognized packed dictionary magic header"))?;
+        if rest.len() < 2 {
+            return Err(invalid("truncated packed dictionary header"));
+        }
+        let (version_bytes, rest) = rest.split_at(2);
+        let version = u16::from_le_bytes([version_bytes[0], version_bytes[1]]);
+        if version != PACKED_VERSION {
+            return Err(invalid(format!(
+                "packed dictionary format v{} required, found v{}",
+                PACKED_VERSION, version
+            )));
+        }
+
+        let mut dictionary = DictionaryMaxlength::default();
+        let names: [&'static str; 18] = dictionary.bundle_tables().map(|(name, _)| name);
+
+        let mut pos = 0usize;
+        for name in names {
+            let table = decode_dict(rest, &mut pos)?;
+            dictionary.set_bundle_table(name, table);
+        }
+
+        Ok(dictionary)
+    }
+}
+
+#[test]
+fn serialize_then_deserialize_packed_round_trips_table_metadata() {
+    let dictionary = DictionaryMaxlength::default();
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dictionary.serialize_to_packed(file.path()).unwrap();
+
+    let loaded = DictionaryMaxlength::deserialize_from_packed(file.path()).unwrap();
+
+    for (name, table) in dictionary.bundle_tables() {
+        let (_, loaded_table) = loaded
+            .bundle_tables()
+            .into_iter()
+            .find(|(loaded_name, _)| *loaded_name == name)
+            .unwrap();
+        assert_eq!(loaded_table.min_len, table.min_len);
+        assert_eq!(loaded_table.max_len, table.max_len);
+        assert_eq!(loaded_table.map.len(), table.map.len());
+    }
+}
+
+#[test]
+fn deserialize_from_packed_rejects_bad_magic() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), b"not a packed dictionary file").unwrap();
+    assert!(DictionaryMaxlength::deserialize_from_packed(file.path()).is_err());
+}
+
+#[test]
+fn deserialize_from_packed_rejects_unsupported_version() {
+    let dictionary = DictionaryMaxlength::default();
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dictionary.serialize_to_packed(file.path()).unwrap();
+
+    let mut bytes = fs::read(file.path()).unwrap();
+    bytes[4] = 0xff;
+    bytes[5] = 0xff;
+    fs::write(file.path(), &bytes).unwrap();
+
+    assert!(DictionaryMaxlength::deserialize_from_packed(file.path()).is_err());
+}
@@ -5,11 +5,29 @@
 //! specific combination of dictionaries (e.g. S2T, T2S with punctuation, TW/HK/JP variants),
 //! and is built lazily on first use. Subsequent lookups are cheap `Arc` clones.
 
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
 
 use super::DictionaryMaxlength;
+use crate::dictionary_lib::dict_max_len::DictMaxLen;
 use crate::dictionary_lib::StarterUnion;
 
+/// Identity key for a single [`DictMaxLen`] inside a dynamic union's cache
+/// key (see [`Unions::dynamic`]). Dictionaries are plain data with no id
+/// field of their own, so identity is the table's stable memory address for
+/// as long as the owning [`DictionaryMaxlength`] (or caller-supplied
+/// dictionary) isn't moved; if it is, [`DictionaryMaxlength::union_for_dicts`]
+/// simply treats it as a new combination and rebuilds — a wasted cache slot,
+/// not a correctness issue.
+type DictId = usize;
+
+#[inline]
+fn dict_id(dict: &DictMaxLen) -> DictId {
+    dict as *const DictMaxLen as usize
+}
+
 /// Cache slots for all [`StarterUnion`] variants used by the public conversion APIs.
 ///
 /// Each field is a [`OnceLock`] holding an [`Arc<StarterUnion>`]. The first
@@ -66,6 +84,16 @@ pub(super) struct Unions {
     /// Union combining Japanese Shinjitai phrases, characters and
     /// reverse variants (jps_phrases + jps_chars + jp_variants_rev).
     jp_rev_triple: OnceLock<Arc<StarterUnion>>,
+
+    // Dynamic, user-extensible unions
+    /// Cache for unions over caller-supplied dictionary combinations that
+    /// don't correspond to a fixed slot above — e.g. a custom vocabulary
+    /// layered on top of a built-in conversion route. Keyed by the *set* of
+    /// dictionaries making up the union (see [`DictId`]), built and cached
+    /// on first use by [`DictionaryMaxlength::union_for_dicts`] exactly like
+    /// the `OnceLock` slots above, just without a fixed enum variant per
+    /// combination.
+    dynamic: RwLock<FxHashMap<SmallVec<[DictId; 4]>, Arc<StarterUnion>>>,
 }
 
 /// Logical keys identifying every cached [`StarterUnion`] variant used by the
@@ -351,12 +379,57 @@ impl DictionaryMaxlength {
         }
     }
 
+    /// Returns a cached [`StarterUnion`] for an arbitrary combination of
+    /// dictionaries, keyed by their identity rather than a fixed [`UnionKey`]
+    /// variant.
+    ///
+    /// This is the escape hatch for conversion routes that don't correspond
+    /// to one of the hardcoded [`union_for`](Self::union_for) slots — e.g. a
+    /// caller-registered custom vocabulary layered on top of `s2t` — without
+    /// requiring a new `UnionKey` variant and match arm for every such
+    /// combination. The first call for a given sequence of dictionaries
+    /// builds the [`StarterUnion`] and caches it; later calls with the same
+    /// dictionaries (in the same order) return a cloned `Arc`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dicts` – The dictionaries to union, in the order `StarterUnion::build`
+    ///   should see them. Order is part of the cache key, so the same set
+    ///   passed in a different order is treated as a different combination.
+    ///
+    /// # Returns
+    ///
+    /// A shared, cached [`StarterUnion`] for the requested dictionary combination.
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn union_for_dicts(&self, dicts: &[&DictMaxLen]) -> Arc<StarterUnion> {
+        let key: SmallVec<[DictId; 4]> = dicts.iter().map(|d| dict_id(d)).collect();
+
+        if let Some(union) = self.unions.dynamic.read().unwrap().get(&key) {
+            return union.clone();
+        }
+
+        let union = Arc::new(StarterUnion::build(dicts));
+        self.unions
+            .dynamic
+            .write()
+            .unwrap()
+            .entry(key)
+            // Another thread may have won the race and inserted first; keep
+            // its `Arc` rather than the one we just built, same as the
+            // `OnceLock` slots' `get_or_init`.
+            .or_insert_with(|| union.clone())
+            .clone()
+    }
+
     /// Clears all cached [`StarterUnion`] instances.
     ///
     /// This resets the internal [`Unions`] cache back to its default (empty)
-    /// state. All previously built starter tables are dropped, and future calls
-    /// to [`union_for`](Self::union_for) will lazily rebuild the required
-    /// `StarterUnion` instances on demand.
+    /// state — both the fixed `OnceLock` slots and the dynamic map backing
+    /// [`union_for_dicts`](Self::union_for_dicts). All previously built
+    /// starter tables are dropped, and future calls to
+    /// [`union_for`](Self::union_for)/[`union_for_dicts`](Self::union_for_dicts)
+    /// will lazily rebuild the required `StarterUnion` instances on demand.
     ///
     /// This is primarily intended for testing or for rare cases where the
     /// dictionary contents have been reloaded and the cached starter metadata
@@ -417,3 +490,28 @@ fn union_keys_distinct() {
     let b = d.union_for(UnionKey::S2T { punct: true });
     assert!(!std::ptr::eq(Arc::as_ptr(&a), Arc::as_ptr(&b)));
 }
+
+#[test]
+fn union_for_dicts_cached() {
+    let d = DictionaryMaxlength::default();
+    let a = d.union_for_dicts(&[&d.st_phrases, &d.st_characters]);
+    let b = d.union_for_dicts(&[&d.st_phrases, &d.st_characters]);
+    assert!(std::ptr::eq(Arc::as_ptr(&a), Arc::as_ptr(&b)));
+}
+
+#[test]
+fn union_for_dicts_distinct_by_set() {
+    let d = DictionaryMaxlength::default();
+    let a = d.union_for_dicts(&[&d.st_phrases, &d.st_characters]);
+    let b = d.union_for_dicts(&[&d.ts_phrases, &d.ts_characters]);
+    assert!(!std::ptr::eq(Arc::as_ptr(&a), Arc::as_ptr(&b)));
+}
+
+#[test]
+fn union_for_dicts_cleared_by_clear_unions() {
+    let mut d = DictionaryMaxlength::default();
+    let a = d.union_for_dicts(&[&d.st_phrases, &d.st_characters]);
+    d.clear_unions();
+    let b = d.union_for_dicts(&[&d.st_phrases, &d.st_characters]);
+    assert!(!std::ptr::eq(Arc::as_ptr(&a), Arc::as_ptr(&b)));
+}
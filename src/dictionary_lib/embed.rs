@@ -0,0 +1,117 @@
+//! Feature-gated selective embedding of conversion families.
+//!
+//! Today [`DictionaryMaxlength::from_zstd`](crate::dictionary_lib::DictionaryMaxlength::from_zstd)
+//! embeds one monolithic blob covering all eighteen tables. This module instead
+//! stitches together only the families this build's `embed-st`/`embed-tw`/
+//! `embed-hk`/`embed-jp`/`embed-all` cargo features select, leaving any family
+//! that wasn't embedded as empty (`DictMaxLen::default()`) tables — so, e.g.,
+//! an S2T-only binary built with just `embed-st` skips the `tw_*`/`hk_*`/`jp_*`
+//! tables entirely instead of paying for all eighteen.
+//!
+//! See the crate-root `build.rs` for how the per-family `embed_<family>.zstd`
+//! sub-blobs referenced here are produced from the `dicts/` TSV sources.
+
+use crate::dictionary_lib::dict_max_len::DictMaxLen;
+use crate::dictionary_lib::{DictionaryError, DictionaryMaxlength};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+#[cfg(any(feature = "embed-st", feature = "embed-all"))]
+const ST_BLOB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/embed_st.zstd"));
+#[cfg(any(feature = "embed-tw", feature = "embed-all"))]
+const TW_BLOB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/embed_tw.zstd"));
+#[cfg(any(feature = "embed-hk", feature = "embed-all"))]
+const HK_BLOB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/embed_hk.zstd"));
+#[cfg(any(feature = "embed-jp", feature = "embed-all"))]
+const JP_BLOB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/embed_jp.zstd"));
+
+type RawTables = BTreeMap<String, Vec<(String, String)>>;
+
+/// Decompresses and CBOR-decodes one family's sub-blob into its raw `(table name -> pairs)` map.
+fn decode_family(blob: &[u8]) -> Result<RawTables, DictionaryError> {
+    let decompressed =
+        zstd::decode_all(Cursor::new(blob)).map_err(DictionaryError::IoError)?;
+    serde_cbor::from_slice(&decompressed).map_err(DictionaryError::CborParseError)
+}
+
+/// Removes `name` from `tables` and builds its `DictMaxLen`, or an empty one if absent.
+fn take_table(tables: &mut RawTables, name: &str) -> DictMaxLen {
+    tables
+        .remove(name)
+        .map(DictMaxLen::build_from_pairs)
+        .unwrap_or_default()
+}
+
+#[cfg(any(feature = "embed-st", feature = "embed-all"))]
+fn st_tables() -> Result<RawTables, DictionaryError> {
+    decode_family(ST_BLOB)
+}
+#[cfg(not(any(feature = "embed-st", feature = "embed-all")))]
+fn st_tables() -> Result<RawTables, DictionaryError> {
+    Ok(RawTables::new())
+}
+
+#[cfg(any(feature = "embed-tw", feature = "embed-all"))]
+fn tw_tables() -> Result<RawTables, DictionaryError> {
+    decode_family(TW_BLOB)
+}
+#[cfg(not(any(feature = "embed-tw", feature = "embed-all")))]
+fn tw_tables() -> Result<RawTables, DictionaryError> {
+    Ok(RawTables::new())
+}
+
+#[cfg(any(feature = "embed-hk", feature = "embed-all"))]
+fn hk_tables() -> Result<RawTables, DictionaryError> {
+    decode_family(HK_BLOB)
+}
+#[cfg(not(any(feature = "embed-hk", feature = "embed-all")))]
+fn hk_tables() -> Result<RawTables, DictionaryError> {
+    Ok(RawTables::new())
+}
+
+#[cfg(any(feature = "embed-jp", feature = "embed-all"))]
+fn jp_tables() -> Result<RawTables, DictionaryError> {
+    decode_family(JP_BLOB)
+}
+#[cfg(not(any(feature = "embed-jp", feature = "embed-all")))]
+fn jp_tables() -> Result<RawTables, DictionaryError> {
+    Ok(RawTables::new())
+}
+
+/// Builds a [`DictionaryMaxlength`] from whichever `embed-*` features this build enabled.
+///
+/// Families left out entirely fall back to empty tables, so conversions that
+/// rely on them simply find no matches rather than failing to build. Calls
+/// [`finish`](DictionaryMaxlength::finish) before returning, so the result is
+/// immediately usable by `OpenCC`.
+pub fn from_embedded_features() -> Result<DictionaryMaxlength, DictionaryError> {
+    let mut st = st_tables()?;
+    let mut tw = tw_tables()?;
+    let mut hk = hk_tables()?;
+    let mut jp = jp_tables()?;
+
+    // `unions` is private to the `dictionary_maxlength` module, so build on top of
+    // `default()` (all-empty tables) and overwrite the public fields rather than
+    // constructing the struct literal directly.
+    let mut dictionary = DictionaryMaxlength::default();
+    dictionary.st_characters = take_table(&mut st, "st_characters");
+    dictionary.st_phrases = take_table(&mut st, "st_phrases");
+    dictionary.ts_characters = take_table(&mut st, "ts_characters");
+    dictionary.ts_phrases = take_table(&mut st, "ts_phrases");
+    dictionary.st_punctuations = take_table(&mut st, "st_punctuations");
+    dictionary.ts_punctuations = take_table(&mut st, "ts_punctuations");
+    dictionary.tw_phrases = take_table(&mut tw, "tw_phrases");
+    dictionary.tw_phrases_rev = take_table(&mut tw, "tw_phrases_rev");
+    dictionary.tw_variants = take_table(&mut tw, "tw_variants");
+    dictionary.tw_variants_rev = take_table(&mut tw, "tw_variants_rev");
+    dictionary.tw_variants_rev_phrases = take_table(&mut tw, "tw_variants_rev_phrases");
+    dictionary.hk_variants = take_table(&mut hk, "hk_variants");
+    dictionary.hk_variants_rev = take_table(&mut hk, "hk_variants_rev");
+    dictionary.hk_variants_rev_phrases = take_table(&mut hk, "hk_variants_rev_phrases");
+    dictionary.jps_characters = take_table(&mut jp, "jps_characters");
+    dictionary.jps_phrases = take_table(&mut jp, "jps_phrases");
+    dictionary.jp_variants = take_table(&mut jp, "jp_variants");
+    dictionary.jp_variants_rev = take_table(&mut jp, "jp_variants_rev");
+
+    Ok(dictionary.finish())
+}
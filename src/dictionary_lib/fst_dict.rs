@@ -0,0 +1,184 @@
+//! Deterministic acyclic transducer (DAT) backend for phrase lookup.
+//!
+//! [`FstDict`] stores the same `(key, value)` pairs as [`DictMaxLen::map`], but
+//! keyed through a shared-prefix transition table instead of a per-entry
+//! `Box<[char]>` hash key. Common phrase prefixes (very common in CJK lexicons,
+//! e.g. many entries starting with `"中國"`) share the same path through the
+//! table, trading a bit of traversal cost for a much smaller memory footprint
+//! than an `FxHashMap<Box<[char]>, Box<str>>` of the same entries.
+//!
+//! [`DictMaxLen::map`]: crate::dictionary_lib::DictMaxLen
+//!
+//! # Structure
+//!
+//! The table is a flat `Vec<FstState>`. State `0` is the root. Each state
+//! holds its outgoing transitions sorted by `char` (enabling binary search)
+//! plus an optional index into [`FstDict::values`] when the state is
+//! accepting (i.e., some key ends there).
+//!
+//! # Longest match
+//!
+//! [`FstDict::lookup_longest`] walks the table once, left to right, and
+//! remembers the deepest accepting state seen so far. This replaces the old
+//! descending-length probe (`for length in (1..=max_len).rev() { map.get(...) }`)
+//! with a single forward pass.
+use serde::{Deserialize, Serialize};
+
+/// One node of the transition table. See the [module docs](self) for the overall shape.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+struct FstState {
+    /// Outgoing transitions, sorted by `char` for binary search.
+    transitions: Vec<(char, u32)>,
+    /// Index into [`FstDict::values`] if a key ends at this state.
+    value_idx: Option<u32>,
+}
+
+/// A compact, shared-prefix lookup table over `(Box<[char]>, Box<str>)` entries.
+///
+/// Built once via [`FstDict::build`] from an existing dictionary map, then
+/// queried with [`FstDict::lookup_longest`] in place of descending-length
+/// `map.get()` probes.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct FstDict {
+    /// Transition table; `states[0]` is the root.
+    states: Vec<FstState>,
+    /// Flat arena of replacement strings, referenced by [`FstState::value_idx`].
+    values: Vec<Box<str>>,
+}
+
+impl FstDict {
+    /// Builds a [`FstDict`] from `(key, value)` pairs.
+    ///
+    /// Pairs are sorted by key first so that shared prefixes are inserted
+    /// contiguously; this keeps the resulting table small regardless of the
+    /// iteration order of the source map.
+    ///
+    /// ### Duplicates
+    /// If the same key appears twice, the **first** occurrence (in sorted
+    /// order, which for identical keys is simply insertion order) wins; later
+    /// duplicates are skipped.
+    ///
+    /// ### Empty input
+    /// An empty iterator produces a table with only the root state and no
+    /// values; [`lookup_longest`](Self::lookup_longest) then always returns
+    /// `None`.
+    pub fn build<'a, I>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a [char], &'a str)>,
+    {
+        let mut sorted: Vec<(&'a [char], &'a str)> = pairs.into_iter().collect();
+        sorted.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut states = vec![FstState::default()];
+        let mut values = Vec::new();
+
+        let mut prev_key: Option<&[char]> = None;
+        for (key, value) in sorted {
+            if prev_key == Some(key) {
+                continue; // duplicate key: first-wins
+            }
+            prev_key = Some(key);
+
+            let mut state_idx = 0usize;
+            for &ch in key {
+                let transitions = &mut states[state_idx].transitions;
+                state_idx = match transitions.binary_search_by_key(&ch, |&(c, _)| c) {
+                    Ok(pos) => transitions[pos].1 as usize,
+                    Err(pos) => {
+                        let new_idx = states.len() as u32;
+                        states[state_idx]
+                            .transitions
+                            .insert(pos, (ch, new_idx));
+                        states.push(FstState::default());
+                        new_idx as usize
+                    }
+                };
+            }
+
+            let value_idx = values.len() as u32;
+            values.push(Box::from(value));
+            states[state_idx].value_idx = Some(value_idx);
+        }
+
+        Self { states, values }
+    }
+
+    /// Walks `chars` left to right, returning the `(length, value)` of the
+    /// **longest** key that is a prefix of `chars`, or `None` if no key
+    /// matches at all (including when the table is empty).
+    ///
+    /// This is a single traversal: at each step the deepest accepting state
+    /// seen so far is remembered, and the walk stops as soon as the input is
+    /// exhausted or no outgoing transition matches the next character.
+    pub fn lookup_longest(&self, chars: &[char]) -> Option<(usize, &str)> {
+        let mut state_idx = 0usize;
+        let mut best: Option<(usize, u32)> = None;
+
+        for (i, &ch) in chars.iter().enumerate() {
+            let state = &self.states[state_idx];
+            match state.transitions.binary_search_by_key(&ch, |&(c, _)| c) {
+                Ok(pos) => state_idx = state.transitions[pos].1 as usize,
+                Err(_) => break,
+            }
+            if let Some(value_idx) = self.states[state_idx].value_idx {
+                best = Some((i + 1, value_idx));
+            }
+        }
+
+        best.map(|(len, value_idx)| (len, &*self.values[value_idx as usize]))
+    }
+
+    /// Returns `true` if this table has no keys at all.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[test]
+fn lookup_longest_prefers_the_longest_matching_key() {
+    let ni_hao: Vec<char> = "你好".chars().collect();
+    let ni: Vec<char> = "你".chars().collect();
+    let shi_jie: Vec<char> = "世界".chars().collect();
+    let dict = FstDict::build([
+        (ni_hao.as_slice(), "您好"),
+        (ni.as_slice(), "妳"),
+        (shi_jie.as_slice(), "世間"),
+    ]);
+
+    let query: Vec<char> = "你好吗".chars().collect();
+    let (len, value) = dict.lookup_longest(&query).unwrap();
+    assert_eq!(len, 2);
+    assert_eq!(value, "您好");
+
+    let (len, value) = dict.lookup_longest(&ni).unwrap();
+    assert_eq!(len, 1);
+    assert_eq!(value, "妳");
+}
+
+#[test]
+fn lookup_longest_returns_none_for_no_match() {
+    let ni_hao: Vec<char> = "你好".chars().collect();
+    let dict = FstDict::build([(ni_hao.as_slice(), "您好")]);
+    let query: Vec<char> = "世界".chars().collect();
+    assert!(dict.lookup_longest(&query).is_none());
+}
+
+#[test]
+fn build_keeps_first_occurrence_of_duplicate_keys() {
+    let ni_hao: Vec<char> = "你好".chars().collect();
+    let dict = FstDict::build([
+        (ni_hao.as_slice(), "先"),
+        (ni_hao.as_slice(), "後"),
+    ]);
+
+    let (_, value) = dict.lookup_longest(&ni_hao).unwrap();
+    assert_eq!(value, "先");
+}
+
+#[test]
+fn empty_fst_is_empty_and_never_matches() {
+    let dict = FstDict::build(std::iter::empty());
+    assert!(dict.is_empty());
+    let query: Vec<char> = "你好".chars().collect();
+    assert!(dict.lookup_longest(&query).is_none());
+}
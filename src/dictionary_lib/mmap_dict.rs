@@ -0,0 +1,491 @@
+//! A memory-mapped, zero-copy [`DictMaxLen`] backend.
+//!
+//! [`DictMaxLen::open_mmap`] reads a file previously written by
+//! [`DictMaxLen::write_mmap`] and returns an [`MmapDict`] that queries the
+//! mapped pages directly — no `FxHashMap<Box<[char]>, Box<str>>` is ever
+//! built, and the 576 KiB dense BMP accelerators
+//! ([`first_len_mask64`](DictMaxLen::first_len_mask64)/
+//! [`first_char_max_len`](DictMaxLen::first_char_max_len)), when the source
+//! table's arrays span the full BMP and so are stored, are read straight
+//! out of the file rather than rebuilt via
+//! [`populate_starter_indexes`](DictMaxLen::populate_starter_indexes). This
+//! trades [`DictMaxLen`]'s single-allocation hashmap lookup for an
+//! open-addressed index over mapped bytes, in exchange for near-instant
+//! `open_mmap` and a file that many dictionaries/processes can share through
+//! the OS page cache.
+//!
+//! # On-disk layout
+//!
+//! ```text
+//! magic (4 bytes: b"OCMD")
+//! version (u16 LE)
+//! min_len, max_len (LEB128 varints)
+//! key_length_mask (u64 LE)
+//! starter_len_mask: count (varint), then count × (starter codepoint varint, mask u64 LE)
+//! dense flag (u8); if nonzero:
+//!   first_len_mask64: 0x10000 × u64 LE
+//!   first_char_max_len: 0x10000 × u8
+//! index_capacity (u64 LE, power of two)
+//! keys_blob_len (u64 LE, in chars)
+//! values_blob_len (u64 LE, in bytes)
+//! index: index_capacity × 24-byte slots (see [`SLOT_SIZE`])
+//! keys blob: keys_blob_len × u32 LE scalar values, back-to-back per key
+//! values blob: values_blob_len UTF-8 bytes, back-to-back per key
+//! ```
+//!
+//! The header carries `starter_len_mask` and the dense tables directly
+//! (rather than leaving a reader to derive them by scanning every key), so
+//! [`open_mmap`](DictMaxLen::open_mmap) only has to parse the fixed-size
+//! header before the file is ready to query.
+//!
+//! # Index
+//!
+//! Keys are hashed with [`hash_key`] and inserted into an open-addressed
+//! table sized for a load factor of ~0.7, rounded up to a power of two so
+//! probing can mask instead of mod. Collisions resolve via linear probing;
+//! a slot with `key_len == 0` marks "empty" ([`DictMaxLen::build_from_pairs`]
+//! debug-asserts that every key is non-empty; an empty key written here
+//! would read back as an empty slot), so lookup can stop at the first empty
+//! slot encountered along the probe sequence.
+
+use std::fs;
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+
+use super::dict_max_len::DictMaxLen;
+use super::dictionary_maxlength::{DictionaryError, DictionaryMaxlength};
+
+const MMAP_MAGIC: [u8; 4] = *b"OCMD";
+const MMAP_VERSION: u16 = 1;
+
+/// Byte size of one index slot: `hash(u64) + key_offset(u32) + key_len(u32) +
+/// value_offset(u32) + value_len(u32)`.
+const SLOT_SIZE: usize = 8 + 4 + 4 + 4 + 4;
+
+const BMP: usize = 0x10000;
+
+#[inline]
+fn invalid(message: impl Into<String>) -> DictionaryError {
+    DictionaryError::InvalidMmapDict(message.into())
+}
+
+/// Deterministic FNV-1a hash over a key's `char`s, used both when building
+/// the index and when probing it at lookup time — it only needs to agree
+/// with itself across a build/open pair, not with any other hasher in this
+/// crate.
+fn hash_key(key: &[char]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &c in key {
+        h ^= c as u64;
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Result<u64, DictionaryError> {
+    let mut result: u64 = 0;
+    let mut shift: u32 = 0;
+    loop {
+        let byte = *buf
+            .get(*pos)
+            .ok_or_else(|| invalid("truncated varint in mmap dictionary header"))?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return Err(invalid("varint too long in mmap dictionary header"));
+        }
+    }
+}
+
+fn read_u64(buf: &[u8], pos: &mut usize) -> Result<u64, DictionaryError> {
+    let end = *pos + 8;
+    let bytes: [u8; 8] = buf
+        .get(*pos..end)
+        .ok_or_else(|| invalid("truncated fixed-width field in mmap dictionary header"))?
+        .try_into()
+        .unwrap();
+    *pos = end;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Builds the on-disk bytes [`DictMaxLen::write_mmap`] writes — see the
+/// [module docs](self) for the layout.
+fn encode(dict: &DictMaxLen) -> Vec<u8> {
+    let mut entries: Vec<(&[char], &str)> = dict
+        .map
+        .iter()
+        .map(|(k, v)| (k.as_ref(), v.as_ref()))
+        .collect();
+    // Stable ordering, purely so two builds from the same `map` produce byte-identical files.
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&MMAP_MAGIC);
+    out.extend_from_slice(&MMAP_VERSION.to_le_bytes());
+
+    write_varint(&mut out, dict.min_len as u64);
+    write_varint(&mut out, dict.max_len as u64);
+    out.extend_from_slice(&dict.key_length_mask.to_le_bytes());
+
+    write_varint(&mut out, dict.starter_len_mask.len() as u64);
+    for (&starter, &mask) in &dict.starter_len_mask {
+        write_varint(&mut out, starter as u64);
+        out.extend_from_slice(&mask.to_le_bytes());
+    }
+
+    // This format always stores the full BMP span (not the watermark-bounded
+    // range `populate_starter_indexes` may use), so check the exact length
+    // rather than `is_populated()` — a watermarked dict would otherwise be
+    // flagged dense and have its (shorter) arrays written and misread by
+    // `open_mmap` as if they spanned the whole BMP.
+    let dense = dict.first_len_mask64.len() == 0x10000 && dict.first_char_max_len.len() == 0x10000;
+    out.push(dense as u8);
+    if dense {
+        for &mask in &dict.first_len_mask64 {
+            out.extend_from_slice(&mask.to_le_bytes());
+        }
+        out.extend_from_slice(&dict.first_char_max_len);
+    }
+
+    // Open-addressed index sized for a ~0.7 load factor, rounded up to a power of two.
+    let capacity = ((entries.len() as f64 / 0.7).ceil() as u64)
+        .max(1)
+        .next_power_of_two();
+    let mask = capacity - 1;
+
+    struct Slot {
+        hash: u64,
+        key_offset: u32,
+        key_len: u32,
+        value_offset: u32,
+        value_len: u32,
+    }
+    let mut slots: Vec<Option<Slot>> = (0..capacity).map(|_| None).collect();
+
+    let mut keys_blob: Vec<u8> = Vec::new();
+    let mut values_blob: Vec<u8> = Vec::new();
+    let mut keys_len_chars: u64 = 0;
+
+    for (key, value) in &entries {
+        let key_offset = keys_len_chars as u32;
+        for &c in *key {
+            keys_blob.extend_from_slice(&(c as u32).to_le_bytes());
+        }
+        keys_len_chars += key.len() as u64;
+
+        let value_offset = values_blob.len() as u32;
+        let value_bytes = value.as_bytes();
+        values_blob.extend_from_slice(value_bytes);
+
+        let h = hash_key(key);
+        let mut idx = (h as usize) & (mask as usize);
+        while slots[idx].is_some() {
+            idx = (idx + 1) & (mask as usize);
+        }
+        slots[idx] = Some(Slot {
+            hash: h,
+            key_offset,
+            key_len: key.len() as u32,
+            value_offset,
+            value_len: value_bytes.len() as u32,
+        });
+    }
+
+    out.extend_from_slice(&capacity.to_le_bytes());
+    out.extend_from_slice(&keys_len_chars.to_le_bytes());
+    out.extend_from_slice(&(values_blob.len() as u64).to_le_bytes());
+
+    for slot in &slots {
+        match slot {
+            Some(s) => {
+                out.extend_from_slice(&s.hash.to_le_bytes());
+                out.extend_from_slice(&s.key_offset.to_le_bytes());
+                out.extend_from_slice(&s.key_len.to_le_bytes());
+                out.extend_from_slice(&s.value_offset.to_le_bytes());
+                out.extend_from_slice(&s.value_len.to_le_bytes());
+            }
+            None => out.extend_from_slice(&[0u8; SLOT_SIZE]),
+        }
+    }
+
+    out.extend_from_slice(&keys_blob);
+    out.extend_from_slice(&values_blob);
+
+    out
+}
+
+/// A memory-mapped, zero-copy view over a [`DictMaxLen`] written by
+/// [`DictMaxLen::write_mmap`] — see the [module docs](self) for the format
+/// and [`DictMaxLen::open_mmap`] for how to obtain one.
+pub struct MmapDict {
+    mmap: memmap2::Mmap,
+    index_offset: usize,
+    keys_offset: usize,
+    values_offset: usize,
+    capacity: u64,
+    min_len: usize,
+    max_len: usize,
+    key_length_mask: u64,
+    starter_len_mask: FxHashMap<char, u64>,
+    first_len_mask64: Vec<u64>,
+    first_char_max_len: Vec<u8>,
+}
+
+impl MmapDict {
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    pub fn key_length_mask(&self) -> u64 {
+        self.key_length_mask
+    }
+
+    /// Same gate [`DictMaxLen::starter_allows_dict`] provides, backed by the
+    /// dense tables read from the mapped file instead of an in-heap `Vec`.
+    pub fn starter_allows_dict(&self, starter: char, length: usize, bit: usize) -> bool {
+        let u = starter as u32;
+        if self.first_len_mask64.len() == BMP && (u as usize) < BMP {
+            let i = u as usize;
+            if length <= 64 {
+                return (self.first_len_mask64[i] >> bit) & 1 == 1;
+            }
+            return (length as u64) <= self.first_char_max_len[i] as u64;
+        }
+        if length > 64 {
+            return false;
+        }
+        self.starter_len_mask
+            .get(&starter)
+            .is_some_and(|mask| (mask >> bit) & 1 == 1)
+    }
+
+    fn slot_at(&self, idx: u64) -> (u64, u32, u32, u32, u32) {
+        let base = self.index_offset + idx as usize * SLOT_SIZE;
+        let bytes = &self.mmap[base..base + SLOT_SIZE];
+        let hash = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let key_offset = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let key_len = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+        let value_offset = u32::from_le_bytes(bytes[16..20].try_into().unwrap());
+        let value_len = u32::from_le_bytes(bytes[20..24].try_into().unwrap());
+        (hash, key_offset, key_len, value_offset, value_len)
+    }
+
+    fn key_matches(&self, key_offset: u32, key_len: u32, key: &[char]) -> bool {
+        if key_len as usize != key.len() {
+            return false;
+        }
+        let base = self.keys_offset + key_offset as usize * 4;
+        for (i, &expected) in key.iter().enumerate() {
+            let off = base + i * 4;
+            let scalar = u32::from_le_bytes(self.mmap[off..off + 4].try_into().unwrap());
+            if char::from_u32(scalar) != Some(expected) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Looks up `key` directly against the mapped pages — no key or value is
+    /// copied until the caller owns the returned `&str`.
+    pub fn get(&self, key: &[char]) -> Option<&str> {
+        if key.is_empty() || self.capacity == 0 {
+            return None;
+        }
+        let h = hash_key(key);
+        let mask = self.capacity - 1;
+        let mut idx = h & mask;
+        for _ in 0..self.capacity {
+            let (slot_hash, key_offset, key_len, value_offset, value_len) = self.slot_at(idx);
+            if key_len == 0 {
+                return None;
+            }
+            if slot_hash == h && self.key_matches(key_offset, key_len, key) {
+                let start = self.values_offset + value_offset as usize;
+                let bytes = &self.mmap[start..start + value_len as usize];
+                return std::str::from_utf8(bytes).ok();
+            }
+            idx = (idx + 1) & mask;
+        }
+        None
+    }
+}
+
+impl DictMaxLen {
+    /// Writes this table to `path` in the memory-mappable format
+    /// [`open_mmap`](Self::open_mmap) reads back — see the [module
+    /// docs](super::mmap_dict) for the on-disk layout.
+    pub fn write_mmap<P: AsRef<Path>>(&self, path: P) -> Result<(), DictionaryError> {
+        let bytes = encode(self);
+        fs::write(&path, bytes).map_err(|err| {
+            let msg = format!("Failed to write mmap dictionary file: {}", err);
+            DictionaryMaxlength::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })
+    }
+
+    /// Opens a file previously written by [`write_mmap`](Self::write_mmap)
+    /// and returns a zero-copy [`MmapDict`] view over it: the header
+    /// (`min_len`/`max_len`/`key_length_mask`/`starter_len_mask`/dense
+    /// tables) is parsed once, and every key/value lookup afterward reads
+    /// straight out of the mapped pages without building an
+    /// `FxHashMap<Box<[char]>, Box<str>>`.
+    ///
+    /// # Safety
+    /// Backed by [`memmap2::Mmap::map`], which is safe as long as the file
+    /// isn't truncated or rewritten by another process while mapped —
+    /// see that function's own `# Safety` section.
+    pub fn open_mmap<P: AsRef<Path>>(path: P) -> Result<MmapDict, DictionaryError> {
+        let file = fs::File::open(&path).map_err(DictionaryError::IoError)?;
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(|err| {
+            let msg = format!("Failed to mmap dictionary file: {}", err);
+            DictionaryMaxlength::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        let buf: &[u8] = &mmap;
+        let rest = buf
+            .strip_prefix(&MMAP_MAGIC)
+            .ok_or_else(|| invalid("missing or unrecognized mmap dictionary magic header"))?;
+        if rest.len() < 2 {
+            return Err(invalid("truncated mmap dictionary header"));
+        }
+        let version = u16::from_le_bytes([rest[0], rest[1]]);
+        if version != MMAP_VERSION {
+            return Err(invalid(format!(
+                "mmap dictionary format v{} required, found v{}",
+                MMAP_VERSION, version
+            )));
+        }
+
+        let mut pos = MMAP_MAGIC.len() + 2;
+        let min_len = read_varint(buf, &mut pos)? as usize;
+        let max_len = read_varint(buf, &mut pos)? as usize;
+        let key_length_mask = read_u64(buf, &mut pos)?;
+
+        let starter_count = read_varint(buf, &mut pos)? as usize;
+        let mut starter_len_mask = FxHashMap::default();
+        starter_len_mask.reserve(starter_count);
+        for _ in 0..starter_count {
+            let codepoint = read_varint(buf, &mut pos)? as u32;
+            let starter = char::from_u32(codepoint)
+                .ok_or_else(|| invalid("invalid starter codepoint in mmap dictionary header"))?;
+            let mask = read_u64(buf, &mut pos)?;
+            starter_len_mask.insert(starter, mask);
+        }
+
+        let dense = *buf
+            .get(pos)
+            .ok_or_else(|| invalid("truncated mmap dictionary (missing dense-table flag)"))?
+            != 0;
+        pos += 1;
+
+        let (first_len_mask64, first_char_max_len) = if dense {
+            let mut masks = Vec::with_capacity(BMP);
+            for _ in 0..BMP {
+                masks.push(read_u64(buf, &mut pos)?);
+            }
+            let caps_end = pos + BMP;
+            let caps = buf
+                .get(pos..caps_end)
+                .ok_or_else(|| invalid("truncated mmap dictionary dense cap table"))?
+                .to_vec();
+            pos = caps_end;
+            (masks, caps)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        let capacity = read_u64(buf, &mut pos)?;
+        let keys_blob_len = read_u64(buf, &mut pos)?;
+        let values_blob_len = read_u64(buf, &mut pos)?;
+
+        let index_offset = pos;
+        let index_bytes = capacity as usize * SLOT_SIZE;
+        let keys_offset = index_offset + index_bytes;
+        let keys_bytes = keys_blob_len as usize * 4;
+        let values_offset = keys_offset + keys_bytes;
+        let values_end = values_offset + values_blob_len as usize;
+
+        if buf.len() < values_end {
+            return Err(invalid("truncated mmap dictionary (index/keys/values region)"));
+        }
+
+        Ok(MmapDict {
+            mmap,
+            index_offset,
+            keys_offset,
+            values_offset,
+            capacity,
+            min_len,
+            max_len,
+            key_length_mask,
+            starter_len_mask,
+            first_len_mask64,
+            first_char_max_len,
+        })
+    }
+}
+
+#[test]
+fn write_then_open_mmap_round_trips_lookups() {
+    let mut dict = DictMaxLen::build_from_pairs([
+        ("你好".to_string(), "您好".to_string()),
+        ("你".to_string(), "妳".to_string()),
+        ("世界".to_string(), "世間".to_string()),
+    ]);
+    dict.populate_starter_indexes();
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dict.write_mmap(file.path()).unwrap();
+    let opened = DictMaxLen::open_mmap(file.path()).unwrap();
+
+    assert_eq!(opened.min_len(), dict.min_len);
+    assert_eq!(opened.max_len(), dict.max_len);
+    let ni: Vec<char> = "你好".chars().collect();
+    assert_eq!(opened.get(&ni), Some("您好"));
+    let n: Vec<char> = "你".chars().collect();
+    assert_eq!(opened.get(&n), Some("妳"));
+    let w: Vec<char> = "世界".chars().collect();
+    assert_eq!(opened.get(&w), Some("世間"));
+}
+
+#[test]
+fn open_mmap_lookup_miss_returns_none() {
+    let mut dict = DictMaxLen::build_from_pairs([("你好".to_string(), "您好".to_string())]);
+    dict.populate_starter_indexes();
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dict.write_mmap(file.path()).unwrap();
+    let opened = DictMaxLen::open_mmap(file.path()).unwrap();
+
+    let missing: Vec<char> = "再見".chars().collect();
+    assert_eq!(opened.get(&missing), None);
+}
+
+#[test]
+fn open_mmap_rejects_bad_magic() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(file.path(), b"not an mmap dictionary file").unwrap();
+    assert!(DictMaxLen::open_mmap(file.path()).is_err());
+}
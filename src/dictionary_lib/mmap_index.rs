@@ -0,0 +1,636 @@
+//! A second memory-mapped, zero-copy [`DictMaxLen`] backend, modeled on
+//! Mercurial's revlog index: a small fixed-width header carrying flag bits
+//! and section offsets, rather than [`mmap_dict`](super::mmap_dict)'s
+//! single growing stream of varints and a trailing dense-flag byte.
+//!
+//! The two formats solve the same problem differently:
+//!
+//! - [`mmap_dict`](super::mmap_dict) copies the dense BMP tables into owned
+//!   `Vec`s while parsing the header, and stores scalars as LEB128 varints.
+//! - This format never copies the dense tables at all —
+//!   [`MmapIndex::first_len_mask64`]/[`MmapIndex::first_char_max_len`] are
+//!   slices borrowed directly from the mapped bytes — and stores scalars and
+//!   section offsets at fixed byte positions so the header never needs a
+//!   cursor to parse.
+//!
+//! # On-disk layout
+//!
+//! ```text
+//! magic (4 bytes: b"OCIX")
+//! version (u16 LE)
+//! flags (u8): bit 0 = DENSE_PRESENT, bit 1 = HAS_ASTRAL, bit 2 = WATERMARKED
+//! endianness (u8): 0 = little-endian host, 1 = big-endian host
+//! min_len, max_len, key_length_mask, starter_base (u64 LE each)
+//! starter_len_mask_count, dense_len (u64 LE each)
+//! sparse_offset, dense_offset, index_offset, keys_offset, values_offset (u64 LE each)
+//! index_capacity, keys_blob_len, values_blob_len (u64 LE each)
+//! -- padding to `sparse_offset` --
+//! sparse section: starter_len_mask_count × (codepoint u32 LE, mask u64 LE)
+//! -- padding to `dense_offset` (always 8-byte aligned) --
+//! dense section (only if DENSE_PRESENT): dense_len × u64 in host-native byte
+//!   order (see "Endianness" below), then dense_len × u8
+//! -- padding to `index_offset` --
+//! index: index_capacity × 24-byte slots (see [`SLOT_SIZE`])
+//! keys blob: keys_blob_len × u32 LE scalar values, back-to-back per key
+//! values blob: values_blob_len UTF-8 bytes, back-to-back per key
+//! ```
+//!
+//! `HAS_ASTRAL` records whether any key's first `char` is outside the BMP
+//! (such keys never hit the dense path regardless of `DENSE_PRESENT`, same
+//! as [`DictMaxLen::starter_allows_dict`]). `WATERMARKED` records whether
+//! `starter_base` is nonzero, purely as a human-readable hint when
+//! inspecting a file — readers must honor `starter_base` either way.
+//!
+//! # Endianness
+//!
+//! Every scalar in the header, the sparse section, and the index is written
+//! as an explicit LE integer and converted back with `from_le_bytes`, so it
+//! reads correctly on any host. The dense section is the exception: to keep
+//! it truly zero-copy, its bytes are written in the *host's native* byte
+//! order and reinterpreted in place as `&[u64]`/`&[u8]` on load, with no
+//! per-element conversion. [`load_mmap`](DictMaxLen::load_mmap) checks the
+//! `endianness` byte against the current host before trusting the dense
+//! section, and refuses to load (rather than silently reinterpreting
+//! byte-swapped masks) when they don't match.
+
+use std::fs;
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+
+use super::dict_max_len::DictMaxLen;
+use super::dictionary_maxlength::{DictionaryError, DictionaryMaxlength};
+
+const INDEX_MAGIC: [u8; 4] = *b"OCIX";
+const INDEX_VERSION: u16 = 1;
+
+const DENSE_PRESENT: u8 = 1 << 0;
+const HAS_ASTRAL: u8 = 1 << 1;
+const WATERMARKED: u8 = 1 << 2;
+
+/// `endianness` byte written for the current host — `0` on little-endian,
+/// `1` on big-endian. [`load_mmap`](DictMaxLen::load_mmap) rejects a file
+/// whose byte differs from this.
+#[cfg(target_endian = "little")]
+const HOST_ENDIAN: u8 = 0;
+#[cfg(target_endian = "big")]
+const HOST_ENDIAN: u8 = 1;
+
+/// Byte size of the fixed-width header, before the sparse section begins.
+/// `4 (magic) + 2 (version) + 1 (flags) + 1 (endianness) + 14 × 8 (u64 fields)`.
+const HEADER_SIZE: usize = 4 + 2 + 1 + 1 + 14 * 8;
+
+/// Byte size of one index slot: `hash(u64) + key_offset(u32) + key_len(u32) +
+/// value_offset(u32) + value_len(u32)` — same layout as [`mmap_dict`](super::mmap_dict).
+const SLOT_SIZE: usize = 8 + 4 + 4 + 4 + 4;
+
+#[inline]
+fn invalid(message: impl Into<String>) -> DictionaryError {
+    DictionaryError::InvalidMmapIndex(message.into())
+}
+
+/// Rounds `value` up to the next multiple of 8, so the dense section can be
+/// reinterpreted as `&[u64]` without an unaligned read.
+#[inline]
+fn align8(value: usize) -> usize {
+    (value + 7) & !7
+}
+
+/// Same FNV-1a hash [`mmap_dict`](super::mmap_dict) uses — it only needs to
+/// agree with itself across a build/load pair.
+fn hash_key(key: &[char]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &c in key {
+        h ^= c as u64;
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h
+}
+
+fn read_u64(buf: &[u8], pos: usize) -> Result<u64, DictionaryError> {
+    let bytes: [u8; 8] = buf
+        .get(pos..pos + 8)
+        .ok_or_else(|| invalid("truncated fixed-width field in mmap index header"))?
+        .try_into()
+        .unwrap();
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Builds the on-disk bytes [`DictMaxLen::save_index`] writes — see the
+/// [module docs](self) for the layout.
+fn encode(dict: &DictMaxLen) -> Vec<u8> {
+    let mut entries: Vec<(&[char], &str)> = dict
+        .map
+        .iter()
+        .map(|(k, v)| (k.as_ref(), v.as_ref()))
+        .collect();
+    // Stable ordering, purely so two builds from the same `map` produce byte-identical files.
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+    let has_astral = entries
+        .iter()
+        .any(|(key, _)| key.first().is_some_and(|c| (*c as u32) > 0xFFFF));
+    let dense_present = !dict.first_len_mask64.is_empty()
+        && dict.first_len_mask64.len() == dict.first_char_max_len.len();
+    let watermarked = dense_present && dict.starter_base != 0;
+
+    let mut flags = 0u8;
+    if dense_present {
+        flags |= DENSE_PRESENT;
+    }
+    if has_astral {
+        flags |= HAS_ASTRAL;
+    }
+    if watermarked {
+        flags |= WATERMARKED;
+    }
+
+    // Sparse section.
+    let mut sparse = Vec::new();
+    for (&starter, &mask) in &dict.starter_len_mask {
+        sparse.extend_from_slice(&(starter as u32).to_le_bytes());
+        sparse.extend_from_slice(&mask.to_le_bytes());
+    }
+
+    // Dense section, written in the host's native byte order so it can be
+    // reinterpreted in place on load — see the module docs' "Endianness" section.
+    let mut dense = Vec::new();
+    if dense_present {
+        for &mask in &dict.first_len_mask64 {
+            dense.extend_from_slice(&mask.to_ne_bytes());
+        }
+        dense.extend_from_slice(&dict.first_char_max_len);
+    }
+
+    // Open-addressed index, same scheme as `mmap_dict`.
+    let capacity = ((entries.len() as f64 / 0.7).ceil() as u64)
+        .max(1)
+        .next_power_of_two();
+    let mask = capacity - 1;
+
+    struct Slot {
+        hash: u64,
+        key_offset: u32,
+        key_len: u32,
+        value_offset: u32,
+        value_len: u32,
+    }
+    let mut slots: Vec<Option<Slot>> = (0..capacity).map(|_| None).collect();
+
+    let mut keys_blob: Vec<u8> = Vec::new();
+    let mut values_blob: Vec<u8> = Vec::new();
+    let mut keys_len_chars: u64 = 0;
+
+    for (key, value) in &entries {
+        let key_offset = keys_len_chars as u32;
+        for &c in *key {
+            keys_blob.extend_from_slice(&(c as u32).to_le_bytes());
+        }
+        keys_len_chars += key.len() as u64;
+
+        let value_offset = values_blob.len() as u32;
+        let value_bytes = value.as_bytes();
+        values_blob.extend_from_slice(value_bytes);
+
+        let h = hash_key(key);
+        let mut idx = (h as usize) & (mask as usize);
+        while slots[idx].is_some() {
+            idx = (idx + 1) & (mask as usize);
+        }
+        slots[idx] = Some(Slot {
+            hash: h,
+            key_offset,
+            key_len: key.len() as u32,
+            value_offset,
+            value_len: value_bytes.len() as u32,
+        });
+    }
+
+    // Lay out section offsets up front so the header can carry them directly.
+    let sparse_offset = HEADER_SIZE;
+    let dense_offset = align8(sparse_offset + sparse.len());
+    let index_offset = dense_offset + dense.len();
+    let keys_offset = index_offset + capacity as usize * SLOT_SIZE;
+    let values_offset = keys_offset + keys_blob.len();
+
+    let mut out = Vec::with_capacity(values_offset + values_blob.len());
+    out.extend_from_slice(&INDEX_MAGIC);
+    out.extend_from_slice(&INDEX_VERSION.to_le_bytes());
+    out.push(flags);
+    out.push(HOST_ENDIAN);
+    out.extend_from_slice(&(dict.min_len as u64).to_le_bytes());
+    out.extend_from_slice(&(dict.max_len as u64).to_le_bytes());
+    out.extend_from_slice(&dict.key_length_mask.to_le_bytes());
+    out.extend_from_slice(&(dict.starter_base as u64).to_le_bytes());
+    out.extend_from_slice(&(dict.starter_len_mask.len() as u64).to_le_bytes());
+    out.extend_from_slice(&(dict.first_len_mask64.len() as u64).to_le_bytes());
+    out.extend_from_slice(&(sparse_offset as u64).to_le_bytes());
+    out.extend_from_slice(&(dense_offset as u64).to_le_bytes());
+    out.extend_from_slice(&(index_offset as u64).to_le_bytes());
+    out.extend_from_slice(&(keys_offset as u64).to_le_bytes());
+    out.extend_from_slice(&(values_offset as u64).to_le_bytes());
+    out.extend_from_slice(&capacity.to_le_bytes());
+    out.extend_from_slice(&keys_len_chars.to_le_bytes());
+    out.extend_from_slice(&(values_blob.len() as u64).to_le_bytes());
+    debug_assert_eq!(out.len(), HEADER_SIZE);
+
+    out.extend_from_slice(&sparse);
+    out.resize(dense_offset, 0);
+    out.extend_from_slice(&dense);
+    out.resize(index_offset, 0);
+
+    for slot in &slots {
+        match slot {
+            Some(s) => {
+                out.extend_from_slice(&s.hash.to_le_bytes());
+                out.extend_from_slice(&s.key_offset.to_le_bytes());
+                out.extend_from_slice(&s.key_len.to_le_bytes());
+                out.extend_from_slice(&s.value_offset.to_le_bytes());
+                out.extend_from_slice(&s.value_len.to_le_bytes());
+            }
+            None => out.extend_from_slice(&[0u8; SLOT_SIZE]),
+        }
+    }
+
+    out.extend_from_slice(&keys_blob);
+    out.extend_from_slice(&values_blob);
+
+    out
+}
+
+/// A memory-mapped, zero-copy view over a [`DictMaxLen`] written by
+/// [`DictMaxLen::save_index`] — see the [module docs](self) for the format
+/// and [`DictMaxLen::load_mmap`] for how to obtain one.
+pub struct MmapIndex {
+    mmap: memmap2::Mmap,
+    flags: u8,
+    min_len: usize,
+    max_len: usize,
+    key_length_mask: u64,
+    starter_base: u32,
+    sparse: FxHashMap<char, u64>,
+    dense_offset: usize,
+    dense_len: usize,
+    index_offset: usize,
+    keys_offset: usize,
+    values_offset: usize,
+    capacity: u64,
+}
+
+impl MmapIndex {
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    pub fn key_length_mask(&self) -> u64 {
+        self.key_length_mask
+    }
+
+    /// Mirrors [`DictMaxLen::is_populated`]: reports the `DENSE_PRESENT` flag
+    /// recorded at save time, not a re-derived length check.
+    pub fn is_populated(&self) -> bool {
+        self.flags & DENSE_PRESENT != 0
+    }
+
+    /// Per-starter length bitmasks, viewed directly over the mapped file —
+    /// no copy, no rebuild. Empty when `DENSE_PRESENT` is unset.
+    ///
+    /// # Safety
+    /// `dense_offset` is 8-byte aligned by construction (see [`align8`]), and
+    /// `memmap2::Mmap` pages are OS-page aligned, so the base pointer plus an
+    /// 8-byte-aligned offset is itself 8-byte aligned — the reinterpret cast
+    /// below never performs an unaligned read. The slice length relies on
+    /// [`load_mmap`](MmapIndex::load_mmap) having already checked, with
+    /// overflow-checked arithmetic, that `dense_offset + dense_len * 9` fits
+    /// within the mapped file — a `MmapIndex` can only exist with a `dense_len`
+    /// that's safe to hand to `from_raw_parts` here.
+    pub fn first_len_mask64(&self) -> &[u64] {
+        if !self.is_populated() {
+            return &[];
+        }
+        let ptr = unsafe { self.mmap.as_ptr().add(self.dense_offset) as *const u64 };
+        unsafe { std::slice::from_raw_parts(ptr, self.dense_len) }
+    }
+
+    /// Per-starter maximum key length cap, viewed directly over the mapped
+    /// file. Empty when `DENSE_PRESENT` is unset.
+    pub fn first_char_max_len(&self) -> &[u8] {
+        if !self.is_populated() {
+            return &[];
+        }
+        let start = self.dense_offset + self.dense_len * 8;
+        &self.mmap[start..start + self.dense_len]
+    }
+
+    /// Same gate [`DictMaxLen::starter_allows_dict`] provides, backed by the
+    /// borrowed dense tables (when present) with the same `starter_base`
+    /// bounds-checking as the in-heap implementation.
+    pub fn starter_allows_dict(&self, starter: char, length: usize, bit: usize) -> bool {
+        let u = starter as u32;
+        if self.is_populated() {
+            if let Some(i) = u
+                .checked_sub(self.starter_base)
+                .map(|off| off as usize)
+                .filter(|&i| i < self.dense_len)
+            {
+                let masks = self.first_len_mask64();
+                if bit < 64 {
+                    return (masks[i] >> bit) & 1 == 1;
+                }
+                return length <= self.first_char_max_len()[i] as usize;
+            }
+        }
+        if bit >= 64 {
+            return false;
+        }
+        self.sparse
+            .get(&starter)
+            .is_some_and(|mask| (mask >> bit) & 1 == 1)
+    }
+
+    fn slot_at(&self, idx: u64) -> (u64, u32, u32, u32, u32) {
+        let base = self.index_offset + idx as usize * SLOT_SIZE;
+        let bytes = &self.mmap[base..base + SLOT_SIZE];
+        let hash = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let key_offset = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let key_len = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+        let value_offset = u32::from_le_bytes(bytes[16..20].try_into().unwrap());
+        let value_len = u32::from_le_bytes(bytes[20..24].try_into().unwrap());
+        (hash, key_offset, key_len, value_offset, value_len)
+    }
+
+    fn key_matches(&self, key_offset: u32, key_len: u32, key: &[char]) -> bool {
+        if key_len as usize != key.len() {
+            return false;
+        }
+        let base = self.keys_offset + key_offset as usize * 4;
+        for (i, &expected) in key.iter().enumerate() {
+            let off = base + i * 4;
+            let scalar = u32::from_le_bytes(self.mmap[off..off + 4].try_into().unwrap());
+            if char::from_u32(scalar) != Some(expected) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Looks up `key` directly against the mapped pages — no key or value is
+    /// copied until the caller owns the returned `&str`.
+    pub fn get(&self, key: &[char]) -> Option<&str> {
+        if key.is_empty() || self.capacity == 0 {
+            return None;
+        }
+        let h = hash_key(key);
+        let mask = self.capacity - 1;
+        let mut idx = h & mask;
+        for _ in 0..self.capacity {
+            let (slot_hash, key_offset, key_len, value_offset, value_len) = self.slot_at(idx);
+            if key_len == 0 {
+                return None;
+            }
+            if slot_hash == h && self.key_matches(key_offset, key_len, key) {
+                let start = self.values_offset + value_offset as usize;
+                let bytes = &self.mmap[start..start + value_len as usize];
+                return std::str::from_utf8(bytes).ok();
+            }
+            idx = (idx + 1) & mask;
+        }
+        None
+    }
+}
+
+impl DictMaxLen {
+    /// Writes this table to `path` in the flagged, section-offset mmap
+    /// format [`load_mmap`](Self::load_mmap) reads back — see the [module
+    /// docs](super::mmap_index) for the on-disk layout.
+    pub fn save_index<P: AsRef<Path>>(&self, path: P) -> Result<(), DictionaryError> {
+        let bytes = encode(self);
+        fs::write(&path, bytes).map_err(|err| {
+            let msg = format!("Failed to write mmap index file: {}", err);
+            DictionaryMaxlength::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })
+    }
+
+    /// Opens a file previously written by [`save_index`](Self::save_index)
+    /// and returns a zero-copy [`MmapIndex`] view over it. Unlike
+    /// [`open_mmap`](Self::open_mmap), the dense tables are never copied
+    /// into owned `Vec`s — [`MmapIndex::first_len_mask64`]/
+    /// [`MmapIndex::first_char_max_len`] borrow straight from the mapped
+    /// bytes.
+    ///
+    /// Fails loudly (rather than returning garbage masks) if the file's
+    /// `endianness` byte doesn't match this host, since the dense section is
+    /// stored in native byte order to keep it zero-copy.
+    ///
+    /// # Safety
+    /// Backed by [`memmap2::Mmap::map`], which is safe as long as the file
+    /// isn't truncated or rewritten by another process while mapped —
+    /// see that function's own `# Safety` section.
+    pub fn load_mmap<P: AsRef<Path>>(path: P) -> Result<MmapIndex, DictionaryError> {
+        let file = fs::File::open(&path).map_err(DictionaryError::IoError)?;
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(|err| {
+            let msg = format!("Failed to mmap dictionary index file: {}", err);
+            DictionaryMaxlength::set_last_error(&msg);
+            DictionaryError::IoError(err)
+        })?;
+
+        let buf: &[u8] = &mmap;
+        let rest = buf
+            .strip_prefix(&INDEX_MAGIC)
+            .ok_or_else(|| invalid("missing or unrecognized mmap index magic header"))?;
+        if buf.len() < HEADER_SIZE {
+            return Err(invalid("truncated mmap index header"));
+        }
+        let version = u16::from_le_bytes([rest[0], rest[1]]);
+        if version != INDEX_VERSION {
+            return Err(invalid(format!(
+                "mmap index format v{} required, found v{}",
+                INDEX_VERSION, version
+            )));
+        }
+
+        let flags = buf[6];
+        let endianness = buf[7];
+        if endianness != HOST_ENDIAN {
+            return Err(invalid(
+                "mmap index was written on a host with different endianness; \
+                 rebuild it on a matching-endianness host rather than risk \
+                 silently misreading the dense tables",
+            ));
+        }
+
+        let min_len = read_u64(buf, 8)? as usize;
+        let max_len = read_u64(buf, 16)? as usize;
+        let key_length_mask = read_u64(buf, 24)?;
+        let starter_base = read_u64(buf, 32)? as u32;
+        let sparse_count = read_u64(buf, 40)? as usize;
+        let dense_len = read_u64(buf, 48)? as usize;
+        let sparse_offset = read_u64(buf, 56)? as usize;
+        let dense_offset = read_u64(buf, 64)? as usize;
+        let index_offset = read_u64(buf, 72)? as usize;
+        let keys_offset = read_u64(buf, 80)? as usize;
+        let values_offset = read_u64(buf, 88)? as usize;
+        let capacity = read_u64(buf, 96)?;
+        let keys_blob_len = read_u64(buf, 104)?;
+        let values_blob_len = read_u64(buf, 112)?;
+
+        if dense_offset % 8 != 0 {
+            return Err(invalid("mmap index dense section is not 8-byte aligned"));
+        }
+
+        let mut sparse = FxHashMap::default();
+        sparse.reserve(sparse_count);
+        let mut pos = sparse_offset;
+        for _ in 0..sparse_count {
+            let codepoint_bytes: [u8; 4] = buf
+                .get(pos..pos + 4)
+                .ok_or_else(|| invalid("truncated sparse section in mmap index"))?
+                .try_into()
+                .unwrap();
+            let codepoint = u32::from_le_bytes(codepoint_bytes);
+            let starter = char::from_u32(codepoint)
+                .ok_or_else(|| invalid("invalid starter codepoint in mmap index"))?;
+            let mask = read_u64(buf, pos + 4)?;
+            sparse.insert(starter, mask);
+            pos += 4 + 8;
+        }
+
+        let values_end = values_offset
+            .checked_add(values_blob_len as usize)
+            .ok_or_else(|| invalid("mmap index values section length overflows"))?;
+        if buf.len() < values_end {
+            return Err(invalid("truncated mmap index (dense/index/keys/values region)"));
+        }
+        if flags & DENSE_PRESENT != 0 {
+            // `dense_len`/`dense_offset` come straight from file-controlled `u64`s, so
+            // this must be checked arithmetic: an unchecked `dense_offset + dense_len *
+            // 9` can wrap on a crafted file, slipping past the `dense_end > index_offset`
+            // guard below with a tiny wrapped value while `self.dense_len` itself stays
+            // huge — which is exactly the length `first_len_mask64` later hands to
+            // `slice::from_raw_parts`. Bounding against `buf.len()` here (not just
+            // `index_offset`) is what keeps that later unsafe slice in-bounds.
+            let dense_end = dense_len
+                .checked_mul(8)
+                .and_then(|values_bytes| values_bytes.checked_add(dense_len))
+                .and_then(|section_len| section_len.checked_add(dense_offset))
+                .ok_or_else(|| invalid("mmap index dense section length overflows"))?;
+            if dense_end > buf.len() || dense_end > index_offset {
+                return Err(invalid("mmap index dense section overruns the index section"));
+            }
+        }
+        let index_bytes = capacity as usize * SLOT_SIZE;
+        if index_offset + index_bytes != keys_offset {
+            return Err(invalid("mmap index index/keys section boundary mismatch"));
+        }
+        let keys_bytes = keys_blob_len as usize * 4;
+        if keys_offset + keys_bytes != values_offset {
+            return Err(invalid("mmap index keys/values section boundary mismatch"));
+        }
+
+        Ok(MmapIndex {
+            mmap,
+            flags,
+            min_len,
+            max_len,
+            key_length_mask,
+            starter_base,
+            sparse,
+            dense_offset,
+            dense_len,
+            index_offset,
+            keys_offset,
+            values_offset,
+            capacity,
+        })
+    }
+}
+
+#[test]
+fn save_then_load_mmap_round_trips_lookups() {
+    let mut dict = DictMaxLen::build_from_pairs([
+        ("你好".to_string(), "您好".to_string()),
+        ("你".to_string(), "妳".to_string()),
+        ("世界".to_string(), "世間".to_string()),
+    ]);
+    dict.populate_starter_indexes();
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dict.save_index(file.path()).unwrap();
+    let loaded = DictMaxLen::load_mmap(file.path()).unwrap();
+
+    assert_eq!(loaded.min_len(), dict.min_len);
+    assert_eq!(loaded.max_len(), dict.max_len);
+    let ni: Vec<char> = "你好".chars().collect();
+    assert_eq!(loaded.get(&ni), Some("您好"));
+    let n: Vec<char> = "你".chars().collect();
+    assert_eq!(loaded.get(&n), Some("妳"));
+    let w: Vec<char> = "世界".chars().collect();
+    assert_eq!(loaded.get(&w), Some("世間"));
+}
+
+#[test]
+fn load_mmap_lookup_miss_returns_none() {
+    let mut dict = DictMaxLen::build_from_pairs([("你好".to_string(), "您好".to_string())]);
+    dict.populate_starter_indexes();
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dict.save_index(file.path()).unwrap();
+    let loaded = DictMaxLen::load_mmap(file.path()).unwrap();
+
+    let missing: Vec<char> = "再見".chars().collect();
+    assert_eq!(loaded.get(&missing), None);
+}
+
+#[test]
+fn load_mmap_rejects_bad_magic() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(file.path(), b"not an mmap index file").unwrap();
+    assert!(DictMaxLen::load_mmap(file.path()).is_err());
+}
+
+#[test]
+fn load_mmap_rejects_dense_len_that_would_overflow_the_bounds_check() {
+    // A crafted file claiming an absurd `dense_len` must be rejected via
+    // checked arithmetic, not accepted after `dense_offset + dense_len * 9`
+    // silently wraps back into range.
+    let mut dict = DictMaxLen::build_from_pairs([("你好".to_string(), "您好".to_string())]);
+    dict.populate_starter_indexes();
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dict.save_index(file.path()).unwrap();
+
+    let mut bytes = fs::read(file.path()).unwrap();
+    bytes[48..56].copy_from_slice(&u64::MAX.to_le_bytes());
+    fs::write(file.path(), &bytes).unwrap();
+
+    assert!(DictMaxLen::load_mmap(file.path()).is_err());
+}
+
+#[test]
+fn load_mmap_rejects_dense_len_within_index_offset_but_past_the_file() {
+    // A smaller but still-bogus `dense_len` that happens to land within
+    // `index_offset` must still be caught by the direct `buf.len()` bound,
+    // since `index_offset` itself isn't trustworthy either.
+    let mut dict = DictMaxLen::build_from_pairs([("你好".to_string(), "您好".to_string())]);
+    dict.populate_starter_indexes();
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    dict.save_index(file.path()).unwrap();
+
+    let mut bytes = fs::read(file.path()).unwrap();
+    // `dense_len * 9` alone already dwarfs the file, with no risk of
+    // overflowing `u64`/`usize` arithmetic on its own.
+    let oversized_dense_len = bytes.len() as u64;
+    bytes[48..56].copy_from_slice(&oversized_dense_len.to_le_bytes());
+    // Push index_offset out far enough that `dense_end > index_offset` alone
+    // would pass, leaving only the `buf.len()` bound to catch it.
+    bytes[72..80].copy_from_slice(&u64::MAX.to_le_bytes());
+    fs::write(file.path(), &bytes).unwrap();
+
+    assert!(DictMaxLen::load_mmap(file.path()).is_err());
+}
@@ -1,7 +1,36 @@
+pub mod automaton;
+pub mod block_sparse_index;
+pub mod byte_fst;
+pub mod compact_dict;
+pub mod dictd;
 pub mod dictionary_maxlength;
 pub mod dict_max_len;
+#[cfg(any(
+    feature = "embed-st",
+    feature = "embed-tw",
+    feature = "embed-hk",
+    feature = "embed-jp",
+    feature = "embed-all"
+))]
+pub mod embed;
+pub mod fst_dict;
+pub mod mmap_dict;
+pub mod mmap_index;
+pub mod parallel_zstd;
+pub mod starter_index;
 pub mod starter_union;
 
-pub use self::dictionary_maxlength::{DictionaryMaxlength, DictionaryError};
+pub use self::automaton::{Automaton, MatchEngine};
+pub use self::block_sparse_index::BlockSparseStarterIndex;
+pub use self::byte_fst::{build_byte_fst, ByteFstDict};
+pub use self::dictionary_maxlength::{
+    BadLine, Codec, DictField, DictionaryError, DictionaryMaxlength, LazyDictionary,
+    LineErrorPolicy,
+};
 pub use self::dict_max_len::*;
+pub use self::fst_dict::FstDict;
+pub use self::mmap_dict::MmapDict;
+pub use self::mmap_index::MmapIndex;
+pub use self::parallel_zstd::ParallelZstdEncoder;
+pub use self::starter_index::CompressedStarterIndex;
 pub use self::starter_union::*;
\ No newline at end of file
@@ -0,0 +1,228 @@
+//! Multi-threaded Zstd encoding for large dictionary builds.
+//!
+//! [`DictionaryMaxlength::save_cbor_compressed`](crate::dictionary_lib::DictionaryMaxlength::save_cbor_compressed)
+//! serializes to CBOR and streams the result through a single `zstd::Encoder`,
+//! which at high compression levels is the dominant cost of building a custom
+//! dictionary. [`ParallelZstdEncoder`] instead splits the CBOR byte stream into
+//! fixed-size blocks, compresses them independently across a worker pool, and
+//! writes them out in order — each block is its own complete Zstd frame,
+//! written after a 4-byte magic header and a `u64` block count, each with a
+//! `u64` length prefix. Read the file back with [`decode_parallel`]; a plain
+//! `zstd::Decoder` over the whole file fails on the header.
+
+use crate::dictionary_lib::dictionary_maxlength::DictionaryError;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::sync::{mpsc, Mutex};
+use std::{io, thread};
+
+/// 4-byte magic header identifying a block-framed parallel Zstd stream,
+/// checked by [`decode_parallel`] before reading any blocks.
+const MAGIC: [u8; 4] = *b"OCPZ";
+
+/// Builder for parallel, block-split Zstd encoding of a serialized dictionary.
+///
+/// Defaults to [`std::thread::available_parallelism`] worker threads and a
+/// 1 MiB block size; use [`with_num_threads`](Self::with_num_threads) and
+/// [`with_block_size`](Self::with_block_size) to trade memory (larger blocks,
+/// fewer threads doing more work each) for throughput (smaller blocks spread
+/// across more threads).
+#[derive(Debug, Clone, Copy)]
+pub struct ParallelZstdEncoder {
+    level: i32,
+    num_threads: usize,
+    block_size: usize,
+}
+
+impl Default for ParallelZstdEncoder {
+    fn default() -> Self {
+        Self {
+            level: 19,
+            num_threads: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            block_size: 1 << 20,
+        }
+    }
+}
+
+impl ParallelZstdEncoder {
+    /// Creates a builder with the default level (19), thread count (available
+    /// parallelism), and block size (1 MiB).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the Zstd compression level applied to every block.
+    pub fn with_level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the number of worker threads; clamped to at least 1.
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = num_threads.max(1);
+        self
+    }
+
+    /// Sets the block size in bytes that the CBOR stream is split into before
+    /// compression; clamped to at least 1.
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Compresses `bytes` into `path` as a sequence of independently
+    /// Zstd-compressed, length-prefixed blocks, fanned out across this
+    /// builder's worker pool and written back in their original order.
+    ///
+    /// # Returns
+    /// - `Ok(())` on success
+    /// - `Err(DictionaryError)` if compression or I/O fails
+    pub fn encode(&self, bytes: &[u8], path: &str) -> Result<(), DictionaryError> {
+        let blocks: Vec<&[u8]> = if bytes.is_empty() {
+            Vec::new()
+        } else {
+            bytes.chunks(self.block_size).collect()
+        };
+        let num_threads = self.num_threads.min(blocks.len().max(1));
+        let level = self.level;
+
+        // Bounded in the sense that gave it its name: there's exactly one job
+        // per block, so the channel never holds more than `blocks.len()` items.
+        let (job_tx, job_rx) = mpsc::channel::<(usize, &[u8])>();
+        for job in blocks.iter().copied().enumerate() {
+            job_tx.send(job).expect("receiver kept alive by this function");
+        }
+        drop(job_tx);
+        let job_rx = Mutex::new(job_rx);
+
+        let (result_tx, result_rx) = mpsc::channel::<(usize, Result<Vec<u8>, io::Error>)>();
+
+        thread::scope(|scope| {
+            for _ in 0..num_threads {
+                let job_rx = &job_rx;
+                let result_tx = result_tx.clone();
+                scope.spawn(move || loop {
+                    let job = job_rx.lock().unwrap().recv();
+                    let Ok((index, block)) = job else {
+                        break;
+                    };
+                    let compressed = zstd::encode_all(block, level);
+                    if result_tx.send((index, compressed)).is_err() {
+                        break;
+                    }
+                });
+            }
+        });
+        drop(result_tx);
+
+        // The ordered writer: collect every worker's result by index, then
+        // emit blocks in original sequence regardless of completion order.
+        let mut compressed_blocks: Vec<Option<Result<Vec<u8>, io::Error>>> =
+            (0..blocks.len()).map(|_| None).collect();
+        for (index, compressed) in result_rx {
+            compressed_blocks[index] = Some(compressed);
+        }
+
+        let file = File::create(path).map_err(DictionaryError::IoError)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(&MAGIC).map_err(DictionaryError::IoError)?;
+        writer
+            .write_all(&(compressed_blocks.len() as u64).to_le_bytes())
+            .map_err(DictionaryError::IoError)?;
+
+        for slot in compressed_blocks {
+            let compressed = slot
+                .expect("every block index was sent exactly once above")
+                .map_err(DictionaryError::IoError)?;
+            writer
+                .write_all(&(compressed.len() as u64).to_le_bytes())
+                .map_err(DictionaryError::IoError)?;
+            writer.write_all(&compressed).map_err(DictionaryError::IoError)?;
+        }
+
+        writer.flush().map_err(DictionaryError::IoError)?;
+        Ok(())
+    }
+}
+
+/// Reads a block-framed stream written by [`ParallelZstdEncoder::encode`] and
+/// returns the concatenated decompressed bytes.
+///
+/// Decompresses blocks sequentially; since decompression is far cheaper than
+/// the high-level compression this format targets, the single-threaded
+/// pass is rarely the bottleneck. A plain `zstd::Decoder` over the raw file
+/// (skipping the length prefixes) would also work, since each block is a
+/// complete, self-delimiting Zstd frame.
+pub fn decode_parallel<R: Read>(mut reader: R) -> Result<Vec<u8>, DictionaryError> {
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(DictionaryError::IoError)?;
+    if magic != MAGIC {
+        return Err(DictionaryError::InvalidBundle(
+            "missing or unrecognized parallel Zstd block-stream magic header".into(),
+        ));
+    }
+
+    let block_count = read_u64(&mut reader)?;
+    let mut out = Vec::new();
+    for _ in 0..block_count {
+        let compressed_len = read_u64(&mut reader)?;
+        let mut compressed = vec![0u8; compressed_len as usize];
+        reader
+            .read_exact(&mut compressed)
+            .map_err(DictionaryError::IoError)?;
+        let decompressed = zstd::decode_all(compressed.as_slice()).map_err(DictionaryError::IoError)?;
+        out.extend_from_slice(&decompressed);
+    }
+
+    Ok(out)
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, DictionaryError> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes).map_err(DictionaryError::IoError)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[test]
+fn encode_then_decode_round_trips_multi_block_input() {
+    let data: Vec<u8> = (0..10_000u32).map(|n| (n % 256) as u8).collect();
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+
+    ParallelZstdEncoder::new()
+        .with_level(3)
+        .with_num_threads(4)
+        .with_block_size(1024)
+        .encode(&data, path)
+        .unwrap();
+
+    let decoded = decode_parallel(File::open(path).unwrap()).unwrap();
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn encode_then_decode_round_trips_empty_input() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+
+    ParallelZstdEncoder::new().encode(&[], path).unwrap();
+
+    let decoded = decode_parallel(File::open(path).unwrap()).unwrap();
+    assert!(decoded.is_empty());
+}
+
+#[test]
+fn decode_parallel_rejects_bad_magic() {
+    assert!(decode_parallel(b"not a parallel zstd stream".as_slice()).is_err());
+}
+
+#[test]
+fn with_num_threads_and_block_size_are_clamped_to_at_least_one() {
+    let encoder = ParallelZstdEncoder::new()
+        .with_num_threads(0)
+        .with_block_size(0);
+    assert_eq!(encoder.num_threads, 1);
+    assert_eq!(encoder.block_size, 1);
+}
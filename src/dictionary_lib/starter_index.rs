@@ -0,0 +1,169 @@
+//! A run-length-compressed alternative to [`DictMaxLen`](super::dict_max_len::DictMaxLen)'s
+//! dense BMP starter-index arrays (`first_len_mask64` / `first_char_max_len`).
+//!
+//! The dense arrays cost a fixed 576 KiB per table (512 KiB + 64 KiB)
+//! regardless of how many of the 65 536 BMP code points actually start a
+//! key — real CJK dictionaries typically populate only a few thousand
+//! starters, leaving the rest zero. [`CompressedStarterIndex`] instead
+//! stores sorted runs of contiguous "live" code points plus a compact side
+//! array holding the `u64` length mask and `u8` max length only for those
+//! starters, trading a binary search per gate for a large cut in resident
+//! memory when many dictionaries are held at once.
+//!
+//! # Layout
+//! - `runs`: sorted, non-overlapping `(start, len)` pairs, one per maximal
+//!   contiguous range of live code points.
+//! - `run_base`: parallel prefix-sum array — `run_base[i]` is the number of
+//!   live code points in all runs before run `i`, so a live code point's
+//!   slot in `masks`/`caps` is `run_base[i] + (code point - runs[i].0)`.
+//! - `masks` / `caps`: one entry per live code point, in run order, holding
+//!   exactly what `first_len_mask64`/`first_char_max_len` would hold for
+//!   that code point.
+//!
+//! A lookup binary-searches `runs` for the run containing the starter's
+//! code point, then adds the within-run offset to that run's precomputed
+//! base to find its slot — `O(log R)` where `R` is the run count, which is
+//! small in practice since live CJK starters cluster into a handful of
+//! Unicode blocks.
+
+/// Run-length-compressed starter index; see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct CompressedStarterIndex {
+    runs: Vec<(u32, u32)>,
+    run_base: Vec<u32>,
+    masks: Vec<u64>,
+    caps: Vec<u8>,
+}
+
+impl CompressedStarterIndex {
+    /// Builds a compressed index from `entries`, which must be sorted
+    /// ascending by code point and contain at most one entry per live BMP
+    /// starter (code point, length mask, max length).
+    pub fn build(entries: &[(char, u64, u8)]) -> Self {
+        let mut runs = Vec::new();
+        let mut run_base = Vec::new();
+        let mut masks = Vec::with_capacity(entries.len());
+        let mut caps = Vec::with_capacity(entries.len());
+
+        let mut i = 0;
+        while i < entries.len() {
+            let start = entries[i].0 as u32;
+            let base = masks.len() as u32;
+            let mut expected = start;
+            let mut j = i;
+            while j < entries.len() && entries[j].0 as u32 == expected {
+                masks.push(entries[j].1);
+                caps.push(entries[j].2);
+                expected += 1;
+                j += 1;
+            }
+            runs.push((start, expected - start));
+            run_base.push(base);
+            i = j;
+        }
+
+        CompressedStarterIndex {
+            runs,
+            run_base,
+            masks,
+            caps,
+        }
+    }
+
+    /// Number of live starters represented.
+    pub fn len(&self) -> usize {
+        self.masks.len()
+    }
+
+    /// `true` if no starter is live (no keys loaded).
+    pub fn is_empty(&self) -> bool {
+        self.masks.is_empty()
+    }
+
+    /// Number of runs the live starters were compressed into.
+    pub fn run_count(&self) -> usize {
+        self.runs.len()
+    }
+
+    #[inline]
+    fn slot(&self, codepoint: u32) -> Option<usize> {
+        let idx = self.runs.partition_point(|&(start, _)| start <= codepoint);
+        if idx == 0 {
+            return None;
+        }
+        let (start, len) = self.runs[idx - 1];
+        let offset = codepoint - start;
+        if offset >= len {
+            return None;
+        }
+        Some(self.run_base[idx - 1] as usize + offset as usize)
+    }
+
+    /// Same contract as
+    /// [`DictMaxLen::starter_allows_dict`](super::dict_max_len::DictMaxLen::starter_allows_dict)'s
+    /// dense fast-path: `true` if a key of `length` exists starting with
+    /// `starter`, using `bit = length - 1` for `length <= 64`, or the
+    /// stored cap for longer keys.
+    #[inline]
+    pub fn allows(&self, starter: char, length: usize, bit: usize) -> bool {
+        let Some(slot) = self.slot(starter as u32) else {
+            return false;
+        };
+        if bit < 64 {
+            return (self.masks[slot] >> bit) & 1 != 0;
+        }
+        length <= self.caps[slot] as usize
+    }
+
+    /// Returns the raw length mask for `starter`, or `0` if it's in no run
+    /// (no keys start with it). Same contract as
+    /// [`DictMaxLen::get_starter_mask`](super::dict_max_len::DictMaxLen::get_starter_mask).
+    #[inline]
+    pub fn get_mask(&self, starter: char) -> u64 {
+        self.slot(starter as u32).map_or(0, |slot| self.masks[slot])
+    }
+}
+
+#[test]
+fn build_compresses_contiguous_runs_and_answers_allows() {
+    let entries = [('a', 0b1, 1u8), ('b', 0b10, 1u8), ('d', 0b100, 1u8)];
+    let index = CompressedStarterIndex::build(&entries);
+
+    assert_eq!(index.len(), 3);
+    assert_eq!(index.run_count(), 2); // "a".."b" is one run, "d" is another
+    assert!(!index.is_empty());
+
+    assert!(index.allows('a', 1, 0));
+    assert!(!index.allows('a', 2, 1));
+    assert!(index.allows('b', 2, 1));
+    assert!(index.allows('d', 3, 2));
+}
+
+#[test]
+fn lookups_for_unlisted_starters_return_defaults() {
+    let entries = [('b', 0b1, 1u8)];
+    let index = CompressedStarterIndex::build(&entries);
+
+    assert!(!index.allows('a', 1, 0));
+    assert!(!index.allows('c', 1, 0));
+    assert_eq!(index.get_mask('a'), 0);
+    assert_eq!(index.get_mask('b'), 0b1);
+}
+
+#[test]
+fn empty_index_has_no_runs_and_allows_nothing() {
+    let index = CompressedStarterIndex::build(&[]);
+    assert!(index.is_empty());
+    assert_eq!(index.run_count(), 0);
+    assert!(!index.allows('a', 1, 0));
+    assert_eq!(index.get_mask('a'), 0);
+}
+
+#[test]
+fn allows_falls_back_to_cap_for_bit_at_or_above_64() {
+    let entries = [('a', u64::MAX, 70u8)];
+    let index = CompressedStarterIndex::build(&entries);
+
+    assert!(index.allows('a', 70, 69));
+    assert!(!index.allows('a', 71, 70));
+}
@@ -11,11 +11,14 @@ use rustc_hash::FxHashMap;
 ///
 /// # Structure
 ///
-/// Starters in the Unicode **BMP** (`0x0000..=0xFFFF`) are stored in dense
-/// fixed-size vectors:
+/// Starters in the Unicode **BMP** (`0x0000..=0xFFFF`) are queried through:
 ///
-/// - [`bmp_mask`]: a `u64` bitmask encoding which phrase lengths exist  
-/// - [`bmp_cap`]: the maximum phrase length for that starter  
+/// - [`bmp_mask`](Self::bmp_mask): a `u64` bitmask encoding which phrase lengths exist
+/// - [`bmp_cap`](Self::bmp_cap): the maximum phrase length for that starter
+///
+/// backed by either a dense `0x10000`-entry table ([`build`](Self::build)) or a
+/// deduplicated paged table ([`build_paged`](Self::build_paged)) — both methods
+/// return identical query results, differing only in memory/lookup-speed tradeoff.
 ///
 /// Starters **outside the BMP** are far less common, so they are stored
 /// sparsely:
@@ -37,11 +40,13 @@ use rustc_hash::FxHashMap;
 ///
 /// # Invariants
 ///
-/// - `bmp_mask.len() == 0x10000`  
-/// - `bmp_cap.len()  == 0x10000`  
-/// - If a bit is set in `bmp_mask[i]`, at least one dictionary contains a key
-///   that begins with that starter and has the corresponding length.  
-/// - `bmp_cap[i]` is always ≥ the highest set bit (converted to a length).  
+/// - `bmp_mask`/`bmp_cap` accept any `cp < 0x10000`, dense or paged.
+/// - If a bit is set in `bmp_mask(i)`, at least one dictionary contains a key
+///   that begins with that starter and has the corresponding length.
+/// - `bmp_cap(i)` is always ≥ the highest set bit (converted to a length).
+/// - A `0` result (from either query) is indistinguishable from "no
+///   dictionary starts here with that length" — paging never changes what a
+///   lookup returns, only how it's stored.
 ///
 /// These invariants are ensured by [`StarterUnion::build`].
 ///
@@ -71,18 +76,11 @@ use rustc_hash::FxHashMap;
 /// ensuring consistent, high-performance starter gating across the entire engine.
 #[derive(Default, Debug)]
 pub struct StarterUnion {
-    /// Dense BMP per-starter bitmask.
-    ///
-    /// Indexed by `starter as usize`, giving a `u64` bitmask with one bit per
-    /// possible length (1..=64). The most common case (CJK characters, ASCII,
-    /// punctuation) is handled here.
-    pub bmp_mask: Vec<u64>, // size: 0x10000
-
-    /// Dense BMP per-starter maximum phrase length.
-    ///
-    /// Same indexing as [`bmp_mask`]. This provides the upper bound on the
-    /// candidate window size during longest-match probing.
-    pub bmp_cap: Vec<u8>, // size: 0x10000
+    /// BMP per-starter bitmask and maximum-length tables, in whichever
+    /// representation [`build`](Self::build)/[`build_paged`](Self::build_paged)
+    /// chose. Use [`bmp_mask`](Self::bmp_mask)/[`bmp_cap`](Self::bmp_cap) to
+    /// query either representation identically.
+    bmp: BmpTables,
 
     /// Sparse per-starter bitmask for astral (non-BMP) codepoints.
     ///
@@ -97,6 +95,100 @@ pub struct StarterUnion {
     pub astral_cap: FxHashMap<char, u8>,
 }
 
+/// BMP table storage backing [`StarterUnion`], chosen by
+/// [`StarterUnion::build`] (dense) or [`StarterUnion::build_paged`] (paged).
+#[derive(Debug)]
+enum BmpTables {
+    /// Two flat `0x10000`-entry vectors, indexed directly by codepoint —
+    /// the fastest lookup, at a fixed 576 KiB regardless of how many
+    /// starters are actually in use.
+    Dense { mask: Vec<u64>, cap: Vec<u8> },
+    /// [`PagedTable`]s for both the mask and cap arrays — see
+    /// [`StarterUnion::build_paged`].
+    Paged {
+        mask: PagedTable<u64>,
+        cap: PagedTable<u8>,
+    },
+}
+
+impl Default for BmpTables {
+    fn default() -> Self {
+        BmpTables::Dense {
+            mask: Vec::new(),
+            cap: Vec::new(),
+        }
+    }
+}
+
+/// A two-level paged table over the `0x10000` BMP codepoint space, modeled
+/// on the block-deduplication scheme used by Rust's own unicode-table
+/// generator: the space is split into 256 pages of 256 entries each, a page
+/// that's entirely zero is never stored (the overwhelmingly common case for
+/// real OpenCC dictionaries, which only ever touch a few thousand distinct
+/// starters), and pages with identical contents are deduplicated to a
+/// single stored copy.
+///
+/// A zero result is indistinguishable whether it comes from an unallocated
+/// page or a stored zero entry, preserving [`StarterUnion`]'s "no dictionary
+/// starts here" invariant.
+#[derive(Debug)]
+struct PagedTable<T> {
+    /// One slot per page (`codepoint >> 8`); `None` means the page is all
+    /// zero and was never stored.
+    page_of: [Option<u16>; 256],
+    /// Deduplicated page contents, indexed by the `u16` in `page_of`.
+    pages: Vec<[T; 256]>,
+}
+
+impl<T> Default for PagedTable<T> {
+    fn default() -> Self {
+        PagedTable {
+            page_of: [None; 256],
+            pages: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Default + Eq + std::hash::Hash> PagedTable<T> {
+    /// Pages `dense` (which must have exactly `0x10000` entries) into a
+    /// [`PagedTable`], skipping all-zero pages and deduplicating the rest.
+    fn from_dense(dense: &[T]) -> Self {
+        debug_assert_eq!(dense.len(), 0x10000);
+
+        let mut page_of = [None; 256];
+        let mut pages: Vec<[T; 256]> = Vec::new();
+        let mut dedup: FxHashMap<[T; 256], u16> = FxHashMap::default();
+        let zero_page = [T::default(); 256];
+
+        for (page_idx, chunk) in dense.chunks_exact(256).enumerate() {
+            let mut page = zero_page;
+            page.copy_from_slice(chunk);
+
+            if page == zero_page {
+                continue;
+            }
+
+            let slot = *dedup.entry(page).or_insert_with(|| {
+                pages.push(page);
+                (pages.len() - 1) as u16
+            });
+            page_of[page_idx] = Some(slot);
+        }
+
+        Self { page_of, pages }
+    }
+
+    /// Looks up `idx` (`< 0x10000`), returning `T::default()` for an
+    /// unallocated (all-zero) page.
+    #[inline]
+    fn get(&self, idx: usize) -> T {
+        match self.page_of[idx >> 8] {
+            Some(p) => self.pages[p as usize][idx & 0xFF],
+            None => T::default(),
+        }
+    }
+}
+
 impl StarterUnion {
     /// Builds a combined **starter metadata union** from multiple [`DictMaxLen`]
     /// dictionaries.
@@ -152,6 +244,49 @@ impl StarterUnion {
     /// A fully merged [`StarterUnion`] containing the union of all starters,
     /// masks, and maximum lengths across all provided dictionaries.
     pub fn build(dicts: &[&DictMaxLen]) -> Self {
+        let (bmp_mask, bmp_cap, astral_mask, astral_cap) = Self::merge_dense(dicts);
+        Self {
+            bmp: BmpTables::Dense {
+                mask: bmp_mask,
+                cap: bmp_cap,
+            },
+            astral_mask,
+            astral_cap,
+        }
+    }
+
+    /// Builds a [`StarterUnion`] exactly like [`build`](Self::build), except
+    /// the BMP tables are stored as two [`PagedTable`]s rather than two flat
+    /// `0x10000`-entry vectors.
+    ///
+    /// Real OpenCC dictionaries only ever use a few thousand distinct BMP
+    /// starters, so most of the dense 0x10000-entry space is zero — paging
+    /// skips storing any all-zero page and deduplicates identical nonzero
+    /// pages, trading a small amount of lookup indirection (one extra array
+    /// read per query) for substantially less memory on memory-constrained
+    /// embeddings. Callers who need maximum lookup speed over minimal memory
+    /// should use [`build`](Self::build) instead — both expose the same
+    /// [`bmp_mask`](Self::bmp_mask)/[`bmp_cap`](Self::bmp_cap) query methods,
+    /// so conversion code is unaffected by which representation backs a
+    /// given `StarterUnion`.
+    pub fn build_paged(dicts: &[&DictMaxLen]) -> Self {
+        let (bmp_mask, bmp_cap, astral_mask, astral_cap) = Self::merge_dense(dicts);
+        Self {
+            bmp: BmpTables::Paged {
+                mask: PagedTable::from_dense(&bmp_mask),
+                cap: PagedTable::from_dense(&bmp_cap),
+            },
+            astral_mask,
+            astral_cap,
+        }
+    }
+
+    /// Shared merge pass behind [`build`](Self::build)/[`build_paged`](Self::build_paged):
+    /// unions every dictionary's starter masks/caps into flat BMP vectors
+    /// plus the sparse astral maps, before either is wrapped as-is or paged.
+    fn merge_dense(
+        dicts: &[&DictMaxLen],
+    ) -> (Vec<u64>, Vec<u8>, FxHashMap<char, u64>, FxHashMap<char, u8>) {
         const N: usize = 0x10000;
         let mut bmp_mask = vec![0u64; N];
         let mut bmp_cap = vec![0u8; N];
@@ -188,11 +323,28 @@ impl StarterUnion {
             }
         }
 
-        Self {
-            bmp_mask,
-            bmp_cap,
-            astral_mask,
-            astral_cap,
+        (bmp_mask, bmp_cap, astral_mask, astral_cap)
+    }
+
+    /// Bitmask of phrase lengths for the BMP starter at codepoint `cp`
+    /// (`cp < 0x10000`), or `0` if no dictionary starts a match there —
+    /// identical whether this `StarterUnion` was built dense or paged.
+    #[inline]
+    pub fn bmp_mask(&self, cp: usize) -> u64 {
+        match &self.bmp {
+            BmpTables::Dense { mask, .. } => mask[cp],
+            BmpTables::Paged { mask, .. } => mask.get(cp),
+        }
+    }
+
+    /// Maximum phrase length for the BMP starter at codepoint `cp`
+    /// (`cp < 0x10000`), or `0` if no dictionary starts a match there —
+    /// identical whether this `StarterUnion` was built dense or paged.
+    #[inline]
+    pub fn bmp_cap(&self, cp: usize) -> u8 {
+        match &self.bmp {
+            BmpTables::Dense { cap, .. } => cap[cp],
+            BmpTables::Paged { cap, .. } => cap.get(cp),
         }
     }
 }
@@ -0,0 +1,343 @@
+//! Ideographic Description Sequence (IDS) decomposition — breaking a CJK
+//! character into its visual sub-components, joined by a Unicode
+//! Ideographic Description Character (IDC) denoting their layout (left-right,
+//! above-below, full/partial enclosure, overlay).
+//!
+//! Like [`romanization`](crate::romanization), this is kept as its own
+//! table rather than folded into [`DictionaryMaxlength`](crate::dictionary_lib::DictionaryMaxlength)'s
+//! bundle: a decomposition entry's value is an IDS *string* (an operator
+//! followed by its component characters, themselves possibly further IDS
+//! sequences), not another convertible Chinese phrase. It's still keyed and
+//! loaded the same way as any other table — a single-character
+//! [`DictMaxLen`] key mapping to its one-level decomposition — so
+//! [`OpenCC::decompose`](crate::OpenCC::decompose) expands it recursively on
+//! top, rather than requiring the source table to spell out every character
+//! fully expanded down to strokes.
+//!
+//! This enables component-level fuzzy matching: two characters that don't
+//! share a direct `jp_variants`/`tw_variants` entry (e.g. a Japanese
+//! Shinjitai form and its Kyūjitai counterpart) can still be recognized as
+//! related if [`OpenCC::decompose_string`](crate::OpenCC::decompose_string)
+//! produces the same (or a closely overlapping) flattened IDS string for
+//! both.
+
+use crate::dictionary_lib::dict_max_len::DictMaxLen;
+use crate::dictionary_lib::DictionaryError;
+use std::path::Path;
+
+/// One Unicode Ideographic Description Character (IDC), denoting how an
+/// [`IdsTree::Node`]'s components are laid out relative to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdsOperator {
+    /// `⿰` U+2FF0 — left to right.
+    LeftRight,
+    /// `⿱` U+2FF1 — above to below.
+    AboveBelow,
+    /// `⿲` U+2FF2 — left to middle to right.
+    LeftMiddleRight,
+    /// `⿳` U+2FF3 — above to middle to below.
+    AboveMiddleBelow,
+    /// `⿴` U+2FF4 — full surround.
+    FullSurround,
+    /// `⿵` U+2FF5 — surround from above.
+    SurroundFromAbove,
+    /// `⿶` U+2FF6 — surround from below.
+    SurroundFromBelow,
+    /// `⿷` U+2FF7 — surround from left.
+    SurroundFromLeft,
+    /// `⿸` U+2FF8 — surround from upper left.
+    SurroundFromUpperLeft,
+    /// `⿹` U+2FF9 — surround from upper right.
+    SurroundFromUpperRight,
+    /// `⿺` U+2FFA — surround from lower left.
+    SurroundFromLowerLeft,
+    /// `⿻` U+2FFB — overlaid.
+    Overlaid,
+}
+
+impl IdsOperator {
+    /// Recognizes `c` as one of the twelve IDC codepoints (`U+2FF0..=U+2FFB`).
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            '⿰' => IdsOperator::LeftRight,
+            '⿱' => IdsOperator::AboveBelow,
+            '⿲' => IdsOperator::LeftMiddleRight,
+            '⿳' => IdsOperator::AboveMiddleBelow,
+            '⿴' => IdsOperator::FullSurround,
+            '⿵' => IdsOperator::SurroundFromAbove,
+            '⿶' => IdsOperator::SurroundFromBelow,
+            '⿷' => IdsOperator::SurroundFromLeft,
+            '⿸' => IdsOperator::SurroundFromUpperLeft,
+            '⿹' => IdsOperator::SurroundFromUpperRight,
+            '⿺' => IdsOperator::SurroundFromLowerLeft,
+            '⿻' => IdsOperator::Overlaid,
+            _ => return None,
+        })
+    }
+
+    /// The IDC codepoint this operator renders as.
+    pub fn to_char(self) -> char {
+        match self {
+            IdsOperator::LeftRight => '⿰',
+            IdsOperator::AboveBelow => '⿱',
+            IdsOperator::LeftMiddleRight => '⿲',
+            IdsOperator::AboveMiddleBelow => '⿳',
+            IdsOperator::FullSurround => '⿴',
+            IdsOperator::SurroundFromAbove => '⿵',
+            IdsOperator::SurroundFromBelow => '⿶',
+            IdsOperator::SurroundFromLeft => '⿷',
+            IdsOperator::SurroundFromUpperLeft => '⿸',
+            IdsOperator::SurroundFromUpperRight => '⿹',
+            IdsOperator::SurroundFromLowerLeft => '⿺',
+            IdsOperator::Overlaid => '⿻',
+        }
+    }
+
+    /// Number of components this operator joins: 3 for the two
+    /// left-middle-right/above-middle-below operators, 2 for every other.
+    pub fn arity(self) -> usize {
+        match self {
+            IdsOperator::LeftMiddleRight | IdsOperator::AboveMiddleBelow => 3,
+            _ => 2,
+        }
+    }
+}
+
+/// A parsed Ideographic Description Sequence: either a leaf character (an
+/// atomic component with no further decomposition available) or an IDC
+/// joining its sub-components, returned by
+/// [`OpenCC::decompose`](crate::OpenCC::decompose).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdsTree {
+    /// An atomic component — either genuinely unanalyzable, or a character
+    /// whose own decomposition was cut off by the depth limit or cycle
+    /// detection in [`OpenCC::decompose`](crate::OpenCC::decompose).
+    Leaf(char),
+    /// An operator joining `components` (`components.len()` always equals
+    /// `operator.arity()`).
+    Node {
+        /// The layout this node's components are joined under.
+        operator: IdsOperator,
+        /// Sub-trees, in the operator's natural reading order (e.g.
+        /// left-to-right for [`IdsOperator::LeftRight`]).
+        components: Vec<IdsTree>,
+    },
+}
+
+impl IdsTree {
+    /// Renders this tree back into a flat IDS string — prefix notation, an
+    /// operator immediately followed by its components, recursively.
+    pub fn flatten(&self) -> String {
+        match self {
+            IdsTree::Leaf(c) => c.to_string(),
+            IdsTree::Node {
+                operator,
+                components,
+            } => {
+                let mut out = String::new();
+                out.push(operator.to_char());
+                for component in components {
+                    out.push_str(&component.flatten());
+                }
+                out
+            }
+        }
+    }
+}
+
+/// Parses a raw IDS string (one table entry's value, e.g. `"⿰女子"` for 好,
+/// possibly itself nested, e.g. `"⿰女⿱子一"`) into an [`IdsTree`] one level
+/// at a time via recursive descent: an operator consumes exactly
+/// [`IdsOperator::arity`] following sequences (each itself either a leaf or
+/// another operator), everything else is a leaf.
+///
+/// Returns `None` for an empty string.
+pub(crate) fn parse_ids_string(s: &str) -> Option<IdsTree> {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.is_empty() {
+        return None;
+    }
+    let mut pos = 0;
+    Some(parse_sequence(&chars, &mut pos))
+}
+
+fn parse_sequence(chars: &[char], pos: &mut usize) -> IdsTree {
+    let c = chars[*pos];
+    *pos += 1;
+    match IdsOperator::from_char(c) {
+        Some(operator) => {
+            let arity = operator.arity();
+            let mut components = Vec::with_capacity(arity);
+            for _ in 0..arity {
+                if *pos >= chars.len() {
+                    break;
+                }
+                components.push(parse_sequence(chars, pos));
+            }
+            IdsTree::Node {
+                operator,
+                components,
+            }
+        }
+        None => IdsTree::Leaf(c),
+    }
+}
+
+/// The single-character-keyed decomposition table backing
+/// [`OpenCC::decompose`](crate::OpenCC::decompose)/[`decompose_string`](crate::OpenCC::decompose_string).
+///
+/// `Default` yields an empty (but valid) table, so an [`OpenCC`](crate::OpenCC)
+/// whose `dicts/` directory has no `IDS.txt` still constructs successfully —
+/// `decompose` simply returns `None` for every character until a real table
+/// is loaded.
+#[derive(Debug, Default)]
+pub struct IdsDict {
+    /// Single-character key → raw (possibly nested) IDS string value.
+    pub table: DictMaxLen,
+}
+
+impl IdsDict {
+    /// Loads the decomposition table from a tab-separated `IDS.txt` lexicon
+    /// file in `base_dir`, using the same file format as
+    /// [`DictionaryMaxlength::from_dicts`](crate::dictionary_lib::DictionaryMaxlength::from_dicts):
+    /// `key\tvalue`, one entry per line, `#`-prefixed comments and blank
+    /// lines skipped, a leading BOM stripped from the first data line.
+    /// `value` is kept in full (not truncated to its first
+    /// whitespace-separated token), since an IDS string has no whitespace to
+    /// truncate at in the first place.
+    ///
+    /// # Errors
+    /// - [`DictionaryError::IoError`] if the file cannot be read.
+    /// - [`DictionaryError::LoadFileError`] if a data line is missing a TAB.
+    pub fn from_dicts<P: AsRef<Path>>(base_dir: P) -> Result<Self, DictionaryError> {
+        let path = base_dir.as_ref().join("IDS.txt");
+        let path_str = path.display().to_string();
+        let content = std::fs::read_to_string(&path).map_err(DictionaryError::IoError)?;
+
+        let mut pairs: Vec<(String, String)> = Vec::new();
+        let mut saw_data_line = false;
+
+        for (lineno, raw_line) in content.lines().enumerate() {
+            let mut line = raw_line.trim_end();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if !saw_data_line {
+                if let Some(rest) = line.strip_prefix('\u{FEFF}') {
+                    line = rest;
+                }
+                saw_data_line = true;
+            }
+
+            let Some((k, v)) = line.split_once('\t') else {
+                return Err(DictionaryError::LoadFileError {
+                    path: path_str.clone(),
+                    lineno: lineno + 1,
+                    message: "missing TAB separator".to_string(),
+                });
+            };
+
+            pairs.push((k.to_owned(), v.to_owned()));
+        }
+
+        Ok(IdsDict {
+            table: DictMaxLen::build_from_pairs(pairs),
+        })
+    }
+}
+
+#[test]
+fn ids_operator_round_trips_char_and_arity() {
+    assert_eq!(IdsOperator::from_char('⿰'), Some(IdsOperator::LeftRight));
+    assert_eq!(IdsOperator::LeftRight.to_char(), '⿰');
+    assert_eq!(IdsOperator::LeftRight.arity(), 2);
+    assert_eq!(IdsOperator::LeftMiddleRight.arity(), 3);
+    assert_eq!(IdsOperator::from_char('好'), None);
+}
+
+#[test]
+fn parse_ids_string_builds_a_leaf_for_a_single_char() {
+    let tree = parse_ids_string("女").unwrap();
+    assert_eq!(tree, IdsTree::Leaf('女'));
+    assert_eq!(tree.flatten(), "女");
+}
+
+#[test]
+fn parse_ids_string_builds_a_binary_node() {
+    // 好 = ⿰女子 ("left-right" of 女 and 子).
+    let tree = parse_ids_string("⿰女子").unwrap();
+    assert_eq!(
+        tree,
+        IdsTree::Node {
+            operator: IdsOperator::LeftRight,
+            components: vec![IdsTree::Leaf('女'), IdsTree::Leaf('子')],
+        }
+    );
+    assert_eq!(tree.flatten(), "⿰女子");
+}
+
+#[test]
+fn parse_ids_string_handles_nested_operators() {
+    let tree = parse_ids_string("⿰女⿱子一").unwrap();
+    assert_eq!(
+        tree,
+        IdsTree::Node {
+            operator: IdsOperator::LeftRight,
+            components: vec![
+                IdsTree::Leaf('女'),
+                IdsTree::Node {
+                    operator: IdsOperator::AboveBelow,
+                    components: vec![IdsTree::Leaf('子'), IdsTree::Leaf('一')],
+                },
+            ],
+        }
+    );
+    assert_eq!(tree.flatten(), "⿰女⿱子一");
+}
+
+#[test]
+fn parse_ids_string_returns_none_for_empty_input() {
+    assert_eq!(parse_ids_string(""), None);
+}
+
+#[test]
+fn parse_ids_string_truncates_components_when_input_runs_out() {
+    // An operator with no following characters at all still yields a node,
+    // just with fewer components than its arity.
+    let tree = parse_ids_string("⿰").unwrap();
+    assert_eq!(
+        tree,
+        IdsTree::Node {
+            operator: IdsOperator::LeftRight,
+            components: vec![],
+        }
+    );
+}
+
+#[test]
+fn ids_dict_from_dicts_loads_ids_txt() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("IDS.txt"), "好\t⿰女子\n").unwrap();
+
+    let dict = IdsDict::from_dicts(dir.path()).unwrap();
+    let key: Vec<char> = "好".chars().collect();
+    assert_eq!(dict.table.map.get(key.as_slice()).map(|v| &**v), Some("⿰女子"));
+}
+
+#[test]
+fn ids_dict_from_dicts_errors_on_missing_tab_separator() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("IDS.txt"), "好 ⿰女子\n").unwrap();
+
+    let err = IdsDict::from_dicts(dir.path()).unwrap_err();
+    assert!(matches!(err, DictionaryError::LoadFileError { .. }));
+}
+
+#[test]
+fn default_ids_dict_is_empty() {
+    let dict = IdsDict::default();
+    let key: Vec<char> = "好".chars().collect();
+    assert_eq!(dict.table.map.get(key.as_slice()).map(|v| &**v), None);
+}
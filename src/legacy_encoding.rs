@@ -0,0 +1,95 @@
+//! Encoding-aware wrapper around [`OpenCC::convert`](crate::OpenCC::convert) for legacy
+//! Chinese text encodings, gated behind this crate's `legacy-encodings` feature.
+//!
+//! Real-world Simplified Chinese is still frequently stored as GB18030/GBK, and Traditional
+//! Chinese as Big5 — every other entry point in this crate assumes `&str`/UTF-8, so converting
+//! such a file means transcoding it first. This module wraps `encoding_rs` to do that decode as
+//! part of one call, via [`OpenCC::convert_from_encoding`](crate::OpenCC::convert_from_encoding).
+//!
+//! # Detection
+//! When the caller doesn't know a file's encoding up front,
+//! [`OpenCC::detect_encoding`](crate::OpenCC::detect_encoding) tries each of
+//! [`CANDIDATE_ENCODINGS`] in turn and checks [`OpenCC::zho_check`](crate::OpenCC::zho_check)
+//! on the decoded text: a GB-family decode (GB18030, GBK, HZ) that reads as Simplified, or a
+//! Big5 decode that reads as Traditional, is treated as a confident match and returned
+//! immediately. If no candidate's script matches its encoding family this way, the first
+//! candidate that decoded without substituting U+FFFD for any malformed byte is returned as a
+//! fallback — this is a heuristic, not a guarantee, for genuinely ambiguous or non-Chinese
+//! input.
+
+use encoding_rs::{Encoding, BIG5, GB18030, GBK, HZ};
+
+/// Result metadata returned alongside the converted or decoded text from
+/// [`OpenCC::convert_from_encoding`](crate::OpenCC::convert_from_encoding) and
+/// [`OpenCC::detect_encoding`](crate::OpenCC::detect_encoding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodingInfo {
+    /// The `encoding_rs` label actually used to decode the input — either `label` as passed
+    /// to [`OpenCC::convert_from_encoding`](crate::OpenCC::convert_from_encoding), or the
+    /// winning candidate's label from [`OpenCC::detect_encoding`](crate::OpenCC::detect_encoding).
+    pub label: &'static str,
+    /// `true` if the decoder substituted U+FFFD for at least one malformed byte sequence.
+    pub had_errors: bool,
+}
+
+/// Encodings [`OpenCC::detect_encoding`](crate::OpenCC::detect_encoding) tries when no label
+/// is given, in order: GB18030 (a superset of GBK, itself a superset of GB2312), GBK, Big5,
+/// then HZ (`HZ-GB-2312`) — the common legacy encodings for Simplified (GB-family) and
+/// Traditional (Big5) Chinese text, plus HZ's 7-bit mail-safe variant of GB2312.
+pub const CANDIDATE_ENCODINGS: [&Encoding; 4] = [GB18030, GBK, BIG5, HZ];
+
+#[test]
+fn convert_from_encoding_round_trips_gb18030() {
+    let opencc = crate::OpenCC::new();
+    let (gb18030_bytes, _, had_errors) = GB18030.encode("你好，世界！龙马精神！");
+    assert!(!had_errors);
+
+    let (converted, info) = opencc
+        .convert_from_encoding(&gb18030_bytes, "gb18030", "s2t", false)
+        .unwrap();
+    assert_eq!(converted, "你好，世界！龍馬精神！");
+    assert_eq!(info.label, "gb18030");
+    assert!(!info.had_errors);
+}
+
+#[test]
+fn convert_from_encoding_round_trips_big5() {
+    let opencc = crate::OpenCC::new();
+    let (big5_bytes, _, had_errors) = BIG5.encode("你好，世界！龍馬精神！");
+    assert!(!had_errors);
+
+    let (converted, info) = opencc
+        .convert_from_encoding(&big5_bytes, "big5", "t2s", false)
+        .unwrap();
+    assert_eq!(converted, "你好，世界！龙马精神！");
+    assert_eq!(info.label, "Big5");
+    assert!(!info.had_errors);
+}
+
+#[test]
+fn convert_from_encoding_rejects_unknown_label() {
+    let opencc = crate::OpenCC::new();
+    assert!(opencc
+        .convert_from_encoding(b"irrelevant", "not-a-real-encoding", "s2t", false)
+        .is_none());
+}
+
+#[test]
+fn detect_encoding_identifies_gb18030_simplified_text() {
+    let opencc = crate::OpenCC::new();
+    let (gb18030_bytes, _, _) = GB18030.encode("龙马精神");
+
+    let (decoded, info) = opencc.detect_encoding(&gb18030_bytes).unwrap();
+    assert_eq!(decoded, "龙马精神");
+    assert_eq!(info.label, "gb18030");
+}
+
+#[test]
+fn detect_encoding_identifies_big5_traditional_text() {
+    let opencc = crate::OpenCC::new();
+    let (big5_bytes, _, _) = BIG5.encode("龍馬精神");
+
+    let (decoded, info) = opencc.detect_encoding(&big5_bytes).unwrap();
+    assert_eq!(decoded, "龍馬精神");
+    assert_eq!(info.label, "Big5");
+}
@@ -22,25 +22,81 @@ use once_cell::sync::Lazy;
 use rayon::prelude::*;
 use regex::Regex;
 use rustc_hash::FxHashMap;
+use std::io::{self, Read, Write};
 use std::iter::Iterator;
-use std::sync::Mutex;
 
+/// Async Read/Write conversion surface, behind the `async` feature.
+#[cfg(feature = "async")]
+pub mod async_convert;
+/// Optional ICU4X break-aware work chunking for the Rayon parallel path.
+pub mod chunking;
 /// Delimiters helper for splitting and matching delimiters.
 pub mod delimiter_set;
 /// Bridge helper for conversion plan and core converter functions.
 mod dict_refs;
+/// Lossy UTF-8 decoding for converting raw, not-necessarily-valid-UTF-8 byte input.
+mod utf8_lossy;
+/// Ideographic Description Sequence (IDS) decomposition of CJK characters.
+pub mod ids;
+/// Legacy Chinese text encoding (GB18030/GBK/Big5/HZ) support, behind the `legacy-encodings` feature.
+#[cfg(feature = "legacy-encodings")]
+pub mod legacy_encoding;
 /// Dictionary utilities for managing multiple OpenCC lexicons.
 pub mod dictionary_lib;
+/// Optional Unicode normalization of dictionary keys and conversion input.
+pub mod normalize;
+/// Configurable punctuation-style rewriting (curly quotes ↔ corner brackets), run after script conversion.
+pub mod punctuation;
+/// Optional Pinyin/Jyutping romanization output, run alongside script conversion.
+pub mod romanization;
+/// Span/edit records describing what a conversion rewrote and where.
+pub mod spans;
+/// Lua-scripted conversion pipelines, behind the `scripting` feature.
+#[cfg(feature = "scripting")]
+pub mod scripting;
 
 use crate::delimiter_set::is_delimiter;
+#[cfg(feature = "async")]
+pub use crate::async_convert::{AsyncConverter, SyncConverter};
+pub use crate::chunking::ChunkStrategy;
 pub use crate::dict_refs::DictRefs;
+pub use crate::dictionary_lib::MatchEngine;
+pub use crate::ids::{IdsOperator, IdsTree};
+#[cfg(feature = "legacy-encodings")]
+pub use crate::legacy_encoding::EncodingInfo;
+pub use crate::normalize::NormForm;
+pub use crate::punctuation::PunctuationMapping;
+pub use crate::romanization::PinyinStyle;
+pub use crate::spans::{AlignmentSpan, Span};
+#[cfg(feature = "scripting")]
+pub use crate::scripting::{ScriptedPipeline, ScriptedRound};
+#[cfg(feature = "scripting")]
+use crate::scripting::ScriptedPipelines;
 use crate::dictionary_lib::dictionary_maxlength::UnionKey;
 use crate::dictionary_lib::StarterUnion;
+use crate::normalize::normalize;
 use dictionary_lib::dict_max_len::DictMaxLen;
 use dictionary_lib::DictionaryMaxlength;
-
-/// Thread-safe holder for the last error message (if any).
-static LAST_ERROR: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+use std::sync::Arc;
+use unicode_general_category::{get_general_category, GeneralCategory};
+
+/// Stand-in for [`scripting::ScriptedPipelines`] when the `scripting` feature is off, so
+/// [`OpenCC`]'s `scripted_pipelines` field stays unconditional across every constructor
+/// instead of needing a `#[cfg]` on each one's struct-literal line.
+#[cfg(not(feature = "scripting"))]
+#[derive(Default)]
+struct ScriptedPipelines;
+
+thread_local! {
+    /// Per-thread holder for the last error message (if any).
+    ///
+    /// Kept `thread_local` rather than a process-global mutex-guarded slot so that
+    /// concurrent FFI callers — each driving its own `OpenCC*` on its own OS
+    /// thread, as `opencc_set_parallel` already advertises supporting — can't
+    /// clobber each other's error between a `convert` call and the matching
+    /// `opencc_last_error` read.
+    static LAST_ERROR: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
 // const DELIMITERS: &'static str = " \t\n\r!\"#$%&'()*+,-./:;<=>?@[\\]^_{}|~＝、。﹁﹂—－（）《》〈〉？！…／＼︒︑︔︓︿﹀︹︺︙︐［﹇］﹈︕︖︰︳︴︽︾︵︶｛︷｝︸﹃﹄【︻】︼　～．，；：";
 /// Regular expression used to normalize or strip punctuation from input.
 static STRIP_REGEX: Lazy<Regex> =
@@ -56,6 +112,66 @@ pub struct OpenCC {
     dictionary: DictionaryMaxlength,
     /// Flag indicator for parallelism
     is_parallel: bool,
+    /// Instance-level override for [`delimiter_set::FULL_DELIMITER_SET`], installed via
+    /// [`set_delimiters`](Self::set_delimiters). `None` means segmentation consults the
+    /// global default.
+    delimiters: Option<delimiter_set::DelimiterSet>,
+    /// Instance-level Unicode normalization form applied to conversion input
+    /// ahead of segmentation, installed via
+    /// [`set_normalization`](Self::set_normalization). `None` (the default)
+    /// leaves input untouched, matching every release before this field
+    /// existed.
+    normalization: Option<normalize::NormForm>,
+    /// Instance-level dictionary-matching engine, installed via
+    /// [`set_match_engine`](Self::set_match_engine). Defaults to
+    /// [`MatchEngine::Fmm`], matching every release before this field
+    /// existed.
+    match_engine: MatchEngine,
+    /// Whether a trailing run of variation selectors / combining marks
+    /// (see [`is_attachable_mark`]) stays attached to the base character
+    /// that precedes it during emission, installed via
+    /// [`set_preserve_variation_sequences`](Self::set_preserve_variation_sequences).
+    /// Defaults to `true`; set `false` to restore the pre-existing behavior
+    /// of matching/emitting every `char` independently.
+    preserve_variation_sequences: bool,
+    /// Instance-level strategy for dividing each delimiter-bounded range
+    /// into the pieces handed to Rayon on the parallel path, installed via
+    /// [`set_chunk_strategy`](Self::set_chunk_strategy). Defaults to
+    /// [`ChunkStrategy::DelimiterOnly`], matching every release before this
+    /// field existed.
+    chunk_strategy: ChunkStrategy,
+    /// Minimum chars a chunk must keep on either side of an ICU4X
+    /// break-aware split, installed via
+    /// [`set_min_chunk_len`](Self::set_min_chunk_len). Only consulted when
+    /// `chunk_strategy` is [`ChunkStrategy::IcuBreakAware`]. Defaults to
+    /// `256`.
+    min_chunk_len: usize,
+    /// Pinyin/Jyutping syllable tables backing
+    /// [`to_pinyin`](Self::to_pinyin)/[`to_jyutping`](Self::to_jyutping),
+    /// loaded the same way `dictionary` is — see
+    /// [`romanization::RomanizationDict::from_dicts`]. Empty (not an error)
+    /// if the `dicts/` directory has no romanization files.
+    romanization: romanization::RomanizationDict,
+    /// Character-decomposition table backing [`decompose`](Self::decompose)/
+    /// [`decompose_string`](Self::decompose_string), loaded the same way
+    /// `dictionary` is — see [`ids::IdsDict::from_dicts`]. Empty (not an
+    /// error) if the `dicts/` directory has no `IDS.txt`.
+    ids: ids::IdsDict,
+    /// Maximum recursion depth [`decompose`](Self::decompose) expands a
+    /// character's components to, installed via
+    /// [`set_ids_max_depth`](Self::set_ids_max_depth). Defaults to `4`.
+    ids_max_depth: usize,
+    /// Punctuation-style rewrite table backing
+    /// [`convert_with_punctuation`](Self::convert_with_punctuation), installed via
+    /// [`set_punctuation_mapping`](Self::set_punctuation_mapping). `Arc`-wrapped so swapping it
+    /// (or cloning an `OpenCC`) never re-hashes the table. Defaults to
+    /// [`PunctuationMapping::default`]'s curly-quote ↔ corner-bracket pairs.
+    punctuation_mapping: Arc<punctuation::PunctuationMapping>,
+    /// Lua-registered pipelines installed via
+    /// [`load_pipeline_script`](Self::load_pipeline_script), behind the `scripting` feature —
+    /// a no-op unit type when the feature is off, so this field stays unconditional across
+    /// every constructor. `Arc`-wrapped so cloning an `OpenCC` shares registered pipelines.
+    scripted_pipelines: Arc<ScriptedPipelines>,
 }
 
 /// Iterates viable phrase lengths in **descending order** using a starter bitmask,
@@ -143,8 +259,9 @@ fn for_each_len_dec(mask: u64, cap_here: usize, mut f: impl FnMut(usize) -> bool
 /// This function uses fast lookups with per-starter metadata:
 ///
 /// - For **BMP characters** (`u <= 0xFFFF`):
-///   - If dense arrays are available (`first_char_max_len` and `first_len_mask64`
-///     both cover the full BMP range):
+///   - If dense arrays are available and cover `starter` (they may be
+///     watermark-bounded to the live starter range rather than the full BMP
+///     — see [`DictMaxLen::starter_base`]):
 ///     1. Checks the **length bitmask** (`first_len_mask64`) for the starter.
 ///        - If the bitmask is nonzero, only returns `true` if the corresponding
 ///          `bit` for the target `length` is set.
@@ -167,8 +284,9 @@ fn for_each_len_dec(mask: u64, cap_here: usize, mut f: impl FnMut(usize) -> bool
 ///
 /// # Safety
 /// - Uses unchecked indexing (`get_unchecked`) when dense arrays are available
-///   for maximum speed. Safe because arrays are guaranteed to have 0x10000 length
-///   when the dense path is active.
+///   for maximum speed. Safe because the offset is computed from `starter_base`
+///   and bounds-checked against the arrays' actual (possibly watermarked) length
+///   before the dense path is taken.
 ///
 /// # Examples
 /// ```ignore
@@ -183,12 +301,21 @@ fn starter_allows_dict(dict: &DictMaxLen, starter: char, length: usize, bit: usi
     let u = starter as u32;
 
     if u <= 0xFFFF {
-        let i = u as usize;
-        // If dense arrays are not populated (lazy), fall back to sparse `starter_cap`
-        let have_dense =
-            dict.first_char_max_len.len() == 0x10000 && dict.first_len_mask64.len() == 0x10000;
+        // Dense arrays may be watermark-bounded to the live starter range
+        // rather than spanning the full BMP (see `DictMaxLen::starter_base`),
+        // so resolve `u` relative to the base and bounds-check before
+        // indexing instead of assuming index `u` into a full 0x10000 table.
+        let dense_index = if !dict.first_len_mask64.is_empty()
+            && dict.first_len_mask64.len() == dict.first_char_max_len.len()
+        {
+            u.checked_sub(dict.starter_base)
+                .map(|off| off as usize)
+                .filter(|&i| i < dict.first_len_mask64.len())
+        } else {
+            None
+        };
 
-        if have_dense {
+        if let Some(i) = dense_index {
             // 1) Per-starter length bitmask: most selective → check first if nonzero
             let m = unsafe { *dict.first_len_mask64.get_unchecked(i) };
             if m != 0 {
@@ -213,6 +340,25 @@ fn starter_allows_dict(dict: &DictMaxLen, starter: char, length: usize, bit: usi
     }
 }
 
+/// `true` if `c` must stay attached to whatever base character precedes it: a
+/// Unicode variation selector (standard `U+FE00..=U+FE0F`, or an Ideographic
+/// Variation Sequence selector from the supplementary `U+E0100..=U+E01EF`
+/// block), a combining mark (general category `Mn`, Nonspacing_Mark, or
+/// `Mc`, Spacing_Mark), or the Zero Width Joiner (`U+200D`), which joins
+/// what precedes it to what follows rather than standing alone.
+///
+/// Dictionary keys are matched on the base run only — this only gates
+/// *emission*, so a base character followed by one of these never gets
+/// separated from it by a dictionary replacement or a passthrough emit.
+#[inline]
+fn is_attachable_mark(c: char) -> bool {
+    matches!(c, '\u{FE00}'..='\u{FE0F}' | '\u{E0100}'..='\u{E01EF}' | '\u{200D}')
+        || matches!(
+            get_general_category(c),
+            GeneralCategory::NonspacingMark | GeneralCategory::SpacingMark
+        )
+}
+
 impl OpenCC {
     /// Creates a new `OpenCC` instance using built-in dictionary constants.
     ///
@@ -246,6 +392,17 @@ impl OpenCC {
         OpenCC {
             dictionary,
             is_parallel,
+            delimiters: None,
+            normalization: None,
+            match_engine: MatchEngine::Fmm,
+            preserve_variation_sequences: true,
+            chunk_strategy: ChunkStrategy::DelimiterOnly,
+            min_chunk_len: 256,
+            romanization: Self::load_romanization(),
+            ids: Self::load_ids(),
+            ids_max_depth: 4,
+            punctuation_mapping: Arc::new(punctuation::PunctuationMapping::default()),
+            scripted_pipelines: Arc::new(ScriptedPipelines::default()),
         }
     }
 
@@ -277,6 +434,17 @@ impl OpenCC {
         OpenCC {
             dictionary,
             is_parallel,
+            delimiters: None,
+            normalization: None,
+            match_engine: MatchEngine::Fmm,
+            preserve_variation_sequences: true,
+            chunk_strategy: ChunkStrategy::DelimiterOnly,
+            min_chunk_len: 256,
+            romanization: Self::load_romanization(),
+            ids: Self::load_ids(),
+            ids_max_depth: 4,
+            punctuation_mapping: Arc::new(punctuation::PunctuationMapping::default()),
+            scripted_pipelines: Arc::new(ScriptedPipelines::default()),
         }
     }
 
@@ -316,6 +484,241 @@ impl OpenCC {
         OpenCC {
             dictionary,
             is_parallel,
+            delimiters: None,
+            normalization: None,
+            match_engine: MatchEngine::Fmm,
+            preserve_variation_sequences: true,
+            chunk_strategy: ChunkStrategy::DelimiterOnly,
+            min_chunk_len: 256,
+            romanization: Self::load_romanization(),
+            ids: Self::load_ids(),
+            ids_max_depth: 4,
+            punctuation_mapping: Arc::new(punctuation::PunctuationMapping::default()),
+            scripted_pipelines: Arc::new(ScriptedPipelines::default()),
+        }
+    }
+
+    /// Creates an `OpenCC` instance by memory-mapping a `bincode`-encoded dictionary file.
+    ///
+    /// This is a thin wrapper around [`DictionaryMaxlength::from_mmap`], useful for large
+    /// custom dictionary builds where reading the whole file into memory up front
+    /// (as [`from_cbor`](Self::from_cbor) does) is undesirable.
+    ///
+    /// # Arguments
+    /// * `filename` – Path to a `.bincode` file produced by `DictionaryMaxlength::to_bincode`.
+    ///
+    /// # Returns
+    /// A fully initialized `OpenCC` instance, or one with empty dictionaries if mapping
+    /// or deserialization fails.
+    ///
+    /// # Errors
+    /// If mapping or deserialization fails, the dictionary is defaulted and the error is
+    /// stored via `set_last_error()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// fn main() {
+    ///     let cc = OpenCC::from_mmap("./dicts.bincode");
+    ///     println!("{}", cc.convert("汉字", "s2t", false));
+    /// }
+    /// ```
+    pub fn from_mmap(filename: &str) -> Self {
+        let dictionary = DictionaryMaxlength::from_mmap(filename).unwrap_or_else(|err| {
+            Self::set_last_error(&format!("Failed to create dictionary: {}", err));
+            DictionaryMaxlength::default()
+        });
+        let is_parallel = true;
+
+        OpenCC {
+            dictionary,
+            is_parallel,
+            delimiters: None,
+            normalization: None,
+            match_engine: MatchEngine::Fmm,
+            preserve_variation_sequences: true,
+            chunk_strategy: ChunkStrategy::DelimiterOnly,
+            min_chunk_len: 256,
+            romanization: Self::load_romanization(),
+            ids: Self::load_ids(),
+            ids_max_depth: 4,
+            punctuation_mapping: Arc::new(punctuation::PunctuationMapping::default()),
+            scripted_pipelines: Arc::new(ScriptedPipelines::default()),
+        }
+    }
+
+    /// Creates an `OpenCC` instance by loading dictionaries from this crate's compact
+    /// packed dictionary format.
+    ///
+    /// This is a thin wrapper around [`DictionaryMaxlength::deserialize_from_packed`], the
+    /// ship-in-app alternative to [`from_cbor`](Self::from_cbor): the packed format stores
+    /// sorted, front-coded, varint-encoded keys plus the precomputed starter accelerators
+    /// (the dense BMP arrays only when they span the full BMP — see the `packed` module docs),
+    /// so loading it skips both CBOR's per-key overhead and the dense-table rebuild that
+    /// `from_cbor` pays after deserializing.
+    ///
+    /// # Arguments
+    /// * `filename` – Path to a file produced by `DictionaryMaxlength::serialize_to_packed`.
+    ///
+    /// # Returns
+    /// A fully initialized `OpenCC` instance, or one with empty dictionaries if deserialization fails.
+    ///
+    /// # Errors
+    /// If deserialization fails, the dictionary is defaulted and the error is stored
+    /// via `set_last_error()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// fn main() {
+    ///     let cc = OpenCC::from_packed("./dicts.packed");
+    ///     println!("{}", cc.convert("汉字", "s2t", false));
+    /// }
+    /// ```
+    pub fn from_packed(filename: &str) -> Self {
+        let dictionary =
+            DictionaryMaxlength::deserialize_from_packed(filename).unwrap_or_else(|err| {
+                Self::set_last_error(&format!("Failed to create dictionary: {}", err));
+                DictionaryMaxlength::default()
+            });
+        let is_parallel = true;
+
+        OpenCC {
+            dictionary,
+            is_parallel,
+            delimiters: None,
+            normalization: None,
+            match_engine: MatchEngine::Fmm,
+            preserve_variation_sequences: true,
+            chunk_strategy: ChunkStrategy::DelimiterOnly,
+            min_chunk_len: 256,
+            romanization: Self::load_romanization(),
+            ids: Self::load_ids(),
+            ids_max_depth: 4,
+            punctuation_mapping: Arc::new(punctuation::PunctuationMapping::default()),
+            scripted_pipelines: Arc::new(ScriptedPipelines::default()),
+        }
+    }
+
+    /// Creates an `OpenCC` instance by memory-mapping a Zstd-compressed dictionary file.
+    ///
+    /// This is a thin wrapper around [`DictionaryMaxlength::from_mmap_zstd`], useful for
+    /// the `dictionary_maxlength.zstd` artifact the dictionary generator produces by
+    /// default, where reading the whole compressed file into memory up front (as
+    /// [`from_dicts`](Self::from_dicts)'s loader or [`from_cbor`](Self::from_cbor) do)
+    /// is undesirable for cold-start latency.
+    ///
+    /// # Arguments
+    /// * `filename` – Path to a `.zstd` file produced by
+    ///   `DictionaryMaxlength::save_cbor_compressed`.
+    ///
+    /// # Returns
+    /// A fully initialized `OpenCC` instance, or one with empty dictionaries if mapping,
+    /// decompression, or deserialization fails.
+    ///
+    /// # Errors
+    /// If mapping, decompression, or deserialization fails, the dictionary is defaulted
+    /// and the error is stored via `set_last_error()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// fn main() {
+    ///     let cc = OpenCC::from_mmap_zstd("./dictionary_maxlength.zstd");
+    ///     println!("{}", cc.convert("汉字", "s2t", false));
+    /// }
+    /// ```
+    pub fn from_mmap_zstd(filename: &str) -> Self {
+        let dictionary = DictionaryMaxlength::from_mmap_zstd(filename).unwrap_or_else(|err| {
+            Self::set_last_error(&format!("Failed to create dictionary: {}", err));
+            DictionaryMaxlength::default()
+        });
+        let is_parallel = true;
+
+        OpenCC {
+            dictionary,
+            is_parallel,
+            delimiters: None,
+            normalization: None,
+            match_engine: MatchEngine::Fmm,
+            preserve_variation_sequences: true,
+            chunk_strategy: ChunkStrategy::DelimiterOnly,
+            min_chunk_len: 256,
+            romanization: Self::load_romanization(),
+            ids: Self::load_ids(),
+            ids_max_depth: 4,
+            punctuation_mapping: Arc::new(punctuation::PunctuationMapping::default()),
+            scripted_pipelines: Arc::new(ScriptedPipelines::default()),
+        }
+    }
+
+    /// Creates an `OpenCC` instance from only the conversion families this build embeds.
+    ///
+    /// A thin wrapper around [`DictionaryMaxlength::from_embedded_features`]; only
+    /// available when at least one `embed-st`/`embed-tw`/`embed-hk`/`embed-jp`/`embed-all`
+    /// cargo feature is enabled. Families that weren't embedded simply find no matches.
+    #[cfg(any(
+        feature = "embed-st",
+        feature = "embed-tw",
+        feature = "embed-hk",
+        feature = "embed-jp",
+        feature = "embed-all"
+    ))]
+    pub fn from_embedded_features() -> Self {
+        let dictionary = DictionaryMaxlength::from_embedded_features().unwrap_or_else(|err| {
+            Self::set_last_error(&format!("Failed to create dictionary: {}", err));
+            DictionaryMaxlength::default()
+        });
+        let is_parallel = true;
+
+        OpenCC {
+            dictionary,
+            is_parallel,
+            delimiters: None,
+            normalization: None,
+            match_engine: MatchEngine::Fmm,
+            preserve_variation_sequences: true,
+            chunk_strategy: ChunkStrategy::DelimiterOnly,
+            min_chunk_len: 256,
+            romanization: Self::load_romanization(),
+            ids: Self::load_ids(),
+            ids_max_depth: 4,
+            punctuation_mapping: Arc::new(punctuation::PunctuationMapping::default()),
+            scripted_pipelines: Arc::new(ScriptedPipelines::default()),
+        }
+    }
+
+    /// Creates an `OpenCC` instance from the embedded Zstd-compressed `bincode`
+    /// dictionary blob.
+    ///
+    /// A thin wrapper around [`DictionaryMaxlength::from_embedded_bincode`],
+    /// useful when `bincode`'s faster decode over CBOR (see that method's
+    /// docs) is worth shipping the extra `dicts/dictionary_maxlength.bincode.zst`
+    /// artifact alongside the default [`OpenCC::new`] path.
+    pub fn from_embedded_bincode() -> Self {
+        let dictionary = DictionaryMaxlength::from_embedded_bincode().unwrap_or_else(|err| {
+            Self::set_last_error(&format!("Failed to create dictionary: {}", err));
+            DictionaryMaxlength::default()
+        });
+        let is_parallel = true;
+
+        OpenCC {
+            dictionary,
+            is_parallel,
+            delimiters: None,
+            normalization: None,
+            match_engine: MatchEngine::Fmm,
+            preserve_variation_sequences: true,
+            chunk_strategy: ChunkStrategy::DelimiterOnly,
+            min_chunk_len: 256,
+            romanization: Self::load_romanization(),
+            ids: Self::load_ids(),
+            ids_max_depth: 4,
+            punctuation_mapping: Arc::new(punctuation::PunctuationMapping::default()),
+            scripted_pipelines: Arc::new(ScriptedPipelines::default()),
         }
     }
 
@@ -341,7 +744,7 @@ impl OpenCC {
         let mut start = 0;
 
         for (i, ch) in chars.iter().enumerate() {
-            if is_delimiter(*ch) {
+            if self.is_delimiter(*ch) {
                 if inclusive {
                     ranges.push(start..i + 1);
                 } else {
@@ -361,6 +764,25 @@ impl OpenCC {
         ranges
     }
 
+    /// Further divides each delimiter range in `ranges` per this instance's
+    /// [`ChunkStrategy`] (installed via [`set_chunk_strategy`](Self::set_chunk_strategy))
+    /// before it's handed to Rayon — see the [`chunking`] module docs. A
+    /// no-op under the default [`ChunkStrategy::DelimiterOnly`].
+    #[inline]
+    fn rebalance_ranges(
+        &self,
+        ranges: Vec<std::ops::Range<usize>>,
+        chars: &[char],
+    ) -> Vec<std::ops::Range<usize>> {
+        if self.chunk_strategy == ChunkStrategy::DelimiterOnly {
+            return ranges;
+        }
+        ranges
+            .into_iter()
+            .flat_map(|r| chunking::rebalance(r, chars, self.min_chunk_len, self.chunk_strategy))
+            .collect()
+    }
+
     /// Internal bridge that drives FMM conversion using a precomputed **starter union**.
     ///
     /// Splits `text` into delimiter‑aware segments, then converts each segment independently via
@@ -385,7 +807,13 @@ impl OpenCC {
     /// # Parallelism
     /// If `self.is_parallel` is `true`:
     /// - Input chars are collected using a parallel iterator.
-    /// - Each segment is converted in parallel (`into_par_iter()`).
+    /// - If [`set_chunk_strategy`](Self::set_chunk_strategy) installed
+    ///   [`ChunkStrategy::IcuBreakAware`], each delimiter range wider than
+    ///   [`set_min_chunk_len`](Self::set_min_chunk_len) is first split
+    ///   further at ICU4X sentence-break boundaries (see the [`chunking`]
+    ///   module docs) — this helps delimiter-sparse prose that would
+    ///   otherwise arrive as one giant range.
+    /// - Each resulting segment is converted in parallel (`into_par_iter()`).
     /// This can significantly improve throughput on large inputs with many segments.
     ///
     /// # Behavior
@@ -410,6 +838,10 @@ impl OpenCC {
     /// - If the set or contents of `dictionaries` changes, rebuild the union
     ///   (this routine is typically called by a higher‑level helper that does so).
     /// - Internal bridge used by higher‑level routines (e.g., [`DictRefs::apply_segment_replace`]).
+    /// - If [`set_match_engine`](Self::set_match_engine) installed
+    ///   `MatchEngine::Automaton`, `union`/`max_word_length` are ignored in
+    ///   favor of a cached [`dictionary_lib::Automaton`] built from the same
+    ///   `dictionaries`.
     ///
     #[inline]
     fn segment_replace_with_union(
@@ -419,15 +851,32 @@ impl OpenCC {
         max_word_length: usize,
         union: &StarterUnion,
     ) -> String {
-        let chars: Vec<char> = if self.is_parallel {
-            text.par_chars().collect()
-        } else {
-            text.chars().collect()
-        };
+        let chars: Vec<char> = self.collect_chars(text);
 
         let ranges = self.get_chars_range(&chars, false);
 
+        if self.match_engine == MatchEngine::Automaton {
+            let automaton = self.dictionary.automaton_for_dicts(dictionaries);
+            if self.is_parallel {
+                let ranges = self.rebalance_ranges(ranges, &chars);
+                return ranges
+                    .into_par_iter()
+                    .with_min_len(8)
+                    .map(|r| automaton.replace_leftmost_longest(&chars[r]))
+                    .reduce(String::new, |mut a, b| {
+                        a.push_str(&b);
+                        a
+                    });
+            }
+            let mut out = String::with_capacity(text.len());
+            for r in ranges {
+                out.push_str(&automaton.replace_leftmost_longest(&chars[r]));
+            }
+            return out;
+        }
+
         if self.is_parallel {
+            let ranges = self.rebalance_ranges(ranges, &chars);
             ranges
                 .into_par_iter()
                 .with_min_len(8)
@@ -526,7 +975,7 @@ impl OpenCC {
         }
 
         let text_length = text_chars.len();
-        if text_length == 1 && is_delimiter(text_chars[0]) {
+        if text_length == 1 && self.is_delimiter(text_chars[0]) {
             return text_chars[0].to_string();
         }
 
@@ -544,7 +993,7 @@ impl OpenCC {
             // Pull precomputed mask + cap
             let (mask, cap_u8) = if u0 <= 0xFFFF {
                 let idx = u0 as usize;
-                (union.bmp_mask[idx], union.bmp_cap[idx])
+                (union.bmp_mask(idx), union.bmp_cap(idx))
             } else {
                 (
                     *union.astral_mask.get(&c0).unwrap_or(&0),
@@ -555,6 +1004,13 @@ impl OpenCC {
             if mask == 0 || cap_u8 == 0 {
                 result.push(c0);
                 start_pos += 1;
+                while self.preserve_variation_sequences
+                    && start_pos < text_length
+                    && is_attachable_mark(text_chars[start_pos])
+                {
+                    result.push(text_chars[start_pos]);
+                    start_pos += 1;
+                }
                 continue;
             }
 
@@ -611,11 +1067,186 @@ impl OpenCC {
                 result.push(c0);
                 start_pos += 1;
             }
+
+            while self.preserve_variation_sequences
+                && start_pos < text_length
+                && is_attachable_mark(text_chars[start_pos])
+            {
+                result.push(text_chars[start_pos]);
+                start_pos += 1;
+            }
         }
 
         result
     }
 
+    /// Span-emitting counterpart of [`convert_by_union`](Self::convert_by_union): walks the
+    /// same longest-first, first-hit-wins FMM loop over a single delimiter-free segment, but
+    /// pushes a [`Span`] for every dictionary hit and every passthrough char instead of
+    /// building a `String`.
+    ///
+    /// `base` offsets every emitted span's `range` by the segment's start position within the
+    /// text the caller is scanning, so spans from every segment land in one consistent
+    /// coordinate space. `dict_index` is the position of the matching dictionary within
+    /// `dictionaries`, the same precedence order [`convert_by_union`](Self::convert_by_union) probes.
+    ///
+    /// Always uses the union-pruned FMM matcher regardless of
+    /// [`set_match_engine`](Self::set_match_engine) — [`MatchEngine::Automaton`] has no
+    /// per-dictionary attribution to report, so spans are only defined for the FMM path.
+    fn span_by_union(
+        &self,
+        text_chars: &[char],
+        dictionaries: &[&DictMaxLen],
+        max_word_length: usize,
+        union: &StarterUnion,
+        base: usize,
+        out: &mut Vec<Span>,
+    ) {
+        if text_chars.is_empty() {
+            return;
+        }
+
+        let text_length = text_chars.len();
+        if text_length == 1 && self.is_delimiter(text_chars[0]) {
+            out.push(Span {
+                range: base..base + 1,
+                source: text_chars[0].to_string(),
+                replacement: text_chars[0].to_string(),
+                dict_index: None,
+            });
+            return;
+        }
+
+        let is_multy_dicts = dictionaries.len() > 1;
+        let mut start_pos = 0;
+
+        while start_pos < text_length {
+            let c0 = text_chars[start_pos];
+            let u0 = c0 as u32;
+            let rem = text_length - start_pos;
+            let global_cap = max_word_length.min(rem);
+
+            let (mask, cap_u8) = if u0 <= 0xFFFF {
+                let idx = u0 as usize;
+                (union.bmp_mask(idx), union.bmp_cap(idx))
+            } else {
+                (
+                    *union.astral_mask.get(&c0).unwrap_or(&0),
+                    *union.astral_cap.get(&c0).unwrap_or(&0),
+                )
+            };
+
+            if mask == 0 || cap_u8 == 0 {
+                out.push(Span {
+                    range: base + start_pos..base + start_pos + 1,
+                    source: c0.to_string(),
+                    replacement: c0.to_string(),
+                    dict_index: None,
+                });
+                start_pos += 1;
+                self.attach_trailing_marks(text_chars, &mut start_pos, base, out);
+                continue;
+            }
+
+            let cap_here = global_cap.min(cap_u8 as usize);
+            let mut matched = false;
+
+            for_each_len_dec(mask, cap_here, |length| {
+                let cap_bit = if length >= 64 { 63 } else { length - 1 };
+
+                for (dict_index, &dict) in dictionaries.iter().enumerate() {
+                    if !dict.has_key_len(length) {
+                        continue;
+                    }
+                    if is_multy_dicts && !starter_allows_dict(dict, c0, length, cap_bit) {
+                        continue;
+                    }
+
+                    let slice = &text_chars[start_pos..start_pos + length];
+                    if let Some(val) = dict.map.get(slice) {
+                        out.push(Span {
+                            range: base + start_pos..base + start_pos + length,
+                            source: slice.iter().collect(),
+                            replacement: val.to_string(),
+                            dict_index: Some(dict_index),
+                        });
+                        start_pos += length;
+                        matched = true;
+                        return true;
+                    }
+                }
+
+                false
+            });
+
+            if !matched {
+                out.push(Span {
+                    range: base + start_pos..base + start_pos + 1,
+                    source: c0.to_string(),
+                    replacement: c0.to_string(),
+                    dict_index: None,
+                });
+                start_pos += 1;
+            }
+
+            self.attach_trailing_marks(text_chars, &mut start_pos, base, out);
+        }
+    }
+
+    /// Consumes a trailing run of [`is_attachable_mark`] characters starting at `*start_pos`
+    /// (when [`preserve_variation_sequences`](Self::set_preserve_variation_sequences) is
+    /// enabled), appending each one verbatim to the most recently pushed span in `out` rather
+    /// than starting a new passthrough span — keeping a variation selector or combining mark
+    /// attached to whatever base character or phrase match precedes it.
+    fn attach_trailing_marks(
+        &self,
+        text_chars: &[char],
+        start_pos: &mut usize,
+        base: usize,
+        out: &mut [Span],
+    ) {
+        if !self.preserve_variation_sequences {
+            return;
+        }
+        while *start_pos < text_chars.len() && is_attachable_mark(text_chars[*start_pos]) {
+            let c = text_chars[*start_pos];
+            if let Some(last) = out.last_mut() {
+                last.range = last.range.start..base + *start_pos + 1;
+                last.source.push(c);
+                last.replacement.push(c);
+            }
+            *start_pos += 1;
+        }
+    }
+
+    /// Span-emitting counterpart of
+    /// [`segment_replace_with_union`](Self::segment_replace_with_union): splits `text` into
+    /// delimiter-aware segments the same way, then runs [`span_by_union`](Self::span_by_union)
+    /// over each, offsetting every span by its segment's start so the returned `Vec<Span>`
+    /// covers `text` in original left-to-right order.
+    ///
+    /// Unlike `segment_replace_with_union`, this always runs serially — spans are a
+    /// diagnostics/tooling surface rather than the hot conversion path, so there's no need to
+    /// pay `rayon`'s reduce-and-concatenate overhead for an API that most callers will use on
+    /// one string at a time.
+    fn span_replace_with_union(
+        &self,
+        text: &str,
+        dictionaries: &[&DictMaxLen],
+        max_word_length: usize,
+        union: &StarterUnion,
+    ) -> Vec<Span> {
+        let chars: Vec<char> = self.collect_chars(text);
+        let ranges = self.get_chars_range(&chars, false);
+
+        let mut out = Vec::with_capacity(chars.len());
+        for r in ranges {
+            let base = r.start;
+            self.span_by_union(&chars[r], dictionaries, max_word_length, union, base, &mut out);
+        }
+        out
+    }
+
     /// Converts text using the given dictionaries with **greedy maximum-match**,
     /// without relying on a precomputed [`StarterUnion`].
     ///
@@ -633,6 +1264,10 @@ impl OpenCC {
     /// - Useful when:
     ///   - Only single-character dictionaries are applied (e.g. `st`, `ts`);
     ///   - You don’t want to build a [`StarterUnion`] upfront.
+    /// - Single-dictionary calls on a dictionary whose `fst` or `byte_fst` is
+    ///   built instead do one forward traversal of that
+    ///   dictionary's [`fst`](DictMaxLen::fst) (see [`DictMaxLen::lookup_longest`])
+    ///   rather than probing `map` once per candidate length.
     ///
     /// # Parameters
     /// - `text_chars`: Input text, pre-split into `char`s.
@@ -655,7 +1290,7 @@ impl OpenCC {
         }
 
         let text_length = text_chars.len();
-        if text_length == 1 && is_delimiter(text_chars[0]) {
+        if text_length == 1 && self.is_delimiter(text_chars[0]) {
             return text_chars[0].to_string();
         }
 
@@ -667,23 +1302,47 @@ impl OpenCC {
             let mut best_match_length = 0usize;
             let mut best_match: &str = "";
 
-            // greedy: try longest length first
-            for length in (1..=max_length).rev() {
-                let candidate = &text_chars[start_pos..start_pos + length];
-
-                for dictionary in dictionaries {
-                    if !dictionary.has_key_len(length) {
-                        continue;
-                    }
-                    if let Some(value) = dictionary.map.get(candidate) {
+            if let [only_dict] = dictionaries {
+                if only_dict.fst.is_some() || only_dict.byte_fst.is_some() {
+                    // Single-dictionary fast path: one forward traversal of the FST
+                    // (byte-level when `byte_fst` is present, char-level otherwise)
+                    // finds the longest match directly, replacing the descending-length
+                    // `map.get()` probe below (which only applies when there's more
+                    // than one dictionary, since ties there are broken by dict order).
+                    let candidate = &text_chars[start_pos..start_pos + max_length];
+                    if let Some((length, value)) = only_dict.lookup_longest(candidate) {
                         best_match_length = length;
                         best_match = value;
-                        break;
+                    }
+                } else {
+                    for length in (1..=max_length).rev() {
+                        let candidate = &text_chars[start_pos..start_pos + length];
+                        if let Some(value) = only_dict.map.get(candidate) {
+                            best_match_length = length;
+                            best_match = value;
+                            break;
+                        }
                     }
                 }
+            } else {
+                // greedy: try longest length first
+                for length in (1..=max_length).rev() {
+                    let candidate = &text_chars[start_pos..start_pos + length];
 
-                if best_match_length > 0 {
-                    break;
+                    for dictionary in dictionaries {
+                        if !dictionary.has_key_len(length) {
+                            continue;
+                        }
+                        if let Some(value) = dictionary.map.get(candidate) {
+                            best_match_length = length;
+                            best_match = value;
+                            break;
+                        }
+                    }
+
+                    if best_match_length > 0 {
+                        break;
+                    }
                 }
             }
 
@@ -691,11 +1350,26 @@ impl OpenCC {
                 // no dictionary hit: emit single char and move on
                 result.push(text_chars[start_pos]);
                 start_pos += 1;
+                while self.preserve_variation_sequences
+                    && start_pos < text_length
+                    && is_attachable_mark(text_chars[start_pos])
+                {
+                    result.push(text_chars[start_pos]);
+                    start_pos += 1;
+                }
                 continue;
             }
 
             result.push_str(best_match);
             start_pos += best_match_length;
+
+            while self.preserve_variation_sequences
+                && start_pos < text_length
+                && is_attachable_mark(text_chars[start_pos])
+            {
+                result.push(text_chars[start_pos]);
+                start_pos += 1;
+            }
         }
 
         result
@@ -740,14 +1414,331 @@ impl OpenCC {
         self.is_parallel = is_parallel;
     }
 
-    /// Converts Simplified Chinese text to Traditional Chinese.
-    ///
-    /// This function performs dictionary-based segment replacement using two levels of dictionaries:
-    /// - Phrase-level mappings (`st_phrases`)
-    /// - Character-level mappings (`st_characters`)
+    /// Tests whether `c` is a segmentation delimiter for this instance,
+    /// consulting a custom set installed by [`set_delimiters`](Self::set_delimiters)
+    /// ahead of the global [`delimiter_set::FULL_DELIMITER_SET`].
+    #[inline]
+    fn is_delimiter(&self, c: char) -> bool {
+        match &self.delimiters {
+            Some(set) => set.contains(c),
+            None => is_delimiter(c),
+        }
+    }
+
+    /// Installs a custom delimiter set built from every character in `chars`,
+    /// so segmentation stops breaking on characters the built-in
+    /// [`delimiter_set::FULL_DELIMITERS`] treats as delimiters, or starts
+    /// breaking on ones it doesn't (e.g. keeping `/` or `-` inside a product
+    /// code or URL intact).
     ///
-    /// If `punctuation` is enabled, an additional punctuation-level dictionary (`st_punctuations`)
-    /// is included in the conversion pipeline. The input is segmented based on configured delimiters,
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let mut cc = OpenCC::new();
+    /// cc.set_delimiters(" \t\n.,!?".chars());
+    /// ```
+    pub fn set_delimiters(&mut self, chars: impl IntoIterator<Item = char>) {
+        self.delimiters = Some(delimiter_set::DelimiterSetBuilder::from_chars(chars).build());
+    }
+
+    /// Installs a custom delimiter set built from every character across
+    /// `ranges` (each an inclusive `[lo, hi]` pair), as
+    /// [`set_delimiters`](Self::set_delimiters) but for whole Unicode blocks
+    /// instead of individually enumerated characters.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let mut cc = OpenCC::new();
+    /// // Treat all of CJK Symbols and Punctuation as segment breaks.
+    /// cc.set_delimiter_ranges(&[('\u{3000}', '\u{303F}')]);
+    /// ```
+    pub fn set_delimiter_ranges(&mut self, ranges: &[(char, char)]) {
+        self.delimiters = Some(
+            delimiter_set::DelimiterSetBuilder::from_ranges(ranges.iter().copied()).build(),
+        );
+    }
+
+    /// Removes any custom delimiter set installed by
+    /// [`set_delimiters`](Self::set_delimiters) or
+    /// [`set_delimiter_ranges`](Self::set_delimiter_ranges), reverting
+    /// segmentation to the global [`delimiter_set::FULL_DELIMITER_SET`].
+    pub fn reset_delimiters(&mut self) {
+        self.delimiters = None;
+    }
+
+    /// Installs a Unicode normalization form to apply to conversion input
+    /// ahead of segmentation, so mixed composed/decomposed text (precomposed
+    /// vs. combining-sequence Latin, CJK compatibility ideographs) still matches dictionary keys stored in a
+    /// different form. `None` (the default) leaves input untouched.
+    ///
+    /// This is independent of this crate's compile-time `nfc`/`nfd`/`nfkc`/
+    /// `nfkd` cargo features, which instead normalize dictionary *keys* to
+    /// match a single fixed form baked into the embedded dictionary blobs —
+    /// this setting normalizes the *input* at runtime instead, so it works
+    /// regardless of which (if any) of those features built this binary.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::{OpenCC, NormForm};
+    /// let mut cc = OpenCC::new();
+    /// cc.set_normalization(Some(NormForm::Nfc));
+    /// ```
+    pub fn set_normalization(&mut self, form: Option<normalize::NormForm>) {
+        self.normalization = form;
+    }
+
+    /// Installs the dictionary-matching engine used by
+    /// [`segment_replace_with_union`](Self::segment_replace_with_union).
+    /// `MatchEngine::Fmm` (the default) probes every viable length at every
+    /// position via [`convert_by_union`](Self::convert_by_union);
+    /// `MatchEngine::Automaton` instead scans each segment once through a
+    /// compiled [`dictionary_lib::Automaton`], built once per dictionary set
+    /// and cached internally the same way the union-FMM path caches its
+    /// `StarterUnion`, so it amortizes well across repeated conversions with
+    /// the same configuration — benchmark both for your workload.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::{OpenCC, MatchEngine};
+    /// let mut cc = OpenCC::new();
+    /// cc.set_match_engine(MatchEngine::Automaton);
+    /// ```
+    pub fn set_match_engine(&mut self, engine: MatchEngine) {
+        self.match_engine = engine;
+    }
+
+    /// Returns whether a trailing run of variation selectors / combining
+    /// marks is kept attached to its preceding base character during
+    /// emission (see [`is_attachable_mark`]). Defaults to `true`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let cc = OpenCC::new();
+    /// assert_eq!(cc.get_preserve_variation_sequences(), true);
+    /// ```
+    pub fn get_preserve_variation_sequences(&self) -> bool {
+        self.preserve_variation_sequences
+    }
+
+    /// Sets whether a trailing run of variation selectors (standard
+    /// `U+FE00..=U+FE0F` and Ideographic Variation Sequence selectors
+    /// `U+E0100..=U+E01EF`), combining marks (general category `Mn` or `Mc`),
+    /// or Zero Width Joiners (`U+200D`) stays attached to the base character
+    /// it follows (see [`is_attachable_mark`]).
+    ///
+    /// With this enabled (the default), after a dictionary match or a
+    /// no-match single-char emit, any immediately following variation
+    /// selectors / combining marks are consumed and appended verbatim to
+    /// whatever was just emitted — a selector that follows a multi-char
+    /// phrase match stays attached to the phrase's last emitted character,
+    /// and a selector is never looked up as (or split into) its own
+    /// dictionary key. Set this to `false` to restore this crate's
+    /// pre-existing behavior of matching/emitting every `char` independently,
+    /// which can separate a base ideograph from its selector.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let mut cc = OpenCC::new();
+    /// cc.set_preserve_variation_sequences(false);
+    /// assert!(!cc.get_preserve_variation_sequences());
+    /// ```
+    pub fn set_preserve_variation_sequences(&mut self, preserve: bool) {
+        self.preserve_variation_sequences = preserve;
+    }
+
+    /// Returns the strategy currently used to divide each delimiter-bounded
+    /// range into pieces for the Rayon parallel path. Defaults to
+    /// [`ChunkStrategy::DelimiterOnly`].
+    pub fn get_chunk_strategy(&self) -> ChunkStrategy {
+        self.chunk_strategy
+    }
+
+    /// Installs the strategy used to divide each delimiter-bounded range
+    /// into pieces for the Rayon parallel path (see the [`chunking`] module
+    /// docs). Only consulted when [`is_parallel`](Self::set_parallel) is
+    /// `true`; the serial path always converts each delimiter range whole.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::{OpenCC, ChunkStrategy};
+    /// let mut cc = OpenCC::new();
+    /// cc.set_chunk_strategy(ChunkStrategy::IcuBreakAware);
+    /// ```
+    pub fn set_chunk_strategy(&mut self, strategy: ChunkStrategy) {
+        self.chunk_strategy = strategy;
+    }
+
+    /// Returns the minimum chunk length (in chars) an ICU4X break-aware
+    /// split must leave on either side, per [`set_min_chunk_len`](Self::set_min_chunk_len).
+    /// Defaults to `256`.
+    pub fn get_min_chunk_len(&self) -> usize {
+        self.min_chunk_len
+    }
+
+    /// Sets the minimum chunk length (in chars) an ICU4X break-aware split
+    /// must leave on either side of the break — a delimiter range no wider
+    /// than this is left whole, and a candidate break point closer than this
+    /// to either end of the range is skipped. Only consulted when
+    /// [`ChunkStrategy::IcuBreakAware`] is installed via
+    /// [`set_chunk_strategy`](Self::set_chunk_strategy); has no effect under
+    /// [`ChunkStrategy::DelimiterOnly`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let mut cc = OpenCC::new();
+    /// cc.set_min_chunk_len(512);
+    /// ```
+    pub fn set_min_chunk_len(&mut self, min_chunk_len: usize) {
+        self.min_chunk_len = min_chunk_len;
+    }
+
+    /// Returns the maximum recursion depth [`decompose`](Self::decompose)
+    /// expands a character's components to. Defaults to `4`.
+    pub fn get_ids_max_depth(&self) -> usize {
+        self.ids_max_depth
+    }
+
+    /// Sets the maximum recursion depth [`decompose`](Self::decompose)
+    /// expands a character's components to before leaving the rest as
+    /// [`IdsTree::Leaf`]s. `0` disables expansion entirely — `decompose`
+    /// then only ever returns the character's own one-level table entry (or
+    /// `None`). Independent of cycle detection, which always applies
+    /// regardless of this setting.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let mut cc = OpenCC::new();
+    /// cc.set_ids_max_depth(8);
+    /// ```
+    pub fn set_ids_max_depth(&mut self, max_depth: usize) {
+        self.ids_max_depth = max_depth;
+    }
+
+    /// Returns the punctuation-style rewrite table
+    /// [`convert_with_punctuation`](Self::convert_with_punctuation) uses. Defaults to
+    /// [`PunctuationMapping::default`]'s curly-quote ↔ corner-bracket pairs.
+    pub fn get_punctuation_mapping(&self) -> Arc<PunctuationMapping> {
+        Arc::clone(&self.punctuation_mapping)
+    }
+
+    /// Installs the punctuation-style rewrite table
+    /// [`convert_with_punctuation`](Self::convert_with_punctuation) uses — override this with a
+    /// custom [`PunctuationMapping::from_pairs`] table when a target convention (e.g. Hong Kong
+    /// house style) differs from the default curly-quote ↔ corner-bracket pairs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::{OpenCC, PunctuationMapping};
+    /// let mut cc = OpenCC::new();
+    /// cc.set_punctuation_mapping(PunctuationMapping::from_pairs([('“', '「'), ('”', '」')]));
+    /// ```
+    pub fn set_punctuation_mapping(&mut self, mapping: PunctuationMapping) {
+        self.punctuation_mapping = Arc::new(mapping);
+    }
+
+    /// Loads a `.lua` pipeline-registration script from `path` and registers every pipeline it
+    /// builds, behind the `scripting` feature — see the [`scripting`] module docs for the
+    /// `opencc.*` API the script sees. Once registered, a pipeline's name becomes usable as an
+    /// ordinary `config` argument to [`convert`](Self::convert)/[`convert_spans`](Self::convert_spans),
+    /// exactly like a built-in config.
+    ///
+    /// # Errors
+    /// An [`mlua::Error`] if `path` can't be read, or the script fails to parse/run.
+    #[cfg(feature = "scripting")]
+    pub fn load_pipeline_script(&self, path: &str) -> mlua::Result<()> {
+        let source = std::fs::read_to_string(path).map_err(mlua::Error::external)?;
+        self.load_pipeline_script_str(&source)
+    }
+
+    /// Like [`load_pipeline_script`](Self::load_pipeline_script), but runs `source` directly
+    /// rather than reading it from a file — useful for scripts built or fetched at runtime.
+    #[cfg(feature = "scripting")]
+    pub fn load_pipeline_script_str(&self, source: &str) -> mlua::Result<()> {
+        scripting::run_pipeline_script(source, Arc::clone(&self.scripted_pipelines))
+    }
+
+    /// Runs `config` as a script-registered pipeline name, or `None` if no such pipeline is
+    /// registered — the fallback [`convert`](Self::convert) tries once none of the built-in
+    /// configs match. See the [`scripting`] module docs for exactly what each round does and
+    /// the `on_match`/`set_punctuation` caveats.
+    #[cfg(feature = "scripting")]
+    fn convert_scripted(&self, input: &str, config: &str) -> Option<String> {
+        let pipeline = self.scripted_pipelines.get(config)?;
+        let mut current = normalize(input).into_owned();
+
+        for round in &pipeline.rounds {
+            let dicts: Vec<&DictMaxLen> = round
+                .dict_keys
+                .iter()
+                .filter_map(|key| self.dictionary.dict_by_key(key))
+                .collect();
+            if dicts.is_empty() {
+                continue;
+            }
+            let max_len = dicts.iter().map(|d| d.max_len).max().unwrap_or(1);
+            let union = if round.use_union {
+                self.dictionary.union_for_dicts(&dicts)
+            } else {
+                Arc::new(StarterUnion::build(&dicts))
+            };
+
+            let mut out = String::with_capacity(current.len());
+            for span in self.span_replace_with_union(&current, &dicts, max_len, &union) {
+                if span.is_passthrough() {
+                    out.push_str(&span.replacement);
+                } else {
+                    out.push_str(&pipeline.apply_override(&span.source, &span.replacement));
+                }
+            }
+            current = out;
+        }
+
+        if pipeline.punctuation {
+            current = self
+                .punctuation_mapping
+                .rewrite(&current, pipeline.guesses_simplified_source());
+        }
+
+        Some(current)
+    }
+
+    /// Collects `text` into a `Vec<char>`, honoring [`is_parallel`](Self::set_parallel)
+    /// for the initial collection and, if [`set_normalization`](Self::set_normalization)
+    /// installed a form, re-normalizing the char stream through
+    /// [`normalize::normalize_chars`] afterward.
+    ///
+    /// Canonical reordering and (for NFC) recomposition are inherently
+    /// sequential, so normalization always runs as one extra sequential pass
+    /// over the already-collected chars rather than inside the parallel
+    /// collection itself; with no normalization form installed (the
+    /// default), this is exactly the pre-existing single collection pass.
+    #[inline]
+    fn collect_chars(&self, text: &str) -> Vec<char> {
+        let chars: Vec<char> = if self.is_parallel {
+            text.par_chars().collect()
+        } else {
+            text.chars().collect()
+        };
+
+        match self.normalization {
+            Some(form) => normalize::normalize_chars(form, chars.into_iter()).collect(),
+            None => chars,
+        }
+    }
+
+    /// Converts Simplified Chinese text to Traditional Chinese.
+    ///
+    /// This function performs dictionary-based segment replacement using two levels of dictionaries:
+    /// - Phrase-level mappings (`st_phrases`)
+    /// - Character-level mappings (`st_characters`)
+    ///
+    /// If `punctuation` is enabled, an additional punctuation-level dictionary (`st_punctuations`)
+    /// is included in the conversion pipeline. The input is segmented based on configured delimiters,
     /// and each non-delimiter segment is processed using longest-match rules.
     ///
     /// This function is parallelized when the `is_parallel` flag is set (default is `true`),
@@ -825,7 +1816,7 @@ impl OpenCC {
         let u2 = self.dictionary.union_for(UnionKey::TwVariantsOnly);
 
         DictRefs::new(&round_1, u1)
-            .with_round_2(&round_2, u2)
+            .with_round(&round_2, u2)
             .apply_segment_replace(input, |input, refs, max_len, union| {
                 self.segment_replace_with_union(input, refs, max_len, union)
             })
@@ -852,7 +1843,7 @@ impl OpenCC {
             ],
             u1,
         )
-        .with_round_2(&round_2, u2)
+        .with_round(&round_2, u2)
         .apply_segment_replace(input, |input, refs, max_len, union| {
             self.segment_replace_with_union(input, refs, max_len, union)
         })
@@ -880,8 +1871,8 @@ impl OpenCC {
 
         // Use the DictRefs struct to handle 3 rounds
         DictRefs::new(&round_1, u1)
-            .with_round_2(&round_2, u2)
-            .with_round_3(&round_3, u3)
+            .with_round(&round_2, u2)
+            .with_round(&round_3, u3)
             .apply_segment_replace(input, |input, refs, max_len, union| {
                 self.segment_replace_with_union(input, refs, max_len, union)
             })
@@ -906,7 +1897,7 @@ impl OpenCC {
             .union_for(UnionKey::T2S { punct: punctuation });
 
         DictRefs::new(&round_1, u1)
-            .with_round_2(&round_2, u2)
+            .with_round(&round_2, u2)
             .apply_segment_replace(input, |input, refs, max_len, union| {
                 self.segment_replace_with_union(input, refs, max_len, union)
             })
@@ -926,7 +1917,7 @@ impl OpenCC {
         let round_2 = [&self.dictionary.hk_variants];
         let u2 = self.dictionary.union_for(UnionKey::HkVariantsOnly);
         DictRefs::new(&round_1, u1)
-            .with_round_2(&round_2, u2)
+            .with_round(&round_2, u2)
             .apply_segment_replace(input, |input, refs, max_len, union| {
                 self.segment_replace_with_union(input, refs, max_len, union)
             })
@@ -949,7 +1940,7 @@ impl OpenCC {
             .dictionary
             .union_for(UnionKey::T2S { punct: punctuation });
         DictRefs::new(&round_1, u1)
-            .with_round_2(&round_2, u2)
+            .with_round(&round_2, u2)
             .apply_segment_replace(input, |input, refs, max_len, union| {
                 self.segment_replace_with_union(input, refs, max_len, union)
             })
@@ -976,7 +1967,7 @@ impl OpenCC {
         let round_2 = [&self.dictionary.tw_variants];
         let u2 = self.dictionary.union_for(UnionKey::TwVariantsOnly);
         let output = DictRefs::new(&round_1, u1)
-            .with_round_2(&round_2, u2)
+            .with_round(&round_2, u2)
             .apply_segment_replace(input, |input, refs, max_len, union| {
                 self.segment_replace_with_union(input, refs, max_len, union)
             });
@@ -1014,7 +2005,7 @@ impl OpenCC {
         let u2 = self.dictionary.union_for(UnionKey::TwPhrasesRevOnly);
 
         let output = DictRefs::new(&round_1, u1)
-            .with_round_2(&round_2, u2)
+            .with_round(&round_2, u2)
             .apply_segment_replace(input, |input, refs, max_len, union| {
                 self.segment_replace_with_union(input, refs, max_len, union)
             });
@@ -1112,6 +2103,13 @@ impl OpenCC {
     /// | `hk2t`     | Hong Kong → Traditional Chinese           | ❌                |
     /// | `jp2t`     | Japanese → Traditional Chinese            | ❌                |
     /// | `t2jp`     | Traditional Chinese → Japanese            | ❌                |
+    /// | `s2pinyin` | Simplified Chinese → Pinyin (tone marks)  | ❌                |
+    /// | `s2jyutping` | Simplified Chinese → Jyutping            | ❌                |
+    ///
+    /// The last two dispatch to [`to_pinyin`](Self::to_pinyin)/[`to_jyutping`](Self::to_jyutping)
+    /// rather than a script-conversion round; `punctuation` is accepted for a
+    /// uniform signature but has no effect on them. Call `to_pinyin` directly
+    /// for [`PinyinStyle::ToneNumbers`].
     ///
     /// # Arguments
     ///
@@ -1161,13 +2159,867 @@ impl OpenCC {
             "hk2t" => self.hk2t(input),
             "jp2t" => self.jp2t(input),
             "t2jp" => self.t2jp(input),
-            _ => {
-                Self::set_last_error(format!("Invalid config: {}", config).as_str());
-                format!("Invalid config: {}", config)
+            "s2pinyin" => self.to_pinyin(input, PinyinStyle::ToneMarks),
+            "s2jyutping" => self.to_jyutping(input),
+            other => {
+                #[cfg(feature = "scripting")]
+                if let Some(result) = self.convert_scripted(input, other) {
+                    return result;
+                }
+                Self::set_last_error(format!("Invalid config: {}", other).as_str());
+                format!("Invalid config: {}", other)
+            }
+        }
+    }
+
+    /// Converts `input` like [`convert`](Self::convert), but accepts raw bytes that aren't
+    /// guaranteed to be valid UTF-8 — a socket read, a file whose encoding wasn't checked — so
+    /// callers don't need to pre-validate or bail on invalid input themselves.
+    ///
+    /// Splits `input` into maximal valid UTF-8 runs via [`utf8_lossy::lossy_chunks`], converts
+    /// each run through [`convert`](Self::convert) independently, and replaces each invalid
+    /// byte with one U+FFFD — see the [`utf8_lossy`] module docs for exactly how invalid spans
+    /// are delimited and why this differs from `String::from_utf8_lossy`'s maximal-subpart
+    /// merging. Converting run-by-run rather than building one lossily-repaired `String` first
+    /// means U+FFFD never crosses a [`convert`](Self::convert) call, so a dictionary match can't
+    /// accidentally span a corrupted byte.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// let cc = OpenCC::new();
+    /// let mut input = "汉字".as_bytes().to_vec();
+    /// input.push(0xFF); // a byte that's never valid UTF-8
+    /// let output = cc.convert_bytes(&input, "s2t", false);
+    /// assert_eq!(output, format!("{}\u{FFFD}", cc.convert("汉字", "s2t", false)));
+    /// ```
+    pub fn convert_bytes(&self, input: &[u8], config: &str, punctuation: bool) -> String {
+        let mut output = String::with_capacity(input.len());
+        for chunk in utf8_lossy::lossy_chunks(input) {
+            match chunk {
+                utf8_lossy::LossyChunk::Valid(s) => {
+                    output.push_str(&self.convert(s, config, punctuation))
+                }
+                utf8_lossy::LossyChunk::Invalid => output.push('\u{FFFD}'),
+            }
+        }
+        output
+    }
+
+    /// [`zho_check`](Self::zho_check) counterpart of [`convert_bytes`](Self::convert_bytes):
+    /// decodes `input` the same lossy way, then checks the script of the resulting text.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// let cc = OpenCC::new();
+    /// assert_eq!(cc.zho_check_bytes("漢字".as_bytes()), 1);
+    /// ```
+    pub fn zho_check_bytes(&self, input: &[u8]) -> i32 {
+        let mut text = String::with_capacity(input.len());
+        for chunk in utf8_lossy::lossy_chunks(input) {
+            match chunk {
+                utf8_lossy::LossyChunk::Valid(s) => text.push_str(s),
+                utf8_lossy::LossyChunk::Invalid => text.push('\u{FFFD}'),
+            }
+        }
+        self.zho_check(&text)
+    }
+
+    /// Decodes `bytes` as `label` (an `encoding_rs` WHATWG label such as `"gb18030"`,
+    /// `"gbk"`, `"big5"`, or `"hz-gb-2312"`) and converts the result like
+    /// [`convert`](Self::convert) — for Simplified text stored as GB18030/GBK or
+    /// Traditional text stored as Big5, without a separate transcoding step.
+    ///
+    /// Returns `None` if `label` isn't a label `encoding_rs` recognizes. Malformed byte
+    /// sequences for the chosen encoding decode to U+FFFD, the same as
+    /// [`Encoding::decode`](encoding_rs::Encoding::decode) — check
+    /// [`EncodingInfo::had_errors`] if that distinction matters to the caller.
+    ///
+    /// Requires the `legacy-encodings` feature.
+    #[cfg(feature = "legacy-encodings")]
+    pub fn convert_from_encoding(
+        &self,
+        bytes: &[u8],
+        label: &str,
+        config: &str,
+        punctuation: bool,
+    ) -> Option<(String, EncodingInfo)> {
+        let encoding = encoding_rs::Encoding::for_label(label.as_bytes())?;
+        let (decoded, _, had_errors) = encoding.decode(bytes);
+        let converted = self.convert(&decoded, config, punctuation);
+        Some((
+            converted,
+            EncodingInfo {
+                label: encoding.name(),
+                had_errors,
+            },
+        ))
+    }
+
+    /// Guesses which legacy Chinese encoding `bytes` is stored in, without a caller-supplied
+    /// label — see the [`legacy_encoding`] module docs for the candidate list and scoring
+    /// heuristic. Returns the decoded UTF-8 text and which candidate won, or `None` if no
+    /// candidate decoded cleanly.
+    ///
+    /// Requires the `legacy-encodings` feature.
+    #[cfg(feature = "legacy-encodings")]
+    pub fn detect_encoding(&self, bytes: &[u8]) -> Option<(String, EncodingInfo)> {
+        let mut fallback: Option<(String, EncodingInfo)> = None;
+
+        for &encoding in legacy_encoding::CANDIDATE_ENCODINGS.iter() {
+            let (decoded, _, had_errors) = encoding.decode(bytes);
+            if had_errors {
+                continue;
+            }
+
+            let info = EncodingInfo {
+                label: encoding.name(),
+                had_errors,
+            };
+            let expects_simplified = encoding != encoding_rs::BIG5;
+            let script = self.zho_check(&decoded);
+            let confident = if expects_simplified {
+                script == 2
+            } else {
+                script == 1
+            };
+
+            if confident {
+                return Some((decoded.into_owned(), info));
+            }
+            if fallback.is_none() {
+                fallback = Some((decoded.into_owned(), info));
+            }
+        }
+
+        fallback
+    }
+
+    /// Returns the dictionary rounds `config` would run — the same `(dictionaries, union)`
+    /// pairs each `sX`/`tX` method above builds for itself — or `None` for an unrecognized
+    /// config. Shared by [`convert_spans`](Self::convert_spans), which needs the last round's
+    /// dictionaries and union to attribute spans, instead of only the folded `String` result
+    /// [`DictRefs::apply_segment_replace`] returns.
+    #[allow(clippy::type_complexity)]
+    fn config_rounds(
+        &self,
+        config: &str,
+        punctuation: bool,
+    ) -> Option<Vec<(Vec<&DictMaxLen>, Arc<StarterUnion>)>> {
+        let d = &self.dictionary;
+        Some(match config {
+            "s2t" => {
+                let mut r1 = vec![&d.st_phrases, &d.st_characters];
+                if punctuation {
+                    r1.push(&d.st_punctuations);
+                }
+                let u1 = d.union_for(UnionKey::S2T { punct: punctuation });
+                vec![(r1, u1)]
+            }
+            "t2s" => {
+                let mut r1 = vec![&d.ts_phrases, &d.ts_characters];
+                if punctuation {
+                    r1.push(&d.ts_punctuations);
+                }
+                let u1 = d.union_for(UnionKey::T2S { punct: punctuation });
+                vec![(r1, u1)]
+            }
+            "s2tw" => {
+                let mut r1 = vec![&d.st_phrases, &d.st_characters];
+                if punctuation {
+                    r1.push(&d.st_punctuations);
+                }
+                let u1 = d.union_for(UnionKey::S2T { punct: punctuation });
+                let r2 = vec![&d.tw_variants];
+                let u2 = d.union_for(UnionKey::TwVariantsOnly);
+                vec![(r1, u1), (r2, u2)]
+            }
+            "tw2s" => {
+                let r1 = vec![&d.tw_variants_rev_phrases, &d.tw_variants_rev];
+                let u1 = d.union_for(UnionKey::TwRevPair);
+                let mut r2 = vec![&d.ts_phrases, &d.ts_characters];
+                if punctuation {
+                    r2.push(&d.ts_punctuations);
+                }
+                let u2 = d.union_for(UnionKey::T2S { punct: punctuation });
+                vec![(r1, u1), (r2, u2)]
+            }
+            "s2twp" => {
+                let mut r1 = vec![&d.st_phrases, &d.st_characters];
+                if punctuation {
+                    r1.push(&d.st_punctuations);
+                }
+                let u1 = d.union_for(UnionKey::S2T { punct: punctuation });
+                let r2 = vec![&d.tw_phrases];
+                let u2 = d.union_for(UnionKey::TwPhrasesOnly);
+                let r3 = vec![&d.tw_variants];
+                let u3 = d.union_for(UnionKey::TwVariantsOnly);
+                vec![(r1, u1), (r2, u2), (r3, u3)]
+            }
+            "tw2sp" => {
+                let r1 = vec![
+                    &d.tw_phrases_rev,
+                    &d.tw_variants_rev_phrases,
+                    &d.tw_variants_rev,
+                ];
+                let u1 = d.union_for(UnionKey::Tw2SpR1TwRevTriple);
+                let mut r2 = vec![&d.ts_phrases, &d.ts_characters];
+                if punctuation {
+                    r2.push(&d.ts_punctuations);
+                }
+                let u2 = d.union_for(UnionKey::T2S { punct: punctuation });
+                vec![(r1, u1), (r2, u2)]
+            }
+            "s2hk" => {
+                let mut r1 = vec![&d.st_phrases, &d.st_characters];
+                if punctuation {
+                    r1.push(&d.st_punctuations);
+                }
+                let u1 = d.union_for(UnionKey::S2T { punct: punctuation });
+                let r2 = vec![&d.hk_variants];
+                let u2 = d.union_for(UnionKey::HkVariantsOnly);
+                vec![(r1, u1), (r2, u2)]
+            }
+            "hk2s" => {
+                let r1 = vec![&d.hk_variants_rev_phrases, &d.hk_variants_rev];
+                let u1 = d.union_for(UnionKey::HkRevPair);
+                let mut r2 = vec![&d.ts_phrases, &d.ts_characters];
+                if punctuation {
+                    r2.push(&d.ts_punctuations);
+                }
+                let u2 = d.union_for(UnionKey::T2S { punct: punctuation });
+                vec![(r1, u1), (r2, u2)]
+            }
+            "t2tw" => {
+                let r1 = vec![&d.tw_variants];
+                let u1 = d.union_for(UnionKey::TwVariantsOnly);
+                vec![(r1, u1)]
+            }
+            "t2twp" => {
+                let r1 = vec![&d.tw_phrases];
+                let u1 = d.union_for(UnionKey::TwPhrasesOnly);
+                let r2 = vec![&d.tw_variants];
+                let u2 = d.union_for(UnionKey::TwVariantsOnly);
+                vec![(r1, u1), (r2, u2)]
+            }
+            "tw2t" => {
+                let r1 = vec![&d.tw_variants_rev_phrases, &d.tw_variants_rev];
+                let u1 = d.union_for(UnionKey::TwRevPair);
+                vec![(r1, u1)]
+            }
+            "tw2tp" => {
+                let r1 = vec![&d.tw_variants_rev_phrases, &d.tw_variants_rev];
+                let u1 = d.union_for(UnionKey::TwRevPair);
+                let r2 = vec![&d.tw_phrases_rev];
+                let u2 = d.union_for(UnionKey::TwPhrasesRevOnly);
+                vec![(r1, u1), (r2, u2)]
+            }
+            "t2hk" => {
+                let r1 = vec![&d.hk_variants];
+                let u1 = d.union_for(UnionKey::HkVariantsOnly);
+                vec![(r1, u1)]
+            }
+            "hk2t" => {
+                let r1 = vec![&d.hk_variants_rev_phrases, &d.hk_variants_rev];
+                let u1 = d.union_for(UnionKey::HkRevPair);
+                vec![(r1, u1)]
+            }
+            "t2jp" => {
+                let r1 = vec![&d.jp_variants];
+                let u1 = d.union_for(UnionKey::JpVariantsOnly);
+                vec![(r1, u1)]
+            }
+            "jp2t" => {
+                let r1 = vec![&d.jps_phrases, &d.jps_characters, &d.jp_variants_rev];
+                let u1 = d.union_for(UnionKey::JpRevTriple);
+                vec![(r1, u1)]
+            }
+            _ => return None,
+        })
+    }
+
+    /// Converts `text` like [`convert`](Self::convert), but instead of a `String` returns an
+    /// iterator of [`Span`]s reporting exactly what was rewritten and where — each dictionary
+    /// hit's source char range, matched phrase, replacement, and which dictionary in the
+    /// round's precedence chain produced it, plus passthrough spans for unmatched chars and
+    /// preserved delimiters.
+    ///
+    /// Internally this is a thin adaptor over the same segmentation
+    /// [`segment_replace_with_union`](Self::segment_replace_with_union) uses
+    /// ([`get_chars_range`](Self::get_chars_range) for delimiter-aware splitting,
+    /// [`span_by_union`](Self::span_by_union) for the longest-first FMM loop itself) — it
+    /// currently collects every span into a `Vec` before returning its iterator, so it isn't
+    /// lazily streaming past that point, but callers see the same `impl Iterator<Item = Span>`
+    /// shape a future incremental version would expose. A thin wrapper —
+    /// `spans.map(|s| s.replacement).collect::<String>()` — reproduces
+    /// [`convert`](Self::convert)'s output for any single-round config.
+    ///
+    /// # Multi-round configs
+    /// `s2tw`, `s2twp`, `tw2s`, `tw2sp`, `s2hk`, `hk2s`, `t2twp`, and `tw2tp` run more than one
+    /// dictionary round;
+    /// every round before the last still runs as a plain string transform (the same way
+    /// [`DictRefs::apply_segment_replace`] folds rounds for [`convert`](Self::convert)), and
+    /// only the last round is span-attributed. For these configs, a span's `range`/`source`
+    /// describe offsets into that intermediate text, not the caller's original `text` — see
+    /// [`Span`]'s docs. Every other config is single-round, so spans describe the original
+    /// input directly.
+    ///
+    /// # Match engine
+    /// Always uses the union-pruned FMM matcher for the final round, regardless of
+    /// [`set_match_engine`](Self::set_match_engine): [`MatchEngine::Automaton`] has no
+    /// per-dictionary attribution to report spans with.
+    ///
+    /// # Errors
+    /// If `config` is unrecognized, records the same `"Invalid config: {config}"` message
+    /// [`convert`](Self::convert) does via `set_last_error()` and returns an empty iterator.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// let cc = OpenCC::new();
+    /// let spans: Vec<_> = cc.convert_spans("汉字", "s2t", false).collect();
+    /// let rebuilt: String = spans.iter().map(|s| s.replacement.as_str()).collect();
+    /// assert_eq!(rebuilt, cc.convert("汉字", "s2t", false));
+    /// ```
+    pub fn convert_spans(
+        &self,
+        text: &str,
+        config: &str,
+        punctuation: bool,
+    ) -> impl Iterator<Item = Span> {
+        let Some(rounds) = self.config_rounds(&config.to_lowercase(), punctuation) else {
+            Self::set_last_error(format!("Invalid config: {}", config).as_str());
+            return Vec::new().into_iter();
+        };
+
+        let mut current = normalize(text).into_owned();
+        let mut spans = Vec::new();
+        let last = rounds.len().saturating_sub(1);
+
+        for (i, (dicts, union)) in rounds.iter().enumerate() {
+            let max_len = dicts.iter().map(|d| d.max_len).max().unwrap_or(1);
+            if i == last {
+                spans = self.span_replace_with_union(&current, dicts, max_len, union);
+            } else {
+                current = self.segment_replace_with_union(&current, dicts, max_len, union);
+            }
+        }
+
+        spans.into_iter()
+    }
+
+    /// Converts `input` like [`convert`](Self::convert), but also returns a
+    /// [`Vec<AlignmentSpan>`](AlignmentSpan) mapping every matched phrase or passthrough
+    /// char's source `char` range to the `byte` range it produced in the returned `String` —
+    /// for tooling that needs to translate cursor positions or re-anchor inline markup across
+    /// the conversion.
+    ///
+    /// A thin wrapper over [`convert_spans`](Self::convert_spans): each [`Span`]'s
+    /// [`range`](Span::range) becomes an [`AlignmentSpan::src_char_range`] verbatim, and
+    /// [`dst_byte_range`](AlignmentSpan::dst_byte_range) is the byte span its
+    /// [`replacement`](Span::replacement) occupies as spans are concatenated in order — so the
+    /// same [`Span`] multi-round caveat applies here: for `s2tw`, `s2twp`, `tw2s`, `tw2sp`,
+    /// `s2hk`, `hk2s`, `t2twp`, and `tw2tp`, `src_char_range` describes offsets into the intermediate text the
+    /// last round saw, not the caller's original `input`.
+    ///
+    /// # Errors
+    /// If `config` is unrecognized, records the same `"Invalid config: {config}"` message
+    /// [`convert`](Self::convert) does via `set_last_error()` and returns `(String::new(), vec![])`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// let cc = OpenCC::new();
+    /// let (output, alignment) = cc.convert_with_alignment("汉字", "s2t", false);
+    /// assert_eq!(output, cc.convert("汉字", "s2t", false));
+    ///
+    /// // Concatenating every span's `dst_byte_range` slice, in order, reconstructs `output`
+    /// // exactly — confirming the ranges are contiguous, non-overlapping, and correctly placed.
+    /// let reassembled: String = alignment
+    ///     .iter()
+    ///     .map(|span| &output[span.dst_byte_range.clone()])
+    ///     .collect();
+    /// assert_eq!(reassembled, output);
+    /// ```
+    pub fn convert_with_alignment(
+        &self,
+        input: &str,
+        config: &str,
+        punctuation: bool,
+    ) -> (String, Vec<AlignmentSpan>) {
+        let mut output = String::with_capacity(input.len());
+        let mut alignment = Vec::new();
+
+        for span in self.convert_spans(input, config, punctuation) {
+            let start = output.len();
+            output.push_str(&span.replacement);
+            let end = output.len();
+            alignment.push(AlignmentSpan {
+                src_char_range: span.range,
+                dst_byte_range: start..end,
+            });
+        }
+
+        (output, alignment)
+    }
+
+    /// Romanizes `input` into space-separated Pinyin syllables, using
+    /// [`RomanizationDict::pinyin_tone_marks`](romanization::RomanizationDict::pinyin_tone_marks)
+    /// or [`pinyin_tone_numbers`](romanization::RomanizationDict::pinyin_tone_numbers)
+    /// per `style`. Reuses the same greedy-longest-match FMM machinery as
+    /// script conversion, so a multi-character phrase entry (polyphone
+    /// disambiguation) wins over a single-character default the same way a
+    /// phrase table beats a character table in [`convert`](Self::convert).
+    ///
+    /// A char with no dictionary entry (non-Chinese text, stray punctuation,
+    /// or simply a table that hasn't been loaded — see
+    /// [`RomanizationDict`](romanization::RomanizationDict)) passes through
+    /// as its own space-separated token.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::{OpenCC, PinyinStyle};
+    /// let cc = OpenCC::new();
+    /// let _ = cc.to_pinyin("你好", PinyinStyle::ToneMarks);
+    /// ```
+    pub fn to_pinyin(&self, input: &str, style: PinyinStyle) -> String {
+        let dict = match style {
+            PinyinStyle::ToneMarks => &self.romanization.pinyin_tone_marks,
+            PinyinStyle::ToneNumbers => &self.romanization.pinyin_tone_numbers,
+        };
+        self.romanize_with_dict(input, dict)
+    }
+
+    /// Romanizes `input` into space-separated Jyutping (Cantonese) syllables
+    /// via [`RomanizationDict::jyutping`](romanization::RomanizationDict::jyutping).
+    /// See [`to_pinyin`](Self::to_pinyin) for matching/passthrough behavior.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let cc = OpenCC::new();
+    /// let _ = cc.to_jyutping("你好");
+    /// ```
+    pub fn to_jyutping(&self, input: &str) -> String {
+        self.romanize_with_dict(input, &self.romanization.jyutping)
+    }
+
+    /// Shared driver for [`to_pinyin`](Self::to_pinyin)/[`to_jyutping`](Self::to_jyutping):
+    /// builds a one-off [`StarterUnion`] from `dict` (see the
+    /// [`romanization`] module docs for why this isn't cached like
+    /// `DictionaryMaxlength::union_for`'s script-conversion unions) and runs
+    /// [`romanize_by_union`](Self::romanize_by_union) over the whole input.
+    fn romanize_with_dict(&self, input: &str, dict: &DictMaxLen) -> String {
+        let chars = self.collect_chars(input);
+        let dictionaries: [&DictMaxLen; 1] = [dict];
+        let union = StarterUnion::build(&dictionaries);
+        let max_word_length = dict.max_len.max(1);
+        self.romanize_by_union(&chars, &dictionaries, max_word_length, &union)
+    }
+
+    /// Romanization counterpart of [`convert_by_union`](Self::convert_by_union):
+    /// walks the same longest-first, first-hit-wins FMM loop over `text_chars`,
+    /// but instead of concatenating replacement text directly, joins every
+    /// matched phrase's (already space-separated) romanization, or every
+    /// passthrough char verbatim, with a single space — so a multi-character
+    /// phrase match contributes one space-joined run of syllables while an
+    /// unmatched char becomes its own token.
+    ///
+    /// Unlike `convert_by_union`, delimiters aren't special-cased: this is
+    /// meant to run once over the whole input rather than per
+    /// delimiter-bounded segment, since the output is syllables rather than
+    /// positionally-aligned replacement text.
+    fn romanize_by_union(
+        &self,
+        text_chars: &[char],
+        dictionaries: &[&DictMaxLen],
+        max_word_length: usize,
+        union: &StarterUnion,
+    ) -> String {
+        if text_chars.is_empty() {
+            return String::new();
+        }
+
+        let text_length = text_chars.len();
+        let is_multy_dicts = dictionaries.len() > 1;
+        let mut result = String::with_capacity(text_length * 5);
+        let mut start_pos = 0;
+
+        while start_pos < text_length {
+            let c0 = text_chars[start_pos];
+            let u0 = c0 as u32;
+            let rem = text_length - start_pos;
+            let global_cap = max_word_length.min(rem);
+
+            let (mask, cap_u8) = if u0 <= 0xFFFF {
+                let idx = u0 as usize;
+                (union.bmp_mask(idx), union.bmp_cap(idx))
+            } else {
+                (
+                    *union.astral_mask.get(&c0).unwrap_or(&0),
+                    *union.astral_cap.get(&c0).unwrap_or(&0),
+                )
+            };
+
+            if mask == 0 || cap_u8 == 0 {
+                if !result.is_empty() {
+                    result.push(' ');
+                }
+                result.push(c0);
+                start_pos += 1;
+                continue;
             }
+
+            let cap_here = global_cap.min(cap_u8 as usize);
+            let mut matched = false;
+
+            let text_ptr = text_chars.as_ptr();
+
+            for_each_len_dec(mask, cap_here, |length| {
+                let cap_bit = if length >= 64 { 63 } else { length - 1 };
+                let mut data_ptr: *const char = std::ptr::null();
+                let mut data_len: usize = 0;
+
+                for &dict in dictionaries {
+                    if !dict.has_key_len(length) {
+                        continue;
+                    }
+                    if is_multy_dicts && !starter_allows_dict(dict, c0, length, cap_bit) {
+                        continue;
+                    }
+                    if data_ptr.is_null() {
+                        data_ptr = unsafe { text_ptr.add(start_pos) };
+                        data_len = length;
+                    }
+                    let slice: &[char] = unsafe { std::slice::from_raw_parts(data_ptr, data_len) };
+
+                    if let Some(val) = dict.map.get(slice) {
+                        if !result.is_empty() {
+                            result.push(' ');
+                        }
+                        result.push_str(val);
+                        start_pos += length;
+                        matched = true;
+                        return true;
+                    }
+                }
+
+                false
+            });
+
+            if !matched {
+                if !result.is_empty() {
+                    result.push(' ');
+                }
+                result.push(c0);
+                start_pos += 1;
+            }
+        }
+
+        result
+    }
+
+    /// Decomposes `ch` into an [`IdsTree`] of its visual sub-components,
+    /// using [`ids::IdsDict`] and recursively expanding each component's own
+    /// entry up to [`get_ids_max_depth`](Self::get_ids_max_depth) levels
+    /// deep. Returns `None` if `ch` has no table entry at all.
+    ///
+    /// A component that maps back to an ancestor already being expanded in
+    /// the current call (a cycle — e.g. a malformed or intentionally
+    /// self-referential table entry) stops recursion there and emits it as
+    /// an [`IdsTree::Leaf`], the same as hitting the depth limit.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let cc = OpenCC::new();
+    /// let _ = cc.decompose('好');
+    /// ```
+    pub fn decompose(&self, ch: char) -> Option<IdsTree> {
+        let raw = self.ids.table.map.get([ch].as_slice())?;
+        let shallow = ids::parse_ids_string(raw)?;
+        let mut ancestors = vec![ch];
+        Some(self.expand_ids_tree(shallow, self.ids_max_depth, &mut ancestors))
+    }
+
+    /// Recursively expands every [`IdsTree::Leaf`] in `tree` by looking up
+    /// its own table entry, up to `depth_remaining` levels, tracking
+    /// `ancestors` for cycle detection (see [`decompose`](Self::decompose)).
+    fn expand_ids_tree(
+        &self,
+        tree: IdsTree,
+        depth_remaining: usize,
+        ancestors: &mut Vec<char>,
+    ) -> IdsTree {
+        match tree {
+            IdsTree::Leaf(c) => {
+                if depth_remaining == 0 || ancestors.contains(&c) {
+                    return IdsTree::Leaf(c);
+                }
+                let Some(raw) = self.ids.table.map.get([c].as_slice()) else {
+                    return IdsTree::Leaf(c);
+                };
+                let Some(shallow) = ids::parse_ids_string(raw) else {
+                    return IdsTree::Leaf(c);
+                };
+                ancestors.push(c);
+                let expanded = self.expand_ids_tree(shallow, depth_remaining - 1, ancestors);
+                ancestors.pop();
+                expanded
+            }
+            IdsTree::Node {
+                operator,
+                components,
+            } => IdsTree::Node {
+                operator,
+                components: components
+                    .into_iter()
+                    .map(|c| self.expand_ids_tree(c, depth_remaining, ancestors))
+                    .collect(),
+            },
         }
     }
 
+    /// Flattens [`decompose`](Self::decompose) over every char in `input`
+    /// into one concatenated IDS string — a char with no table entry passes
+    /// through verbatim, exactly like an unmatched char in
+    /// [`convert`](Self::convert).
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    /// let cc = OpenCC::new();
+    /// let _ = cc.decompose_string("你好");
+    /// ```
+    pub fn decompose_string(&self, input: &str) -> String {
+        let mut out = String::with_capacity(input.len() * 2);
+        for ch in input.chars() {
+            match self.decompose(ch) {
+                Some(tree) => out.push_str(&tree.flatten()),
+                None => out.push(ch),
+            }
+        }
+        out
+    }
+
+    /// Converts `reader` to `writer` incrementally, in bounded chunks, instead
+    /// of materializing the whole input as a `Vec<char>` and the whole output
+    /// as a `String` the way [`convert`](Self::convert) does — so multi-gigabyte
+    /// files or endless pipes can be converted in constant memory.
+    ///
+    /// # Correctness
+    /// Segmentation never lets a matched phrase span a delimiter (see
+    /// [`is_delimiter`](Self::is_delimiter)), so a delimiter is always a safe
+    /// point to cut the stream and flush everything up to and including it
+    /// through [`convert`](Self::convert) — the same per-config `StarterUnion`
+    /// that call builds is cached (see `DictionaryMaxlength`'s union cache)
+    /// and simply reused, `Arc`-cloned, by every subsequent chunk.
+    ///
+    /// Internally this maintains two carry buffers: a byte buffer for UTF-8
+    /// sequences split across a read boundary, and a char buffer for text
+    /// read since the last delimiter. Each round tops the byte buffer up to a
+    /// fixed 64 KiB window, decodes its valid UTF-8 prefix, and looks
+    /// backward for the last delimiter in the char buffer; everything before
+    /// it is converted and written, and the unterminated remainder is kept
+    /// for the next round. At EOF the residual remainder is flushed
+    /// regardless of whether it ends in a delimiter.
+    ///
+    /// # Delimiter-sparse input
+    /// Classical Chinese and other punctuation-sparse prose can run
+    /// thousands of chars between delimiters, which would otherwise let the
+    /// char carry buffer grow to the size of the remaining input. For a
+    /// *single-round* config (`s2t`, `t2s`, `t2tw`, `tw2t`, `t2hk`,
+    /// `hk2t`, `t2jp`, `jp2t` — see [`Span`]'s docs for the
+    /// same single/multi-round split), once the carry buffer grows past
+    /// [`STREAM_FLUSH_THRESHOLD`] chars past the config's longest dictionary
+    /// entry with no delimiter in sight, everything except a trailing
+    /// `max_word_length - 1`-char tail is flushed early. That margin is
+    /// exactly how far a match starting in the flushed prefix could still
+    /// reach, so the flushed text converts identically to however it would
+    /// have converted as part of a longer buffer — a window is never
+    /// finalized shorter than `max_word_length` chars unless EOF is reached.
+    /// Multi-round configs (`s2tw`, `s2twp`, `tw2s`, `tw2sp`, `s2hk`,
+    /// `hk2s`, `t2twp`, `tw2tp`) don't get this early flush, since a safe cut for the first
+    /// round's matching isn't necessarily safe for the round reading its
+    /// output — they keep the delimiter-only behavior above, so a very long
+    /// delimiter-free span in those configs still grows the carry buffer
+    /// unbounded.
+    ///
+    /// Returns the total number of bytes written. Fails with
+    /// [`io::ErrorKind::InvalidData`] if the stream ends in the middle of a
+    /// UTF-8 sequence.
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// let converter = OpenCC::new();
+    /// let input = "汉字转换测试".as_bytes();
+    /// let mut output = Vec::new();
+    /// converter.convert_stream(input, &mut output, "s2t", false).unwrap();
+    /// assert_eq!(String::from_utf8(output).unwrap(), "漢字轉換測試");
+    /// ```
+    pub fn convert_stream<R: Read, W: Write>(
+        &self,
+        mut reader: R,
+        mut writer: W,
+        config: &str,
+        punctuation: bool,
+    ) -> io::Result<usize> {
+        const WINDOW: usize = 64 * 1024;
+
+        let single_round_max_len = self.single_round_max_word_length(config, punctuation);
+
+        let mut pending_bytes: Vec<u8> = Vec::with_capacity(WINDOW);
+        let mut carry_chars: Vec<char> = Vec::new();
+        let mut written = 0usize;
+        let mut read_buf = [0u8; WINDOW];
+
+        loop {
+            let mut eof = false;
+            while pending_bytes.len() < WINDOW {
+                let n = reader.read(&mut read_buf)?;
+                if n == 0 {
+                    eof = true;
+                    break;
+                }
+                pending_bytes.extend_from_slice(&read_buf[..n]);
+            }
+
+            match std::str::from_utf8(&pending_bytes) {
+                Ok(valid) => {
+                    carry_chars.extend(valid.chars());
+                    pending_bytes.clear();
+                }
+                Err(e) => {
+                    let valid_up_to = e.valid_up_to();
+                    let valid = std::str::from_utf8(&pending_bytes[..valid_up_to]).unwrap();
+                    carry_chars.extend(valid.chars());
+                    pending_bytes.drain(..valid_up_to);
+                }
+            }
+
+            if eof {
+                if !pending_bytes.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "stream ended with a truncated UTF-8 sequence",
+                    ));
+                }
+                if !carry_chars.is_empty() {
+                    let segment: String = carry_chars.drain(..).collect();
+                    let converted = self.convert(&segment, config, punctuation);
+                    writer.write_all(converted.as_bytes())?;
+                    written += converted.len();
+                }
+                writer.flush()?;
+                return Ok(written);
+            }
+
+            if let Some(cut) = carry_chars.iter().rposition(|&c| self.is_delimiter(c)) {
+                let segment: String = carry_chars.drain(..=cut).collect();
+                let converted = self.convert(&segment, config, punctuation);
+                writer.write_all(converted.as_bytes())?;
+                written += converted.len();
+            } else if let Some(max_len) = single_round_max_len {
+                if carry_chars.len() > Self::STREAM_FLUSH_THRESHOLD + max_len {
+                    let keep = max_len.saturating_sub(1);
+                    let cut = carry_chars.len() - keep;
+                    let segment: String = carry_chars.drain(..cut).collect();
+                    let converted = self.convert(&segment, config, punctuation);
+                    writer.write_all(converted.as_bytes())?;
+                    written += converted.len();
+                }
+            }
+        }
+    }
+
+    /// Alias for [`convert_stream`](Self::convert_stream) under the name
+    /// callers familiar with other `Read`/`Write` conversion APIs (which
+    /// tend to call the source side a "reader" rather than a "stream")
+    /// expect, discarding the byte count for callers who only care that the
+    /// write succeeded.
+    pub fn convert_reader<R: Read, W: Write>(
+        &self,
+        reader: R,
+        writer: W,
+        config: &str,
+        punctuation: bool,
+    ) -> io::Result<()> {
+        self.convert_stream(reader, writer, config, punctuation)?;
+        Ok(())
+    }
+
+    /// How far past a single-round config's longest dictionary entry
+    /// [`convert_stream`](Self::convert_stream) lets its char carry buffer
+    /// grow, with no delimiter in sight, before flushing early. Large enough
+    /// that ordinary prose essentially never triggers an early flush; small
+    /// enough that a delimiter-free stream still converts in bounded memory.
+    const STREAM_FLUSH_THRESHOLD: usize = 64 * 1024;
+
+    /// The longest dictionary entry [`convert_stream`](Self::convert_stream)
+    /// needs to look ahead for `config`, or `None` if `config` resolves to
+    /// more than one [`config_rounds`](Self::config_rounds) round (or to no
+    /// round at all) — see `convert_stream`'s "Delimiter-sparse input" docs
+    /// for why only a single-round config gets the early-flush optimization.
+    fn single_round_max_word_length(&self, config: &str, punctuation: bool) -> Option<usize> {
+        let rounds = self.config_rounds(&config.to_lowercase(), punctuation)?;
+        let [(dicts, _)] = rounds.as_slice() else {
+            return None;
+        };
+        Some(dicts.iter().map(|d| d.max_len).max().unwrap_or(1).max(1))
+    }
+
+    /// Converts `input` like [`convert`](Self::convert), but writes its output to `out`
+    /// incrementally in [`Utf8Chunks`] of at most `chunk_bytes` bytes, so a multi-megabyte
+    /// in-memory document converts in bounded peak memory rather than materializing the whole
+    /// output `String` at once.
+    ///
+    /// Unlike [`convert_stream`](Self::convert_stream), which reads from any `R: Read` and cuts
+    /// only at delimiter boundaries so cross-chunk context never affects a match,
+    /// `convert_streaming` takes an already-in-memory `&str` and slices it purely on byte-size
+    /// grounds — each chunk is converted **independently** via [`convert`](Self::convert), and
+    /// [`Utf8Chunks`] only guarantees a multibyte `char` is never split across a chunk seam, not
+    /// that a dictionary phrase isn't. Pick a `chunk_bytes` generous enough that this rarely
+    /// matters (a few KiB comfortably exceeds any dictionary entry), or prefer
+    /// [`convert_stream`](Self::convert_stream) when byte-for-byte parity with
+    /// [`convert`](Self::convert) on arbitrary input is required.
+    ///
+    /// # Errors
+    /// Propagates any [`io::Error`] writing to `out` produces.
+    ///
+    /// # Panics
+    /// Panics if `chunk_bytes == 0` (see [`Utf8Chunks::new`]).
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// let cc = OpenCC::new();
+    /// let mut out = Vec::new();
+    /// cc.convert_streaming("汉字转换测试", "s2t", false, &mut out, 4).unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap(), cc.convert("汉字转换测试", "s2t", false));
+    /// ```
+    pub fn convert_streaming<W: Write>(
+        &self,
+        input: &str,
+        config: &str,
+        punctuation: bool,
+        out: &mut W,
+        chunk_bytes: usize,
+    ) -> io::Result<()> {
+        for chunk in Utf8Chunks::new(input, chunk_bytes) {
+            let converted = self.convert(chunk, config, punctuation);
+            out.write_all(converted.as_bytes())?;
+        }
+        Ok(())
+    }
+
     /// Internal: Applies a fast character-level Simplified-to-Traditional conversion.
     ///
     /// This method performs a low-overhead transformation using only the `st_characters`
@@ -1187,11 +3039,7 @@ impl OpenCC {
     /// This bypasses phrase-level and punctuation dictionaries for performance.
     fn st(&self, input: &str) -> String {
         let dict_refs = [&self.dictionary.st_characters];
-        let chars: Vec<char> = if self.is_parallel {
-            input.par_chars().collect()
-        } else {
-            input.chars().collect()
-        };
+        let chars = self.collect_chars(input);
         self.convert_by(&chars, &dict_refs, 1)
     }
 
@@ -1212,11 +3060,7 @@ impl OpenCC {
     /// This is a minimal-pass check — punctuation and phrases are not processed.
     fn ts(&self, input: &str) -> String {
         let dict_refs = [&self.dictionary.ts_characters];
-        let chars: Vec<char> = if self.is_parallel {
-            input.par_chars().collect()
-        } else {
-            input.chars().collect()
-        };
+        let chars = self.collect_chars(input);
         self.convert_by(&chars, &dict_refs, 1)
     }
 
@@ -1263,38 +3107,58 @@ impl OpenCC {
         }
     }
 
-    #[allow(dead_code)]
-    fn convert_punctuation(text: &str, config: &str) -> String {
-        let mut s2t_punctuation_chars: FxHashMap<&str, &str> = FxHashMap::default();
-        s2t_punctuation_chars.insert("“", "「");
-        s2t_punctuation_chars.insert("”", "」");
-        s2t_punctuation_chars.insert("‘", "『");
-        s2t_punctuation_chars.insert("’", "』");
-
-        let t2s_punctuation_chars: FxHashMap<&str, &str> = s2t_punctuation_chars
-            .iter()
-            .map(|(&k, &v)| (v, k))
-            .collect();
-
-        let mapping = if config.starts_with('s') {
-            &s2t_punctuation_chars
-        } else {
-            &t2s_punctuation_chars
-        };
-
-        let pattern = mapping
-            .keys()
-            .map(|k| regex::escape(k))
-            .collect::<Vec<_>>()
-            .join("|");
+    /// Converts `text` like [`convert`](Self::convert) (with dictionary-based punctuation
+    /// disabled — see below), then rewrites quote style through
+    /// [`get_punctuation_mapping`](Self::get_punctuation_mapping): curly quotes to corner
+    /// brackets for a `config` starting with `s` (Simplified-to-X), or the reverse for every
+    /// other `config`.
+    ///
+    /// This is a distinct localization step from `convert`'s own `punctuation: bool` flag,
+    /// which instead runs the `st_punctuations`/`ts_punctuations` dictionaries as part of
+    /// segmentation — this method always passes `punctuation: false` to the underlying
+    /// `convert` call so the two mechanisms can't both rewrite the same marks. Install a
+    /// custom [`PunctuationMapping`] via
+    /// [`set_punctuation_mapping`](Self::set_punctuation_mapping) for a convention other than
+    /// the default curly-quote ↔ corner-bracket pairs (e.g. Hong Kong vs. Taiwan house style).
+    ///
+    /// # Example
+    /// ```rust
+    /// use opencc_fmmseg::OpenCC;
+    ///
+    /// let cc = OpenCC::new();
+    /// assert_eq!(cc.convert_with_punctuation("“汉字”", "s2t"), "「漢字」");
+    /// ```
+    pub fn convert_with_punctuation(&self, text: &str, config: &str) -> String {
+        let converted = self.convert(text, config, false);
+        let to_traditional = config.to_lowercase().starts_with('s');
+        self.punctuation_mapping.rewrite(&converted, to_traditional)
+    }
 
-        let regex = Regex::new(&pattern).unwrap();
+    /// Loads [`romanization::RomanizationDict`] from the same `"dicts"` base
+    /// directory [`from_dicts`](Self::from_dicts) reads its script-conversion
+    /// tables from. Romanization files are optional: a missing or unreadable
+    /// file falls back to an empty table and records the failure via
+    /// [`set_last_error`](Self::set_last_error) instead of failing
+    /// construction — every `OpenCC` constructor calls this, including the
+    /// embedded/CBOR/mmap ones that never touch `dicts/` for anything else.
+    fn load_romanization() -> romanization::RomanizationDict {
+        romanization::RomanizationDict::from_dicts("dicts").unwrap_or_else(|err| {
+            Self::set_last_error(&format!("Failed to load romanization dictionaries: {}", err));
+            romanization::RomanizationDict::default()
+        })
+    }
 
-        regex
-            .replace_all(text, |caps: &regex::Captures| {
-                mapping[caps.get(0).unwrap().as_str()]
-            })
-            .into_owned()
+    /// Loads [`ids::IdsDict`] from the same `"dicts"` base directory
+    /// [`from_dicts`](Self::from_dicts) reads its script-conversion tables
+    /// from. A missing or unreadable `IDS.txt` falls back to an empty table
+    /// and records the failure via [`set_last_error`](Self::set_last_error)
+    /// instead of failing construction, the same as
+    /// [`load_romanization`](Self::load_romanization).
+    fn load_ids() -> ids::IdsDict {
+        ids::IdsDict::from_dicts("dicts").unwrap_or_else(|err| {
+            Self::set_last_error(&format!("Failed to load IDS decomposition table: {}", err));
+            ids::IdsDict::default()
+        })
     }
 
     /// Records an error message as the most recent OpenCC runtime error.
@@ -1312,14 +3176,18 @@ impl OpenCC {
     /// OpenCC::set_last_error("Failed to load dictionary.");
     /// ```
     pub fn set_last_error(err_msg: &str) {
-        let mut last_error = LAST_ERROR.lock().unwrap();
-        *last_error = Some(err_msg.to_string());
+        LAST_ERROR.with(|last_error| {
+            *last_error.borrow_mut() = Some(err_msg.to_string());
+        });
     }
 
-    /// Retrieves the most recently recorded error message, if any.
+    /// Retrieves the most recently recorded error message, if any, **on the
+    /// calling thread**.
     ///
     /// This can be used by consumers after calling `convert()` or dictionary loaders
     /// to inspect whether any non-fatal errors occurred (e.g., fallback to default dict).
+    /// Because the error store is thread-local, this only ever reflects errors this
+    /// same thread produced, even if other threads are converting concurrently.
     ///
     /// # Returns
     /// An `Option<String>` containing the error message, or `None` if no error was recorded.
@@ -1332,8 +3200,18 @@ impl OpenCC {
     /// }
     /// ```
     pub fn get_last_error() -> Option<String> {
-        let last_error = LAST_ERROR.lock().unwrap();
-        last_error.clone()
+        LAST_ERROR.with(|last_error| last_error.borrow().clone())
+    }
+
+    /// Clears this thread's recorded error message, if any.
+    ///
+    /// Used to discard a stale error after a subsequent call succeeds, so a
+    /// caller polling [`get_last_error()`](Self::get_last_error) doesn't see
+    /// an error that no longer applies.
+    pub fn clear_last_error() {
+        LAST_ERROR.with(|last_error| {
+            *last_error.borrow_mut() = None;
+        });
     }
 }
 
@@ -1364,7 +3242,7 @@ impl OpenCC {
 /// let substring = &input[..safe_index]; // No panic!
 /// println!("Safe prefix: {}", substring);
 /// ```
-pub fn find_max_utf8_length(sv: &str, max_byte_count: usize) -> usize {
+pub const fn find_max_utf8_length(sv: &str, max_byte_count: usize) -> usize {
     // 1. No longer than max byte count
     if sv.len() <= max_byte_count {
         return sv.len();
@@ -1376,3 +3254,92 @@ pub fn find_max_utf8_length(sv: &str, max_byte_count: usize) -> usize {
     }
     byte_count
 }
+
+/// Checks whether `index` is a valid UTF-8 character boundary within `bytes` — `true` at the
+/// very start or end of `bytes`, or at any byte that isn't a continuation byte
+/// (`0b10xxxxxx`); `false` otherwise, including when `index` is out of bounds.
+///
+/// This is the same boundary rule [`find_max_utf8_length`] backtracks against, exposed
+/// standalone for a caller with its own candidate byte offset (not one `find_max_utf8_length`
+/// already searched for) — e.g. checking a split point before slicing a raw buffer.
+///
+/// `const fn`, so both this and [`find_max_utf8_length`] compose with compile-time buffer
+/// sizing the same way `str::from_utf8`/`Utf8Error::valid_up_to` do.
+///
+/// # Example
+/// ```rust
+/// use opencc_fmmseg::is_utf8_boundary;
+///
+/// let bytes = "汉字".as_bytes();
+/// assert!(is_utf8_boundary(bytes, 0));
+/// assert!(is_utf8_boundary(bytes, 3)); // after the first 3-byte char
+/// assert!(!is_utf8_boundary(bytes, 1)); // mid-character
+/// ```
+pub const fn is_utf8_boundary(bytes: &[u8], index: usize) -> bool {
+    if index == 0 || index == bytes.len() {
+        return true;
+    }
+    if index > bytes.len() {
+        return false;
+    }
+    (bytes[index] & 0b1100_0000) != 0b1000_0000
+}
+
+/// Byte-bounded, boundary-safe `&str` chunk iterator built on [`find_max_utf8_length`], backing
+/// [`OpenCC::convert_streaming`].
+///
+/// Each [`next`](Iterator::next) call yields the longest prefix of the remaining text that's at
+/// most `chunk_bytes` bytes and ends on a valid UTF-8 character boundary — backtracking off
+/// continuation bytes (`0b10xxxxxx`) exactly as [`find_max_utf8_length`] does, so a multibyte
+/// character is never split across two yielded chunks.
+///
+/// If `chunk_bytes` is smaller than the remaining text's first `char`, that char can't possibly
+/// fit within the cap on its own; rather than get stuck (`find_max_utf8_length` would otherwise
+/// backtrack all the way to `0`), that one char is yielded as its own slightly-oversized chunk
+/// so the iterator always makes progress.
+///
+/// # Example
+/// ```rust
+/// use opencc_fmmseg::Utf8Chunks;
+///
+/// let chunks: Vec<&str> = Utf8Chunks::new("汉字转换测试", 4).collect();
+/// assert_eq!(chunks.concat(), "汉字转换测试");
+/// assert!(chunks.iter().all(|c| c.len() <= 4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Utf8Chunks<'a> {
+    remaining: &'a str,
+    chunk_bytes: usize,
+}
+
+impl<'a> Utf8Chunks<'a> {
+    /// Creates an iterator over `s` in chunks of at most `chunk_bytes` bytes each.
+    ///
+    /// # Panics
+    /// Panics if `chunk_bytes == 0` — no cap that small could ever hold even a single ASCII
+    /// char without the "always make progress" fallback quietly growing every chunk to 1 byte
+    /// anyway, so a zero cap almost certainly indicates a caller mistake.
+    pub fn new(s: &'a str, chunk_bytes: usize) -> Self {
+        assert!(chunk_bytes > 0, "chunk_bytes must be greater than zero");
+        Utf8Chunks {
+            remaining: s,
+            chunk_bytes,
+        }
+    }
+}
+
+impl<'a> Iterator for Utf8Chunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        let first_char_len = self.remaining.chars().next().map_or(1, |c| c.len_utf8());
+        let cut = find_max_utf8_length(self.remaining, self.chunk_bytes).max(first_char_len);
+        let (chunk, rest) = self.remaining.split_at(cut);
+        self.remaining = rest;
+        Some(chunk)
+    }
+}
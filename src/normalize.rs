@@ -0,0 +1,112 @@
+//! Optional Unicode normalization of dictionary keys and conversion input.
+//!
+//! Precomposed vs. decomposed (or compatibility) variants of the same CJK
+//! character or punctuation mark are, as far as Unicode is concerned, the
+//! same text — but a dictionary key only matches on exact `char` sequence,
+//! so a decomposed input silently fails to look up a precomposed dictionary
+//! entry (or vice versa). Enabling one of this crate's `nfc`/`nfd`/`nfkc`/
+//! `nfkd` cargo features normalizes every dictionary key (in
+//! [`DictMaxLen::build_from_pairs`](crate::dictionary_lib::DictMaxLen::build_from_pairs)
+//! and `DictionaryMaxlength::from_dicts`'s `load_dict`) and every conversion
+//! input (in [`DictRefs::apply_segment_replace`](crate::DictRefs::apply_segment_replace))
+//! through the same form, so both sides agree.
+//!
+//! At most one of these features should be enabled at a time. With none
+//! enabled, [`normalize`] is a no-op that borrows its input unchanged.
+//!
+//! # Embedded dictionaries
+//! The embedded Zstd/CBOR blobs bundled with this crate are built assuming a
+//! **fixed** normalization form (none, by default). If you enable a
+//! normalization feature, rebuild those blobs with the matching feature
+//! enabled (see `dict-generate`) — otherwise dictionary keys loaded from the
+//! blob and freshly normalized input text will disagree, degrading match
+//! rates instead of improving them.
+
+use std::borrow::Cow;
+
+use unicode_normalization::UnicodeNormalization;
+
+// Reject builds that select more than one normalization form: the embedded
+// Zstd/CBOR dictionary blobs are built assuming exactly one fixed form (or
+// none), so an ambiguous selection here would silently mismatch whichever
+// form those blobs were actually built with.
+#[cfg(any(
+    all(feature = "nfc", feature = "nfd"),
+    all(feature = "nfc", feature = "nfkc"),
+    all(feature = "nfc", feature = "nfkd"),
+    all(feature = "nfd", feature = "nfkc"),
+    all(feature = "nfd", feature = "nfkd"),
+    all(feature = "nfkc", feature = "nfkd"),
+))]
+compile_error!(
+    "at most one of the `nfc`, `nfd`, `nfkc`, `nfkd` features may be enabled at a time \
+     — the embedded dictionary blobs are built assuming a single, fixed normalization form"
+);
+
+/// A runtime-selectable Unicode normalization form for [`OpenCC::set_normalization`](crate::OpenCC::set_normalization),
+/// applied to conversion input ahead of segmentation — independent of (and
+/// composable with) the compile-time `nfc`/`nfd`/`nfkc`/`nfkd` features above,
+/// which instead normalize dictionary keys to match a fixed embedded-blob form.
+///
+/// Use this when feeding real-world text that mixes composed and decomposed
+/// forms (precomposed vs. combining-sequence Latin, CJK compatibility
+/// ideographs; fullwidth/halfwidth variants are compatibility mappings,
+/// which neither form folds) and you want every input to agree
+/// with however the dictionary keys happen to be stored, without rebuilding
+/// the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormForm {
+    /// Canonical decomposition followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition only.
+    Nfd,
+}
+
+/// Applies `form` to a `char` stream, as a lazy adaptor with no intermediate
+/// `String` allocation — so it composes directly with the `par_chars()`/
+/// `chars()` collection already used to build the `Vec<char>` segmentation
+/// operates on.
+///
+/// Every `char` a Rust `&str` yields is already a well-formed Unicode scalar
+/// value (that's what makes it a `char`), so unlike a byte-oriented decoder
+/// there's no ill-formed input here to map to U+FFFD — that concern belongs
+/// to whatever produced the `&str` in the first place (e.g. decoding raw
+/// bytes of an unknown encoding).
+pub fn normalize_chars<'a>(
+    form: NormForm,
+    chars: impl Iterator<Item = char> + 'a,
+) -> Box<dyn Iterator<Item = char> + 'a> {
+    match form {
+        NormForm::Nfc => Box::new(chars.nfc()),
+        NormForm::Nfd => Box::new(chars.nfd()),
+    }
+}
+
+/// Normalizes `s` through this crate's selected Unicode normalization form.
+///
+/// A no-op (returns `Cow::Borrowed(s)`) unless exactly one of the `nfc`,
+/// `nfd`, `nfkc`, or `nfkd` cargo features is enabled, in which case `s` is
+/// rewritten into that normalization form.
+#[inline]
+pub fn normalize(s: &str) -> Cow<'_, str> {
+    #[cfg(feature = "nfc")]
+    {
+        return Cow::Owned(s.nfc().collect());
+    }
+    #[cfg(feature = "nfd")]
+    {
+        return Cow::Owned(s.nfd().collect());
+    }
+    #[cfg(feature = "nfkc")]
+    {
+        return Cow::Owned(s.nfkc().collect());
+    }
+    #[cfg(feature = "nfkd")]
+    {
+        return Cow::Owned(s.nfkd().collect());
+    }
+    #[cfg(not(any(feature = "nfc", feature = "nfd", feature = "nfkc", feature = "nfkd")))]
+    {
+        Cow::Borrowed(s)
+    }
+}
@@ -0,0 +1,78 @@
+//! Configurable punctuation-style rewriting for
+//! [`OpenCC::convert_with_punctuation`](crate::OpenCC::convert_with_punctuation) — independent
+//! of the `punctuation` dictionary round `convert`'s own `punctuation: bool` flag enables
+//! (`st_punctuations`/`ts_punctuations`), which maps individual marks phrase-by-phrase during
+//! segmentation. This module instead rewrites **quote style** after conversion: Simplified text
+//! conventionally uses curly quotes (`“”`/`‘’`), while Traditional text conventionally uses
+//! corner brackets (`「」`/`『』`) — a distinct localization step most callers currently have
+//! to reimplement themselves.
+//!
+//! [`PunctuationMapping`] builds its lookup tables once, at construction, rather than
+//! recompiling a pattern on every call — so installing one via
+//! [`OpenCC::set_punctuation_mapping`](crate::OpenCC::set_punctuation_mapping) and reusing the
+//! same [`OpenCC`](crate::OpenCC) for many conversions pays that cost exactly once. A caller
+//! whose Traditional convention differs from the default (e.g. Hong Kong house style keeping
+//! curly quotes for some marks) can override it with a custom [`PunctuationMapping::from_pairs`]
+//! table instead.
+
+use rustc_hash::FxHashMap;
+
+/// A bidirectional table of `(simplified-style char, traditional-style char)` pairs, used by
+/// [`OpenCC::convert_with_punctuation`](crate::OpenCC::convert_with_punctuation) to rewrite
+/// punctuation style after script conversion.
+///
+/// Both lookup directions are built once at construction (see [`from_pairs`](Self::from_pairs)),
+/// so rewriting text is a plain per-`char` hash-map lookup with no per-call setup cost.
+#[derive(Debug, Clone)]
+pub struct PunctuationMapping {
+    /// Simplified-style char → Traditional-style char (the s2t direction).
+    to_traditional: FxHashMap<char, char>,
+    /// Traditional-style char → Simplified-style char (the t2s direction).
+    to_simplified: FxHashMap<char, char>,
+}
+
+impl PunctuationMapping {
+    /// Builds a mapping from `(simplified-style, traditional-style)` char pairs — the reverse
+    /// (t2s) direction is derived automatically, so callers only ever list each pair once.
+    ///
+    /// A later pair overwrites an earlier one that shares the same first or second element,
+    /// the same "last one wins" rule `FxHashMap::insert` already follows.
+    pub fn from_pairs(pairs: impl IntoIterator<Item = (char, char)>) -> Self {
+        let mut to_traditional = FxHashMap::default();
+        let mut to_simplified = FxHashMap::default();
+        for (simplified, traditional) in pairs {
+            to_traditional.insert(simplified, traditional);
+            to_simplified.insert(traditional, simplified);
+        }
+        PunctuationMapping {
+            to_traditional,
+            to_simplified,
+        }
+    }
+
+    /// Rewrites `text` one `char` at a time in the `to_traditional` direction if `true`,
+    /// `to_simplified` otherwise; a char absent from the table passes through unchanged.
+    pub(crate) fn rewrite(&self, text: &str, to_traditional: bool) -> String {
+        let map = if to_traditional {
+            &self.to_traditional
+        } else {
+            &self.to_simplified
+        };
+        text.chars()
+            .map(|c| *map.get(&c).unwrap_or(&c))
+            .collect()
+    }
+}
+
+impl Default for PunctuationMapping {
+    /// The four curly-quote ↔ corner-bracket pairs `convert_with_punctuation` has always
+    /// rewritten: `“”` ↔ `「」` and `‘’` ↔ `『』`.
+    fn default() -> Self {
+        PunctuationMapping::from_pairs([
+            ('\u{201C}', '「'), // “ -> 「
+            ('\u{201D}', '」'), // ” -> 」
+            ('\u{2018}', '『'), // ‘ -> 『
+            ('\u{2019}', '』'), // ’ -> 』
+        ])
+    }
+}
@@ -0,0 +1,196 @@
+//! Optional Pinyin / Jyutping romanization output, run alongside (not
+//! through) the char-to-char Simplified/Traditional conversion dictionaries.
+//!
+//! Unlike `st_characters`/`st_phrases`/etc., a romanization table maps a
+//! Chinese phrase to a *space-separated syllable string*, not another
+//! Chinese phrase — so it's kept out of
+//! [`DictionaryMaxlength`](crate::dictionary_lib::DictionaryMaxlength)'s
+//! CBOR/packed bundle (whose schema assumes every table's values are
+//! themselves convertible Chinese text) and loaded as its own, independent
+//! [`RomanizationDict`] instead. It reuses the exact same [`DictMaxLen`]
+//! max-word-length table and [`StarterUnion`] pruning as script conversion,
+//! so a multi-character phrase entry (polyphone disambiguation, e.g. `重`
+//! read as `chóng` in `重复` vs. `zhòng` in `重要`) still wins over a
+//! single-character default the same way `st_phrases` beats `st_characters`
+//! today.
+//!
+//! # Known limitation
+//! [`OpenCC::to_pinyin`](crate::OpenCC::to_pinyin) and
+//! [`OpenCC::to_jyutping`](crate::OpenCC::to_jyutping) build a fresh
+//! [`StarterUnion`] on every call rather than caching one the way
+//! `DictionaryMaxlength::union_for` does for script-conversion configs —
+//! fine for occasional use, but callers romanizing in a hot loop should
+//! batch their input into one call rather than calling per sentence.
+
+use crate::dictionary_lib::dict_max_len::DictMaxLen;
+use crate::dictionary_lib::DictionaryError;
+use std::path::Path;
+
+/// Pinyin rendering style for [`OpenCC::to_pinyin`](crate::OpenCC::to_pinyin).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PinyinStyle {
+    /// Diacritic tone marks, e.g. `nǐ hǎo`.
+    #[default]
+    ToneMarks,
+    /// Trailing tone digits `1`-`5` (`5` = neutral tone), e.g. `ni3 hao3`.
+    ToneNumbers,
+}
+
+/// Syllable tables backing [`OpenCC::to_pinyin`](crate::OpenCC::to_pinyin) /
+/// [`OpenCC::to_jyutping`](crate::OpenCC::to_jyutping): one [`DictMaxLen`]
+/// per Pinyin style plus one for Jyutping, each keyed like any other
+/// dictionary table (a phrase mapping to its romanization) so multi-character
+/// polyphone entries take precedence over single-character defaults via the
+/// same greedy-longest-match rule `st_phrases`/`st_characters` already use.
+///
+/// `Default` yields three empty (but valid, populated-as-empty) tables, so
+/// an [`OpenCC`](crate::OpenCC) whose `dicts/` directory has no romanization
+/// files still constructs successfully — `to_pinyin`/`to_jyutping` simply
+/// return every input char unmatched (passed through verbatim) until a real
+/// table is loaded.
+#[derive(Debug, Default)]
+pub struct RomanizationDict {
+    /// Tone-mark Pinyin syllables (e.g. `"你好" -> "nǐ hǎo"`).
+    pub pinyin_tone_marks: DictMaxLen,
+    /// Tone-number Pinyin syllables (e.g. `"你好" -> "ni3 hao3"`).
+    pub pinyin_tone_numbers: DictMaxLen,
+    /// Jyutping (Cantonese) syllables (e.g. `"你好" -> "nei5 hou2"`).
+    pub jyutping: DictMaxLen,
+}
+
+impl RomanizationDict {
+    /// Loads all three tables from tab-separated `.txt` lexicon files in
+    /// `base_dir`, using the same file format as
+    /// [`DictionaryMaxlength::from_dicts`](crate::dictionary_lib::DictionaryMaxlength::from_dicts):
+    /// `key\tvalue`, one entry per line, `#`-prefixed comments and blank
+    /// lines skipped, a leading BOM stripped from the first data line.
+    /// Unlike that loader, `value` is kept in full (spaces included) rather
+    /// than truncated to its first whitespace-separated token, since the
+    /// whole point here is a multi-syllable romanization string.
+    ///
+    /// # Expected files
+    /// ```bash
+    /// dicts/
+    /// ├── PinyinToneMarks.txt
+    /// ├── PinyinToneNumbers.txt
+    /// └── Jyutping.txt
+    /// ```
+    ///
+    /// # Errors
+    /// - [`DictionaryError::IoError`] if a file cannot be read.
+    /// - [`DictionaryError::LoadFileError`] if a data line is missing a TAB.
+    pub fn from_dicts<P: AsRef<Path>>(base_dir: P) -> Result<Self, DictionaryError> {
+        let base_dir = base_dir.as_ref();
+        Ok(RomanizationDict {
+            pinyin_tone_marks: load_table(base_dir, "PinyinToneMarks.txt")?,
+            pinyin_tone_numbers: load_table(base_dir, "PinyinToneNumbers.txt")?,
+            jyutping: load_table(base_dir, "Jyutping.txt")?,
+        })
+    }
+}
+
+/// Reads one romanization `.txt` file and builds its [`DictMaxLen`] — the
+/// same tab-separated format [`RomanizationDict::from_dicts`] documents.
+fn load_table(base_dir: &Path, filename: &str) -> Result<DictMaxLen, DictionaryError> {
+    let path = base_dir.join(filename);
+    let path_str = path.display().to_string();
+    let content = std::fs::read_to_string(&path).map_err(DictionaryError::IoError)?;
+
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    let mut saw_data_line = false;
+
+    for (lineno, raw_line) in content.lines().enumerate() {
+        let mut line = raw_line.trim_end();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if !saw_data_line {
+            if let Some(rest) = line.strip_prefix('\u{FEFF}') {
+                line = rest;
+            }
+            saw_data_line = true;
+        }
+
+        let Some((k, v)) = line.split_once('\t') else {
+            return Err(DictionaryError::LoadFileError {
+                path: path_str.clone(),
+                lineno: lineno + 1,
+                message: "missing TAB separator".to_string(),
+            });
+        };
+
+        pairs.push((k.to_owned(), v.to_owned()));
+    }
+
+    Ok(DictMaxLen::build_from_pairs(pairs))
+}
+
+#[test]
+fn from_dicts_loads_all_three_tables() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("PinyinToneMarks.txt"), "你好\tnǐ hǎo\n").unwrap();
+    std::fs::write(dir.path().join("PinyinToneNumbers.txt"), "你好\tni3 hao3\n").unwrap();
+    std::fs::write(dir.path().join("Jyutping.txt"), "你好\tnei5 hou2\n").unwrap();
+
+    let dict = RomanizationDict::from_dicts(dir.path()).unwrap();
+
+    let key: Vec<char> = "你好".chars().collect();
+    assert_eq!(dict.pinyin_tone_marks.map.get(key.as_slice()).map(|v| &**v), Some("nǐ hǎo"));
+    assert_eq!(dict.pinyin_tone_numbers.map.get(key.as_slice()).map(|v| &**v), Some("ni3 hao3"));
+    assert_eq!(dict.jyutping.map.get(key.as_slice()).map(|v| &**v), Some("nei5 hou2"));
+}
+
+#[test]
+fn from_dicts_skips_comments_and_blank_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let content = "# a comment\n\n你好\tnǐ hǎo\n";
+    std::fs::write(dir.path().join("PinyinToneMarks.txt"), content).unwrap();
+    std::fs::write(dir.path().join("PinyinToneNumbers.txt"), "").unwrap();
+    std::fs::write(dir.path().join("Jyutping.txt"), "").unwrap();
+
+    let dict = RomanizationDict::from_dicts(dir.path()).unwrap();
+    let key: Vec<char> = "你好".chars().collect();
+    assert_eq!(dict.pinyin_tone_marks.map.get(key.as_slice()).map(|v| &**v), Some("nǐ hǎo"));
+}
+
+#[test]
+fn from_dicts_strips_bom_from_first_data_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let content = "\u{FEFF}你好\tnǐ hǎo\n";
+    std::fs::write(dir.path().join("PinyinToneMarks.txt"), content).unwrap();
+    std::fs::write(dir.path().join("PinyinToneNumbers.txt"), "").unwrap();
+    std::fs::write(dir.path().join("Jyutping.txt"), "").unwrap();
+
+    let dict = RomanizationDict::from_dicts(dir.path()).unwrap();
+    let key: Vec<char> = "你好".chars().collect();
+    assert_eq!(dict.pinyin_tone_marks.map.get(key.as_slice()).map(|v| &**v), Some("nǐ hǎo"));
+}
+
+#[test]
+fn from_dicts_errors_on_missing_tab_separator() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("PinyinToneMarks.txt"), "你好 ni hao\n").unwrap();
+    std::fs::write(dir.path().join("PinyinToneNumbers.txt"), "").unwrap();
+    std::fs::write(dir.path().join("Jyutping.txt"), "").unwrap();
+
+    let err = RomanizationDict::from_dicts(dir.path()).unwrap_err();
+    assert!(matches!(err, DictionaryError::LoadFileError { .. }));
+}
+
+#[test]
+fn from_dicts_errors_on_missing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = RomanizationDict::from_dicts(dir.path()).unwrap_err();
+    assert!(matches!(err, DictionaryError::IoError(_)));
+}
+
+#[test]
+fn default_romanization_dict_is_empty_and_passes_through() {
+    let dict = RomanizationDict::default();
+    let key: Vec<char> = "你好".chars().collect();
+    assert_eq!(dict.pinyin_tone_marks.map.get(key.as_slice()).map(|v| &**v), None);
+    assert_eq!(dict.pinyin_tone_numbers.map.get(key.as_slice()).map(|v| &**v), None);
+    assert_eq!(dict.jyutping.map.get(key.as_slice()).map(|v| &**v), None);
+}
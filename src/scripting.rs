@@ -0,0 +1,333 @@
+//! Lua-scripted conversion pipelines, gated behind this crate's `scripting` feature.
+//!
+//! [`OpenCC::convert`](crate::OpenCC::convert) only understands the fixed set of built-in
+//! configs (`s2t`, `t2s`, …), each hard-wired in [`config_rounds`](crate::OpenCC). This module
+//! lets a caller register an additional, named pipeline — an ordered sequence of dictionary
+//! rounds plus an optional per-match override callback — from a `.lua` script, then use that
+//! name as an ordinary `config` argument to [`convert`](crate::OpenCC::convert)/
+//! [`convert_spans`](crate::OpenCC::convert_spans) without recompiling the crate.
+//!
+//! # Script API
+//! A script builds one or more pipelines through a small global `opencc` table:
+//!
+//! ```lua
+//! opencc.begin_pipeline("custom1")
+//! opencc.add_round({"st_phrases", "st_characters"}, true)
+//! opencc.add_round({"tw_variants"}, true)
+//! opencc.set_punctuation(true)
+//! opencc.on_match(function(source, replacement)
+//!     if source == "某词" then return "override" end
+//!     return replacement -- nil/no return keeps the proposed replacement
+//! end)
+//! opencc.register()
+//! ```
+//!
+//! - `begin_pipeline(name)` starts building a pipeline registered under `name` once
+//!   `register()` is called; any rounds/settings from a prior unfinished `begin_pipeline`
+//!   are discarded.
+//! - `add_round(dict_keys, use_union)` appends a [`ScriptedRound`] naming loaded [`DictMaxLen`]
+//!   tables by field name (`"st_phrases"`, `"tw_variants"`, … — see
+//!   [`DictionaryMaxlength::dict_by_key`](crate::dictionary_lib::DictionaryMaxlength::dict_by_key)
+//!   for the full list) and whether this round resolves matches through the
+//!   [`StarterUnion`]-pruned path.
+//! - `set_punctuation(bool)` toggles whether [`OpenCC::convert_with_punctuation`](crate::OpenCC::convert_with_punctuation)'s
+//!   quote-style rewrite runs once the pipeline's rounds finish (direction is guessed from
+//!   whether the first round's first dictionary key starts with `st_`, mirroring how
+//!   `convert_with_punctuation` itself infers direction from a config name's leading `s`).
+//! - `on_match(fn(source, replacement) -> replacement_or_nil)` installs a callback invoked once
+//!   per matched phrase (passthrough chars don't invoke it); returning a string overrides the
+//!   replacement, returning nothing keeps it.
+//! - `register()` finalizes the pipeline under construction.
+//!
+//! # Caveats
+//! - `use_union = false` still resolves matches through the union-pruned algorithm internally
+//!   (so the override callback can see each match's source/replacement as a [`Span`]); only the
+//!   union itself is rebuilt fresh every conversion rather than cached via
+//!   [`union_for_dicts`](crate::dictionary_lib::DictionaryMaxlength::union_for_dicts) — a
+//!   simplification until the simpler unpruned matcher also produces per-match spans.
+//! - The `punctuation` argument [`OpenCC::convert`](crate::OpenCC::convert) otherwise accepts
+//!   has no effect on a scripted config, the same way it has no effect on `s2pinyin`/
+//!   `s2jyutping` — a scripted pipeline's own `set_punctuation` call decides instead.
+//!
+//! [`Span`]: crate::Span
+
+use std::sync::{Arc, RwLock};
+
+use mlua::{Function, Lua, RegistryKey};
+use rustc_hash::FxHashMap;
+
+/// One dictionary round of a [`ScriptedPipeline`], as registered by a script's
+/// `opencc.add_round(dict_keys, use_union)` call.
+#[derive(Debug, Clone)]
+pub struct ScriptedRound {
+    /// Field names of the [`DictMaxLen`](crate::dictionary_lib::DictMaxLen) tables this round
+    /// probes, in precedence order — resolved per conversion via
+    /// [`DictionaryMaxlength::dict_by_key`](crate::dictionary_lib::DictionaryMaxlength::dict_by_key).
+    /// A key that doesn't resolve to a loaded dictionary is silently skipped.
+    pub dict_keys: Vec<String>,
+    /// Whether this round resolves matches through the [`StarterUnion`](crate::dictionary_lib::StarterUnion)-pruned
+    /// path — see the module's "Caveats" section for what this does and doesn't currently affect.
+    pub use_union: bool,
+}
+
+/// A named, script-registered conversion pipeline, usable as an ordinary `config` string once
+/// loaded via [`OpenCC::load_pipeline_script`](crate::OpenCC::load_pipeline_script) — see the
+/// module docs for the Lua API that builds one.
+pub struct ScriptedPipeline {
+    /// This pipeline's dictionary rounds, run in order.
+    pub rounds: Vec<ScriptedRound>,
+    /// Whether [`OpenCC::convert_with_punctuation`](crate::OpenCC::convert_with_punctuation)'s
+    /// quote-style rewrite runs once `rounds` finish.
+    pub punctuation: bool,
+    /// The script's `opencc.on_match` callback, if installed, kept alive alongside the `Lua`
+    /// instance that owns it (a [`RegistryKey`] is only valid for the `Lua` that created it).
+    on_match: Option<(Arc<Lua>, Arc<RegistryKey>)>,
+}
+
+impl ScriptedPipeline {
+    /// Invokes this pipeline's `on_match` callback (if any) with the matched `source` phrase
+    /// and the `proposed` dictionary replacement, returning the callback's override if it
+    /// returned a string, or `proposed` unchanged otherwise — including when there's no
+    /// callback installed, or the callback itself errors or returns nothing. A scripting bug
+    /// degrades to the unmodified conversion rather than failing it.
+    pub(crate) fn apply_override<'a>(&self, source: &str, proposed: &'a str) -> std::borrow::Cow<'a, str> {
+        let Some((lua, key)) = &self.on_match else {
+            return std::borrow::Cow::Borrowed(proposed);
+        };
+        let Ok(f) = lua.registry_value::<Function>(key) else {
+            return std::borrow::Cow::Borrowed(proposed);
+        };
+        match f.call::<Option<String>>((source, proposed)) {
+            Ok(Some(overridden)) => std::borrow::Cow::Owned(overridden),
+            _ => std::borrow::Cow::Borrowed(proposed),
+        }
+    }
+
+    /// `true` if this pipeline's first round's first dictionary key looks like a
+    /// simplified-side starter (`st_`...) — the heuristic [`set_punctuation`] direction uses,
+    /// mirroring how [`OpenCC::convert_with_punctuation`](crate::OpenCC::convert_with_punctuation)
+    /// infers direction from a built-in config name's leading `s`.
+    pub(crate) fn guesses_simplified_source(&self) -> bool {
+        self.rounds
+            .first()
+            .and_then(|r| r.dict_keys.first())
+            .is_some_and(|key| key.starts_with("st_"))
+    }
+}
+
+/// Registry of every pipeline a script has registered, keyed by the name passed to
+/// `opencc.begin_pipeline`/`opencc.register`. Held by [`OpenCC`](crate::OpenCC) behind an `Arc`
+/// so cloning an `OpenCC` shares scripted pipelines the same way it shares loaded dictionaries.
+#[derive(Default)]
+pub(crate) struct ScriptedPipelines {
+    pipelines: RwLock<FxHashMap<String, Arc<ScriptedPipeline>>>,
+}
+
+impl ScriptedPipelines {
+    pub(crate) fn get(&self, name: &str) -> Option<Arc<ScriptedPipeline>> {
+        self.pipelines.read().unwrap().get(name).cloned()
+    }
+
+    fn insert(&self, name: String, pipeline: ScriptedPipeline) {
+        self.pipelines
+            .write()
+            .unwrap()
+            .insert(name, Arc::new(pipeline));
+    }
+}
+
+/// In-progress pipeline state between a script's `begin_pipeline` and `register` calls.
+struct PipelineBuilder {
+    name: String,
+    rounds: Vec<ScriptedRound>,
+    punctuation: bool,
+    on_match: Option<RegistryKey>,
+}
+
+impl PipelineBuilder {
+    fn new(name: String) -> Self {
+        PipelineBuilder {
+            name,
+            rounds: Vec::new(),
+            punctuation: false,
+            on_match: None,
+        }
+    }
+}
+
+/// Runs `source` as a pipeline-registration script against `pipelines`, installing every
+/// pipeline it registers. See the module docs for the `opencc.*` API the script sees.
+///
+/// Returns an [`mlua::Error`] for a Lua syntax/runtime error, or for `add_round`/
+/// `set_punctuation`/`on_match`/`register` called before a `begin_pipeline`.
+pub(crate) fn run_pipeline_script(
+    source: &str,
+    pipelines: Arc<ScriptedPipelines>,
+) -> mlua::Result<()> {
+    let lua = Arc::new(Lua::new());
+    let builder: Arc<std::sync::Mutex<Option<PipelineBuilder>>> = Arc::new(std::sync::Mutex::new(None));
+    let opencc_table = lua.create_table()?;
+
+    {
+        let builder = builder.clone();
+        opencc_table.set(
+            "begin_pipeline",
+            lua.create_function(move |_, name: String| {
+                *builder.lock().unwrap() = Some(PipelineBuilder::new(name));
+                Ok(())
+            })?,
+        )?;
+    }
+    {
+        let builder = builder.clone();
+        opencc_table.set(
+            "add_round",
+            lua.create_function(move |_, (dict_keys, use_union): (Vec<String>, bool)| {
+                let mut guard = builder.lock().unwrap();
+                let b = guard.as_mut().ok_or_else(|| {
+                    mlua::Error::RuntimeError("add_round called before begin_pipeline".into())
+                })?;
+                b.rounds.push(ScriptedRound {
+                    dict_keys,
+                    use_union,
+                });
+                Ok(())
+            })?,
+        )?;
+    }
+    {
+        let builder = builder.clone();
+        opencc_table.set(
+            "set_punctuation",
+            lua.create_function(move |_, punctuation: bool| {
+                let mut guard = builder.lock().unwrap();
+                let b = guard.as_mut().ok_or_else(|| {
+                    mlua::Error::RuntimeError("set_punctuation called before begin_pipeline".into())
+                })?;
+                b.punctuation = punctuation;
+                Ok(())
+            })?,
+        )?;
+    }
+    {
+        let builder = builder.clone();
+        opencc_table.set(
+            "on_match",
+            lua.create_function(move |lua, f: Function| {
+                let key = lua.create_registry_value(f)?;
+                let mut guard = builder.lock().unwrap();
+                let b = guard.as_mut().ok_or_else(|| {
+                    mlua::Error::RuntimeError("on_match called before begin_pipeline".into())
+                })?;
+                b.on_match = Some(key);
+                Ok(())
+            })?,
+        )?;
+    }
+    {
+        let builder = builder.clone();
+        let lua_for_pipelines = lua.clone();
+        opencc_table.set(
+            "register",
+            lua.create_function(move |_, ()| {
+                let b = builder.lock().unwrap().take().ok_or_else(|| {
+                    mlua::Error::RuntimeError("register called before begin_pipeline".into())
+                })?;
+                if b.rounds.is_empty() {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "pipeline '{}' has no rounds",
+                        b.name
+                    )));
+                }
+                pipelines.insert(
+                    b.name,
+                    ScriptedPipeline {
+                        rounds: b.rounds,
+                        punctuation: b.punctuation,
+                        on_match: b.on_match.map(|key| (lua_for_pipelines.clone(), Arc::new(key))),
+                    },
+                );
+                Ok(())
+            })?,
+        )?;
+    }
+
+    lua.globals().set("opencc", opencc_table)?;
+    lua.load(source).exec()
+}
+
+#[test]
+fn run_pipeline_script_registers_a_pipeline() {
+    let pipelines = Arc::new(ScriptedPipelines::default());
+    let script = r#"
+        opencc.begin_pipeline("custom1")
+        opencc.add_round({"st_phrases", "st_characters"}, true)
+        opencc.add_round({"tw_variants"}, true)
+        opencc.set_punctuation(true)
+        opencc.register()
+    "#;
+    run_pipeline_script(script, pipelines.clone()).unwrap();
+
+    let pipeline = pipelines.get("custom1").unwrap();
+    assert_eq!(pipeline.rounds.len(), 2);
+    assert_eq!(pipeline.rounds[0].dict_keys, vec!["st_phrases", "st_characters"]);
+    assert!(pipeline.rounds[0].use_union);
+    assert!(pipeline.punctuation);
+    assert!(pipeline.guesses_simplified_source());
+}
+
+#[test]
+fn run_pipeline_script_errors_on_add_round_before_begin_pipeline() {
+    let pipelines = Arc::new(ScriptedPipelines::default());
+    let script = r#"opencc.add_round({"st_phrases"}, true)"#;
+    assert!(run_pipeline_script(script, pipelines).is_err());
+}
+
+#[test]
+fn run_pipeline_script_errors_on_register_with_no_rounds() {
+    let pipelines = Arc::new(ScriptedPipelines::default());
+    let script = r#"
+        opencc.begin_pipeline("empty")
+        opencc.register()
+    "#;
+    assert!(run_pipeline_script(script, pipelines).is_err());
+}
+
+#[test]
+fn run_pipeline_script_missing_pipeline_returns_none() {
+    let pipelines = Arc::new(ScriptedPipelines::default());
+    assert!(pipelines.get("nonexistent").is_none());
+}
+
+#[test]
+fn on_match_override_replaces_proposed_replacement() {
+    let pipelines = Arc::new(ScriptedPipelines::default());
+    let script = r#"
+        opencc.begin_pipeline("withcallback")
+        opencc.add_round({"st_phrases"}, true)
+        opencc.on_match(function(source, replacement)
+            if source == "某词" then return "override" end
+            return replacement
+        end)
+        opencc.register()
+    "#;
+    run_pipeline_script(script, pipelines.clone()).unwrap();
+    let pipeline = pipelines.get("withcallback").unwrap();
+
+    assert_eq!(pipeline.apply_override("某词", "proposed"), "override");
+    assert_eq!(pipeline.apply_override("other", "proposed"), "proposed");
+}
+
+#[test]
+fn apply_override_passes_through_when_no_callback_installed() {
+    let pipelines = Arc::new(ScriptedPipelines::default());
+    let script = r#"
+        opencc.begin_pipeline("nocallback")
+        opencc.add_round({"st_phrases"}, true)
+        opencc.register()
+    "#;
+    run_pipeline_script(script, pipelines.clone()).unwrap();
+    let pipeline = pipelines.get("nocallback").unwrap();
+
+    assert_eq!(pipeline.apply_override("anything", "proposed"), "proposed");
+}
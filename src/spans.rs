@@ -0,0 +1,62 @@
+//! Span/edit records describing what [`OpenCC::convert_spans`](crate::OpenCC::convert_spans)
+//! rewrote and where, for diff viewers, highlighters, or conversion
+//! analytics built on top of the plain [`convert`](crate::OpenCC::convert) output.
+
+use std::ops::Range;
+
+/// One contiguous edit (or passthrough) produced by
+/// [`OpenCC::convert_spans`](crate::OpenCC::convert_spans).
+///
+/// # Range semantics
+/// `range` is a `char` range (not a byte offset) into the text that entered
+/// the *matching* round — the round whose dictionaries actually produced
+/// these spans. For every single-round config (`s2t`, `t2s`, `t2tw`,
+/// `tw2t`, `t2hk`, `hk2t`, `t2jp`, `jp2t`) that round is
+/// the whole conversion, so `range`/`source` describe the caller's original
+/// input verbatim. For multi-round configs (`s2tw`, `s2twp`, `tw2s`,
+/// `tw2sp`, `s2hk`, `hk2s`, `t2twp`, `tw2tp`) every round before the last still runs as a
+/// plain string transform first, so `range`/`source` describe offsets into
+/// that intermediate text rather than the original input — see
+/// [`convert_spans`](crate::OpenCC::convert_spans)'s docs for why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// `char` range this span covers in the matching round's input text.
+    pub range: Range<usize>,
+    /// The text this span covers before replacement.
+    pub source: String,
+    /// The text this span was rewritten to. Equal to `source` for a
+    /// passthrough span (an unmatched char or a preserved delimiter).
+    pub replacement: String,
+    /// Index into the matching round's dictionary precedence list (the
+    /// order [`OpenCC::convert_spans`](crate::OpenCC::convert_spans) probed
+    /// them in) of the dictionary that produced this span, or `None` for a
+    /// passthrough span.
+    pub dict_index: Option<usize>,
+}
+
+impl Span {
+    /// `true` if this span is a passthrough: an unmatched char or a
+    /// preserved delimiter, as opposed to a dictionary-matched replacement.
+    pub fn is_passthrough(&self) -> bool {
+        self.dict_index.is_none()
+    }
+}
+
+/// One contiguous edit produced by
+/// [`OpenCC::convert_with_alignment`](crate::OpenCC::convert_with_alignment), mapping a `char`
+/// range in the source text to the `byte` range it produced in the output string — enough for a
+/// caller to translate cursor positions or inline markup across a conversion without needing
+/// [`Span`]'s matched/replacement text or dictionary attribution.
+///
+/// Like [`Span::range`], `src_char_range` describes the matching round's input text, so the same
+/// [`Span`]-level multi-round caveat (`s2tw`, `s2twp`, `tw2s`, `tw2sp`, `s2hk`, `hk2s`, `t2twp`,
+/// `tw2tp`) applies —
+/// see [`convert_with_alignment`](crate::OpenCC::convert_with_alignment)'s docs. Spans are
+/// contiguous and cover the whole input with no gaps or overlaps in either range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignmentSpan {
+    /// `char` range this span covers in the matching round's input text.
+    pub src_char_range: Range<usize>,
+    /// `byte` range this span covers in the converted output string.
+    pub dst_byte_range: Range<usize>,
+}
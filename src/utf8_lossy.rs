@@ -0,0 +1,96 @@
+//! Lossy UTF-8 decoding for [`OpenCC::convert_bytes`](crate::OpenCC::convert_bytes)/
+//! [`zho_check_bytes`](crate::OpenCC::zho_check_bytes) — accepts raw bytes that aren't
+//! guaranteed valid UTF-8 (a socket read, a file of unknown encoding) without requiring the
+//! caller to pre-validate or bail.
+//!
+//! Mirrors the design of the standard library's internal `Utf8Lossy`/`Utf8LossyChunksIter`:
+//! walk the bytes once, classify each position by a UTF-8 char-width lookup table (indexed by
+//! the leading byte — `0` for a continuation byte or a lead that's never valid, `1` for ASCII,
+//! `2`/`3`/`4` for a multi-byte lead), and split the input into maximal valid `&str` runs
+//! separated by single-byte-wide invalid spans. Unlike `String::from_utf8_lossy`'s
+//! maximal-subpart merging (which can fold several bad bytes of one truncated sequence into a
+//! single replacement char), every invalid byte here becomes its own U+FFFD — simpler, and
+//! exactly the rule [`lossy_chunks`] documents: one broken byte in, one replacement char out,
+//! resuming right after it.
+
+/// One maximal run produced by [`lossy_chunks`]: either a valid UTF-8 `&str` slice of the
+/// original input, or a single invalid byte standing in for one U+FFFD replacement char.
+pub(crate) enum LossyChunk<'a> {
+    Valid(&'a str),
+    Invalid,
+}
+
+/// Per-leading-byte UTF-8 sequence width: `0` marks a continuation byte (`0x80..=0xBF`) or a
+/// lead byte that's never valid UTF-8 (`0xC0`, `0xC1`, `0xF5..=0xFF`), `1` is a plain ASCII
+/// byte, and `2`/`3`/`4` are the widths of a valid multi-byte lead.
+#[rustfmt::skip]
+const UTF8_CHAR_WIDTH: [u8; 256] = [
+    // 0x00 ..= 0x7F: ASCII
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    // 0x80 ..= 0xBF: continuation bytes, never a lead byte
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    // 0xC0, 0xC1: always overlong, never valid
+    0, 0,
+    // 0xC2 ..= 0xDF: two-byte lead
+    2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+    2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+    // 0xE0 ..= 0xEF: three-byte lead
+    3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3,
+    // 0xF0 ..= 0xF4: four-byte lead
+    4, 4, 4, 4, 4,
+    // 0xF5 ..= 0xFF: beyond Unicode's max codepoint, never valid
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// Splits `bytes` into maximal valid UTF-8 `&str` runs and single-byte invalid spans, in
+/// order — see the module docs for the replacement rule.
+pub(crate) fn lossy_chunks(bytes: &[u8]) -> Vec<LossyChunk<'_>> {
+    let mut chunks = Vec::new();
+    let mut run_start = 0usize;
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        let width = UTF8_CHAR_WIDTH[bytes[i] as usize] as usize;
+
+        if width == 1 {
+            i += 1;
+            continue;
+        }
+
+        // A multi-byte lead (`width` in `2..=4`) still needs its continuation bytes and the
+        // resulting codepoint validated — `std::str::from_utf8` runs the same check the
+        // standard library's internal `run_utf8_validation` does, rejecting overlong
+        // encodings, surrogates, and sequences truncated at the end of `bytes` in one call.
+        if width > 1 && i + width <= bytes.len() && std::str::from_utf8(&bytes[i..i + width]).is_ok() {
+            i += width;
+            continue;
+        }
+
+        if run_start < i {
+            chunks.push(LossyChunk::Valid(
+                std::str::from_utf8(&bytes[run_start..i]).unwrap(),
+            ));
+        }
+        chunks.push(LossyChunk::Invalid);
+        i += 1;
+        run_start = i;
+    }
+
+    if run_start < bytes.len() {
+        chunks.push(LossyChunk::Valid(
+            std::str::from_utf8(&bytes[run_start..]).unwrap(),
+        ));
+    }
+
+    chunks
+}
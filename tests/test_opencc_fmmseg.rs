@@ -351,4 +351,46 @@ mod tests {
             assert_eq!(chars[range.end - 1], '\n');
         }
     }
+
+    #[test]
+    fn ivs_selector_stays_attached_in_spans_test() {
+        let opencc = OpenCC::new();
+        let input = format!("{}{}", "龙", '\u{FE00}');
+        let spans: Vec<_> = opencc.convert_spans(&input, "s2t", false).collect();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].source, input);
+        assert_eq!(spans[0].replacement, format!("{}{}", "龍", '\u{FE00}'));
+    }
+
+    #[test]
+    fn combining_mark_stays_attached_to_passthrough_char_test() {
+        let opencc = OpenCC::new();
+        // 'e' + COMBINING ACUTE ACCENT (category Mn) has no dictionary entry, so it's a
+        // passthrough — but the mark must stay grouped with its base char as one span.
+        let input = format!("e{}", '\u{0301}');
+        let spans: Vec<_> = opencc.convert_spans(&input, "s2t", false).collect();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].source, input);
+        assert!(spans[0].is_passthrough());
+    }
+
+    #[test]
+    fn ivs_roundtrip_s2t_t2s_test() {
+        let opencc = OpenCC::new();
+        let input = format!("{}{}{}", "龙", '\u{FE00}', "马精神");
+        let traditional = opencc.s2t(&input, false);
+        let back = opencc.t2s(&traditional, false);
+        assert_eq!(back, input);
+    }
+
+    #[test]
+    fn preserve_variation_sequences_can_be_disabled_test() {
+        let mut opencc = OpenCC::new();
+        opencc.set_preserve_variation_sequences(false);
+        assert!(!opencc.get_preserve_variation_sequences());
+
+        let input = format!("{}{}", "龙", '\u{FE00}');
+        let spans: Vec<_> = opencc.convert_spans(&input, "s2t", false).collect();
+        assert_eq!(spans.len(), 2);
+    }
 }
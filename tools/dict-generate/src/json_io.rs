@@ -1,8 +1,12 @@
 // json_io.rs (CLI only)
 use opencc_fmmseg::dictionary_lib::{DictMaxLen, DictionaryMaxlength};
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
 // stable key order for diffs
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -26,10 +30,87 @@ pub struct DictMaxLenSerde {
     pub starter_len_mask: BTreeMap<String, u64>,
 }
 
+/// Controls how a loaded [`DictMaxLenSerde`]'s `key_length_mask`/`starter_len_mask` are treated
+/// relative to what [`into_internal_with_policy`](DictMaxLenSerde::into_internal_with_policy)
+/// recomputes from `map` itself — selected via dict-generate's `--mask-policy` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MaskPolicy {
+    /// Trust a nonzero stored mask as-is, only recomputing when it's absent — today's behavior,
+    /// and the cheapest: the per-starter masks are not recomputed when the artifact already
+    /// carries them (the key-length mask is folded in the same pass that builds `map` either way).
+    #[default]
+    Trust,
+    /// Always recompute the masks from `map` and compare them against whatever was stored,
+    /// keeping the recomputed (trustworthy) values either way and reporting every mismatch.
+    Verify,
+    /// Always recompute the masks from `map` and use them, ignoring any stored value without
+    /// comparing or reporting.
+    Recompute,
+}
+
+/// One starter whose stored `starter_len_mask` bit pattern didn't match what [`MaskPolicy::Verify`]
+/// recomputed from `map`.
+#[derive(Debug, Clone, Copy)]
+pub struct MaskMismatch {
+    pub starter: char,
+    pub stored: u64,
+    pub recomputed: u64,
+}
+
+/// The mismatches [`MaskPolicy::Verify`] found for one [`DictMaxLen`] table, empty under
+/// [`MaskPolicy::Trust`]/[`MaskPolicy::Recompute`] since neither mode compares stored vs.
+/// recomputed values.
+#[derive(Debug, Clone, Default)]
+pub struct MaskVerifyReport {
+    pub key_length_mismatch: Option<(u64, u64)>,
+    pub starter_mismatches: Vec<MaskMismatch>,
+}
+
+impl MaskVerifyReport {
+    pub fn has_mismatches(&self) -> bool {
+        self.key_length_mismatch.is_some() || !self.starter_mismatches.is_empty()
+    }
+}
+
+/// Formats every non-empty section report from
+/// [`DictionaryMaxlengthSerde::into_internal_with_policy`] into a single human-readable warning,
+/// suitable for
+/// [`DictionaryMaxlength::set_last_error`](opencc_fmmseg::dictionary_lib::DictionaryMaxlength::set_last_error).
+pub fn format_mask_reports(reports: &[(&'static str, MaskVerifyReport)]) -> String {
+    let mut out = format!(
+        "mask verification found stale precomputed masks in {} section(s):",
+        reports.len()
+    );
+    for (section, report) in reports {
+        if let Some((stored, recomputed)) = report.key_length_mismatch {
+            out.push_str(&format!(
+                "\n  {section}: key_length_mask stored=0x{stored:016x} recomputed=0x{recomputed:016x}"
+            ));
+        }
+        for mismatch in &report.starter_mismatches {
+            out.push_str(&format!(
+                "\n  {section}: starter {:?} stored=0x{:016x} recomputed=0x{:016x}",
+                mismatch.starter, mismatch.stored, mismatch.recomputed
+            ));
+        }
+    }
+    out
+}
+
 impl DictMaxLenSerde {
     #[allow(dead_code)]
     pub fn into_internal(self) -> DictMaxLen {
+        self.into_internal_with_policy(MaskPolicy::Trust).0
+    }
+
+    /// Like [`into_internal`](Self::into_internal), but builds `map`/`min_len`/`max_len` exactly
+    /// once regardless of `policy`, reusing that single recomputation pass to either backfill an
+    /// absent stored mask (`Trust`), cross-check a present one without discarding it on mismatch
+    /// (`Verify`), or ignore it outright (`Recompute`) — see [`MaskPolicy`] for what each variant
+    /// does and [`MaskVerifyReport`] for what `Verify` reports back.
+    pub fn into_internal_with_policy(self, policy: MaskPolicy) -> (DictMaxLen, MaskVerifyReport) {
         let mut out = DictMaxLen::default();
+        let mut report = MaskVerifyReport::default();
 
         // Build map, and compute min/max + key_length_mask on the fly
         let mut min_seen = usize::MAX;
@@ -56,7 +137,6 @@ impl DictMaxLenSerde {
             out.map.insert(key, v.into_boxed_str());
         }
 
-        // Prefer JSON-provided values; fallback to recomputed
         out.max_len = if self.max_len != 0 {
             self.max_len
         } else {
@@ -70,22 +150,31 @@ impl DictMaxLenSerde {
             0
         };
 
-        // key_length_mask: prefer provided nonzero mask, else recomputed
-        out.key_length_mask = if self.key_length_mask != 0 {
-            self.key_length_mask
-        } else {
-            mask
+        out.key_length_mask = match policy {
+            MaskPolicy::Recompute => mask,
+            MaskPolicy::Trust => {
+                if self.key_length_mask != 0 {
+                    self.key_length_mask
+                } else {
+                    mask
+                }
+            }
+            MaskPolicy::Verify => {
+                if self.key_length_mask != 0 && self.key_length_mask != mask {
+                    report.key_length_mismatch = Some((self.key_length_mask, mask));
+                }
+                mask
+            }
         };
 
-        // NEW: starter_len_mask: use provided map if present; otherwise derive from out.map
-        if self.starter_len_mask.is_empty() {
+        let need_recomputed_starters = match policy {
+            MaskPolicy::Trust => self.starter_len_mask.is_empty(),
+            MaskPolicy::Verify | MaskPolicy::Recompute => true,
+        };
+        let recomputed_starters = need_recomputed_starters.then(|| {
             let mut m = FxHashMap::default();
-            // Heuristic: starters ≤ unique first chars in map, capped at BMP
-            // (reserve is optional; remove if you prefer)
-            let mut seen = FxHashSet::default();
             for (k_chars, _) in out.map.iter() {
                 if let Some(&c0) = k_chars.first() {
-                    if seen.insert(c0) { /* counting unique starters */ }
                     let len = k_chars.len();
                     let entry = m.entry(c0).or_insert(0u64);
                     let b = len.wrapping_sub(1);
@@ -94,30 +183,427 @@ impl DictMaxLenSerde {
                     }
                 }
             }
-            // If you still want a reserve, do it before the loop as:
-            // m.reserve(seen.len());
-            out.starter_len_mask = m;
+            m
+        });
+
+        out.starter_len_mask = match policy {
+            MaskPolicy::Recompute => recomputed_starters.unwrap(),
+            MaskPolicy::Trust => {
+                if self.starter_len_mask.is_empty() {
+                    recomputed_starters.unwrap()
+                } else {
+                    let mut m = FxHashMap::default();
+                    m.reserve(self.starter_len_mask.len());
+                    for (s, mask) in self.starter_len_mask {
+                        if let Some(ch) = s.chars().next() {
+                            m.insert(ch, mask);
+                        }
+                    }
+                    m
+                }
+            }
+            MaskPolicy::Verify => {
+                let recomputed = recomputed_starters.unwrap();
+                if !self.starter_len_mask.is_empty() {
+                    let mut stored: FxHashMap<char, u64> = FxHashMap::default();
+                    for (s, mask) in &self.starter_len_mask {
+                        if let Some(ch) = s.chars().next() {
+                            stored.insert(ch, *mask);
+                        }
+                    }
+                    let mut starters: Vec<char> =
+                        stored.keys().chain(recomputed.keys()).copied().collect::<FxHashSet<_>>().into_iter().collect();
+                    starters.sort_unstable();
+                    for ch in starters {
+                        let stored_mask = stored.get(&ch).copied().unwrap_or(0);
+                        let recomputed_mask = recomputed.get(&ch).copied().unwrap_or(0);
+                        if stored_mask != recomputed_mask {
+                            report.starter_mismatches.push(MaskMismatch {
+                                starter: ch,
+                                stored: stored_mask,
+                                recomputed: recomputed_mask,
+                            });
+                        }
+                    }
+                }
+                recomputed
+            }
+        };
+
+        // Rebuild runtime accelerators (dense BMP vectors) from sparse maps
+        out.first_len_mask64.clear();
+        out.first_char_max_len.clear();
+        out.populate_starter_indexes();
+
+        (out, report)
+    }
+}
+
+/// Deserializes a `{phrase: replacement, ...}` JSON object directly into an in-progress
+/// [`DictMaxLen`]'s `map`, folding `min_seen`/`max_seen`/`mask` as each entry arrives instead of
+/// collecting a [`BTreeMap<String, String>`] first — see [`from_json_reader`] for why this
+/// matters for large merged dictionaries.
+struct PhraseMapSeed<'a> {
+    out: &'a mut DictMaxLen,
+    min_seen: &'a mut usize,
+    max_seen: &'a mut usize,
+    mask: &'a mut u64,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for PhraseMapSeed<'a> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for PhraseMapSeed<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a phrase -> replacement object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some((k, v)) = map.next_entry::<String, String>()? {
+            let key: Box<[char]> = k.chars().collect::<Vec<_>>().into_boxed_slice();
+            let len = key.len();
+
+            if len < *self.min_seen {
+                *self.min_seen = len;
+            }
+            if len > *self.max_seen {
+                *self.max_seen = len;
+            }
+            let b = len.wrapping_sub(1);
+            if b < 64 {
+                *self.mask |= 1u64 << b;
+            }
+
+            self.out.map.insert(key, v.into_boxed_str());
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes a `{starter: mask, ...}` JSON object directly into an in-progress
+/// [`DictMaxLen`]'s `starter_len_mask`, the same streaming-in-place approach as [`PhraseMapSeed`].
+struct StarterLenMaskSeed<'a> {
+    out: &'a mut DictMaxLen,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for StarterLenMaskSeed<'a> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for StarterLenMaskSeed<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a starter-char -> length-mask object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some((k, v)) = map.next_entry::<String, u64>()? {
+            if let Some(ch) = k.chars().next() {
+                self.out.starter_len_mask.insert(ch, v);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Streaming counterpart to [`DictMaxLenSerde::into_internal`]: deserializes one table section
+/// straight into a [`DictMaxLen`], entry by entry, via [`PhraseMapSeed`]/[`StarterLenMaskSeed`]
+/// rather than building a [`DictMaxLenSerde`] DTO first.
+struct DictMaxLenSeed;
+
+impl<'de> DeserializeSeed<'de> for DictMaxLenSeed {
+    type Value = DictMaxLen;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(DictMaxLenVisitor)
+    }
+}
+
+struct DictMaxLenVisitor;
+
+impl<'de> Visitor<'de> for DictMaxLenVisitor {
+    type Value = DictMaxLen;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a dictionary-table object with `map`, `max_len`, `min_len`, `key_length_mask`, `starter_len_mask`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut out = DictMaxLen::default();
+        let mut min_seen = usize::MAX;
+        let mut max_seen = 0usize;
+        let mut mask: u64 = 0;
+        let mut json_max_len = 0usize;
+        let mut json_min_len = 0usize;
+        let mut json_key_length_mask = 0u64;
+        let mut starter_len_mask_seen = false;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "map" => {
+                    map.next_value_seed(PhraseMapSeed {
+                        out: &mut out,
+                        min_seen: &mut min_seen,
+                        max_seen: &mut max_seen,
+                        mask: &mut mask,
+                    })?;
+                }
+                "max_len" => json_max_len = map.next_value()?,
+                "min_len" => json_min_len = map.next_value()?,
+                "key_length_mask" => json_key_length_mask = map.next_value()?,
+                "starter_len_mask" => {
+                    starter_len_mask_seen = true;
+                    map.next_value_seed(StarterLenMaskSeed { out: &mut out })?;
+                }
+                _ => {
+                    let _ignored: IgnoredAny = map.next_value()?;
+                }
+            }
+        }
+
+        // Same provided-vs-recomputed preference as `DictMaxLenSerde::into_internal`.
+        out.max_len = if json_max_len != 0 { json_max_len } else { max_seen };
+        out.min_len = if json_min_len != 0 {
+            json_min_len
+        } else if !out.map.is_empty() {
+            min_seen
         } else {
-            let mut m = FxHashMap::default();
-            // Reserve by provided size (cheap and safe)
-            m.reserve(self.starter_len_mask.len());
-            for (s, mask) in self.starter_len_mask {
-                if let Some(ch) = s.chars().next() {
-                    m.insert(ch, mask);
+            0
+        };
+        out.key_length_mask = if json_key_length_mask != 0 {
+            json_key_length_mask
+        } else {
+            mask
+        };
+
+        if !starter_len_mask_seen || out.starter_len_mask.is_empty() {
+            let mut derived = FxHashMap::default();
+            for (k_chars, _) in out.map.iter() {
+                if let Some(&c0) = k_chars.first() {
+                    let len = k_chars.len();
+                    let entry = derived.entry(c0).or_insert(0u64);
+                    let b = len.wrapping_sub(1);
+                    if b < 64 {
+                        *entry |= 1u64 << b;
+                    }
                 }
             }
-            out.starter_len_mask = m;
+            out.starter_len_mask = derived;
         }
 
-        // Rebuild runtime accelerators (dense BMP vectors) from sparse maps
         out.first_len_mask64.clear();
         out.first_char_max_len.clear();
         out.populate_starter_indexes();
 
-        out
+        Ok(out)
     }
 }
 
+/// Streaming counterpart to [`DictionaryMaxlengthSerde::into_internal`]: deserializes the
+/// eighteen named table sections straight into a [`DictionaryMaxlength`] via [`DictMaxLenSeed`]
+/// instead of first collecting a [`DictionaryMaxlengthSerde`] DTO of eighteen
+/// `BTreeMap<String, String>`s.
+///
+/// `current_section` is updated with the field name just before that field's value is
+/// deserialized, so [`try_from_json_reader`] can report which section a parse error happened in
+/// even though the error itself (propagated as the generic `A::Error`) can't carry that context.
+struct DictionaryMaxlengthVisitor<'a> {
+    current_section: &'a Cell<Option<&'static str>>,
+}
+
+const DICT_FIELD_NAMES: [&str; 18] = [
+    "st_characters",
+    "st_phrases",
+    "ts_characters",
+    "ts_phrases",
+    "tw_phrases",
+    "tw_phrases_rev",
+    "tw_variants",
+    "tw_variants_rev",
+    "tw_variants_rev_phrases",
+    "hk_variants",
+    "hk_variants_rev",
+    "hk_variants_rev_phrases",
+    "jps_characters",
+    "jps_phrases",
+    "jp_variants",
+    "jp_variants_rev",
+    "st_punctuations",
+    "ts_punctuations",
+];
+
+impl<'de, 'a> Visitor<'de> for DictionaryMaxlengthVisitor<'a> {
+    type Value = DictionaryMaxlength;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a dictionary-maxlength object with eighteen table sections")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut out = DictionaryMaxlength::default();
+
+        while let Some(key) = map.next_key::<String>()? {
+            macro_rules! set_field {
+                ($name:literal, $field:ident) => {{
+                    self.current_section.set(Some($name));
+                    out.$field = map.next_value_seed(DictMaxLenSeed)?;
+                }};
+            }
+            match key.as_str() {
+                "st_characters" => set_field!("st_characters", st_characters),
+                "st_phrases" => set_field!("st_phrases", st_phrases),
+                "ts_characters" => set_field!("ts_characters", ts_characters),
+                "ts_phrases" => set_field!("ts_phrases", ts_phrases),
+                "tw_phrases" => set_field!("tw_phrases", tw_phrases),
+                "tw_phrases_rev" => set_field!("tw_phrases_rev", tw_phrases_rev),
+                "tw_variants" => set_field!("tw_variants", tw_variants),
+                "tw_variants_rev" => set_field!("tw_variants_rev", tw_variants_rev),
+                "tw_variants_rev_phrases" => {
+                    set_field!("tw_variants_rev_phrases", tw_variants_rev_phrases)
+                }
+                "hk_variants" => set_field!("hk_variants", hk_variants),
+                "hk_variants_rev" => set_field!("hk_variants_rev", hk_variants_rev),
+                "hk_variants_rev_phrases" => {
+                    set_field!("hk_variants_rev_phrases", hk_variants_rev_phrases)
+                }
+                "jps_characters" => set_field!("jps_characters", jps_characters),
+                "jps_phrases" => set_field!("jps_phrases", jps_phrases),
+                "jp_variants" => set_field!("jp_variants", jp_variants),
+                "jp_variants_rev" => set_field!("jp_variants_rev", jp_variants_rev),
+                "st_punctuations" => set_field!("st_punctuations", st_punctuations),
+                "ts_punctuations" => set_field!("ts_punctuations", ts_punctuations),
+                _ => {
+                    let _ignored: IgnoredAny = map.next_value()?;
+                }
+            }
+        }
+
+        self.current_section.set(None);
+        Ok(out.finish())
+    }
+}
+
+/// Loads a [`DictionaryMaxlength`] from a `DictionaryMaxlengthSerde`-shaped JSON document read
+/// from `reader`, one phrase entry at a time, instead of [`serde_json::from_reader`]ing a
+/// [`DictionaryMaxlengthSerde`] DTO and converting it via
+/// [`into_internal`](DictionaryMaxlengthSerde::into_internal) afterward. Each of the eighteen
+/// tables' `map` objects is folded directly into its target [`DictMaxLen`] as it's parsed, so the
+/// intermediate `BTreeMap<String, String>` the DTO path builds per table never exists — roughly
+/// halving peak allocation for a multi-megabyte merged dictionary loaded from a file or stdin.
+///
+/// Prefer [`try_from_json_reader`] for a diagnosable error on malformed input; this is the
+/// bare-`serde_json::Error` entry point for callers that don't need section/line/column context.
+#[allow(dead_code)]
+pub fn from_json_reader<R: Read>(reader: R) -> serde_json::Result<DictionaryMaxlength> {
+    let current_section = Cell::new(None);
+    let mut de = serde_json::Deserializer::from_reader(reader);
+    let dictionary = de.deserialize_struct(
+        "DictionaryMaxlength",
+        &DICT_FIELD_NAMES,
+        DictionaryMaxlengthVisitor {
+            current_section: &current_section,
+        },
+    )?;
+    de.end()?;
+    Ok(dictionary)
+}
+
+/// A JSON syntax/type error encountered by [`try_from_json_reader`], annotated with the
+/// `serde_json` line/column it occurred at and — when parsing got far enough to know — which of
+/// the eighteen table sections it happened inside.
+#[derive(Debug)]
+pub struct DictError {
+    pub section: Option<&'static str>,
+    pub line: usize,
+    pub column: usize,
+    message: String,
+}
+
+impl DictError {
+    fn new(section: Option<&'static str>, err: serde_json::Error) -> Self {
+        DictError {
+            section,
+            line: err.line(),
+            column: err.column(),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.section {
+            Some(section) => write!(f, "{section}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for DictError {}
+
+/// Fallible counterpart to [`from_json_reader`] for callers that want a diagnosable error
+/// instead of a bare [`serde_json::Error`] on a truncated or hand-edited dictionary artifact.
+/// On failure, also records the formatted message in
+/// [`DictionaryMaxlength::set_last_error`](opencc_fmmseg::dictionary_lib::DictionaryMaxlength::set_last_error)
+/// so FFI-style callers that only poll
+/// [`get_last_error`](opencc_fmmseg::dictionary_lib::DictionaryMaxlength::get_last_error) still
+/// see it.
+pub fn try_from_json_reader<R: Read>(reader: R) -> Result<DictionaryMaxlength, DictError> {
+    let current_section = Cell::new(None);
+    let mut de = serde_json::Deserializer::from_reader(reader);
+    let result = de
+        .deserialize_struct(
+            "DictionaryMaxlength",
+            &DICT_FIELD_NAMES,
+            DictionaryMaxlengthVisitor {
+                current_section: &current_section,
+            },
+        )
+        .and_then(|dictionary| {
+            de.end()?;
+            Ok(dictionary)
+        });
+
+    result.map_err(|err| {
+        let err = DictError::new(current_section.get(), err);
+        DictionaryMaxlength::set_last_error(&err.to_string());
+        err
+    })
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct DictionaryMaxlengthSerde {
     pub st_characters: DictMaxLenSerde,
@@ -140,6 +626,65 @@ pub struct DictionaryMaxlengthSerde {
     pub ts_punctuations: DictMaxLenSerde,
 }
 
+impl DictionaryMaxlengthSerde {
+    /// Rebuilds a runtime [`DictionaryMaxlength`] from this DTO under [`MaskPolicy::Trust`],
+    /// discarding the (necessarily empty) verify report. The dictionary generator's
+    /// `--input ... --in-format json` path does not call it: it uses [`try_from_json_reader`]
+    /// under [`MaskPolicy::Trust`] and [`into_internal_with_policy`](Self::into_internal_with_policy)
+    /// under the other policies.
+    #[allow(dead_code)]
+    pub fn into_internal(self) -> DictionaryMaxlength {
+        self.into_internal_with_policy(MaskPolicy::Trust).0
+    }
+
+    /// Like [`into_internal`](Self::into_internal), but applies `policy` to every one of the
+    /// eighteen sections and returns each section's [`MaskVerifyReport`] alongside the rebuilt
+    /// dictionary — only ever non-empty under [`MaskPolicy::Verify`], and only for sections where
+    /// a stored mask didn't match what was recomputed from `map`.
+    pub fn into_internal_with_policy(
+        self,
+        policy: MaskPolicy,
+    ) -> (DictionaryMaxlength, Vec<(&'static str, MaskVerifyReport)>) {
+        // `DictionaryMaxlength` has a private `unions` field, so build on top
+        // of `default()` and overwrite the public table fields rather than
+        // constructing the struct literal directly (same approach as
+        // `embed::from_embedded_features`).
+        let mut out = DictionaryMaxlength::default();
+        let mut reports = Vec::new();
+
+        macro_rules! convert_field {
+            ($name:literal, $field:ident) => {{
+                let (dict, report) = self.$field.into_internal_with_policy(policy);
+                out.$field = dict;
+                if report.has_mismatches() {
+                    reports.push(($name, report));
+                }
+            }};
+        }
+
+        convert_field!("st_characters", st_characters);
+        convert_field!("st_phrases", st_phrases);
+        convert_field!("ts_characters", ts_characters);
+        convert_field!("ts_phrases", ts_phrases);
+        convert_field!("tw_phrases", tw_phrases);
+        convert_field!("tw_phrases_rev", tw_phrases_rev);
+        convert_field!("tw_variants", tw_variants);
+        convert_field!("tw_variants_rev", tw_variants_rev);
+        convert_field!("tw_variants_rev_phrases", tw_variants_rev_phrases);
+        convert_field!("hk_variants", hk_variants);
+        convert_field!("hk_variants_rev", hk_variants_rev);
+        convert_field!("hk_variants_rev_phrases", hk_variants_rev_phrases);
+        convert_field!("jps_characters", jps_characters);
+        convert_field!("jps_phrases", jps_phrases);
+        convert_field!("jp_variants", jp_variants);
+        convert_field!("jp_variants_rev", jp_variants_rev);
+        convert_field!("st_punctuations", st_punctuations);
+        convert_field!("ts_punctuations", ts_punctuations);
+
+        (out.finish(), reports)
+    }
+}
+
 impl From<&DictMaxLen> for DictMaxLenSerde {
     fn from(d: &DictMaxLen) -> Self {
         // map → BTreeMap<String,String>
@@ -155,10 +700,13 @@ impl From<&DictMaxLen> for DictMaxLenSerde {
                 starter_len_mask.insert(ch.to_string(), *mask);
             }
         } else if !d.first_len_mask64.is_empty() {
-            // If sparse not kept but dense exists, serialize dense back to sparse BMP form
+            // If sparse not kept but dense exists, serialize dense back to sparse
+            // BMP form. The dense arrays may be watermark-bounded (see
+            // `DictMaxLen::starter_base`), so each index must be offset by
+            // `starter_base` to recover the actual codepoint.
             for (i, &m) in d.first_len_mask64.iter().enumerate() {
                 if m != 0 {
-                    if let Some(ch) = char::from_u32(i as u32) {
+                    if let Some(ch) = char::from_u32(d.starter_base + i as u32) {
                         starter_len_mask.insert(ch.to_string(), m);
                     }
                 }
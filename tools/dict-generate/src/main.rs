@@ -2,12 +2,14 @@ mod json_io;
 
 use crate::json_io::DictionaryMaxlengthSerde;
 use clap::{Arg, Command};
-use opencc_fmmseg::dictionary_lib::DictionaryMaxlength;
+use opencc_fmmseg::dictionary_lib::{BadLine, Codec, DictionaryMaxlength, LineErrorPolicy};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
 use std::fs::File;
 use std::io::{BufWriter, Read, Write};
 use std::path::Path;
 use std::time::Duration;
-use std::{fs, io};
+use std::{fs, io, thread};
 use ureq::Agent;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -24,7 +26,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .long("format")
                 .value_name("format")
                 .default_value("zstd")
-                .help("Dictionary format: [zstd|cbor|json]"),
+                .help("Dictionary format: [zstd|cbor|bincode|json|pack]"),
         )
         .arg(
             Arg::new("pretty")
@@ -39,8 +41,171 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .value_name("filename")
                 .help("Write generated dictionary to <filename>. If not specified, a default filename is used."),
         )
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("file")
+                .help("Load an existing dictionary_maxlength.{zstd,cbor,json} file and re-emit it in --format, instead of building from dicts/"),
+        )
+        .arg(
+            Arg::new("in-format")
+                .long("in-format")
+                .value_name("format")
+                .help("Format of --input when it can't be detected from the file extension: [zstd|cbor|bincode|json|pack]"),
+        )
+        .arg(
+            Arg::new("inspect")
+                .long("inspect")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print per-dictionary entry counts and max match length for --input instead of writing a file"),
+        )
+        .arg(
+            Arg::new("dump-starters")
+                .long("dump-starters")
+                .value_name("table")
+                .help("Print the run-length-encoded starter chunks (see DictMaxLen::starter_chunks) for --input's <table>, instead of writing a file"),
+        )
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .visible_alias("refresh")
+                .action(clap::ArgAction::SetTrue)
+                .help("Re-download dicts/ from GitHub even if it already exists, re-recording every file's SHA-256 in the local sidecar"),
+        )
+        .arg(
+            Arg::new("mask-policy")
+                .long("mask-policy")
+                .value_name("policy")
+                .default_value("trust")
+                .help("How --input/--in-format json treats stored key_length_mask/starter_len_mask: [trust|verify|recompute]"),
+        )
+        .arg(
+            Arg::new("dicts-dir")
+                .long("dicts-dir")
+                .value_name("dir")
+                .default_value("dicts")
+                .help("Directory to build from instead of the default dicts/ (see DictionaryMaxlength::from_dir)"),
+        )
+        .arg(
+            Arg::new("manifest")
+                .long("manifest")
+                .value_name("file")
+                .help("JSON manifest mapping each table slot to a filename in --dicts-dir; omit to assume standard upstream OpenCC filenames"),
+        )
+        .arg(
+            Arg::new("codec")
+                .long("codec")
+                .value_name("codec")
+                .default_value("zstd")
+                .help("Compression codec for --format pack: [zstd|lz4|bzip2|xz|none]"),
+        )
+        .arg(
+            Arg::new("level")
+                .long("level")
+                .value_name("n")
+                .default_value("19")
+                .help("Zstd compression level for --format pack --codec zstd (1-22)"),
+        )
+        .arg(
+            Arg::new("lenient")
+                .long("lenient")
+                .action(clap::ArgAction::SetTrue)
+                .help("Skip malformed .txt data lines instead of aborting the build; each one is printed as a warning (see DictionaryMaxlength::from_dicts_with_policy)"),
+        )
         .get_matches();
 
+    let dict_format = matches.get_one::<String>("format").map(String::as_str);
+    let pretty_json = matches.get_flag("pretty"); // default compact if false
+
+    let default_output = match dict_format {
+        Some("zstd") => "dictionary_maxlength.zstd",
+        Some("cbor") => "dictionary_maxlength.cbor",
+        Some("bincode") => "dictionary_maxlength.bincode.zst",
+        Some("json") => "dictionary_maxlength.json",
+        Some("pack") => "dictionary_maxlength.ocfm",
+        _ => "dictionary_maxlength.unknown",
+    };
+
+    let output_file = matches
+        .get_one::<String>("output")
+        .map(|s| s.as_str())
+        .unwrap_or(default_output);
+
+    let level: i32 = matches
+        .get_one::<String>("level")
+        .map(|s| s.as_str())
+        .unwrap_or("19")
+        .parse()
+        .map_err(|_| "Invalid --level: expected an integer")?;
+    let codec = parse_codec(
+        matches.get_one::<String>("codec").map(String::as_str).unwrap_or("zstd"),
+        level,
+    )?;
+
+    // Convert/inspect mode: load an existing artifact instead of building from dicts/.
+    if let Some(input_file) = matches.get_one::<String>("input") {
+        let in_format = matches
+            .get_one::<String>("in-format")
+            .map(String::as_str)
+            .or_else(|| detect_format(Path::new(input_file)))
+            .ok_or_else(|| {
+                format!(
+                    "Cannot detect input format from '{input_file}'; pass --in-format [zstd|cbor|json]"
+                )
+            })?;
+
+        let mask_policy = parse_mask_policy(
+            matches
+                .get_one::<String>("mask-policy")
+                .map(String::as_str)
+                .unwrap_or("trust"),
+        )?;
+        let mut dictionary = load_dictionary(input_file, in_format, mask_policy)?;
+
+        if matches.get_flag("inspect") {
+            print_stats(input_file, &dictionary);
+            return Ok(());
+        }
+
+        if let Some(table_name) = matches.get_one::<String>("dump-starters") {
+            dump_starters(table_name, &dictionary)?;
+            return Ok(());
+        }
+
+        save_dictionary(&mut dictionary, dict_format, output_file, pretty_json, codec)?;
+        eprintln!(
+            "{BLUE}Converted '{input_file}' ({in_format}) -> '{output_file}' ({}){RESET}",
+            dict_format.unwrap_or("unknown")
+        );
+        return Ok(());
+    }
+
+    let force_refresh = matches.get_flag("force");
+    let dicts_dir = matches
+        .get_one::<String>("dicts-dir")
+        .map(String::as_str)
+        .unwrap_or("dicts");
+    let manifest = matches.get_one::<String>("manifest").map(String::as_str);
+    let lenient = matches.get_flag("lenient");
+
+    // A custom --dicts-dir (or --manifest) means the caller is pointing at
+    // their own pack sources, not the upstream dicts/ checkout — skip the
+    // GitHub download prompt entirely and build via `from_dir`.
+    if dicts_dir != "dicts" || manifest.is_some() {
+        let policy = if lenient {
+            LineErrorPolicy::Lenient
+        } else {
+            LineErrorPolicy::Strict
+        };
+        let (mut dictionary, bad_lines) =
+            DictionaryMaxlength::from_dir_with_policy(dicts_dir, manifest, policy)?;
+        warn_bad_lines(&bad_lines);
+        save_dictionary(&mut dictionary, dict_format, output_file, pretty_json, codec)?;
+        eprintln!("{BLUE}Dictionary built from '{dicts_dir}' -> '{output_file}' ({}){RESET}", dict_format.unwrap_or("unknown"));
+        return Ok(());
+    }
+
     let dict_dir = Path::new("dicts");
     if !dict_dir.exists() {
         eprint!("{BLUE}Local 'dicts/' not found. Proceed with downloading dictionaries from GitHub? (Y/n): {RESET}");
@@ -52,55 +217,260 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         if answer.is_empty() || answer == "y" || answer == "yes" {
             eprintln!("{BLUE}Downloading from GitHub...{RESET}");
-            fetch_dicts_from_github(dict_dir)?;
+            fetch_dicts_from_github(dict_dir, force_refresh)?;
         } else {
             eprintln!("{BLUE}Aborted by user. Exiting.{RESET}");
             return Ok(()); // or `std::process::exit(0);` if you want a hard exit
         }
+    } else if force_refresh {
+        eprintln!("{BLUE}--force: refreshing dicts/ from GitHub...{RESET}");
+        fetch_dicts_from_github(dict_dir, force_refresh)?;
     }
 
-    let dict_format = matches.get_one::<String>("format").map(String::as_str);
-    let pretty_json = matches.get_flag("pretty"); // default compact if false
-
-    let default_output = match dict_format {
-        Some("zstd") => "dictionary_maxlength.zstd",
-        Some("cbor") => "dictionary_maxlength.cbor",
-        Some("json") => "dictionary_maxlength.json",
-        _ => "dictionary_maxlength.unknown",
+    let policy = if lenient {
+        LineErrorPolicy::Lenient
+    } else {
+        LineErrorPolicy::Strict
     };
+    let (mut dictionary, bad_lines) = DictionaryMaxlength::from_dicts_with_policy(policy)?;
+    warn_bad_lines(&bad_lines);
+    save_dictionary(&mut dictionary, dict_format, output_file, pretty_json, codec)?;
+    match dict_format {
+        Some("zstd") => eprintln!("{BLUE}Dictionary saved in ZSTD format at: {output_file}{RESET}"),
+        Some("cbor") => eprintln!("{BLUE}Dictionary saved in CBOR format at: {output_file}{RESET}"),
+        Some("bincode") => {
+            eprintln!("{BLUE}Dictionary saved in bincode (Zstd-compressed) format at: {output_file}{RESET}")
+        }
+        Some("json") => {
+            let style = if pretty_json { "pretty" } else { "compact" };
+            eprintln!("{BLUE}Dictionary saved in JSON ({style}) at: {output_file}{RESET}");
+        }
+        Some("pack") => {
+            eprintln!("{BLUE}Dictionary saved as a {codec:?} pack at: {output_file}{RESET}")
+        }
+        other => eprintln!(
+            "{BLUE}Unsupported format: {}{RESET}",
+            other.unwrap_or("unknown")
+        ),
+    }
 
-    let output_file = matches
-        .get_one::<String>("output")
-        .map(|s| s.as_str())
-        .unwrap_or(default_output);
+    Ok(())
+}
 
-    match dict_format {
+/// Parses the `--codec` flag's value, pairing it with `--level` for
+/// [`Codec::Zstd`], into a [`Codec`] for `--format pack`.
+fn parse_codec(name: &str, level: i32) -> Result<Codec, Box<dyn std::error::Error>> {
+    match name.to_lowercase().as_str() {
+        "zstd" => Ok(Codec::Zstd { level }),
+        "lz4" => Ok(Codec::Lz4),
+        "bzip2" => Ok(Codec::Bzip2),
+        "xz" => Ok(Codec::Xz),
+        "none" => Ok(Codec::None),
+        other => Err(format!("Unsupported --codec: {other} (expected zstd|lz4|bzip2|xz|none)").into()),
+    }
+}
+
+/// Prints each [`BadLine`] skipped under `--lenient` as a warning. A no-op
+/// when `bad_lines` is empty (the `--lenient` flag was off, or every line
+/// happened to parse).
+fn warn_bad_lines(bad_lines: &[BadLine]) {
+    const BLUE: &str = "\x1B[1;34m";
+    const RESET: &str = "\x1B[0m";
+
+    for bad_line in bad_lines {
+        eprintln!(
+            "{BLUE}Skipped {}:{} (missing TAB separator): {:?}{RESET}",
+            bad_line.dict, bad_line.line_no, bad_line.content
+        );
+    }
+    if !bad_lines.is_empty() {
+        eprintln!("{BLUE}--lenient: skipped {} malformed line(s){RESET}", bad_lines.len());
+    }
+}
+
+/// Detects a dictionary artifact's format from its file extension.
+fn detect_format(path: &Path) -> Option<&'static str> {
+    match path.extension().and_then(|e| e.to_str())?.to_lowercase().as_str() {
+        "zst" if path.to_string_lossy().ends_with(".bincode.zst") => Some("bincode"),
+        "zstd" | "zst" => Some("zstd"),
+        "cbor" => Some("cbor"),
+        "json" => Some("json"),
+        "ocfm" => Some("pack"),
+        _ => None,
+    }
+}
+
+/// Parses the `--mask-policy` flag's value into a [`json_io::MaskPolicy`].
+fn parse_mask_policy(value: &str) -> Result<json_io::MaskPolicy, Box<dyn std::error::Error>> {
+    match value.to_lowercase().as_str() {
+        "trust" => Ok(json_io::MaskPolicy::Trust),
+        "verify" => Ok(json_io::MaskPolicy::Verify),
+        "recompute" => Ok(json_io::MaskPolicy::Recompute),
+        other => Err(format!("Unsupported --mask-policy: {other} (expected trust|verify|recompute)").into()),
+    }
+}
+
+/// Loads a [`DictionaryMaxlength`] from `path` in the given format, used by
+/// the `--input`/`--in-format` convert and inspect modes. `mask_policy` only affects the `json`
+/// format — see [`json_io::MaskPolicy`] — and is ignored otherwise since the other formats don't
+/// carry a separate stored-vs-recomputed mask distinction.
+fn load_dictionary(
+    path: &str,
+    format: &str,
+    mask_policy: json_io::MaskPolicy,
+) -> Result<DictionaryMaxlength, Box<dyn std::error::Error>> {
+    match format {
+        "zstd" => Ok(DictionaryMaxlength::load_cbor_compressed(path)?),
+        "cbor" => Ok(DictionaryMaxlength::deserialize_from_cbor(path)?),
+        "bincode" => Ok(DictionaryMaxlength::from_bincode_compressed(path)?),
+        "pack" => Ok(DictionaryMaxlength::load_compressed(path)?),
+        "json" => {
+            if mask_policy == json_io::MaskPolicy::Trust {
+                let file = File::open(path)?;
+                Ok(json_io::try_from_json_reader(file)?)
+            } else {
+                let file = File::open(path)?;
+                let dto: DictionaryMaxlengthSerde = serde_json::from_reader(file)?;
+                let (dictionary, reports) = dto.into_internal_with_policy(mask_policy);
+                if !reports.is_empty() {
+                    let message = json_io::format_mask_reports(&reports);
+                    eprintln!("\x1B[1;33mwarning: {message}\x1B[0m");
+                    DictionaryMaxlength::set_last_error(&message);
+                }
+                Ok(dictionary)
+            }
+        }
+        other => Err(format!("Unsupported input format: {other}").into()),
+    }
+}
+
+/// Writes `dictionary` to `output_file` in the given format, shared by the
+/// normal `dicts/`-building path and the `--input` convert path.
+fn save_dictionary(
+    dictionary: &mut DictionaryMaxlength,
+    format: Option<&str>,
+    output_file: &str,
+    pretty_json: bool,
+    codec: Codec,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
         Some("zstd") => {
-            let dictionary = DictionaryMaxlength::from_dicts()?;
-            DictionaryMaxlength::save_compressed(&dictionary, output_file)?;
-            eprintln!("{BLUE}Dictionary saved in ZSTD format at: {output_file}{RESET}");
+            DictionaryMaxlength::save_cbor_compressed(dictionary, output_file)?;
         }
         Some("cbor") => {
-            let dictionary = DictionaryMaxlength::from_dicts()?;
-            let file = File::create(output_file)?;
-            serde_cbor::to_writer(file, &dictionary)?;
-            eprintln!("{BLUE}Dictionary saved in CBOR format at: {output_file}{RESET}");
+            dictionary.serialize_to_cbor(output_file)?;
+        }
+        Some("bincode") => {
+            // Pre-build each table's byte-level FST once here, so the
+            // `from_embedded_bincode`/`from_bincode_compressed` loaders can
+            // just deserialize it instead of rebuilding it on every load.
+            dictionary.build_all_byte_fsts();
+            dictionary.to_bincode_compressed(output_file)?;
         }
         Some("json") => {
-            let dictionary = DictionaryMaxlength::from_dicts()?;
             // IMPORTANT: use DTO for JSON so keys are Strings
-            write_reference_json(&dictionary, output_file, /* pretty = */ pretty_json)?;
-            let style = if pretty_json { "pretty" } else { "compact" };
-            eprintln!("{BLUE}Dictionary saved in JSON ({style}) at: {output_file}{RESET}");
+            write_reference_json(dictionary, output_file, pretty_json)?;
+        }
+        Some("pack") => {
+            dictionary.build_pack(output_file, codec)?;
         }
         other => {
-            let format_str = other.unwrap_or("unknown");
-            eprintln!("{BLUE}Unsupported format: {format_str}{RESET}");
+            return Err(format!("Unsupported format: {}", other.unwrap_or("unknown")).into());
         }
     }
+    Ok(())
+}
+
+/// Prints per-dictionary entry counts and max match length instead of
+/// writing a file, for `--input ... --inspect`.
+fn print_stats(input_file: &str, dictionary: &DictionaryMaxlength) {
+    const BLUE: &str = "\x1B[1;34m";
+    const RESET: &str = "\x1B[0m";
 
+    let tables: [(&str, &opencc_fmmseg::dictionary_lib::DictMaxLen); 18] = [
+        ("st_characters", &dictionary.st_characters),
+        ("st_phrases", &dictionary.st_phrases),
+        ("ts_characters", &dictionary.ts_characters),
+        ("ts_phrases", &dictionary.ts_phrases),
+        ("tw_phrases", &dictionary.tw_phrases),
+        ("tw_phrases_rev", &dictionary.tw_phrases_rev),
+        ("tw_variants", &dictionary.tw_variants),
+        ("tw_variants_rev", &dictionary.tw_variants_rev),
+        ("tw_variants_rev_phrases", &dictionary.tw_variants_rev_phrases),
+        ("hk_variants", &dictionary.hk_variants),
+        ("hk_variants_rev", &dictionary.hk_variants_rev),
+        ("hk_variants_rev_phrases", &dictionary.hk_variants_rev_phrases),
+        ("jps_characters", &dictionary.jps_characters),
+        ("jps_phrases", &dictionary.jps_phrases),
+        ("jp_variants", &dictionary.jp_variants),
+        ("jp_variants_rev", &dictionary.jp_variants_rev),
+        ("st_punctuations", &dictionary.st_punctuations),
+        ("ts_punctuations", &dictionary.ts_punctuations),
+    ];
+
+    eprintln!("{BLUE}Stats for '{input_file}':{RESET}");
+    let mut total_entries = 0usize;
+    for (name, table) in tables {
+        let entries = table.map.len();
+        total_entries += entries;
+        eprintln!("  {name:<24} entries: {entries:>8}   max_len: {}", table.max_len);
+    }
+    eprintln!("{BLUE}Total entries: {total_entries}{RESET}");
+}
+
+/// Prints the run-length-encoded starter chunks (see
+/// [`opencc_fmmseg::dictionary_lib::DictMaxLen::starter_chunks`]) for
+/// `--input ... --dump-starters <table>`, showing exactly which Unicode
+/// ranges `table` gates instead of writing a file.
+fn dump_starters(
+    table_name: &str,
+    dictionary: &DictionaryMaxlength,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tables: [(&str, &opencc_fmmseg::dictionary_lib::DictMaxLen); 18] = [
+        ("st_characters", &dictionary.st_characters),
+        ("st_phrases", &dictionary.st_phrases),
+        ("ts_characters", &dictionary.ts_characters),
+        ("ts_phrases", &dictionary.ts_phrases),
+        ("tw_phrases", &dictionary.tw_phrases),
+        ("tw_phrases_rev", &dictionary.tw_phrases_rev),
+        ("tw_variants", &dictionary.tw_variants),
+        ("tw_variants_rev", &dictionary.tw_variants_rev),
+        ("tw_variants_rev_phrases", &dictionary.tw_variants_rev_phrases),
+        ("hk_variants", &dictionary.hk_variants),
+        ("hk_variants_rev", &dictionary.hk_variants_rev),
+        ("hk_variants_rev_phrases", &dictionary.hk_variants_rev_phrases),
+        ("jps_characters", &dictionary.jps_characters),
+        ("jps_phrases", &dictionary.jps_phrases),
+        ("jp_variants", &dictionary.jp_variants),
+        ("jp_variants_rev", &dictionary.jp_variants_rev),
+        ("st_punctuations", &dictionary.st_punctuations),
+        ("ts_punctuations", &dictionary.ts_punctuations),
+    ];
+
+    let (_, table) = tables
+        .iter()
+        .find(|(name, _)| *name == table_name)
+        .ok_or_else(|| {
+            let known: Vec<&str> = tables.iter().map(|(name, _)| *name).collect();
+            format!("Unknown --dump-starters table '{table_name}'; expected one of: {}", known.join(", "))
+        })?;
+
+    for (start, end, mask, cap) in table.starter_chunks() {
+        if mask == 0 && cap == 0 {
+            continue;
+        }
+        if start == end {
+            println!("{start:?} ({:04X}): mask={mask:#018x} cap={cap}", start as u32);
+        } else {
+            println!(
+                "{start:?}..={end:?} ({:04X}..={:04X}): mask={mask:#018x} cap={cap}",
+                start as u32, end as u32
+            );
+        }
+    }
     Ok(())
 }
+
 pub fn write_reference_json(
     dicts: &DictionaryMaxlength,
     path: impl AsRef<Path>,
@@ -123,54 +493,177 @@ pub fn write_reference_json(
 fn to_io<E: std::error::Error + Send + Sync + 'static>(e: E) -> io::Error {
     io::Error::new(io::ErrorKind::Other, e)
 }
-/// Download missing dict files from GitHub repo
-fn fetch_dicts_from_github(dict_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    let dict_files = [
-        "STCharacters.txt",
-        "STPhrases.txt",
-        "TSCharacters.txt",
-        "TSPhrases.txt",
-        "TWPhrases.txt",
-        "TWPhrasesRev.txt",
-        "TWVariants.txt",
-        "TWVariantsRev.txt",
-        "TWVariantsRevPhrases.txt",
-        "HKVariants.txt",
-        "HKVariantsRev.txt",
-        "HKVariantsRevPhrases.txt",
-        "JPShinjitaiCharacters.txt",
-        "JPShinjitaiPhrases.txt",
-        "JPVariants.txt",
-        "JPVariantsRev.txt",
-        "STPunctuations.txt",
-        "TSPunctuations.txt",
-    ];
+/// Filenames fetched from `dicts/` upstream on GitHub.
+///
+/// There is no pinned-hash manifest here: the upstream repository doesn't
+/// publish signed checksums for these files, and hand-maintaining a table of
+/// expected SHA-256 digests alongside it would silently drift out of sync
+/// the moment upstream updates a dictionary, turning every future download
+/// into a hard failure (see [`fetch_dicts_from_github`]'s checksum-mismatch
+/// error). Instead, integrity is tracked locally: [`fetch_dicts_from_github`]
+/// records each downloaded file's digest in [`SHA256_SIDECAR`] and uses it
+/// only to detect local corruption / decide whether a re-download is needed,
+/// never to "verify" against a value we can't actually vouch for.
+const DICT_FILES: [&str; 18] = [
+    "STCharacters.txt",
+    "STPhrases.txt",
+    "TSCharacters.txt",
+    "TSPhrases.txt",
+    "TWPhrases.txt",
+    "TWPhrasesRev.txt",
+    "TWVariants.txt",
+    "TWVariantsRev.txt",
+    "TWVariantsRevPhrases.txt",
+    "HKVariants.txt",
+    "HKVariantsRev.txt",
+    "HKVariantsRevPhrases.txt",
+    "JPShinjitaiCharacters.txt",
+    "JPShinjitaiPhrases.txt",
+    "JPVariants.txt",
+    "JPVariantsRev.txt",
+    "STPunctuations.txt",
+    "TSPunctuations.txt",
+];
+
+/// Name of the sidecar file (written alongside the downloaded `.txt` files
+/// in `dict_dir`) that records each file's SHA-256 digest as of its last
+/// successful download, one `sha256  filename` line per file (the same
+/// layout `sha256sum` itself emits).
+const SHA256_SIDECAR: &str = ".sha256sums";
+
+const DOWNLOAD_MAX_ATTEMPTS: u32 = 4;
+const DOWNLOAD_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    digest.iter().fold(String::with_capacity(64), |mut acc, b| {
+        use std::fmt::Write;
+        let _ = write!(acc, "{b:02x}");
+        acc
+    })
+}
 
+/// Downloads `url` via `agent`, retrying with exponential backoff on
+/// transient failures before giving up after [`DOWNLOAD_MAX_ATTEMPTS`].
+fn download_with_retry(agent: &Agent, url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut backoff = DOWNLOAD_INITIAL_BACKOFF;
+    let mut last_err = None;
+
+    for attempt in 1..=DOWNLOAD_MAX_ATTEMPTS {
+        match agent.get(url).call() {
+            Ok(response) => {
+                let mut content = Vec::new();
+                response.into_body().into_reader().read_to_end(&mut content)?;
+                return Ok(content);
+            }
+            Err(err) => {
+                last_err = Some(err);
+                if attempt < DOWNLOAD_MAX_ATTEMPTS {
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+
+    Err(format!(
+        "giving up after {DOWNLOAD_MAX_ATTEMPTS} attempts fetching {url}: {}",
+        last_err.expect("loop always sets last_err before exhausting attempts")
+    )
+    .into())
+}
+
+/// Loads [`SHA256_SIDECAR`] from `dict_dir`, returning an empty map if it
+/// doesn't exist yet (e.g. first-ever download).
+fn load_sha256_sidecar(dict_dir: &Path) -> std::collections::HashMap<String, String> {
+    let Ok(content) = fs::read_to_string(dict_dir.join(SHA256_SIDECAR)) else {
+        return std::collections::HashMap::default();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let (sha256, filename) = line.split_once("  ")?;
+            Some((filename.to_string(), sha256.to_string()))
+        })
+        .collect()
+}
+
+/// Writes `sums` back out to [`SHA256_SIDECAR`] in `dict_dir`, in the same
+/// `sha256sum`-compatible `sha256  filename` layout it's read in.
+fn save_sha256_sidecar(
+    dict_dir: &Path,
+    sums: &std::collections::HashMap<String, String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut lines: Vec<String> = DICT_FILES
+        .iter()
+        .filter_map(|&filename| sums.get(filename).map(|sha256| format!("{sha256}  {filename}")))
+        .collect();
+    lines.push(String::new()); // trailing newline
+    fs::write(dict_dir.join(SHA256_SIDECAR), lines.join("\n"))?;
+    Ok(())
+}
+
+/// Download dict files from GitHub, concurrently. There is no upstream
+/// checksum to verify against (see [`DICT_FILES`]'s docs); instead, an
+/// on-disk file is skipped (unless `force` is set) only when its SHA-256
+/// still matches what [`SHA256_SIDECAR`] recorded from its own last
+/// successful download, which catches local corruption/truncation without
+/// pretending to authenticate upstream content we have no pinned digest for.
+fn fetch_dicts_from_github(dict_dir: &Path, force: bool) -> Result<(), Box<dyn std::error::Error>> {
     fs::create_dir_all(dict_dir)?;
 
     let config = Agent::config_builder()
         .timeout_global(Some(Duration::from_secs(10)))
         .build();
     let agent: Agent = config.into();
+    let previous_sums = load_sha256_sidecar(dict_dir);
 
-    for filename in &dict_files {
-        let url = format!(
-            "https://raw.githubusercontent.com/laisuk/opencc-fmmseg/master/dicts/{}",
-            filename
-        );
+    let downloaded: Vec<(String, String)> = DICT_FILES
+        .par_iter()
+        .map(|&filename| -> Result<Option<(String, String)>, Box<dyn std::error::Error + Send + Sync>> {
+            let dest_path = dict_dir.join(filename);
+
+            if !force {
+                if let (Ok(existing), Some(expected_sha256)) =
+                    (fs::read(&dest_path), previous_sums.get(filename))
+                {
+                    if sha256_hex(&existing) == *expected_sha256 {
+                        eprintln!("Up to date, skipping: {filename}");
+                        return Ok(None);
+                    }
+                }
+            }
+
+            let url = format!(
+                "https://raw.githubusercontent.com/laisuk/opencc-fmmseg/master/dicts/{filename}"
+            );
+
+            let content = download_with_retry(&agent, &url).map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
+                format!("{e}").into()
+            })?;
+            let actual_sha256 = sha256_hex(&content);
 
-        let response = agent.get(&url).call()?;
-        let mut content = String::new();
-        response
-            .into_body()
-            .into_reader()
-            .read_to_string(&mut content)?;
+            let mut file = File::create(&dest_path)?;
+            if let Err(err) = file.write_all(&content) {
+                drop(file);
+                let _ = fs::remove_file(&dest_path);
+                return Err(err.into());
+            }
 
-        let dest_path = dict_dir.join(filename);
-        let mut file = File::create(dest_path)?;
-        file.write_all(content.as_bytes())?;
+            eprintln!("Downloaded: {filename}");
+            Ok(Some((filename.to_string(), actual_sha256)))
+        })
+        .collect::<Result<Vec<Option<(String, String)>>, _>>()
+        .map_err(|e| -> Box<dyn std::error::Error> { format!("{e}").into() })?
+        .into_iter()
+        .flatten()
+        .collect();
 
-        eprintln!("Downloaded: {}", filename);
+    if !downloaded.is_empty() {
+        let mut sums = previous_sums;
+        sums.extend(downloaded);
+        save_sha256_sidecar(dict_dir, &sums)?;
     }
 
     Ok(())
@@ -2,12 +2,14 @@ mod office_converter;
 use office_converter::OfficeConverter;
 
 use clap::{Arg, ArgMatches, Command};
-use encoding_rs::Encoding;
+use encoding_rs::{DecoderResult, Encoding};
 use encoding_rs_io::DecodeReaderBytesBuilder;
 use opencc_fmmseg::OpenCC;
+use rayon::prelude::*;
 use std::collections::HashSet;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
 
 const CONFIG_LIST: [&str; 16] = [
     "s2t", "t2s", "s2tw", "tw2s", "s2twp", "tw2sp", "s2hk", "hk2s", "t2tw", "t2twp", "t2hk",
@@ -23,23 +25,31 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             Command::new("convert")
                 .about("Convert plain text using OpenCC")
                 .args(common_args())
+                .args(batch_args())
                 .arg(
                     Arg::new("in_enc")
                         .long("in-enc")
-                        .default_value("UTF-8")
-                        .help("Encoding for input"),
+                        .default_value("auto")
+                        .help("Encoding for input (\"auto\" sniffs BOM/GBK/GB18030/Big5/UTF-8)"),
                 )
                 .arg(
                     Arg::new("out_enc")
                         .long("out-enc")
                         .default_value("UTF-8")
                         .help("Encoding for output"),
+                )
+                .arg(
+                    Arg::new("strict")
+                        .long("strict")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Fail on invalid input bytes instead of lossily replacing them"),
                 ),
         )
         .subcommand(
             Command::new("office")
                 .about("Convert Office or EPUB documents using OpenCC")
                 .args(common_args())
+                .args(batch_args())
                 .arg(
                     Arg::new("format")
                         .short('f')
@@ -58,6 +68,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .long("auto-ext")
                         .action(clap::ArgAction::SetTrue)
                         .help("Infer format from file extension"),
+                )
+                .arg(
+                    Arg::new("in_enc")
+                        .short('e')
+                        .long("in-enc")
+                        .default_value("UTF-8")
+                        .help("Fallback encoding for parts without their own <?xml encoding=?>"),
+                )
+                .arg(
+                    Arg::new("out_enc")
+                        .long("out-enc")
+                        .default_value("UTF-8")
+                        .help("Encoding to re-encode converted parts into"),
                 ),
         )
         .get_matches();
@@ -74,13 +97,14 @@ fn common_args() -> Vec<Arg> {
         Arg::new("input")
             .short('i')
             .long("input")
-            .value_name("file")
-            .help("Input file (use stdin if omitted for non-office documents)"),
+            .value_name("path")
+            .action(clap::ArgAction::Append)
+            .help("Input file, directory, or glob pattern (repeatable; stdin if omitted for non-office documents)"),
         Arg::new("output")
             .short('o')
             .long("output")
             .value_name("file")
-            .help("Output file (use stdout if omitted for non-office documents)"),
+            .help("Output file (use stdout if omitted for non-office documents); ignored in batch mode, see --output-dir"),
         Arg::new("config")
             .short('c')
             .long("config")
@@ -95,13 +119,64 @@ fn common_args() -> Vec<Arg> {
     ]
 }
 
+/// Batch-mode arguments shared by `convert` and `office`: multiple/glob
+/// `--input` values (declared in [`common_args`]) are dispatched through
+/// [`run_batch`] whenever more than one file is matched, a directory or
+/// glob pattern is given, or any of these three are set.
+fn batch_args() -> Vec<Arg> {
+    vec![
+        Arg::new("recursive")
+            .long("recursive")
+            .action(clap::ArgAction::SetTrue)
+            .help("Recurse into directories passed to --input"),
+        Arg::new("output_dir")
+            .long("output-dir")
+            .value_name("dir")
+            .help("Write batch output here, preserving the relative input tree"),
+        Arg::new("jobs")
+            .long("jobs")
+            .value_name("N")
+            .value_parser(clap::value_parser!(usize))
+            .help("Parallel worker count for batch conversion (default: all cores)"),
+    ]
+}
+
+/// Whether `raw_inputs` (and/or `--recursive`/`--output-dir`/`--jobs`)
+/// describe a batch job rather than the classic single-file-or-stdin case.
+fn is_batch(raw_inputs: &[&String], matches: &ArgMatches) -> bool {
+    raw_inputs.len() != 1
+        || is_glob_pattern(raw_inputs[0])
+        || Path::new(raw_inputs[0].as_str()).is_dir()
+        || matches.get_one::<String>("output_dir").is_some()
+        || matches.get_flag("recursive")
+        || matches.get_one::<usize>("jobs").is_some()
+}
+
 fn handle_convert(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
-    let input_file = matches.get_one::<String>("input");
+    let raw_inputs: Vec<&String> = matches
+        .get_many::<String>("input")
+        .map(|v| v.collect())
+        .unwrap_or_default();
+
+    if raw_inputs.is_empty() {
+        return handle_convert_single(matches, None);
+    }
+    if !is_batch(&raw_inputs, matches) {
+        return handle_convert_single(matches, Some(raw_inputs[0]));
+    }
+    handle_convert_batch(matches, &raw_inputs)
+}
+
+fn handle_convert_single(
+    matches: &ArgMatches,
+    input_file: Option<&String>,
+) -> Result<(), Box<dyn std::error::Error>> {
     let output_file = matches.get_one::<String>("output");
     let config = matches.get_one::<String>("config").unwrap();
     let in_enc = matches.get_one::<String>("in_enc").unwrap();
     let out_enc = matches.get_one::<String>("out_enc").unwrap();
     let punctuation = matches.get_flag("punct");
+    let strict = matches.get_flag("strict");
 
     let is_console = input_file.is_none();
     let mut input: Box<dyn Read> = match input_file {
@@ -119,7 +194,7 @@ fn handle_convert(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>
         remove_utf8_bom(&mut buffer);
     }
 
-    let input_str = decode_input(&buffer, in_enc)?;
+    let input_str = decode_input(&buffer, in_enc, is_console, strict)?;
     let output_str = OpenCC::new().convert(&input_str, config, punctuation);
 
     let is_console_output = output_file.is_none();
@@ -140,34 +215,90 @@ fn handle_convert(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+fn handle_convert_batch(
+    matches: &ArgMatches,
+    raw_inputs: &[&String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = matches.get_one::<String>("config").unwrap().clone();
+    let in_enc = matches.get_one::<String>("in_enc").unwrap().clone();
+    let out_enc = matches.get_one::<String>("out_enc").unwrap().clone();
+    let punctuation = matches.get_flag("punct");
+    let strict = matches.get_flag("strict");
+    let recursive = matches.get_flag("recursive");
+    let output_dir = matches.get_one::<String>("output_dir").cloned();
+    let jobs = matches.get_one::<usize>("jobs").copied();
+
+    let entries = expand_inputs(raw_inputs, recursive)?;
+    if entries.is_empty() {
+        eprintln!("❌  No input files matched");
+        return Ok(());
+    }
+
+    let results = run_batch(jobs, entries, |(input, root)| {
+        let output = batch_output_path(&input, &root, output_dir.as_deref(), None);
+        let outcome: io::Result<()> = (|| {
+            let buffer = fs::read(&input)?;
+            let input_str = decode_input(&buffer, &in_enc, false, strict)?;
+            let output_str = OpenCC::new().convert(&input_str, &config, punctuation);
+            if let Some(parent) = output.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = BufWriter::new(File::create(&output)?);
+            encode_and_write_output(&output_str, &out_enc, &mut file)?;
+            file.flush()
+        })();
+
+        match outcome {
+            Ok(()) => JobResult::ok(input, output),
+            Err(e) => JobResult::err(input, output, e.to_string()),
+        }
+    });
+
+    print_batch_summary(&results);
+    Ok(())
+}
+
 fn handle_office(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
     let office_extensions: HashSet<&'static str> =
-        ["docx", "xlsx", "pptx", "odt", "ods", "odp", "epub"].into();
+        ["docx", "xlsx", "pptx", "odt", "ods", "odp", "epub", "pdf"].into();
 
-    let input_file = matches
-        .get_one::<String>("input")
-        .ok_or("❌  Input file is required for office mode")?;
+    let raw_inputs: Vec<&String> = matches
+        .get_many::<String>("input")
+        .map(|v| v.collect())
+        .unwrap_or_default();
+    if raw_inputs.is_empty() {
+        return Err("❌  Input file is required for office mode".into());
+    }
 
+    if !is_batch(&raw_inputs, matches) {
+        return handle_office_single(matches, raw_inputs[0], &office_extensions);
+    }
+    handle_office_batch(matches, &raw_inputs, office_extensions)
+}
+
+fn handle_office_single(
+    matches: &ArgMatches,
+    input_file: &str,
+    office_extensions: &HashSet<&'static str>,
+) -> Result<(), Box<dyn std::error::Error>> {
     let output_file = matches.get_one::<String>("output");
     let config = matches.get_one::<String>("config").unwrap();
     let punctuation = matches.get_flag("punct");
     let keep_font = matches.get_flag("keep_font");
     let auto_ext = matches.get_flag("auto_ext");
     let format = matches.get_one::<String>("format").map(String::as_str);
+    let in_enc = matches.get_one::<String>("in_enc").unwrap();
+    let out_enc = matches.get_one::<String>("out_enc").unwrap();
 
     let office_format = match format {
-        Some(f) => f.to_lowercase(),
+        Some(f) => normalize_office_format(f, office_extensions)?,
         None => {
             if auto_ext {
-                let ext = std::path::Path::new(input_file)
+                let ext = Path::new(input_file)
                     .extension()
                     .and_then(|e| e.to_str())
                     .ok_or("❌  Cannot infer file extension")?;
-                if office_extensions.contains(ext) {
-                    ext.to_string()
-                } else {
-                    return Err(format!("❌  Unsupported Office extension: .{ext}").into());
-                }
+                normalize_office_format(ext, office_extensions)?
             } else {
                 return Err("❌  Please provide --format or use --auto-ext".into());
             }
@@ -177,7 +308,7 @@ fn handle_office(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>>
     let final_output = match output_file {
         Some(path) => {
             if auto_ext
-                && std::path::Path::new(path).extension().is_none()
+                && Path::new(path).extension().is_none()
                 && office_extensions.contains(office_format.as_str())
             {
                 format!("{path}.{}", office_format)
@@ -186,7 +317,7 @@ fn handle_office(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>>
             }
         }
         None => {
-            let input_path = std::path::Path::new(input_file);
+            let input_path = Path::new(input_file);
             let file_stem = input_path
                 .file_stem()
                 .and_then(|s| s.to_str())
@@ -201,7 +332,7 @@ fn handle_office(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>>
     };
 
     let helper = OpenCC::new();
-    match OfficeConverter::convert(
+    match OfficeConverter::convert_with_rules(
         input_file,
         &final_output,
         &office_format,
@@ -209,6 +340,11 @@ fn handle_office(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>>
         config,
         punctuation,
         keep_font,
+        &[],
+        false,
+        false,
+        in_enc,
+        out_enc,
     ) {
         Ok(result) if result.success => {
             eprintln!(
@@ -227,6 +363,306 @@ fn handle_office(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+fn handle_office_batch(
+    matches: &ArgMatches,
+    raw_inputs: &[&String],
+    office_extensions: HashSet<&'static str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = matches.get_one::<String>("config").unwrap().clone();
+    let punctuation = matches.get_flag("punct");
+    let keep_font = matches.get_flag("keep_font");
+    let format = matches.get_one::<String>("format").cloned();
+    let in_enc = matches.get_one::<String>("in_enc").unwrap().clone();
+    let out_enc = matches.get_one::<String>("out_enc").unwrap().clone();
+    let recursive = matches.get_flag("recursive");
+    let output_dir = matches.get_one::<String>("output_dir").cloned();
+    let jobs = matches.get_one::<usize>("jobs").copied();
+
+    let entries = expand_inputs(raw_inputs, recursive)?;
+    if entries.is_empty() {
+        eprintln!("❌  No input files matched");
+        return Ok(());
+    }
+
+    let helper = OpenCC::new();
+    let results = run_batch(jobs, entries, |(input, root)| {
+        let office_format = match format.as_deref() {
+            Some(f) => normalize_office_format(f, &office_extensions),
+            None => input
+                .extension()
+                .and_then(|e| e.to_str())
+                .ok_or_else(|| "❌  Cannot infer file extension".to_string())
+                .and_then(|ext| normalize_office_format(ext, &office_extensions)),
+        };
+        let office_format = match office_format {
+            Ok(f) => f,
+            Err(message) => return JobResult::err(input.clone(), input, message),
+        };
+
+        let output = batch_output_path(&input, &root, output_dir.as_deref(), Some(&office_format));
+        if let Some(parent) = output.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                return JobResult::err(input, output, e.to_string());
+            }
+        }
+
+        match OfficeConverter::convert_with_rules(
+            &input.to_string_lossy(),
+            &output.to_string_lossy(),
+            &office_format,
+            &helper,
+            &config,
+            punctuation,
+            keep_font,
+            &[],
+            false,
+            false,
+            &in_enc,
+            &out_enc,
+        ) {
+            Ok(result) if result.success => JobResult::ok(input, output),
+            Ok(result) => JobResult::err(input, output, result.message),
+            Err(e) => JobResult::err(input, output, e.to_string()),
+        }
+    });
+
+    print_batch_summary(&results);
+    Ok(())
+}
+
+/// One batch job's outcome, collected by [`run_batch`] into the end-of-run
+/// summary printed by [`print_batch_summary`].
+struct JobResult {
+    input: PathBuf,
+    output: PathBuf,
+    success: bool,
+    message: String,
+}
+
+impl JobResult {
+    fn ok(input: PathBuf, output: PathBuf) -> Self {
+        Self {
+            input,
+            output,
+            success: true,
+            message: "✅ converted".to_string(),
+        }
+    }
+
+    fn err(input: PathBuf, output: PathBuf, message: String) -> Self {
+        Self {
+            input,
+            output,
+            success: false,
+            message,
+        }
+    }
+}
+
+/// Runs `work` over every entry in `entries` using a `rayon` parallel
+/// iterator, so one failed file doesn't abort the rest of the batch.
+/// `jobs` bounds the worker count (`None` uses rayon's default, one thread
+/// per core).
+fn run_batch<F>(jobs: Option<usize>, entries: Vec<(PathBuf, PathBuf)>, work: F) -> Vec<JobResult>
+where
+    F: Fn((PathBuf, PathBuf)) -> JobResult + Sync + Send,
+{
+    let run = || entries.into_par_iter().map(work).collect();
+    match jobs {
+        Some(n) => rayon::ThreadPoolBuilder::new()
+            .num_threads(n)
+            .build()
+            .expect("failed to build rayon thread pool")
+            .install(run),
+        None => run(),
+    }
+}
+
+fn print_batch_summary(results: &[JobResult]) {
+    let succeeded = results.iter().filter(|r| r.success).count();
+    let failed = results.len() - succeeded;
+    eprintln!(
+        "\n📦  Batch complete: {succeeded} succeeded, {failed} failed (of {})",
+        results.len()
+    );
+    for result in results.iter().filter(|r| !r.success) {
+        eprintln!("  ❌ {}: {}", result.input.display(), result.message);
+    }
+    if succeeded > 0 {
+        for result in results.iter().filter(|r| r.success) {
+            eprintln!("  ✅ {} → {}", result.input.display(), result.output.display());
+        }
+    }
+}
+
+/// Whether `raw` should be treated as a glob pattern rather than a literal
+/// path — i.e. it contains any of `*`, `?`, or `[`.
+fn is_glob_pattern(raw: &str) -> bool {
+    raw.chars().any(|c| matches!(c, '*' | '?' | '['))
+}
+
+/// Expands each raw `--input` value into concrete files paired with the
+/// "root" their relative path (under `--output-dir`) is computed from:
+/// glob patterns (containing `*`, `?`, or `[`) are expanded with the `glob`
+/// crate, directories are walked (recursively when `recursive` is set), and
+/// a plain file passes through unchanged. For globs and plain files, `root`
+/// is the file's own parent, so `--output-dir` flattens them by file name;
+/// for directories, `root` is the directory itself, so the relative tree
+/// under it is preserved.
+fn expand_inputs(
+    raw_inputs: &[&String],
+    recursive: bool,
+) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut entries = Vec::new();
+    for raw in raw_inputs {
+        let path = Path::new(raw.as_str());
+        if is_glob_pattern(raw) {
+            let matched = glob::glob(raw).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("bad glob pattern {raw}: {e}"),
+                )
+            })?;
+            for entry in matched {
+                let file = entry.map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+                if file.is_file() {
+                    let root = file.parent().unwrap_or_else(|| Path::new(".")).to_path_buf();
+                    entries.push((file, root));
+                }
+            }
+        } else if path.is_dir() {
+            walk_dir(path, path, recursive, &mut entries)?;
+        } else {
+            let root = path.parent().unwrap_or_else(|| Path::new(".")).to_path_buf();
+            entries.push((path.to_path_buf(), root));
+        }
+    }
+    Ok(entries)
+}
+
+/// Collects every file directly under `dir` into `out` paired with `root`,
+/// recursing into subdirectories when `recursive` is set.
+fn walk_dir(
+    root: &Path,
+    dir: &Path,
+    recursive: bool,
+    out: &mut Vec<(PathBuf, PathBuf)>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if recursive {
+                walk_dir(root, &path, recursive, out)?;
+            }
+        } else {
+            out.push((path, root.to_path_buf()));
+        }
+    }
+    Ok(())
+}
+
+/// Resolves the output path for one batch job's `input`, matched under `root`
+/// (see [`expand_inputs`]).
+///
+/// - With `--output-dir`: preserves `input`'s path relative to `root` under
+///   that directory, swapping the extension to `out_ext` when given (office
+///   formats can change extension; plain-text `convert` passes `None` and
+///   keeps the original).
+/// - Without `--output-dir`: the same `{stem}_converted.{ext}` suffix
+///   convention the single-file `office` mode already uses, placed next to
+///   the input.
+fn batch_output_path(
+    input: &Path,
+    root: &Path,
+    output_dir: Option<&str>,
+    out_ext: Option<&str>,
+) -> PathBuf {
+    let file_stem = input
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("converted");
+    let ext = out_ext.or_else(|| input.extension().and_then(|e| e.to_str()));
+
+    match output_dir {
+        Some(dir) => {
+            let relative = input
+                .strip_prefix(root)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| PathBuf::from(input.file_name().unwrap_or(input.as_os_str())));
+            let mut dest = Path::new(dir).join(relative);
+            if let Some(ext) = ext {
+                dest.set_extension(ext);
+            }
+            dest
+        }
+        None => {
+            let parent = input.parent().unwrap_or_else(|| Path::new("."));
+            match ext {
+                Some(ext) => parent.join(format!("{file_stem}_converted.{ext}")),
+                None => parent.join(format!("{file_stem}_converted")),
+            }
+        }
+    }
+}
+
+/// Common shorthand/alternate names for `--format`, mapped to the extension
+/// [`OfficeConverter`] actually expects.
+const FORMAT_ALIASES: &[(&str, &str)] = &[
+    ("word", "docx"),
+    ("doc", "docx"),
+    ("excel", "xlsx"),
+    ("xls", "xlsx"),
+    ("powerpoint", "pptx"),
+    ("ppt", "pptx"),
+    ("ebook", "epub"),
+];
+
+/// Normalizes a `--format` value (or an inferred file extension): strips a
+/// leading `.` (so `--format .docx` works the same as `--format docx`),
+/// lower-cases it, and resolves it through [`FORMAT_ALIASES`]. Returns an
+/// error listing every supported extension and alias if the result still
+/// isn't one `office_extensions` recognizes.
+fn normalize_office_format(
+    raw: &str,
+    office_extensions: &HashSet<&'static str>,
+) -> Result<String, String> {
+    let trimmed = raw.strip_prefix('.').unwrap_or(raw).to_lowercase();
+    let resolved = FORMAT_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == trimmed)
+        .map(|&(_, ext)| ext.to_string())
+        .unwrap_or(trimmed);
+
+    if office_extensions.contains(resolved.as_str()) {
+        Ok(resolved)
+    } else {
+        Err(format!(
+            "❌  Unsupported Office format: {raw}\nSupported formats are: {}",
+            supported_formats_hint(office_extensions)
+        ))
+    }
+}
+
+/// Builds the "Supported formats are: ..." hint listing every extension in
+/// `office_extensions` plus every alias in [`FORMAT_ALIASES`] that resolves
+/// to one of them.
+fn supported_formats_hint(office_extensions: &HashSet<&'static str>) -> String {
+    let mut extensions: Vec<&str> = office_extensions.iter().copied().collect();
+    extensions.sort_unstable();
+
+    let aliases: Vec<String> = FORMAT_ALIASES
+        .iter()
+        .filter(|(_, ext)| office_extensions.contains(ext))
+        .map(|(alias, ext)| format!("{alias} → {ext}"))
+        .collect();
+
+    if aliases.is_empty() {
+        extensions.join(", ")
+    } else {
+        format!("{} (aliases: {})", extensions.join(", "), aliases.join(", "))
+    }
+}
+
 fn read_input(input: &mut dyn Read, is_console: bool) -> io::Result<Vec<u8>> {
     let mut buffer = Vec::new();
     if is_console {
@@ -243,9 +679,41 @@ fn read_input(input: &mut dyn Read, is_console: bool) -> io::Result<Vec<u8>> {
     Ok(buffer)
 }
 
-fn decode_input(buffer: &[u8], enc: &str) -> io::Result<String> {
+fn decode_input(buffer: &[u8], enc: &str, is_console: bool, strict: bool) -> io::Result<String> {
+    if enc.eq_ignore_ascii_case("auto") {
+        let (encoding, had_bom) = detect_encoding(buffer);
+        if is_console && io::stdin().is_terminal() {
+            eprintln!("🔎  Detected input encoding: {}", encoding.name());
+        }
+        let bytes = if had_bom {
+            &buffer[bom_len(buffer)..]
+        } else {
+            buffer
+        };
+        if encoding == encoding_rs::UTF_8 {
+            return if strict {
+                decode_strict_utf8(bytes)
+            } else {
+                Ok(String::from_utf8_lossy(bytes).into_owned())
+            };
+        }
+        if strict {
+            return decode_strict_with_encoding(bytes, encoding);
+        }
+        let mut reader = DecodeReaderBytesBuilder::new()
+            .encoding(Some(encoding))
+            .build(bytes);
+        let mut decoded = String::new();
+        reader.read_to_string(&mut decoded)?;
+        return Ok(decoded);
+    }
+
     if enc == "UTF-8" {
-        return Ok(String::from_utf8_lossy(buffer).into_owned());
+        return if strict {
+            decode_strict_utf8(buffer)
+        } else {
+            Ok(String::from_utf8_lossy(buffer).into_owned())
+        };
     }
     let encoding = Encoding::for_label(enc.as_bytes()).ok_or_else(|| {
         io::Error::new(
@@ -253,6 +721,9 @@ fn decode_input(buffer: &[u8], enc: &str) -> io::Result<String> {
             format!("Unsupported encoding: {enc}"),
         )
     })?;
+    if strict {
+        return decode_strict_with_encoding(buffer, encoding);
+    }
     let mut reader = DecodeReaderBytesBuilder::new()
         .encoding(Some(encoding))
         .build(buffer);
@@ -261,6 +732,117 @@ fn decode_input(buffer: &[u8], enc: &str) -> io::Result<String> {
     Ok(decoded)
 }
 
+/// Decodes `buffer` as UTF-8, aborting instead of substituting U+FFFD for
+/// malformed input. On failure, reports the byte offset of the first invalid
+/// sequence and the offending bytes, so pipeline users get a precise signal
+/// rather than silently-corrupted output.
+fn decode_strict_utf8(buffer: &[u8]) -> io::Result<String> {
+    std::str::from_utf8(buffer).map(str::to_owned).map_err(|e| {
+        let offset = e.valid_up_to();
+        let bad_len = e.error_len().unwrap_or(buffer.len() - offset);
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid UTF-8 at byte offset {offset}: {:02X?}",
+                &buffer[offset..offset + bad_len]
+            ),
+        )
+    })
+}
+
+/// Decodes `buffer` using `encoding`, aborting instead of substituting
+/// U+FFFD for malformed input, by driving [`encoding_rs`]'s low-level
+/// [`encoding_rs::Decoder`] directly (the convenience `decode*` methods used
+/// elsewhere in this file are inherently lossy and have no "report and stop"
+/// mode). On failure, reports the byte offset of the first invalid sequence
+/// and the offending bytes.
+fn decode_strict_with_encoding(buffer: &[u8], encoding: &'static Encoding) -> io::Result<String> {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut decoded = String::with_capacity(buffer.len());
+    let mut offset = 0;
+    loop {
+        let (result, bytes_read) =
+            decoder.decode_to_string_without_replacement(&buffer[offset..], &mut decoded, true);
+        match result {
+            DecoderResult::InputEmpty => return Ok(decoded),
+            DecoderResult::OutputFull => {
+                decoded.reserve(buffer.len());
+            }
+            DecoderResult::Malformed(bad_len, consumed_after) => {
+                // `bytes_read` already includes the malformed bytes and the
+                // `consumed_after` bytes read past them.
+                let end = (offset + bytes_read).saturating_sub(consumed_after as usize);
+                let start = end.saturating_sub(bad_len as usize);
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid {} sequence at byte offset {start}: {:02X?}",
+                        encoding.name(),
+                        &buffer[start..end]
+                    ),
+                ));
+            }
+        }
+        offset += bytes_read;
+    }
+}
+
+/// Sniffs `buffer`'s text encoding, returning `(encoding, had_bom)`.
+///
+/// Detection order: a byte-order mark first (UTF-8, UTF-16LE/BE via
+/// [`Encoding::for_bom`], plus the UTF-32LE/BE BOMs `encoding_rs` has no
+/// concept of, checked first since they share a prefix with UTF-16's); then,
+/// with no BOM, strict UTF-8 validation; and finally, for the common
+/// "legacy Chinese text file" case, the candidate among GB18030/GBK/Big5
+/// that produces the fewest U+FFFD replacement characters when decoded —
+/// the cheapest available proxy for "fewest invalid byte sequences" without
+/// a full n-gram language model.
+///
+/// UTF-32 input is detected (so its BOM can be stripped and reported) but not
+/// actually decoded as UTF-32 — `encoding_rs` doesn't support it, and UTF-32
+/// Chinese text files are vanishingly rare in practice — so it falls through
+/// to the same UTF-8/legacy scoring as un-BOM'd input.
+fn detect_encoding(buffer: &[u8]) -> (&'static Encoding, bool) {
+    if buffer.starts_with(&[0xFF, 0xFE, 0x00, 0x00]) || buffer.starts_with(&[0x00, 0x00, 0xFE, 0xFF])
+    {
+        let (encoding, _) = detect_encoding(&buffer[4..]);
+        return (encoding, true);
+    }
+    if let Some((encoding, bom_len)) = Encoding::for_bom(buffer) {
+        return (encoding, bom_len > 0);
+    }
+
+    if std::str::from_utf8(buffer).is_ok() {
+        return (encoding_rs::UTF_8, false);
+    }
+
+    const CANDIDATES: [&Encoding; 3] = [encoding_rs::GB18030, encoding_rs::GBK, encoding_rs::BIG5];
+    CANDIDATES
+        .iter()
+        .map(|&encoding| {
+            let (decoded, _, _) = encoding.decode(buffer);
+            let errors = decoded.chars().filter(|&c| c == '\u{FFFD}').count();
+            (encoding, errors)
+        })
+        .min_by_key(|&(_, errors)| errors)
+        .map(|(encoding, _)| (encoding, false))
+        .unwrap_or((encoding_rs::UTF_8, false))
+}
+
+/// The byte length of the BOM [`detect_encoding`] recognized at the start of
+/// `buffer`, so callers can skip past it before decoding.
+fn bom_len(buffer: &[u8]) -> usize {
+    if buffer.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        3
+    } else if buffer.starts_with(&[0xFF, 0xFE, 0x00, 0x00]) || buffer.starts_with(&[0x00, 0x00, 0xFE, 0xFF]) {
+        4
+    } else if buffer.starts_with(&[0xFF, 0xFE]) || buffer.starts_with(&[0xFE, 0xFF]) {
+        2
+    } else {
+        0
+    }
+}
+
 fn encode_and_write_output(output_str: &str, enc: &str, output: &mut dyn Write) -> io::Result<()> {
     if enc == "UTF-8" {
         write!(output, "{}", output_str)
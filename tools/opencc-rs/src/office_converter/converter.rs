@@ -1,8 +1,16 @@
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{self, Read, Write};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
+use encoding_rs::Encoding;
+use quick_xml::events::{BytesText, Event};
+use quick_xml::reader::Reader;
+use quick_xml::writer::Writer;
+use rayon::prelude::*;
 use regex::Regex;
 use tempfile::tempdir;
 use walkdir::WalkDir;
@@ -13,6 +21,8 @@ use zip::{
 
 use opencc_fmmseg::OpenCC;
 
+use super::pdf;
+
 pub struct ConversionResult {
     pub success: bool,
     pub message: String,
@@ -30,6 +40,89 @@ impl OfficeConverter {
         punctuation: bool,
         keep_font: bool,
     ) -> io::Result<ConversionResult> {
+        Self::convert_with_rules(
+            input_path,
+            output_path,
+            format,
+            helper,
+            config,
+            punctuation,
+            keep_font,
+            &[],
+            false,
+            false,
+            "UTF-8",
+            "UTF-8",
+        )
+    }
+
+    /// Like [`convert`](Self::convert), but converts each targeted document
+    /// part (slide, chapter, ...) concurrently across CPU cores via
+    /// [`rayon`] instead of sequentially. Worthwhile for `pptx`/`epub`
+    /// documents with many independent parts; for formats with a single
+    /// target part (`docx`, `xlsx`, the ODF family) it's equivalent to
+    /// [`convert`](Self::convert) plus thread-pool overhead.
+    pub fn convert_parallel(
+        input_path: &str,
+        output_path: &str,
+        format: &str,
+        helper: &OpenCC,
+        config: &str,
+        punctuation: bool,
+        keep_font: bool,
+    ) -> io::Result<ConversionResult> {
+        Self::convert_with_rules(
+            input_path,
+            output_path,
+            format,
+            helper,
+            config,
+            punctuation,
+            keep_font,
+            &[],
+            false,
+            true,
+            "UTF-8",
+            "UTF-8",
+        )
+    }
+
+    /// Like [`convert`](Self::convert), but merges `extra_rules` ahead of the
+    /// built-in [`default_font_mask_rules`] when `keep_font` is set, so a
+    /// caller can protect font names the defaults don't know about (a custom
+    /// schema, a format this module doesn't otherwise target); takes an
+    /// explicit `xml_aware` flag choosing between the two conversion
+    /// strategies (see [`convert_document_part`]); a `parallel` flag
+    /// that converts the targeted document parts concurrently across CPU
+    /// cores via [`rayon`] rather than one at a time, sharing `helper`
+    /// immutably across the worker threads; and `in_enc`/`out_enc` fallback
+    /// labels (e.g. `"UTF-8"`, `"GBK"`, `"Big5"`) used when a part's own
+    /// `<?xml ... encoding="..."?>` declaration is absent or unrecognized
+    /// (see [`decode_xml_part`]/[`reencode_xml_part`]).
+    #[allow(clippy::too_many_arguments)]
+    pub fn convert_with_rules(
+        input_path: &str,
+        output_path: &str,
+        format: &str,
+        helper: &OpenCC,
+        config: &str,
+        punctuation: bool,
+        keep_font: bool,
+        extra_rules: &[FontMaskRule],
+        xml_aware: bool,
+        parallel: bool,
+        in_enc: &str,
+        out_enc: &str,
+    ) -> io::Result<ConversionResult> {
+        // PDFs aren't zip containers, so they bypass the unzip/temp-dir/
+        // replace_with_temp flow below entirely and use their own reader/writer.
+        if format.eq_ignore_ascii_case("pdf") {
+            // No font-name masking step, and no text/markup to tell apart,
+            // for PDF text runs.
+            let _ = (keep_font, xml_aware, parallel, in_enc, out_enc);
+            return pdf::convert_pdf(input_path, output_path, helper, config, punctuation);
+        }
+
         let temp_dir = tempdir()?;
         let temp_path = temp_dir.path();
 
@@ -71,27 +164,58 @@ impl OfficeConverter {
         }
 
         // 2) Convert targeted XML/text files in-place under temp_path
-        for xml_file in get_target_xml_paths(format, temp_path) {
+        let target_paths = get_target_xml_paths(format, temp_path);
+        let convert_one = |xml_file: &PathBuf| -> io::Result<Option<String>> {
             if !xml_file.exists() || !xml_file.is_file() {
-                continue;
+                return Ok(None);
             }
-            let mut content = String::new();
-            File::open(&xml_file)?.read_to_string(&mut content)?;
+            let mut raw = Vec::new();
+            File::open(xml_file)?.read_to_end(&mut raw)?;
 
-            let mut font_map = HashMap::new();
-            if keep_font {
-                mask_font(&mut content, format, &mut font_map);
-            }
+            let (content, source_encoding) = decode_xml_part(&raw, in_enc)?;
+
+            let converted = convert_document_part(
+                content, format, helper, config, punctuation, keep_font, extra_rules, xml_aware,
+            )?;
+
+            let out_encoding = Encoding::for_label(out_enc.as_bytes()).unwrap_or(encoding_rs::UTF_8);
+            let reencoded = reencode_xml_part(&converted, out_encoding);
 
-            let mut converted = helper.convert(&content, config, punctuation);
-            if keep_font {
-                for (marker, original) in font_map {
-                    converted = converted.replace(&marker, &original);
+            File::create(xml_file)?.write_all(&reencoded)?;
+
+            if source_encoding != out_encoding {
+                let rel = xml_file
+                    .strip_prefix(temp_path)
+                    .unwrap_or(xml_file)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                Ok(Some(format!(
+                    "{rel} ({} → {})",
+                    source_encoding.name(),
+                    out_encoding.name()
+                )))
+            } else {
+                Ok(None)
+            }
+        };
+
+        let reencoded_parts: Vec<String> = if parallel {
+            target_paths
+                .par_iter()
+                .map(convert_one)
+                .collect::<io::Result<Vec<Option<String>>>>()?
+                .into_iter()
+                .flatten()
+                .collect()
+        } else {
+            let mut parts = Vec::new();
+            for xml_file in &target_paths {
+                if let Some(part) = convert_one(xml_file)? {
+                    parts.push(part);
                 }
             }
-
-            File::create(&xml_file)?.write_all(converted.as_bytes())?;
-        }
+            parts
+        };
 
         // 3) Output: write to temp then rename to final path
         let out_path = Path::new(output_path);
@@ -159,11 +283,179 @@ impl OfficeConverter {
             Ok(())
         })?;
 
+        let message = if reencoded_parts.is_empty() {
+            "✅ Conversion completed.".to_string()
+        } else {
+            format!(
+                "✅ Conversion completed. Re-encoded parts: {}",
+                reencoded_parts.join(", ")
+            )
+        };
+
         Ok(ConversionResult {
             success: true,
-            message: "✅ Conversion completed.".to_string(),
+            message,
         })
     }
+
+    /// Like [`convert`](Self::convert), but unzips, converts, and re-zips
+    /// entirely in memory — no `tempdir()`, no filesystem access at all —
+    /// so it works from WASM and server contexts where writing temp files
+    /// isn't possible. Callers that already hold document bytes (e.g. from a
+    /// network upload) can pipe them straight through.
+    ///
+    /// The EPUB `mimetype`-first/Stored rule and the zip-slip guard against
+    /// `..`/root-rooted entry names are preserved from the path-driven flow.
+    pub fn convert_reader<R: Read + Seek, W: Write + Seek>(
+        reader: R,
+        writer: W,
+        format: &str,
+        helper: &OpenCC,
+        config: &str,
+        punctuation: bool,
+        keep_font: bool,
+    ) -> io::Result<()> {
+        Self::convert_reader_with_rules(
+            reader, writer, format, helper, config, punctuation, keep_font, &[], false,
+        )
+    }
+
+    /// Like [`convert_reader`](Self::convert_reader), but merges `extra_rules`
+    /// ahead of the built-in [`default_font_mask_rules`] when `keep_font` is
+    /// set, and takes an explicit `xml_aware` flag. See
+    /// [`convert_with_rules`](Self::convert_with_rules).
+    #[allow(clippy::too_many_arguments)]
+    pub fn convert_reader_with_rules<R: Read + Seek, W: Write + Seek>(
+        mut reader: R,
+        writer: W,
+        format: &str,
+        helper: &OpenCC,
+        config: &str,
+        punctuation: bool,
+        keep_font: bool,
+        extra_rules: &[FontMaskRule],
+        xml_aware: bool,
+    ) -> io::Result<()> {
+        let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+
+        {
+            let mut archive = ZipArchive::new(&mut reader)?;
+            for i in 0..archive.len() {
+                let mut entry = archive.by_index(i)?;
+                let raw_name = entry.name().replace('\\', "/");
+                let rel_path = Path::new(&raw_name);
+
+                // Reject zip-slip & roots
+                if rel_path.components().any(|c| {
+                    matches!(
+                        c,
+                        std::path::Component::ParentDir | std::path::Component::RootDir
+                    )
+                }) {
+                    continue;
+                }
+
+                if entry.is_dir() || raw_name.ends_with('/') {
+                    continue;
+                }
+
+                let mut buf = Vec::new();
+                entry.read_to_end(&mut buf)?;
+                entries.push((raw_name, buf));
+            }
+        }
+
+        for (name, bytes) in entries.iter_mut() {
+            if !is_target_entry(format, name) {
+                continue;
+            }
+
+            let content = String::from_utf8(std::mem::take(bytes))
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+            let converted = convert_document_part(
+                content, format, helper, config, punctuation, keep_font, extra_rules, xml_aware,
+            )?;
+
+            *bytes = converted.into_bytes();
+        }
+
+        let mut zip_writer = ZipWriter::new(writer);
+
+        if format.eq_ignore_ascii_case("epub") {
+            if let Some((_, buf)) = entries.iter().find(|(name, _)| name == "mimetype") {
+                let opts: FileOptions<'_, ExtendedFileOptions> =
+                    FileOptions::default().compression_method(CompressionMethod::Stored);
+                zip_writer.start_file("mimetype", opts)?;
+                zip_writer.write_all(buf)?;
+            }
+        }
+
+        for (name, buf) in &entries {
+            if format.eq_ignore_ascii_case("epub") && name == "mimetype" {
+                continue;
+            }
+
+            let method = if name == "mimetype" {
+                CompressionMethod::Stored
+            } else {
+                CompressionMethod::Deflated
+            };
+            let options: FileOptions<'_, ExtendedFileOptions> =
+                FileOptions::default().compression_method(method);
+
+            zip_writer.start_file(name, options)?;
+            zip_writer.write_all(buf)?;
+        }
+
+        zip_writer.finish()?;
+        Ok(())
+    }
+
+    /// Thin wrapper over [`convert_reader`](Self::convert_reader) for callers
+    /// holding the whole document as an in-memory buffer.
+    pub fn convert_bytes(
+        input_bytes: &[u8],
+        format: &str,
+        helper: &OpenCC,
+        config: &str,
+        punctuation: bool,
+        keep_font: bool,
+    ) -> io::Result<Vec<u8>> {
+        Self::convert_bytes_with_rules(
+            input_bytes, format, helper, config, punctuation, keep_font, &[], false,
+        )
+    }
+
+    /// Like [`convert_bytes`](Self::convert_bytes), but merges `extra_rules`
+    /// ahead of the built-in [`default_font_mask_rules`] when `keep_font` is
+    /// set, and takes an explicit `xml_aware` flag. See
+    /// [`convert_with_rules`](Self::convert_with_rules).
+    #[allow(clippy::too_many_arguments)]
+    pub fn convert_bytes_with_rules(
+        input_bytes: &[u8],
+        format: &str,
+        helper: &OpenCC,
+        config: &str,
+        punctuation: bool,
+        keep_font: bool,
+        extra_rules: &[FontMaskRule],
+        xml_aware: bool,
+    ) -> io::Result<Vec<u8>> {
+        let mut output = io::Cursor::new(Vec::new());
+        Self::convert_reader_with_rules(
+            io::Cursor::new(input_bytes),
+            &mut output,
+            format,
+            helper,
+            config,
+            punctuation,
+            keep_font,
+            extra_rules,
+            xml_aware,
+        )?;
+        Ok(output.into_inner())
+    }
 }
 
 /* ---------- Helpers ---------- */
@@ -219,84 +511,457 @@ fn replace_with_temp(
     fs::rename(&tmp_out, final_out)
 }
 
+/// Whether `entry_name` (a zip-internal path, forward-slash separated) is one
+/// of the parts `format` stores convertible text in. Shared by
+/// [`get_target_xml_paths`]'s filesystem walk and
+/// [`OfficeConverter::convert_reader`]'s in-memory entry list, so the two
+/// i/o paths can't drift on which parts of a document get converted.
+fn is_target_entry(format: &str, entry_name: &str) -> bool {
+    match format {
+        "docx" => {
+            entry_name == "word/document.xml"
+                || entry_name == "word/footnotes.xml"
+                || entry_name == "word/endnotes.xml"
+                || entry_name == "word/comments.xml"
+                || (entry_name.starts_with("word/header") && entry_name.ends_with(".xml"))
+                || (entry_name.starts_with("word/footer") && entry_name.ends_with(".xml"))
+                || (entry_name.starts_with("word/charts/") && is_xml_not_rels(entry_name))
+        }
+        "xlsx" => {
+            entry_name == "xl/sharedStrings.xml"
+                || (entry_name.starts_with("xl/comments") && entry_name.ends_with(".xml"))
+                || (entry_name.starts_with("xl/threadedComments/") && entry_name.ends_with(".xml"))
+                || (entry_name.starts_with("xl/charts/") && is_xml_not_rels(entry_name))
+                || (entry_name.starts_with("xl/drawings/") && is_xml_not_rels(entry_name))
+        }
+        "pptx" => {
+            // Slides, notes & chart parts, skip .rels
+            (entry_name.starts_with("ppt/slides/")
+                || entry_name.starts_with("ppt/notesSlides/")
+                || entry_name.starts_with("ppt/charts/"))
+                && is_xml_not_rels(entry_name)
+        }
+        "odt" | "ods" | "odp" => entry_name == "content.xml",
+        "epub" => {
+            let ext = Path::new(entry_name)
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("");
+            matches!(ext, "xhtml" | "opf" | "ncx" | "html")
+        }
+        _ => false,
+    }
+}
+
+/// Whether `entry_name` is an XML part and not its `.rels` relationship
+/// file, for the directory-prefix globs in [`is_target_entry`].
+fn is_xml_not_rels(entry_name: &str) -> bool {
+    entry_name.ends_with(".xml") && !entry_name.ends_with(".rels")
+}
+
 /// Select only the files we intend to modify per format.
 fn get_target_xml_paths(format: &str, base_dir: &Path) -> Vec<PathBuf> {
     let mut result = Vec::new();
+    for entry in WalkDir::new(base_dir).into_iter().filter_map(Result::ok) {
+        let p = entry.path();
+        if !p.is_file() {
+            continue;
+        }
+        let rel = match p.strip_prefix(base_dir) {
+            Ok(rel) => rel.to_string_lossy().replace('\\', "/"),
+            Err(_) => continue,
+        };
+        if is_target_entry(format, &rel) {
+            result.push(p.to_path_buf());
+        }
+    }
+    result
+}
+
+/// Converts one document part's content (already read to a `String`), either
+/// through the streaming XML-text-node path (`xml_aware`) or the legacy
+/// whole-string regex-and-font-mask path. Shared by
+/// [`OfficeConverter::convert_with_rules`] (filesystem) and
+/// [`OfficeConverter::convert_reader_with_rules`] (in-memory) so the two I/O
+/// paths can't drift on which conversion strategy runs.
+///
+/// `xml_aware` makes `keep_font`/`extra_rules` moot — converting only text
+/// nodes never touches the attributes font masking exists to protect — so
+/// they're accepted but ignored in that mode rather than made mutually
+/// exclusive at the call site.
+#[allow(clippy::too_many_arguments)]
+fn convert_document_part(
+    content: String,
+    format: &str,
+    helper: &OpenCC,
+    config: &str,
+    punctuation: bool,
+    keep_font: bool,
+    extra_rules: &[FontMaskRule],
+    xml_aware: bool,
+) -> io::Result<String> {
+    if xml_aware {
+        let _ = (keep_font, extra_rules);
+        return convert_xml_text_only(&content, format, helper, config, punctuation);
+    }
+
+    let mut content = content;
+    let mut font_map = HashMap::new();
+    if keep_font {
+        mask_font(&mut content, format, extra_rules, &mut font_map);
+    }
+
+    let mut converted = helper.convert(&content, config, punctuation);
+    if keep_font {
+        converted = unmask_font(&converted, &font_map);
+    }
+    Ok(converted)
+}
+
+/// The elements whose *direct* character data counts as convertible prose
+/// for `format`, in `xml_aware` mode. Anything outside these — tag names,
+/// attribute values, relationship IDs, `w:instrText` field codes — is
+/// copied through byte-for-byte, since it was never text in the first
+/// place.
+fn text_bearing_elements(format: &str) -> &'static [&'static str] {
     match format {
-        "docx" => result.push(base_dir.join("word/document.xml")),
-        "xlsx" => result.push(base_dir.join("xl/sharedStrings.xml")),
-        "pptx" => {
-            // Slides & notes only, skip .rels
-            for dir in ["ppt/slides", "ppt/notesSlides"] {
-                let root = base_dir.join(dir);
-                if !root.exists() {
-                    continue;
-                }
-                for entry in WalkDir::new(&root).into_iter().filter_map(Result::ok) {
-                    let p = entry.path();
-                    if !p.is_file() {
-                        continue;
-                    }
-                    if p.extension().and_then(|e| e.to_str()) != Some("xml") {
-                        continue;
-                    }
-                    if p.file_name()
-                        .and_then(|n| n.to_str())
-                        .map(|n| n.ends_with(".rels"))
-                        .unwrap_or(false)
-                    {
-                        continue;
-                    }
-                    result.push(p.to_path_buf());
-                }
-            }
+        // "a:t" covers DrawingML chart titles/text runs embedded in
+        // word/charts/*.xml alongside the body's own "w:t".
+        "docx" => &["w:t", "a:t"],
+        "pptx" => &["a:t"],
+        // "a:t" covers xl/charts/*.xml and xl/drawings/*.xml, which use the
+        // same DrawingML text runs as pptx/docx charts; "t" is the plain
+        // shared-string/comment text element.
+        "xlsx" => &["t", "a:t"],
+        "odt" | "ods" | "odp" => &["text:p", "text:span", "text:h", "text:a"],
+        _ => &[],
+    }
+}
+
+/// epub's xhtml/html parts have no fixed set of text-bearing elements — free
+/// text sits directly under `<p>`, `<span>`, `<li>`, list items and more —
+/// so `xml_aware` mode takes the opposite approach there: convert every text
+/// node *except* inside one of these, which hold data that was never meant
+/// to be read as prose.
+const EPUB_NON_PROSE_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Converts only the character data inside known text-bearing elements of an
+/// XML/XHTML document part, leaving every tag name, attribute, and non-text
+/// element untouched — unlike the regex-over-raw-markup approach [`mask_font`]
+/// exists to work around, this mode can't corrupt a `w:instrText` field code
+/// or a smart-tag attribute, because it never sees them as convertible text
+/// to begin with.
+fn convert_xml_text_only(
+    content: &str,
+    format: &str,
+    helper: &OpenCC,
+    config: &str,
+    punctuation: bool,
+) -> io::Result<String> {
+    let is_epub = format.eq_ignore_ascii_case("epub");
+    let text_elements = text_bearing_elements(format);
+
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(false);
+    let mut writer = Writer::new(Vec::new());
+    let mut element_stack: Vec<String> = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(xml_err)?;
+        if matches!(event, Event::Eof) {
+            break;
         }
-        "odt" | "ods" | "odp" => result.push(base_dir.join("content.xml")),
-        "epub" => {
-            for entry in WalkDir::new(base_dir).into_iter().filter_map(Result::ok) {
-                let p = entry.path();
-                if !p.is_file() {
-                    continue;
-                }
-                let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
-                if matches!(ext, "xhtml" | "opf" | "ncx" | "html") {
-                    result.push(p.to_path_buf());
+
+        match event {
+            Event::Start(start) => {
+                element_stack.push(String::from_utf8_lossy(start.name().as_ref()).into_owned());
+                writer.write_event(Event::Start(start)).map_err(xml_err)?;
+            }
+            Event::End(end) => {
+                element_stack.pop();
+                writer.write_event(Event::End(end)).map_err(xml_err)?;
+            }
+            Event::Text(text) => {
+                let in_text_element = if is_epub {
+                    !element_stack
+                        .iter()
+                        .any(|name| EPUB_NON_PROSE_ELEMENTS.contains(&name.as_str()))
+                } else {
+                    element_stack
+                        .last()
+                        .is_some_and(|name| text_elements.contains(&name.as_str()))
+                };
+
+                if in_text_element {
+                    let decoded = text.unescape().map_err(xml_err)?;
+                    let converted = helper.convert(&decoded, config, punctuation);
+                    writer
+                        .write_event(Event::Text(BytesText::new(&converted)))
+                        .map_err(xml_err)?;
+                } else {
+                    writer.write_event(Event::Text(text)).map_err(xml_err)?;
                 }
             }
+            other => writer.write_event(other).map_err(xml_err)?,
         }
-        _ => {}
     }
-    result
+
+    String::from_utf8(writer.into_inner()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn xml_err(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+/// Finds the `encoding="..."` value of a leading `<?xml ... ?>` declaration,
+/// if any. Only the first ~256 bytes are scanned — an XML declaration, if
+/// present, is always the very first thing in the document.
+fn declared_xml_encoding(raw: &[u8]) -> Option<String> {
+    static DECL_ENCODING_RE: OnceLock<Regex> = OnceLock::new();
+    let re = DECL_ENCODING_RE.get_or_init(|| {
+        Regex::new(r#"^\s*<\?xml[^>]*\bencoding\s*=\s*["']([^"']+)["']"#)
+            .expect("built-in XML-declaration pattern is valid regex")
+    });
+
+    let prefix_len = raw.len().min(256);
+    let prefix = String::from_utf8_lossy(&raw[..prefix_len]);
+    re.captures(&prefix)
+        .map(|caps| caps[1].trim().to_string())
 }
 
-fn mask_font(xml: &mut String, format: &str, font_map: &mut HashMap<String, String>) {
-    let pattern = match format {
-        "docx" => r#"(w:(?:eastAsia|ascii|hAnsi|cs)=")(.*?)(")"#,
-        "xlsx" => r#"(val=")(.*?)(")"#,
-        "pptx" => r#"(typeface=")(.*?)(")"#,
-        "odt" | "ods" | "odp" => {
-            r#"((?:style:font-name(?:-asian|-complex)?|svg:font-family|style:name)=['"])([^'"]+)(['"])"#
+/// Decodes a document part's raw bytes into a `String`, using its own
+/// `<?xml ... encoding="..."?>` declaration when present and recognized,
+/// falling back to `fallback_enc` (the CLI's `--in-enc`) otherwise. Returns
+/// the [`Encoding`] actually used, so the caller can compare it against the
+/// requested output encoding to decide whether the part was re-encoded.
+fn decode_xml_part(raw: &[u8], fallback_enc: &str) -> io::Result<(String, &'static Encoding)> {
+    let label = declared_xml_encoding(raw);
+    let encoding = label
+        .as_deref()
+        .and_then(|l| Encoding::for_label(l.as_bytes()))
+        .or_else(|| Encoding::for_label(fallback_enc.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+
+    let (decoded, _, had_errors) = encoding.decode(raw);
+    if had_errors && encoding != encoding_rs::UTF_8 {
+        // Fall back to UTF-8 rather than silently keep a mis-decoded part —
+        // most document parts genuinely are UTF-8 regardless of a stale or
+        // wrong declaration.
+        if let (decoded_utf8, _, false) = encoding_rs::UTF_8.decode(raw) {
+            return Ok((decoded_utf8.into_owned(), encoding_rs::UTF_8));
         }
-        "epub" => r#"(font-family\s*:\s*)([^;"']+)"#,
-        _ => return,
+    }
+    Ok((decoded.into_owned(), encoding))
+}
+
+/// Re-encodes a converted document part into `out_encoding`, rewriting (or
+/// inserting) its `<?xml ... encoding="..."?>` declaration to match so the
+/// written bytes and the declaration they carry never disagree.
+fn reencode_xml_part(xml: &str, out_encoding: &'static Encoding) -> Vec<u8> {
+    let xml = rewrite_xml_declaration_encoding(xml, out_encoding);
+    let (encoded, _, _) = out_encoding.encode(&xml);
+    encoded.into_owned()
+}
+
+/// Rewrites `content`'s leading `<?xml ... ?>` declaration to declare
+/// `out_encoding`, inserting one (UTF-8-only documents don't require it, but
+/// it keeps the part's own metadata honest) if none was present.
+fn rewrite_xml_declaration_encoding(content: &str, out_encoding: &'static Encoding) -> String {
+    static DECL_RE: OnceLock<Regex> = OnceLock::new();
+    let re = DECL_RE.get_or_init(|| {
+        Regex::new(r#"^(\s*<\?xml\b[^>]*?)(?:\s+encoding\s*=\s*["'][^"']+["']([^>]*?))?(\s*\?>)"#)
+            .expect("built-in XML-declaration pattern is valid regex")
+    });
+
+    let label = out_encoding.name();
+    if let Some(caps) = re.captures(content) {
+        let head = &caps[1];
+        // Attributes after an existing `encoding` (e.g. `standalone="yes"`).
+        let after = caps.get(2).map_or("", |m| m.as_str());
+        let tail = &caps[3];
+        let whole = caps.get(0).unwrap();
+        format!(
+            "{head} encoding=\"{label}\"{after}{tail}{rest}",
+            rest = &content[whole.end()..]
+        )
+    } else {
+        format!("<?xml version=\"1.0\" encoding=\"{label}\"?>\n{content}")
+    }
+}
+
+/// One font-name masking rule: every match of `regex`'s `capture_group`'th
+/// capturing group inside a `format` document is swapped for a marker before
+/// conversion, then swapped back untouched afterwards.
+///
+/// The built-ins (see [`default_font_mask_rules`]) cover the formats
+/// [`OfficeConverter`] already targets; a caller that needs to protect an
+/// attribute they don't cover can pass extra rules to
+/// [`OfficeConverter::convert_with_rules`] and friends, merged in ahead of
+/// the defaults.
+pub struct FontMaskRule {
+    pub format: String,
+    pub regex: Regex,
+    pub capture_group: usize,
+}
+
+impl FontMaskRule {
+    pub fn new(
+        format: impl Into<String>,
+        pattern: &str,
+        capture_group: usize,
+    ) -> Result<Self, regex::Error> {
+        Ok(Self {
+            format: format.into(),
+            regex: Regex::new(pattern)?,
+            capture_group,
+        })
+    }
+}
+
+/// The font-mask rules this module ships with, one per format-specific font
+/// attribute, built once and reused for every call.
+///
+/// DrawingML's `<a:latin>`/`<a:ea>`/`<a:cs>` font-role elements in pptx all
+/// share the same `typeface` attribute name, so the single pptx rule below
+/// already masks all three without needing an element-specific pattern.
+///
+/// epub gets three rules rather than one: a quoted `font-family` value (the
+/// common case, including inside an embedded `@font-face` block, which uses
+/// the same `font-family:` property syntax) wasn't matched at all by a single
+/// `[^;"']+` pattern, since that character class excludes the opening quote
+/// itself; splitting quoted/unquoted into separate rules keeps each pattern's
+/// single capture group simple instead of reaching for one pattern with a
+/// variable group index.
+fn default_font_mask_rules() -> &'static [FontMaskRule] {
+    static RULES: OnceLock<Vec<FontMaskRule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        const ODT_PATTERN: &str =
+            r#"(?:style:font-name(?:-asian|-complex)?|svg:font-family|style:name)=['"]([^'"]+)['"]"#;
+
+        vec![
+            FontMaskRule::new("docx", r#"w:(?:eastAsia|ascii|hAnsi|cs)="([^"]*)""#, 1),
+            FontMaskRule::new("xlsx", r#"val="([^"]*)""#, 1),
+            FontMaskRule::new("pptx", r#"typeface="([^"]*)""#, 1),
+            FontMaskRule::new("odt", ODT_PATTERN, 1),
+            FontMaskRule::new("ods", ODT_PATTERN, 1),
+            FontMaskRule::new("odp", ODT_PATTERN, 1),
+            FontMaskRule::new("epub", r#"font-family\s*:\s*"([^"]*)""#, 1),
+            FontMaskRule::new("epub", r#"font-family\s*:\s*'([^']*)'"#, 1),
+            FontMaskRule::new("epub", r#"font-family\s*:\s*([^;"'}]+)"#, 1),
+        ]
+        .into_iter()
+        .map(|r| r.expect("built-in font-mask pattern is valid regex"))
+        .collect()
+    })
+}
+
+/// A hash of `xml`'s own contents, folded into each marker so markers from
+/// different documents (or different runs over the same document) don't
+/// collide if a caller ever merges font maps across calls.
+fn document_fingerprint(xml: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    xml.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Picks a guard character from the Unicode Private Use Area
+/// (`U+E000..=U+F8FF`) that does not already occur anywhere in `xml`, so a
+/// marker built around it is structurally guaranteed (not just statistically
+/// unlikely) to be distinguishable from anything already in the document.
+fn pick_guard_char(xml: &str) -> Option<char> {
+    (0xE000u32..=0xF8FFu32)
+        .filter_map(char::from_u32)
+        .find(|&c| !xml.contains(c))
+}
+
+/// Masks font names in `xml` matched by any rule (from `extra_rules` or the
+/// built-in [`default_font_mask_rules`]) for `format`, recording
+/// marker-to-original-text pairs in `font_map` so [`unmask_font`] can restore
+/// them after conversion.
+///
+/// Markers are bracketed by a guard character verified absent from `xml` (see
+/// [`pick_guard_char`]), so restoring them later can find each marker's exact
+/// span directly instead of relying on a marker's text being unlikely to
+/// occur elsewhere. If every Private Use Area codepoint already occurs in
+/// `xml` — a pathological document — masking is skipped entirely rather than
+/// risk an ambiguous marker.
+fn mask_font(
+    xml: &mut String,
+    format: &str,
+    extra_rules: &[FontMaskRule],
+    font_map: &mut HashMap<String, String>,
+) {
+    let Some(guard) = pick_guard_char(xml) else {
+        return;
     };
-    let re = Regex::new(pattern).unwrap();
-    let mut counter = 0;
-    let mut result_str = String::new();
-    let mut last_end = 0;
-    for caps in re.captures_iter(xml) {
-        let marker = format!("__F_O_N_T_{}__", counter);
+    let fingerprint = document_fingerprint(xml);
+
+    let mut matches: Vec<(usize, usize, String)> = Vec::new();
+    for rule in extra_rules.iter().chain(default_font_mask_rules()) {
+        if rule.format != format {
+            continue;
+        }
+        for caps in rule.regex.captures_iter(xml) {
+            if let Some(g) = caps.get(rule.capture_group) {
+                matches.push((g.start(), g.end(), g.as_str().to_string()));
+            }
+        }
+    }
+    if matches.is_empty() {
+        return;
+    }
+    matches.sort_by_key(|m| m.0);
+
+    let mut result_str = String::with_capacity(xml.len());
+    let mut last_end = 0usize;
+    let mut counter = 0u32;
+    for (start, end, name) in matches {
+        // Matches are taken in start order (equal starts keep rule order,
+        // extras before defaults); a match starting inside an already-masked
+        // span is skipped, whichever rule produced it.
+        if start < last_end {
+            continue;
+        }
+        let marker = format!("{guard}{fingerprint:016x}_{counter}{guard}");
         counter += 1;
-        font_map.insert(marker.clone(), caps[2].to_string());
-        let mat = caps.get(0).unwrap();
-        result_str.push_str(&xml[last_end..mat.start()]);
-        result_str.push_str(&caps[1]);
+        font_map.insert(marker.clone(), name);
+        result_str.push_str(&xml[last_end..start]);
         result_str.push_str(&marker);
-        if caps.len() > 3 {
-            result_str.push_str(&caps[3]);
-        }
-        last_end = mat.end();
+        last_end = end;
     }
     result_str.push_str(&xml[last_end..]);
     *xml = result_str;
 }
+
+/// Restores the font names [`mask_font`] replaced with markers, in a single
+/// forward pass over `xml` that locates each marker by its guard-character
+/// span rather than calling [`str::replace`] once per marker.
+fn unmask_font(xml: &str, font_map: &HashMap<String, String>) -> String {
+    let Some(guard) = font_map.keys().next().and_then(|k| k.chars().next()) else {
+        return xml.to_string();
+    };
+
+    let mut result = String::with_capacity(xml.len());
+    let mut last_end = 0usize;
+    let mut search_from = 0usize;
+
+    while let Some(rel_open) = xml[search_from..].find(guard) {
+        let open = search_from + rel_open;
+        let after_open = open + guard.len_utf8();
+        let Some(rel_close) = xml[after_open..].find(guard) else {
+            break;
+        };
+        let close = after_open + rel_close;
+        let marker_end = close + guard.len_utf8();
+        let marker = &xml[open..marker_end];
+
+        if let Some(original) = font_map.get(marker) {
+            result.push_str(&xml[last_end..open]);
+            result.push_str(original);
+            last_end = marker_end;
+        }
+        search_from = marker_end;
+    }
+    result.push_str(&xml[last_end..]);
+    result
+}
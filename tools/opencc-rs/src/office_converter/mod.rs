@@ -0,0 +1,4 @@
+mod converter;
+mod pdf;
+
+pub use converter::{ConversionResult, FontMaskRule, OfficeConverter};
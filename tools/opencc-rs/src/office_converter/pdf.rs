@@ -0,0 +1,383 @@
+//! Best-effort text-layer conversion for PDF input.
+//!
+//! Unlike the zip-container formats in [`super::converter`], a PDF is not an
+//! archive of XML parts we can unzip, patch, and re-zip: the text lives as
+//! operands of `Tj`/`TJ`/`'`/`"` show-text operators inside each page's
+//! content stream, addressed through the page's current font via glyph codes
+//! rather than Unicode. To convert it we decode each font's `/ToUnicode` CMap
+//! (the same mechanism PDF text-extraction tools use to recover readable
+//! text), run the decoded string through [`OpenCC::convert`], and re-encode
+//! the result back into that font's code space before writing it back into
+//! the operand in place.
+//!
+//! Limitations, by design rather than oversight:
+//! - A font with no `/ToUnicode` entry can't be decoded at all, so its
+//!   strings are left untouched rather than guessed at.
+//! - Re-encoding only succeeds for codes whose `/ToUnicode` entry maps to a
+//!   single Unicode scalar value (the overwhelmingly common case for CJK
+//!   fonts); multi-character ligature entries are left untouched on the
+//!   encode side since there's no way to know which replacement glyph the
+//!   font intends for a character that didn't originally stand alone.
+//! - This rewrites each Tj/TJ operand's *bytes*, not the raw bytes of the
+//!   page's content stream: [`lopdf`] re-serializes the stream afterwards, so
+//!   operator structure is preserved but exact byte offsets necessarily shift
+//!   when a converted string's encoded length differs from the original.
+
+use std::collections::HashMap;
+use std::io;
+
+use lopdf::content::Content;
+use lopdf::{Document, Object};
+
+use opencc_fmmseg::OpenCC;
+
+use super::converter::ConversionResult;
+
+/// A parsed `/ToUnicode` CMap for one font: maps a raw character code (as it
+/// appears in a content stream string operand) to the Unicode text it
+/// represents, and the reverse, so a converted string can be re-encoded into
+/// the same code space.
+#[derive(Default)]
+struct ToUnicodeMap {
+    code_to_unicode: HashMap<u32, String>,
+    unicode_to_code: HashMap<String, u32>,
+    /// Bytes per character code, from the CMap's `codespacerange` (1 for
+    /// simple fonts, 2 for the CID/Identity-H fonts most CJK documents use).
+    code_bytes: usize,
+}
+
+impl ToUnicodeMap {
+    fn parse(cmap_bytes: &[u8]) -> Option<Self> {
+        let text = String::from_utf8_lossy(cmap_bytes);
+        let mut map = ToUnicodeMap {
+            code_bytes: parse_codespace_width(&text).unwrap_or(2),
+            ..Default::default()
+        };
+
+        for_each_section(&text, "beginbfchar", "endbfchar", |seg| {
+            parse_bfchar_section(&tokenize(seg), &mut map);
+        });
+        for_each_section(&text, "beginbfrange", "endbfrange", |seg| {
+            parse_bfrange_section(&tokenize(seg), &mut map);
+        });
+
+        if map.code_to_unicode.is_empty() {
+            None
+        } else {
+            Some(map)
+        }
+    }
+
+    fn insert(&mut self, code: u32, text: String) {
+        if text.chars().count() == 1 {
+            self.unicode_to_code.entry(text.clone()).or_insert(code);
+        }
+        self.code_to_unicode.insert(code, text);
+    }
+
+    /// Splits `bytes` into `code_bytes`-wide codes and maps each to text.
+    /// Returns `None` (leave the original bytes alone) if `bytes` isn't a
+    /// whole number of codes, or any code has no mapping.
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        if self.code_bytes == 0 || bytes.len() % self.code_bytes != 0 {
+            return None;
+        }
+        let mut out = String::new();
+        for chunk in bytes.chunks(self.code_bytes) {
+            let code = chunk.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32);
+            out.push_str(self.code_to_unicode.get(&code)?);
+        }
+        Some(out)
+    }
+
+    /// Reverse of [`decode`](Self::decode): returns `None` if any character
+    /// of `text` has no single-character code in this font.
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        let mut out = Vec::with_capacity(text.len() * self.code_bytes);
+        let mut buf = [0u8; 4];
+        for ch in text.chars() {
+            let code = *self.unicode_to_code.get(ch.encode_utf8(&mut buf))?;
+            for shift in (0..self.code_bytes).rev() {
+                out.push((code >> (8 * shift)) as u8);
+            }
+        }
+        Some(out)
+    }
+}
+
+enum CMapToken {
+    Hex(String),
+    LBracket,
+    RBracket,
+}
+
+/// Tokenizes a CMap section into hex-string (`<...>`) and bracket tokens,
+/// skipping `%`-comments; everything else (names, keywords, whitespace) is
+/// irrelevant to bfchar/bfrange/codespacerange parsing and is dropped.
+fn tokenize(segment: &str) -> Vec<CMapToken> {
+    let bytes = segment.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'<' => {
+                let start = i + 1;
+                let mut j = start;
+                while j < bytes.len() && bytes[j] != b'>' {
+                    j += 1;
+                }
+                let hex = segment[start..j]
+                    .chars()
+                    .filter(|c| c.is_ascii_hexdigit())
+                    .collect();
+                out.push(CMapToken::Hex(hex));
+                i = j;
+            }
+            b'[' => out.push(CMapToken::LBracket),
+            b']' => out.push(CMapToken::RBracket),
+            _ => {}
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Calls `f` with the contents of every `begin<name>...end<name>` block in
+/// `text`, in order. CMaps may split bfchar/bfrange entries across several
+/// such blocks.
+fn for_each_section<'a>(text: &'a str, begin: &str, end: &str, mut f: impl FnMut(&'a str)) {
+    let mut pos = 0;
+    while let Some(start_rel) = text[pos..].find(begin) {
+        let start = pos + start_rel + begin.len();
+        let Some(end_rel) = text[start..].find(end) else {
+            break;
+        };
+        f(&text[start..start + end_rel]);
+        pos = start + end_rel + end.len();
+    }
+}
+
+fn parse_codespace_width(text: &str) -> Option<usize> {
+    let mut width = None;
+    for_each_section(text, "begincodespacerange", "endcodespacerange", |seg| {
+        if width.is_none() {
+            if let Some(CMapToken::Hex(h)) = tokenize(seg).into_iter().next() {
+                width = Some(h.len() / 2);
+            }
+        }
+    });
+    width
+}
+
+fn parse_bfchar_section(tokens: &[CMapToken], map: &mut ToUnicodeMap) {
+    let mut i = 0;
+    while i + 1 < tokens.len() {
+        if let (CMapToken::Hex(src), CMapToken::Hex(dst)) = (&tokens[i], &tokens[i + 1]) {
+            if let (Ok(code), Some(text)) = (u32::from_str_radix(src, 16), utf16be_hex_to_string(dst)) {
+                map.insert(code, text);
+            }
+        }
+        i += 2;
+    }
+}
+
+/// Largest `hi_code - lo_code + 1` span the single-hex-destination
+/// `beginbfrange` form will expand, chosen to comfortably cover the widest
+/// codespace any real font this module targets uses (16-bit CIDs, the
+/// common case for CJK Identity-H fonts). `lo_code`/`hi_code` come straight
+/// from attacker-controlled PDF content (each up to `u32::MAX`), so without
+/// this cap a crafted CMap like `<0000> <FFFFFFFF> <0000>` would drive an
+/// effectively unbounded loop inserting billions of `HashMap` entries — a
+/// range claiming more than this is almost certainly malformed or hostile
+/// rather than a legitimate CMap, so it's skipped entirely rather than
+/// partially expanded.
+const MAX_BFRANGE_SPAN: u32 = 0x1_0000;
+
+fn parse_bfrange_section(tokens: &[CMapToken], map: &mut ToUnicodeMap) {
+    let mut i = 0;
+    while i < tokens.len() {
+        let (CMapToken::Hex(lo), Some(CMapToken::Hex(hi))) = (&tokens[i], tokens.get(i + 1)) else {
+            i += 1;
+            continue;
+        };
+        let (Ok(lo_code), Ok(hi_code)) = (u32::from_str_radix(lo, 16), u32::from_str_radix(hi, 16)) else {
+            i += 2;
+            continue;
+        };
+
+        match tokens.get(i + 2) {
+            Some(CMapToken::Hex(dst)) => {
+                if let Ok(base) = u32::from_str_radix(dst, 16) {
+                    let span = hi_code.checked_sub(lo_code).and_then(|d| d.checked_add(1));
+                    if span.is_some_and(|span| span <= MAX_BFRANGE_SPAN) {
+                        for (offset, code) in (lo_code..=hi_code).enumerate() {
+                            if let Some(ch) = char::from_u32(base + offset as u32) {
+                                map.insert(code, ch.to_string());
+                            }
+                        }
+                    }
+                }
+                i += 3;
+            }
+            Some(CMapToken::LBracket) => {
+                let mut j = i + 3;
+                let mut code = lo_code;
+                while let Some(CMapToken::Hex(dst)) = tokens.get(j) {
+                    if let Some(text) = utf16be_hex_to_string(dst) {
+                        map.insert(code, text);
+                    }
+                    code += 1;
+                    j += 1;
+                }
+                i = j + 1; // skip the closing RBracket
+            }
+            _ => i += 2,
+        }
+    }
+}
+
+/// Decodes a `/ToUnicode` destination hex string as UTF-16BE (the encoding
+/// the PDF spec mandates for these values), handling surrogate pairs.
+fn utf16be_hex_to_string(hex: &str) -> Option<String> {
+    if hex.is_empty() || hex.len() % 4 != 0 {
+        return None;
+    }
+    let units: Vec<u16> = hex
+        .as_bytes()
+        .chunks(4)
+        .map(|c| u16::from_str_radix(std::str::from_utf8(c).ok()?, 16).ok())
+        .collect::<Option<_>>()?;
+    String::from_utf16(&units).ok()
+}
+
+fn io_err(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err.to_string())
+}
+
+/// Converts the text layer of a PDF, bypassing the zip-based unzip/rewrite
+/// flow [`OfficeConverter::convert`](super::converter::OfficeConverter::convert)
+/// uses for office-document formats. Fonts without a `/ToUnicode` CMap are
+/// left untouched (see the module docs for why).
+pub fn convert_pdf(
+    input_path: &str,
+    output_path: &str,
+    helper: &OpenCC,
+    config: &str,
+    punctuation: bool,
+) -> io::Result<ConversionResult> {
+    let mut doc = Document::load(input_path).map_err(io_err)?;
+    let page_ids: Vec<_> = doc.get_pages().into_values().collect();
+
+    let mut any_converted = false;
+    let mut any_font_without_to_unicode = false;
+
+    for page_id in page_ids {
+        let mut font_maps: HashMap<Vec<u8>, Option<ToUnicodeMap>> = HashMap::new();
+        for (name, dict) in doc.get_page_fonts(page_id) {
+            let map = dict
+                .get(b"ToUnicode")
+                .ok()
+                .and_then(|obj| obj.as_reference().ok())
+                .and_then(|r| doc.get_object(r).ok())
+                .and_then(|obj| obj.as_stream().ok())
+                .and_then(|stream| stream.decompressed_content().ok())
+                .and_then(|bytes| ToUnicodeMap::parse(&bytes));
+            if map.is_none() {
+                any_font_without_to_unicode = true;
+            }
+            font_maps.insert(name, map);
+        }
+
+        let content_bytes = match doc.get_page_content(page_id) {
+            Ok(bytes) => bytes,
+            Err(_) => continue,
+        };
+        let mut content = Content::decode(&content_bytes).map_err(io_err)?;
+
+        let mut current_font: Option<Vec<u8>> = None;
+        for op in content.operations.iter_mut() {
+            match op.operator.as_str() {
+                "Tf" => {
+                    if let Some(Object::Name(name)) = op.operands.first() {
+                        current_font = Some(name.clone());
+                    }
+                }
+                "Tj" | "'" | "\"" => {
+                    if let Some(Object::String(bytes, _)) = op.operands.last_mut() {
+                        if let Some(converted) = convert_show_text(
+                            bytes,
+                            current_font.as_deref(),
+                            &font_maps,
+                            helper,
+                            config,
+                            punctuation,
+                        ) {
+                            *bytes = converted;
+                            any_converted = true;
+                        }
+                    }
+                }
+                "TJ" => {
+                    if let Some(Object::Array(items)) = op.operands.first_mut() {
+                        for item in items.iter_mut() {
+                            if let Object::String(bytes, _) = item {
+                                if let Some(converted) = convert_show_text(
+                                    bytes,
+                                    current_font.as_deref(),
+                                    &font_maps,
+                                    helper,
+                                    config,
+                                    punctuation,
+                                ) {
+                                    *bytes = converted;
+                                    any_converted = true;
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let encoded = content.encode().map_err(io_err)?;
+        doc.change_page_content(page_id, encoded).map_err(io_err)?;
+    }
+
+    doc.save(output_path).map_err(io_err)?;
+
+    let message = if any_converted {
+        "✅ Conversion completed.".to_string()
+    } else if any_font_without_to_unicode {
+        "⚠️ No text could be decoded (missing /ToUnicode CMaps); output is an unmodified copy.".to_string()
+    } else {
+        "⚠️ No convertible text found.".to_string()
+    };
+
+    Ok(ConversionResult {
+        success: true,
+        message,
+    })
+}
+
+fn convert_show_text(
+    bytes: &[u8],
+    font: Option<&[u8]>,
+    font_maps: &HashMap<Vec<u8>, Option<ToUnicodeMap>>,
+    helper: &OpenCC,
+    config: &str,
+    punctuation: bool,
+) -> Option<Vec<u8>> {
+    let map = font.and_then(|f| font_maps.get(f)).and_then(|m| m.as_ref())?;
+    let text = map.decode(bytes)?;
+    let converted = helper.convert(&text, config, punctuation);
+    if converted == text {
+        return None;
+    }
+    map.encode(&converted)
+}
@@ -1,10 +1,9 @@
 use std::collections::HashMap;
-use std::fs::{self, File};
-use std::io::{Read, Write};
-use std::path::{Path, PathBuf};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
 
 use regex::Regex;
-use tempfile::tempdir;
 use zip::write::{ExtendedFileOptions, FileOptions};
 // 💡 important!
 use zip::{ZipArchive, ZipWriter};
@@ -20,6 +19,9 @@ pub struct ConversionResult {
 }
 
 impl OfficeDocConverter {
+    /// Thin wrapper over [`convert_bytes`](Self::convert_bytes) for callers
+    /// working with paths: reads `input_path`, converts in memory, and
+    /// writes the rebuilt archive to `output_path`.
     pub fn convert(
         input_path: &str,
         output_path: &str,
@@ -29,24 +31,70 @@ impl OfficeDocConverter {
         punctuation: bool,
         keep_font: bool,
     ) -> ConversionResult {
-        let temp_dir = tempdir().expect("Failed to create temp dir");
-        let temp_path = temp_dir.path().to_path_buf();
-
-        let file = match File::open(input_path) {
-            Ok(f) => f,
-            Err(_) => {
+        let input = match fs::read(input_path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
                 return ConversionResult {
                     success: false,
-                    message: "❌ Failed to open ZIP archive.".to_string(),
+                    message: format!("❌ Failed to open ZIP archive: {}", e),
                 }
             }
         };
 
-        let mut archive = ZipArchive::new(file).unwrap();
+        let output =
+            match Self::convert_bytes(&input, format, helper, config, punctuation, keep_font) {
+                Ok(bytes) => bytes,
+                Err(result) => return result,
+            };
+
+        if Path::new(output_path).exists() {
+            if let Err(e) = fs::remove_file(output_path) {
+                return ConversionResult {
+                    success: false,
+                    message: format!("❌ Failed to remove existing {}: {}", output_path, e),
+                };
+            }
+        }
+
+        if let Err(e) = fs::write(output_path, &output) {
+            return ConversionResult {
+                success: false,
+                message: format!("❌ Failed to write {}: {}", output_path, e),
+            };
+        }
+
+        ConversionResult {
+            success: true,
+            message: "✅ Conversion completed.".to_string(),
+        }
+    }
+
+    /// Like [`convert`](Self::convert), but reads the archive from `input`
+    /// and returns the rebuilt archive's bytes instead of touching the
+    /// filesystem at all — no `tempdir`, no intermediate files — so callers
+    /// that already hold the document in memory (a web upload, a WASM host)
+    /// can convert it without ever writing to disk.
+    pub fn convert_bytes(
+        input: &[u8],
+        format: &str,
+        helper: &mut OpenCC,
+        config: &str,
+        punctuation: bool,
+        keep_font: bool,
+    ) -> Result<Vec<u8>, ConversionResult> {
+        let mut archive = ZipArchive::new(io::Cursor::new(input)).map_err(|e| ConversionResult {
+            success: false,
+            message: truncated_or(&zip_err_to_io(e), "Failed to read ZIP archive"),
+        })?;
+
+        let mut entries: Vec<(String, Vec<u8>)> = Vec::with_capacity(archive.len());
         for i in 0..archive.len() {
-            let mut file = archive.by_index(i).unwrap();
+            let mut entry = archive.by_index(i).map_err(|e| ConversionResult {
+                success: false,
+                message: truncated_or(&zip_err_to_io(e), "Failed to read ZIP entry"),
+            })?;
 
-            let raw_name = file.name().replace('\\', "/");
+            let raw_name = entry.name().replace('\\', "/");
             let relative_path = Path::new(&raw_name);
 
             // Sanitize: skip if file has '..' or is absolute
@@ -58,26 +106,32 @@ impl OfficeDocConverter {
             }) {
                 continue; // Skip unsafe paths
             }
-
-            let out_path = temp_path.join(relative_path);
-            if let Some(parent) = out_path.parent() {
-                fs::create_dir_all(parent).ok();
+            if entry.is_dir() || raw_name.ends_with('/') {
+                continue;
             }
 
-            let mut out_file = File::create(&out_path).unwrap();
-            std::io::copy(&mut file, &mut out_file).ok();
+            let declared_size = entry.size();
+            let mut buf = Vec::with_capacity(declared_size as usize);
+            copy_entry_checked(&mut entry, declared_size, &mut buf).map_err(|e| {
+                ConversionResult {
+                    success: false,
+                    message: truncated_or(&e, "Failed to copy ZIP entry"),
+                }
+            })?;
+            entries.push((raw_name, buf));
         }
 
-        let target_xmls = get_target_xml_paths(format, &temp_path);
-        for xml_file in target_xmls {
-            if !xml_file.exists() {
+        for (name, bytes) in entries.iter_mut() {
+            if !is_target_entry(format, name) {
                 continue;
             }
-            let mut content = String::new();
-            File::open(&xml_file)
-                .unwrap()
-                .read_to_string(&mut content)
-                .unwrap();
+
+            let mut content = String::from_utf8(std::mem::take(bytes)).map_err(|e| {
+                ConversionResult {
+                    success: false,
+                    message: format!("❌ {} is not valid UTF-8: {}", name, e.utf8_error()),
+                }
+            })?;
 
             let mut font_map = HashMap::new();
             if keep_font {
@@ -92,113 +146,106 @@ impl OfficeDocConverter {
                 }
             }
 
-            let mut out_file = File::create(&xml_file).unwrap();
-            out_file.write_all(converted.as_bytes()).unwrap();
-        }
-
-        if Path::new(output_path).exists() {
-            fs::remove_file(output_path).unwrap();
+            *bytes = converted.into_bytes();
         }
 
-        let zip_file = match File::create(output_path) {
-            Ok(f) => f,
-            Err(_) => {
-                return ConversionResult {
-                    success: false,
-                    message: "❌ Failed to create output ZIP.".to_string(),
-                }
-            }
-        };
-
-        let mut zip_writer = ZipWriter::new(zip_file);
-
-        // Replace this section in your code:
-        for entry in walkdir::WalkDir::new(&temp_path) {
-            let entry = entry.unwrap();
-            let path = entry.path();
-            if path.is_file() {
-                let mut buffer = Vec::new();
-                if let Err(e) = File::open(path).and_then(|mut f| f.read_to_end(&mut buffer)) {
-                    return ConversionResult {
-                        success: false,
-                        message: format!("❌ Failed to read file {:?}: {}", path, e),
-                    };
-                }
-
-                let relative_path = match path.strip_prefix(&temp_path) {
-                    Ok(p) => p.to_string_lossy(),
-                    Err(e) => {
-                        return ConversionResult {
-                            success: false,
-                            message: format!("❌ Failed to compute relative path: {}", e),
-                        };
-                    }
-                };
-
-                // FIX: Normalize path separators to forward slashes for ZIP
-                let relative_path = relative_path.replace('\\', "/");
-
+        let mut output = io::Cursor::new(Vec::new());
+        {
+            let mut zip_writer = ZipWriter::new(&mut output);
+            for (name, buf) in &entries {
                 let options: FileOptions<'_, ExtendedFileOptions> =
                     FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
 
-                if let Err(e) = zip_writer
-                    .start_file(&relative_path, options)
-                    .and_then(|_| {
-                        zip_writer
-                            .write_all(&buffer)
-                            .map_err(zip::result::ZipError::Io)
-                    })
-                {
-                    return ConversionResult {
+                if let Err(e) = zip_writer.start_file(name, options).and_then(|_| {
+                    zip_writer
+                        .write_all(buf)
+                        .map_err(zip::result::ZipError::Io)
+                }) {
+                    return Err(ConversionResult {
                         success: false,
-                        message: format!("❌ Failed to write {} to ZIP: {}", relative_path, e),
-                    };
+                        message: format!("❌ Failed to write {} to ZIP: {}", name, e),
+                    });
                 }
             }
-        }
 
-        if let Err(e) = zip_writer.finish() {
-            return ConversionResult {
-                success: false,
-                message: format!("❌ Failed to finalize ZIP file: {}", e),
-            };
+            if let Err(e) = zip_writer.finish() {
+                return Err(ConversionResult {
+                    success: false,
+                    message: format!("❌ Failed to finalize ZIP file: {}", e),
+                });
+            }
         }
 
-        ConversionResult {
-            success: true,
-            message: "✅ Conversion completed.".to_string(),
-        }
+        Ok(output.into_inner())
     }
 }
 
-fn get_target_xml_paths(format: &str, base_dir: &Path) -> Vec<PathBuf> {
-    let mut result = Vec::new();
+/// Whether `entry_name` (a zip-internal path, forward-slash separated) is
+/// one of the parts `format` stores convertible text in.
+fn is_target_entry(format: &str, entry_name: &str) -> bool {
     match format {
-        "docx" => result.push(base_dir.join("word/document.xml")),
-        "xlsx" => result.push(base_dir.join("xl/sharedStrings.xml")),
-        "pptx" => {
-            for entry in walkdir::WalkDir::new(base_dir.join("ppt")) {
-                let path = entry.unwrap().path().to_path_buf();
-                let name = path.file_name().unwrap().to_string_lossy();
-                let path_str = path.to_string_lossy();
-                if name.contains("slide") || path_str.contains("notesSlide") {
-                    result.push(path);
-                }
-            }
-        }
-        "odt" | "ods" | "odp" => result.push(base_dir.join("content.xml")),
+        "docx" => entry_name == "word/document.xml",
+        "xlsx" => entry_name == "xl/sharedStrings.xml",
+        "pptx" => entry_name.contains("slide") || entry_name.contains("notesSlide"),
+        "odt" | "ods" | "odp" => entry_name == "content.xml",
         "epub" => {
-            for entry in walkdir::WalkDir::new(base_dir) {
-                let path = entry.unwrap().path().to_path_buf();
-                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                if matches!(ext, "xhtml" | "opf" | "ncx") {
-                    result.push(path);
-                }
-            }
+            let ext = Path::new(entry_name)
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("");
+            matches!(ext, "xhtml" | "opf" | "ncx")
         }
-        _ => {}
+        _ => false,
+    }
+}
+
+/// Maps a [`zip::result::ZipError`] to an [`io::Error`], preserving
+/// [`io::ErrorKind::UnexpectedEof`] when the archive's central directory (or
+/// an entry's local header) can't be found — the shape a truncated download
+/// or a half-written file takes — so [`truncated_or`] can call it out by name.
+fn zip_err_to_io(err: zip::result::ZipError) -> io::Error {
+    match err {
+        zip::result::ZipError::Io(e) => e,
+        zip::result::ZipError::InvalidArchive(_) => {
+            io::Error::new(io::ErrorKind::UnexpectedEof, err.to_string())
+        }
+        other => io::Error::new(io::ErrorKind::InvalidData, other.to_string()),
+    }
+}
+
+/// Builds a `"❌ {context}: ..."` message for an IO failure, calling out a
+/// truncated/unexpected-end-of-input condition by name rather than just
+/// surfacing the underlying IO message, since that's the most common shape a
+/// half-written or truncated Office file takes.
+fn truncated_or(err: &io::Error, context: &str) -> String {
+    if err.kind() == io::ErrorKind::UnexpectedEof {
+        format!("❌ {context}: file appears truncated")
+    } else {
+        format!("❌ {context}: {err}")
+    }
+}
+
+/// Copies `entry` into `out`, then checks the number of bytes actually copied
+/// against `declared_size` (the entry's own uncompressed-size field from the
+/// zip central directory). A short copy means the archive's data ran out
+/// before the entry did — a truncated or partially-written zip — which
+/// `io::copy` alone wouldn't otherwise surface as an error, since it simply
+/// stops at EOF.
+fn copy_entry_checked(
+    entry: &mut impl Read,
+    declared_size: u64,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let copied = io::copy(entry, out)?;
+    if copied != declared_size {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!(
+                "entry is truncated: copied {copied} bytes but archive declares {declared_size}"
+            ),
+        ));
     }
-    result
+    Ok(())
 }
 
 fn mask_font(xml: &mut String, format: &str, font_map: &mut HashMap<String, String>) {